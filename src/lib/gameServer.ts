@@ -84,15 +84,35 @@ class GameServer {
 		});
 	}
 
-	joinRoom(room_id: string, name: string, token: string, roomPassword?: string) {
+	joinRoom(
+		room_id: string,
+		name: string,
+		token: string,
+		roomPassword?: string,
+		invite?: string,
+		accountSession?: string
+	) {
 		const normalizedName = name.trim();
 		const trimmedPassword = roomPassword?.trim();
+		const trimmedInvite = invite?.trim();
+		const trimmedSession = accountSession?.trim();
 		this.send({
 			JoinRoom: {
 				name: normalizedName,
 				room_id,
 				token,
-				...(trimmedPassword ? { room_password: trimmedPassword } : {})
+				...(trimmedPassword ? { room_password: trimmedPassword } : {}),
+				...(trimmedInvite ? { invite: trimmedInvite } : {}),
+				...(trimmedSession ? { account_session: trimmedSession } : {})
+			}
+		});
+	}
+
+	spectateRoom(room_id: string, spectatorToken: string) {
+		this.send({
+			JoinAsSpectator: {
+				room_id,
+				spectator_token: spectatorToken.trim()
 			}
 		});
 	}
@@ -137,6 +157,44 @@ class GameServer {
 		});
 	}
 
+	requestStatsExportToken() {
+		this.send({
+			RequestStatsExportToken: {}
+		});
+	}
+
+	setStatsOptOut(optOut: boolean) {
+		this.send({
+			SetStatsOptOut: {
+				opt_out: optOut
+			}
+		});
+	}
+
+	setRoomPassword(password: string | null) {
+		this.send({
+			SetRoomPassword: {
+				password
+			}
+		});
+	}
+
+	setPublicListing(listing: { title: string; language: string } | null) {
+		this.send({
+			SetPublicListing: {
+				listing
+			}
+		});
+	}
+
+	claimRoomOwner(ownerToken: string) {
+		this.send({
+			ClaimRoomOwner: {
+				owner_token: ownerToken
+			}
+		});
+	}
+
 	raiseScoreToActiveMin(player: string) {
 		this.send({
 			RaiseScoreToActiveMin: {
@@ -241,6 +299,143 @@ class GameServer {
 		});
 	}
 
+	setStorytellerPassPenalty(penalty: 'none' | 'lose_points' | 'counts_as_turn') {
+		this.send({
+			SetStorytellerPassPenalty: {
+				penalty
+			}
+		});
+	}
+
+	setStorytellerPassPenaltyPoints(points: number) {
+		this.send({
+			SetStorytellerPassPenaltyPoints: {
+				points
+			}
+		});
+	}
+
+	setTiebreakRules(
+		rules: Array<'storyteller_successes' | 'beauty_points' | 'clue_rating' | 'sudden_death'>
+	) {
+		this.send({
+			SetTiebreakRules: {
+				rules
+			}
+		});
+	}
+
+	setHandicapOffset(player: string, points: number) {
+		this.send({
+			SetHandicapOffset: {
+				player,
+				points
+			}
+		});
+	}
+
+	setCatchUpBonusEnabled(enabled: boolean) {
+		this.send({
+			SetCatchUpBonusEnabled: {
+				enabled
+			}
+		});
+	}
+
+	setCatchUpBonusPoints(points: number) {
+		this.send({
+			SetCatchUpBonusPoints: {
+				points
+			}
+		});
+	}
+
+	setCatchUpDeficitPoints(points: number) {
+		this.send({
+			SetCatchUpDeficitPoints: {
+				points
+			}
+		});
+	}
+
+	setSecretObjectivesEnabled(enabled: boolean) {
+		this.send({
+			SetSecretObjectivesEnabled: {
+				enabled
+			}
+		});
+	}
+
+	setSecretObjectiveBonusPoints(points: number) {
+		this.send({
+			SetSecretObjectiveBonusPoints: {
+				points
+			}
+		});
+	}
+
+	sendChat(text: string) {
+		this.send({
+			SendChat: {
+				text
+			}
+		});
+	}
+
+	react(messageId: number, emoji: string) {
+		this.send({
+			React: {
+				message_id: messageId,
+				emoji
+			}
+		});
+	}
+
+	setChatMuted(member: string, muted: boolean) {
+		this.send({
+			SetChatMuted: {
+				member,
+				muted
+			}
+		});
+	}
+
+	deleteChatMessage(messageId: number) {
+		this.send({
+			DeleteChatMessage: {
+				message_id: messageId
+			}
+		});
+	}
+
+	setChatStageRestrictionsEnabled(enabled: boolean) {
+		this.send({
+			SetChatStageRestrictionsEnabled: {
+				enabled
+			}
+		});
+	}
+
+	createSpectatorLink() {
+		this.send({
+			CreateSpectatorLink: {}
+		});
+	}
+
+	revokeSpectatorLink() {
+		this.send({
+			RevokeSpectatorLink: {}
+		});
+	}
+
+	setSpectatorDelay(seconds: number) {
+		this.send({
+			SetSpectatorDelay: {
+				seconds
+			}
+		});
+	}
+
 	setDoubleVoteBonusNormalPoints(points: number) {
 		this.send({
 			SetDoubleVoteBonusNormalPoints: {
@@ -722,6 +917,12 @@ class GameServer {
 		});
 	}
 
+	passStoryteller() {
+		this.send({
+			PassStoryteller: {}
+		});
+	}
+
 	refreshHands() {
 		this.send({
 			RefreshHands: {}
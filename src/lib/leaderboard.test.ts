@@ -1,6 +1,7 @@
 import { describe, expect, test } from 'vitest';
 import {
 	firstActiveRoundForPlayer,
+	handicapHistoryRows,
 	rankEntriesByMode,
 	rankLeaderboardEntries,
 	scoreBreakdownsFromSnapshots
@@ -14,7 +15,8 @@ function round(round_num: number, active_players: string[]): LeaderboardRoundHis
 		total_deltas: {},
 		beauty_deltas: {},
 		total_after_round: {},
-		beauty_total_after_round: {}
+		beauty_total_after_round: {},
+		raw_total_after_round: {}
 	};
 }
 
@@ -49,6 +51,48 @@ describe('firstActiveRoundForPlayer', () => {
 	});
 });
 
+describe('handicapHistoryRows', () => {
+	test('pairs raw and adjusted totals per round with the handicap between them', () => {
+		const first = {
+			...round(2, ['Alice', 'Bob']),
+			total_after_round: { Alice: 6, Bob: 5 },
+			raw_total_after_round: { Alice: 6, Bob: 2 }
+		};
+		const second = {
+			...round(1, ['Alice', 'Bob']),
+			total_after_round: { Alice: 3, Bob: 3 },
+			raw_total_after_round: { Alice: 3, Bob: 0 }
+		};
+
+		expect(handicapHistoryRows([first, second])).toEqual([
+			{
+				roundNum: 1,
+				scores: [
+					{ name: 'Alice', raw: 3, handicap: 0, adjusted: 3 },
+					{ name: 'Bob', raw: 0, handicap: 3, adjusted: 3 }
+				]
+			},
+			{
+				roundNum: 2,
+				scores: [
+					{ name: 'Alice', raw: 6, handicap: 0, adjusted: 6 },
+					{ name: 'Bob', raw: 2, handicap: 3, adjusted: 5 }
+				]
+			}
+		]);
+	});
+
+	test('returns no rows when nobody had a handicap', () => {
+		const plain = {
+			...round(1, ['Alice']),
+			total_after_round: { Alice: 3 },
+			raw_total_after_round: { Alice: 3 }
+		};
+
+		expect(handicapHistoryRows([plain])).toEqual([]);
+	});
+});
+
 describe('scoreBreakdownsFromSnapshots', () => {
 	test('converts server-computed total and beauty snapshots to display breakdowns', () => {
 		const breakdowns = scoreBreakdownsFromSnapshots({
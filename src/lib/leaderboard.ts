@@ -101,6 +101,26 @@ function sortedLeaderboardHistory(history: LeaderboardRoundHistoryEntry[]) {
 	return [...history].sort((a, b) => a.round_num - b.round_num);
 }
 
+export interface HandicapHistoryRow {
+	roundNum: number;
+	scores: Array<{ name: string; raw: number; handicap: number; adjusted: number }>;
+}
+
+// Raw vs. adjusted totals per round; empty when no handicap was ever applied.
+export function handicapHistoryRows(history: LeaderboardRoundHistoryEntry[]): HandicapHistoryRow[] {
+	const rows = sortedLeaderboardHistory(history).map((round) => ({
+		roundNum: round.round_num,
+		scores: Object.entries(round.total_after_round)
+			.map(([playerName, adjusted]) => {
+				const raw = round.raw_total_after_round[playerName] ?? adjusted;
+				return { name: playerName, raw, handicap: adjusted - raw, adjusted };
+			})
+			.sort((a, b) => b.adjusted - a.adjusted || a.name.localeCompare(b.name))
+	}));
+	const hasHandicap = rows.some((row) => row.scores.some((score) => score.handicap !== 0));
+	return hasHandicap ? rows : [];
+}
+
 export function leaderboardModeLabel(mode: LeaderboardViewMode) {
 	switch (mode) {
 		case 'story_only':
@@ -1,5 +1,6 @@
 import { browser } from '$app/environment';
-import { writable } from 'svelte/store';
+import { get, writable } from 'svelte/store';
+import { accountSessionStore, playerTokenStore } from '$lib/store';
 import type {
 	BeautyScoringMode,
 	BeautyVotePointsDivisorMode,
@@ -206,13 +207,19 @@ export async function refreshMostBeautifulStats() {
 		}
 		params.set('games', `${currentMostBeautifulStatsGamesLimit}`);
 		const query = params.toString();
+		const headers: Record<string, string> = {
+			'Cache-Control': 'no-cache'
+		};
+		// room-scoped stats require proof of membership; signed-in members joined as their account
+		const playerToken = get(accountSessionStore) || get(playerTokenStore);
+		if (currentRoomCodeValue !== '' && playerToken !== '') {
+			headers.Authorization = `Bearer ${playerToken}`;
+		}
 		const scopedResponse = await fetch(
 			query === '' ? '/most-beautiful-stats' : `/most-beautiful-stats?${query}`,
 			{
 				method: 'GET',
-				headers: {
-					'Cache-Control': 'no-cache'
-				},
+				headers,
 				cache: 'no-store'
 			}
 		);
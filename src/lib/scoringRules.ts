@@ -0,0 +1,54 @@
+import { writable } from 'svelte/store';
+import type { StorytellerPassPenalty } from '$lib/types';
+
+export const storytellerPassPenalty = writable<StorytellerPassPenalty>('none');
+export const storytellerPassPenaltyPoints = writable(1);
+export const storytellerPassPenaltyPointsMin = writable(0);
+export const storytellerPassPenaltyPointsMax = writable(10);
+
+export const handicapOffsets = writable<Record<string, number>>({});
+export const handicapOffsetMax = writable(20);
+export const handicapPoints = writable<Record<string, number>>({});
+export const catchUpBonusEnabled = writable(false);
+export const catchUpBonusPoints = writable(1);
+export const catchUpBonusPointsMin = writable(1);
+export const catchUpBonusPointsMax = writable(5);
+export const catchUpDeficitPoints = writable(5);
+export const catchUpDeficitPointsMin = writable(1);
+export const catchUpDeficitPointsMax = writable(50);
+
+export function setScoringRulesRoomState(payload: {
+	storytellerPassPenalty?: StorytellerPassPenalty;
+	storytellerPassPenaltyPoints?: number;
+	storytellerPassPenaltyPointsMin?: number;
+	storytellerPassPenaltyPointsMax?: number;
+	handicapOffsets?: Record<string, number>;
+	handicapOffsetMax?: number;
+	handicapPoints?: Record<string, number>;
+	catchUpBonusEnabled?: boolean;
+	catchUpBonusPoints?: number;
+	catchUpBonusPointsMin?: number;
+	catchUpBonusPointsMax?: number;
+	catchUpDeficitPoints?: number;
+	catchUpDeficitPointsMin?: number;
+	catchUpDeficitPointsMax?: number;
+}) {
+	storytellerPassPenalty.set(payload.storytellerPassPenalty ?? 'none');
+	storytellerPassPenaltyPoints.set(payload.storytellerPassPenaltyPoints ?? 1);
+	storytellerPassPenaltyPointsMin.set(payload.storytellerPassPenaltyPointsMin ?? 0);
+	storytellerPassPenaltyPointsMax.set(payload.storytellerPassPenaltyPointsMax ?? 10);
+	handicapOffsets.set(payload.handicapOffsets ?? {});
+	handicapOffsetMax.set(payload.handicapOffsetMax ?? 20);
+	handicapPoints.set(payload.handicapPoints ?? {});
+	catchUpBonusEnabled.set(payload.catchUpBonusEnabled ?? false);
+	catchUpBonusPoints.set(payload.catchUpBonusPoints ?? 1);
+	catchUpBonusPointsMin.set(payload.catchUpBonusPointsMin ?? 1);
+	catchUpBonusPointsMax.set(payload.catchUpBonusPointsMax ?? 5);
+	catchUpDeficitPoints.set(payload.catchUpDeficitPoints ?? 5);
+	catchUpDeficitPointsMin.set(payload.catchUpDeficitPointsMin ?? 1);
+	catchUpDeficitPointsMax.set(payload.catchUpDeficitPointsMax ?? 50);
+}
+
+export function resetScoringRulesClientState() {
+	setScoringRulesRoomState({});
+}
@@ -0,0 +1,15 @@
+import type { SecretObjective, SecretObjectiveKind } from '$lib/types';
+
+const SECRET_OBJECTIVE_LABELS: Record<SecretObjectiveKind, string> = {
+	decoy_double_fool: 'Get two players to vote for your decoy card in one round',
+	beauty_winner_twice: 'Win Most Beautiful twice',
+	precise_storyteller: 'Succeed as storyteller with exactly one correct guesser'
+};
+
+export function formatSecretObjective(objective: SecretObjective): string {
+	const label = SECRET_OBJECTIVE_LABELS[objective.kind];
+	if (objective.completed) {
+		return `${label} (done!)`;
+	}
+	return objective.target > 1 ? `${label} (${objective.progress}/${objective.target})` : label;
+}
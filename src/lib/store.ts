@@ -34,6 +34,20 @@ export const nameStore = createNormalizedNameStore();
 const existingToken = browser ? window.localStorage.getItem('player_token') || '' : '';
 const generatedToken = browser && existingToken === '' ? window.crypto.randomUUID() : existingToken;
 export const playerTokenStore = writable(browser ? generatedToken : '');
+// signed-in account session; empty when playing with just this browser's token
+export const accountSessionStore = writable(
+	browser ? window.localStorage.getItem('account_session') || '' : ''
+);
+// mirrors RoomState.has_password for the current room
+export const roomHasPasswordStore = writable(false);
+export interface PublicRoomListing {
+	title: string;
+	language: string;
+}
+// mirrors RoomState.public_listing for the current room
+export const roomPublicListingStore = writable<PublicRoomListing | null>(null);
+// mirrors RoomState.persistent: the room keeps its link, settings and members between sessions
+export const roomPersistentStore = writable(false);
 
 export function getAssignedRoomName(roomCode: string, token: string): string | null {
 	if (!browser || !token) {
@@ -100,6 +114,16 @@ nameStore.subscribe((value) => {
 	}
 });
 
+accountSessionStore.subscribe((value) => {
+	if (browser) {
+		if (value) {
+			window.localStorage.setItem('account_session', value);
+		} else {
+			window.localStorage.removeItem('account_session');
+		}
+	}
+});
+
 playerTokenStore.subscribe((value) => {
 	if (browser) {
 		if (value) {
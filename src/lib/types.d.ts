@@ -17,6 +17,37 @@ export type StellaQueuedRevealMode = 'animated' | 'fast';
 
 export type BeautyResultsDisplayMode = 'summary' | 'separate' | 'combined';
 
+export type StorytellerPassPenalty = 'none' | 'lose_points' | 'counts_as_turn';
+
+export type TiebreakRule = 'storyteller_successes' | 'beauty_points' | 'clue_rating' | 'sudden_death';
+
+export interface FinalStanding {
+	rank: number;
+	name: string;
+	points: number;
+	decided_by: TiebreakRule | null;
+	observer: boolean;
+	secret_objective: SecretObjective | null;
+	secret_objective_bonus: number;
+}
+
+export interface ChatMessage {
+	id: number;
+	sender: string;
+	text: string;
+	sent_at_ms: number;
+	reactions: Record<string, string[]>;
+}
+
+export type SecretObjectiveKind = 'decoy_double_fool' | 'beauty_winner_twice' | 'precise_storyteller';
+
+export interface SecretObjective {
+	kind: SecretObjectiveKind;
+	progress: number;
+	target: number;
+	completed: boolean;
+}
+
 export type BeautyScoringMode = 'vote_divisor' | 'winner_bonus';
 
 export type BeautyVotePointsDivisorMode = 'manual' | 'player_count_auto' | 'median_auto';
@@ -83,6 +114,8 @@ export interface DixitEndRoundHistoryEntry {
 	player_clue_ratings: Record<string, number>;
 	total_after_round: Record<string, number>;
 	beauty_total_after_round: Record<string, number>;
+	handicap_after_round: Record<string, number>;
+	pass_penalty_deltas: Record<string, number>;
 	results_display_mode: BeautyResultsDisplayMode;
 }
 
@@ -93,6 +126,7 @@ export interface LeaderboardRoundHistoryEntry {
 	beauty_deltas: Record<string, number>;
 	total_after_round: Record<string, number>;
 	beauty_total_after_round: Record<string, number>;
+	raw_total_after_round: Record<string, number>;
 }
 
 export interface LeaderboardScoreSnapshot {
@@ -115,4 +149,13 @@ export type WinCondition =
 	  }
 	| {
 			mode: 'cards_finish';
+	  }
+	| {
+			mode: 'time_limit';
+			minutes: number;
+			finish_cycle: boolean;
+	  }
+	| {
+			mode: 'any_of' | 'all_of';
+			conditions: WinCondition[];
 	  };
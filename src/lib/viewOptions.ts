@@ -54,6 +54,8 @@ export const stickyVotingCardNavigatorCollapsed = createPersistentBooleanStore(
 	'sticky_voting_card_navigator_collapsed',
 	false
 );
+// Sent to the server on join; stored here so it follows the browser across rooms.
+export const statsNameOptOut = createPersistentBooleanStore('stats_name_opt_out', false);
 export const moderatorDevModeEnabled = createPersistentBooleanStore(
 	'moderator_dev_mode_enabled',
 	false
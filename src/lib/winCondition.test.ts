@@ -0,0 +1,53 @@
+import { describe, expect, test } from 'vitest';
+import {
+	MAX_TIME_LIMIT_MINUTES,
+	defaultCompositeConditions,
+	formatWinCondition,
+	simpleWinModes,
+	withWinConditionTarget
+} from './winCondition';
+
+describe('simpleWinModes', () => {
+	test('leaves cycles out of Resonance', () => {
+		expect(simpleWinModes('stella')).not.toContain('cycles');
+		expect(simpleWinModes('dixit_plus')).toContain('cycles');
+	});
+});
+
+describe('withWinConditionTarget', () => {
+	test('clamps time limits to the server maximum', () => {
+		expect(
+			withWinConditionTarget({ mode: 'time_limit', minutes: 60, finish_cycle: true }, 5000)
+		).toEqual({ mode: 'time_limit', minutes: MAX_TIME_LIMIT_MINUTES, finish_cycle: true });
+	});
+
+	test('keeps targets at least 1', () => {
+		expect(withWinConditionTarget({ mode: 'points', target_points: 10 }, 0)).toEqual({
+			mode: 'points',
+			target_points: 1
+		});
+	});
+});
+
+describe('defaultCompositeConditions', () => {
+	test('starts from the current condition plus another mode', () => {
+		const conditions = defaultCompositeConditions(
+			{ mode: 'cycles', target_cycles: 2 },
+			'dixit_plus'
+		);
+		expect(conditions).toEqual([{ mode: 'cycles', target_cycles: 2 }, { mode: 'cards_finish' }]);
+	});
+
+	test('keeps the children when switching between any_of and all_of', () => {
+		const conditions = [
+			{ mode: 'points' as const, target_points: 30 },
+			{ mode: 'time_limit' as const, minutes: 45, finish_cycle: false }
+		];
+		expect(defaultCompositeConditions({ mode: 'any_of', conditions }, 'stella')).toEqual(
+			conditions
+		);
+		expect(formatWinCondition({ mode: 'all_of', conditions })).toBe(
+			'First to 30 points! and 45 minutes'
+		);
+	});
+});
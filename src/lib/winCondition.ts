@@ -1,4 +1,69 @@
-import type { WinCondition } from '$lib/types';
+import type { GameMode, WinCondition } from '$lib/types';
+
+export type SimpleWinCondition = Exclude<WinCondition, { mode: 'any_of' | 'all_of' }>;
+export type SimpleWinMode = SimpleWinCondition['mode'];
+
+// mirrors MAX_COMPOSITE_WIN_CONDITIONS and MAX_WIN_CONDITION_TIME_LIMIT_MINUTES on the server
+export const MAX_COMPOSITE_WIN_CONDITIONS = 4;
+export const MAX_TIME_LIMIT_MINUTES = 24 * 60;
+
+export const WIN_MODE_LABELS: Record<WinCondition['mode'], string> = {
+	cards_finish: 'Cards finish',
+	points: 'Points',
+	cycles: 'Cycles',
+	fixed_rounds: 'Fixed rounds',
+	time_limit: 'Time limit',
+	any_of: 'Any of (first met ends)',
+	all_of: 'All of (every one met)'
+};
+
+export function simpleWinModes(gameMode: GameMode): SimpleWinMode[] {
+	return gameMode === 'stella'
+		? ['cards_finish', 'points', 'fixed_rounds', 'time_limit']
+		: ['cards_finish', 'points', 'cycles', 'fixed_rounds', 'time_limit'];
+}
+
+export function defaultSimpleWinCondition(mode: SimpleWinMode): SimpleWinCondition {
+	if (mode === 'points') return { mode, target_points: 10 };
+	if (mode === 'cycles') return { mode, target_cycles: 1 };
+	if (mode === 'fixed_rounds') return { mode, target_rounds: 6 };
+	if (mode === 'time_limit') return { mode, minutes: 60, finish_cycle: false };
+	return { mode };
+}
+
+export function winConditionTarget(condition: SimpleWinCondition): number | null {
+	if (condition.mode === 'points') return condition.target_points;
+	if (condition.mode === 'cycles') return condition.target_cycles;
+	if (condition.mode === 'fixed_rounds') return condition.target_rounds;
+	if (condition.mode === 'time_limit') return condition.minutes;
+	return null;
+}
+
+export function withWinConditionTarget(
+	condition: SimpleWinCondition,
+	value: number
+): SimpleWinCondition {
+	const normalized = Math.max(1, Math.floor(value || 1));
+	if (condition.mode === 'points') return { ...condition, target_points: normalized };
+	if (condition.mode === 'cycles') return { ...condition, target_cycles: normalized };
+	if (condition.mode === 'fixed_rounds') return { ...condition, target_rounds: normalized };
+	if (condition.mode === 'time_limit') {
+		return { ...condition, minutes: Math.min(MAX_TIME_LIMIT_MINUTES, normalized) };
+	}
+	return condition;
+}
+
+// Starting children for a new composite: the current single condition plus the first other mode.
+export function defaultCompositeConditions(
+	current: WinCondition,
+	gameMode: GameMode
+): SimpleWinCondition[] {
+	if (current.mode === 'any_of' || current.mode === 'all_of') {
+		return current.conditions as SimpleWinCondition[];
+	}
+	const nextMode = simpleWinModes(gameMode).find((mode) => mode !== current.mode) ?? 'points';
+	return [current, defaultSimpleWinCondition(nextMode)];
+}
 
 export function formatWinCondition(winCondition: WinCondition): string {
 	if (winCondition.mode === 'points') {
@@ -10,5 +75,13 @@ export function formatWinCondition(winCondition: WinCondition): string {
 	if (winCondition.mode === 'fixed_rounds') {
 		return `${winCondition.target_rounds} fixed round${winCondition.target_rounds === 1 ? '' : 's'}`;
 	}
+	if (winCondition.mode === 'time_limit') {
+		const limit = `${winCondition.minutes} minute${winCondition.minutes === 1 ? '' : 's'}`;
+		return winCondition.finish_cycle ? `${limit}, then finish the cycle` : limit;
+	}
+	if (winCondition.mode === 'any_of' || winCondition.mode === 'all_of') {
+		const joiner = winCondition.mode === 'any_of' ? ' or ' : ' and ';
+		return winCondition.conditions.map(formatWinCondition).join(joiner);
+	}
 	return 'Play until cards finish';
 }
@@ -0,0 +1,213 @@
+use std::sync::OnceLock;
+
+use argon2::{
+    password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
+    Argon2,
+};
+use rand::Rng;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+// Room tokens with this prefix are issued by the server for signed-in members. Clients cannot
+// send them directly.
+pub const ACCOUNT_TOKEN_PREFIX: &str = "account:";
+pub const MIN_USERNAME_LEN: usize = 3;
+pub const MAX_USERNAME_LEN: usize = 30;
+pub const MIN_ACCOUNT_PASSWORD_LEN: usize = 8;
+pub const MAX_ACCOUNT_PASSWORD_LEN: usize = 200;
+// sessions expire after this long without being used
+pub const ACCOUNT_SESSION_TTL_S: u64 = 90 * 24 * 60 * 60;
+pub const LOGIN_CODE_TTL_S: u64 = 10 * 60;
+pub const LOGIN_CODE_MAX_ATTEMPTS: u32 = 5;
+const LOGIN_CODE_LEN: usize = 8;
+// no 0/O or 1/I/L, since codes are read off a terminal
+const LOGIN_CODE_ALPHABET: &[u8] = b"ABCDEFGHJKMNPQRSTUVWXYZ23456789";
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct Account {
+    pub account_id: String,
+    pub username: String,
+    // stats identity shared by every device signed in to the account
+    pub player_hash: String,
+    pub has_password: bool,
+    pub created_at_s: u64,
+}
+
+impl Account {
+    // the room token used for this account's members; see ACCOUNT_TOKEN_PREFIX
+    pub fn room_token(&self) -> String {
+        format!("{}{}", ACCOUNT_TOKEN_PREFIX, self.account_id)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct AccountSessionResponse {
+    pub account: Account,
+    pub session_token: String,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct AccountMergeReport {
+    pub account_id: String,
+    pub merged_player_hash: String,
+    pub rows_moved: u64,
+    // false when this history was already merged
+    pub merged: bool,
+}
+
+// Same as Room::member_hash_for_name for a member joined with `room_token`.
+pub fn player_hash_for_token(room_token: &str) -> String {
+    sha256_hex(room_token)
+}
+
+pub fn is_account_room_token(token: &str) -> bool {
+    token.trim().starts_with(ACCOUNT_TOKEN_PREFIX)
+}
+
+pub fn normalize_username(username: &str) -> Result<String, String> {
+    let username = username.trim();
+    if username.len() < MIN_USERNAME_LEN || username.len() > MAX_USERNAME_LEN {
+        return Err(format!(
+            "Usernames must be {}-{} characters",
+            MIN_USERNAME_LEN, MAX_USERNAME_LEN
+        ));
+    }
+    if !username
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+    {
+        return Err("Usernames may only use letters, digits, '_', '-' and '.'".to_string());
+    }
+    Ok(username.to_string())
+}
+
+pub fn validate_account_password(password: &str) -> Result<(), String> {
+    if password.len() < MIN_ACCOUNT_PASSWORD_LEN || password.len() > MAX_ACCOUNT_PASSWORD_LEN {
+        return Err(format!(
+            "Passwords must be {}-{} bytes",
+            MIN_ACCOUNT_PASSWORD_LEN, MAX_ACCOUNT_PASSWORD_LEN
+        ));
+    }
+    Ok(())
+}
+
+// Argon2id PHC string with a random salt. Takes tens of milliseconds by design; call it off the
+// async runtime.
+pub fn hash_account_password(password: &str) -> String {
+    let salt = SaltString::encode_b64(&rand::random::<[u8; 16]>())
+        .expect("16 salt bytes are within the PHC salt length limits");
+    Argon2::default()
+        .hash_password(password.as_bytes(), &salt)
+        .expect("default argon2 params accept any password length")
+        .to_string()
+}
+
+pub fn verify_account_password(password_hash: &str, password: &str) -> bool {
+    PasswordHash::new(password_hash).is_ok_and(|parsed| {
+        Argon2::default()
+            .verify_password(password.as_bytes(), &parsed)
+            .is_ok()
+    })
+}
+
+// Does the same Argon2 work as a real check and always fails. Logins for unknown usernames and
+// passwordless accounts use it, so response times do not reveal which usernames exist.
+pub fn verify_missing_account_password(password: &str) -> bool {
+    static DUMMY_HASH: OnceLock<String> = OnceLock::new();
+    let dummy_hash = DUMMY_HASH.get_or_init(|| hash_account_password(&generate_session_token()));
+    let _ = verify_account_password(dummy_hash, password);
+    false
+}
+
+pub fn generate_account_id() -> String {
+    format!("{:032x}", rand::random::<u128>())
+}
+
+pub fn generate_session_token() -> String {
+    format!(
+        "{:032x}{:032x}",
+        rand::random::<u128>(),
+        rand::random::<u128>()
+    )
+}
+
+pub fn generate_login_code() -> String {
+    let mut rng = rand::thread_rng();
+    (0..LOGIN_CODE_LEN)
+        .map(|_| LOGIN_CODE_ALPHABET[rng.gen_range(0..LOGIN_CODE_ALPHABET.len())] as char)
+        .collect()
+}
+
+// Session tokens and login codes are stored hashed; both are random enough that a fast hash is
+// fine. Codes are case-insensitive and ignore spaces.
+pub fn hash_session_token(token: &str) -> String {
+    sha256_hex(token.trim())
+}
+
+pub fn hash_login_code(code: &str) -> String {
+    let code: String = code
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != '-')
+        .collect();
+    sha256_hex(&code.to_ascii_uppercase())
+}
+
+fn sha256_hex(value: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(value.as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn usernames_are_trimmed_and_restricted() {
+        assert_eq!(normalize_username("  Ada.L ").as_deref(), Ok("Ada.L"));
+        assert!(normalize_username("ab").is_err());
+        assert!(normalize_username(&"a".repeat(MAX_USERNAME_LEN + 1)).is_err());
+        assert!(normalize_username("ada lovelace").is_err());
+        assert!(normalize_username("ada:1").is_err());
+    }
+
+    #[test]
+    fn account_passwords_are_salted_and_missing_accounts_never_verify() {
+        let a = hash_account_password("correct horse");
+        let b = hash_account_password("correct horse");
+        assert_ne!(a, b);
+        assert!(a.starts_with("$argon2id$"));
+        assert!(verify_account_password(&a, "correct horse"));
+        assert!(!verify_account_password(&a, "Correct horse"));
+        assert!(!verify_account_password(
+            "not a phc string",
+            "correct horse"
+        ));
+        assert!(!verify_missing_account_password("correct horse"));
+    }
+
+    #[test]
+    fn login_codes_avoid_ambiguous_characters_and_match_loosely() {
+        let code = generate_login_code();
+        assert_eq!(code.len(), LOGIN_CODE_LEN);
+        assert!(code.bytes().all(|c| LOGIN_CODE_ALPHABET.contains(&c)));
+        assert_eq!(hash_login_code("abcd efgh"), hash_login_code("ABCD-EFGH"));
+    }
+
+    #[test]
+    fn account_room_tokens_map_to_the_account_player_hash() {
+        let account = Account {
+            account_id: "42".to_string(),
+            username: "ada".to_string(),
+            player_hash: player_hash_for_token("account:42"),
+            has_password: false,
+            created_at_s: 0,
+        };
+        assert!(is_account_room_token(&account.room_token()));
+        assert!(!is_account_room_token("browser-token"));
+        assert_eq!(
+            player_hash_for_token(&account.room_token()),
+            account.player_hash
+        );
+    }
+}
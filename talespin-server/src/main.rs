@@ -3,14 +3,15 @@ use axum::{
     body::Bytes,
     extract::{
         ws::{Message as WsMessage, WebSocket},
-        Json, Path as AxumPath, Query, State, WebSocketUpgrade,
+        ConnectInfo, Json, Path as AxumPath, Query, Request, State, WebSocketUpgrade,
     },
-    http::{header, Method, StatusCode},
+    http::{header, HeaderMap, Method, StatusCode},
+    middleware::{self, Next},
     response::{IntoResponse, Response},
     routing::{get, post},
     Router,
 };
-use dashmap::DashMap;
+use dashmap::{mapref::entry::Entry, DashMap};
 use image::{codecs::jpeg::JpegEncoder, imageops::FilterType, GenericImageView};
 use indicatif::{ProgressBar, ProgressStyle};
 use serde::{Deserialize, Serialize};
@@ -19,28 +20,59 @@ use std::{
     collections::{HashMap, HashSet, VecDeque},
     env, fs,
     io::BufWriter,
-    net::SocketAddr,
+    net::{IpAddr, SocketAddr},
     path::{Path, PathBuf},
     sync::Arc,
 };
+use subtle::ConstantTimeEq;
+use tokio::sync::watch;
 use tower_http::{
     cors::{Any, CorsLayer},
     trace::TraceLayer,
 };
 
+mod accounts;
 mod avif;
 mod most_beautiful_stats;
+mod rate_limit;
 mod room;
+mod room_id;
 
-use most_beautiful_stats::{MostBeautifulStatsResponse, MostBeautifulStatsStore};
-use rand::distributions::{Distribution, Uniform};
+use accounts::{
+    generate_login_code, hash_account_password, hash_login_code, is_account_room_token,
+    normalize_username, player_hash_for_token, validate_account_password, verify_account_password,
+    verify_missing_account_password, Account, AccountMergeReport, AccountSessionResponse,
+    LOGIN_CODE_TTL_S,
+};
+use most_beautiful_stats::{
+    stats_export_csv, CardStatsResponse, ClueArchiveResponse, ClueSearchQuery, GameReplay,
+    HeadToHeadResponse, MostBeautifulStatsResponse, MostBeautifulStatsStore, PersistentRoomRecord,
+    PlayerErasureMode, PlayerErasureReport, PlayerProfileStats, PlayerRatingHistory,
+    PlayerRatingLeaderboard, StatsExportResponse, StatsExportTable, StatsWriterMetrics,
+    DEFAULT_STATS_WRITER_QUEUE_CAPACITY,
+};
+use rate_limit::{
+    client_ip, IpRateLimiter, RoomRateLimits, TokenBucketConfig, WsMessageLimits,
+    DEFAULT_WS_MAX_MESSAGE_BYTES, DEFAULT_WS_MESSAGE_BURST, DEFAULT_WS_MESSAGE_RATE,
+};
 use room::{
-    canonical_member_name, get_time_s, hash_room_password, Room, ServerMsg, StellaWordPackPreset,
-    WinCondition, MAX_MEMBER_NAME_LEN,
+    canonical_member_name, generate_room_owner_token, get_time_ms, get_time_s,
+    hash_room_owner_token, hash_room_password, validate_win_condition, PersistentRoomSnapshot,
+    PublicRoomSummary, Room, RoomStage, ServerMsg, StellaWordPackPreset, WinCondition,
+    MAX_MEMBER_NAME_LEN, MAX_ROOM_PASSWORD_LEN,
+};
+use room_id::{
+    normalize_custom_room_id, RoomIdGenerator, DEFAULT_ROOM_ID_ALPHABET, DEFAULT_ROOM_ID_LENGTH,
+    DEFAULT_ROOM_ID_WORD_COUNT,
 };
 
 const GARBAGE_COLLECT_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60 * 20); // 20 minutes
 const ROOM_MAINTENANCE_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+const STATS_RETENTION_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60 * 60); // 1 hour
+const PUBLIC_ROOMS_REFRESH_INTERVAL: std::time::Duration = std::time::Duration::from_secs(2);
+const PERSISTENT_ROOM_SAVE_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60);
+// /rooms/public and the directory subscription list at most this many rooms
+const MAX_PUBLIC_ROOMS_LISTED: usize = 200;
 const GC_ROOM_TIMEOUT_S: u64 = 60 * 60; // 1 hour
 
 const BUILTIN_IMAGE_DIR: &str = "../static/assets/cards/";
@@ -62,11 +94,39 @@ const SHOW_IMAGE_PATH_ENV: &str = "TALESPIN_IMAGES_SHOW_PATH_P";
 const DEFAULT_WIN_POINTS_ENV: &str = "TALESPIN_DEFAULT_WIN_POINTS";
 const MAX_MEMBERS_ENV: &str = "TALESPIN_MAX_MEMBERS";
 const MB_STATS_DB_PATH_ENV: &str = "TALESPIN_MB_STATS_DB_PATH";
+const MB_STATS_WRITER_QUEUE_ENV: &str = "TALESPIN_MB_STATS_WRITER_QUEUE";
+const MB_STATS_NAME_RETENTION_DAYS_ENV: &str = "TALESPIN_MB_STATS_NAME_RETENTION_DAYS";
+const ADMIN_TOKEN_ENV: &str = "TALESPIN_ADMIN_TOKEN";
+const DISABLE_GLOBAL_MB_STATS_ENV: &str = "TALESPIN_DISABLE_GLOBAL_MB_STATS_P";
+// room password for room-scoped stats; a header so it stays out of URLs and access logs
+const ROOM_PASSWORD_HEADER: &str = "x-room-password";
+const ROOM_ID_STYLE_ENV: &str = "TALESPIN_ROOM_ID_STYLE";
+const ROOM_ID_LENGTH_ENV: &str = "TALESPIN_ROOM_ID_LENGTH";
+const ROOM_ID_ALPHABET_ENV: &str = "TALESPIN_ROOM_ID_ALPHABET";
+const EXISTS_RATE_LIMIT_ENV: &str = "TALESPIN_EXISTS_RATE_LIMIT";
+const CREATE_RATE_LIMIT_ENV: &str = "TALESPIN_CREATE_RATE_LIMIT";
+const JOIN_RATE_LIMIT_ENV: &str = "TALESPIN_JOIN_RATE_LIMIT";
+const HTTP_RATE_LIMIT_ENV: &str = "TALESPIN_HTTP_RATE_LIMIT";
+const LOGIN_RATE_LIMIT_ENV: &str = "TALESPIN_LOGIN_RATE_LIMIT";
+const MAX_ROOMS_PER_IP_ENV: &str = "TALESPIN_MAX_ROOMS_PER_IP";
+const WS_MESSAGE_RATE_ENV: &str = "TALESPIN_WS_MESSAGE_RATE";
+const WS_MESSAGE_BURST_ENV: &str = "TALESPIN_WS_MESSAGE_BURST";
+const WS_MAX_MESSAGE_BYTES_ENV: &str = "TALESPIN_WS_MAX_MESSAGE_BYTES";
 
 const DEFAULT_CARD_ASPECT_RATIO: &str = "2:3";
 const DEFAULT_CARD_LONG_SIDE: u32 = 1536;
 const DEFAULT_WIN_POINTS: u16 = 10;
 const DEFAULT_MAX_MEMBERS: usize = 64;
+// requests per IP per minute; 0 disables a limit
+const DEFAULT_EXISTS_RATE_LIMIT: u32 = 60;
+const DEFAULT_CREATE_RATE_LIMIT: u32 = 10;
+const DEFAULT_JOIN_RATE_LIMIT: u32 = 30;
+// shared by every route, including card images
+const DEFAULT_HTTP_RATE_LIMIT: u32 = 1200;
+// account registration, password logins, and login codes together
+const DEFAULT_LOGIN_RATE_LIMIT: u32 = 10;
+// live rooms one IP may have created; 0 disables the cap
+const DEFAULT_MAX_ROOMS_PER_IP: u32 = 5;
 const DEFAULT_CACHE_DIR: &str = "~/.cache/talespin";
 const CACHE_SUBDIR_CARDS: &str = "cards";
 const DEFAULT_MB_STATS_DB_FILENAME: &str = "most_beautiful_stats.sqlite3";
@@ -480,6 +540,72 @@ fn parse_max_members_from_env() -> usize {
     DEFAULT_MAX_MEMBERS
 }
 
+fn parse_room_id_generator_from_env() -> RoomIdGenerator {
+    let style = env::var(ROOM_ID_STYLE_ENV)
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    let length = |default: usize| match env::var(ROOM_ID_LENGTH_ENV) {
+        Ok(raw) => raw
+            .trim()
+            .parse::<usize>()
+            .map_err(|_| format!("{}='{}' is not a number", ROOM_ID_LENGTH_ENV, raw)),
+        Err(_) => Ok(default),
+    };
+    let generator = match style.as_str() {
+        "" | "letters" => length(DEFAULT_ROOM_ID_LENGTH).and_then(|length| {
+            let alphabet = env::var(ROOM_ID_ALPHABET_ENV)
+                .unwrap_or_else(|_| DEFAULT_ROOM_ID_ALPHABET.to_string());
+            RoomIdGenerator::letters(length, &alphabet)
+        }),
+        "words" => length(DEFAULT_ROOM_ID_WORD_COUNT).and_then(RoomIdGenerator::words),
+        other => Err(format!("unknown {}='{}'", ROOM_ID_STYLE_ENV, other)),
+    };
+
+    generator.unwrap_or_else(|err| {
+        println!(
+            "Warning: invalid room id settings ({}); using {} lowercase letters",
+            err, DEFAULT_ROOM_ID_LENGTH
+        );
+        RoomIdGenerator::default()
+    })
+}
+
+fn parse_rate_limit_from_env(key: &str, default: u32) -> u32 {
+    if let Ok(raw) = env::var(key) {
+        if let Ok(value) = raw.trim().parse::<u32>() {
+            return value;
+        }
+
+        println!(
+            "Warning: invalid {}='{}'; using default {}",
+            key, raw, default
+        );
+    }
+
+    default
+}
+
+fn parse_ws_message_limits_from_env() -> WsMessageLimits {
+    let rate = parse_rate_limit_from_env(WS_MESSAGE_RATE_ENV, DEFAULT_WS_MESSAGE_RATE);
+    let burst = parse_rate_limit_from_env(WS_MESSAGE_BURST_ENV, DEFAULT_WS_MESSAGE_BURST);
+    let mut max_message_bytes = DEFAULT_WS_MAX_MESSAGE_BYTES;
+    if let Ok(raw) = env::var(WS_MAX_MESSAGE_BYTES_ENV) {
+        match raw.trim().parse::<usize>() {
+            Ok(value) if value > 0 => max_message_bytes = value,
+            _ => println!(
+                "Warning: invalid {}='{}'; using default {}",
+                WS_MAX_MESSAGE_BYTES_ENV, raw, DEFAULT_WS_MAX_MESSAGE_BYTES
+            ),
+        }
+    }
+
+    WsMessageLimits {
+        rate: TokenBucketConfig::per_second(rate, burst),
+        max_message_bytes,
+    }
+}
+
 fn cache_root_dir_from_env() -> PathBuf {
     env::var(CACHE_DIR_ENV)
         .map(|v| expand_home(v.trim()))
@@ -492,6 +618,59 @@ fn parse_mb_stats_db_path_from_env() -> PathBuf {
         .unwrap_or_else(|_| cache_root_dir_from_env().join(DEFAULT_MB_STATS_DB_FILENAME))
 }
 
+fn parse_mb_stats_writer_queue_from_env() -> usize {
+    if let Ok(raw) = env::var(MB_STATS_WRITER_QUEUE_ENV) {
+        if let Ok(value) = raw.trim().parse::<usize>() {
+            if value >= 1 {
+                return value;
+            }
+        }
+
+        println!(
+            "Warning: invalid {}='{}'; using default {}",
+            MB_STATS_WRITER_QUEUE_ENV, raw, DEFAULT_STATS_WRITER_QUEUE_CAPACITY
+        );
+    }
+
+    DEFAULT_STATS_WRITER_QUEUE_CAPACITY
+}
+
+// None keeps display names forever
+fn parse_mb_stats_name_retention_days_from_env() -> Option<u64> {
+    let raw = env::var(MB_STATS_NAME_RETENTION_DAYS_ENV).ok()?;
+    match raw.trim().parse::<u64>() {
+        Ok(0) => None,
+        Ok(days) => Some(days),
+        Err(_) => {
+            println!(
+                "Warning: invalid {}='{}'; keeping display names forever",
+                MB_STATS_NAME_RETENTION_DAYS_ENV, raw
+            );
+            None
+        }
+    }
+}
+
+fn parse_admin_token_from_env() -> Option<String> {
+    env::var(ADMIN_TOKEN_ENV)
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+fn bearer_token(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(header::AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Bearer ")
+        .map(str::trim)
+}
+
+fn room_password_header(headers: &HeaderMap) -> Option<&str> {
+    headers.get(ROOM_PASSWORD_HEADER)?.to_str().ok()
+}
+
 fn env_is_y(key: &str) -> bool {
     env::var(key)
         .map(|v| v.trim().eq_ignore_ascii_case("y"))
@@ -1017,6 +1196,8 @@ struct CreateRoomRequest {
     win_condition: Option<WinCondition>,
     creator_name: Option<String>,
     password: Option<String>,
+    #[serde(default)]
+    unlisted: bool,
 }
 
 #[derive(Debug)]
@@ -1024,30 +1205,8 @@ struct CreateRoomConfig {
     win_condition: WinCondition,
     creator_name: Option<String>,
     password: Option<String>,
-}
-
-fn validate_win_condition(win_condition: WinCondition) -> Result<WinCondition> {
-    match win_condition {
-        WinCondition::Points { target_points } => {
-            if target_points == 0 {
-                return Err(anyhow!("target_points must be >= 1"));
-            }
-            Ok(WinCondition::Points { target_points })
-        }
-        WinCondition::Cycles { target_cycles } => {
-            if target_cycles == 0 {
-                return Err(anyhow!("target_cycles must be >= 1"));
-            }
-            Ok(WinCondition::Cycles { target_cycles })
-        }
-        WinCondition::FixedRounds { target_rounds } => {
-            if target_rounds == 0 {
-                return Err(anyhow!("target_rounds must be >= 1"));
-            }
-            Ok(WinCondition::FixedRounds { target_rounds })
-        }
-        WinCondition::CardsFinish => Ok(WinCondition::CardsFinish),
-    }
+    // unlisted rooms can only be found and joined with the invite key from the full invite link
+    unlisted: bool,
 }
 
 fn parse_create_room_win_condition(
@@ -1059,6 +1218,7 @@ fn parse_create_room_win_condition(
             win_condition: room::default_win_condition_for_game_mode(room::GameMode::DixitPlus),
             creator_name: None,
             password: None,
+            unlisted: false,
         });
     }
 
@@ -1074,7 +1234,7 @@ fn parse_create_room_win_condition(
     let password = password.filter(|password| !password.is_empty());
     if password
         .as_ref()
-        .map(|password| password.len() > 200)
+        .map(|password| password.len() > MAX_ROOM_PASSWORD_LEN)
         .unwrap_or(false)
     {
         return Err(anyhow!("room password too long"));
@@ -1083,6 +1243,7 @@ fn parse_create_room_win_condition(
         win_condition: validate_win_condition(requested)?,
         creator_name: creator_name.filter(|name| !name.is_empty()),
         password,
+        unlisted: request.unlisted,
     })
 }
 
@@ -1100,6 +1261,21 @@ struct ServerState {
     stella_word_pack_presets: Arc<Vec<StellaWordPackPreset>>,
     default_win_points_target: u16,
     max_members: usize,
+    // admin-only endpoints are disabled when unset
+    admin_token: Option<String>,
+    name_retention_days: Option<u64>,
+    // unscoped /most-beautiful-stats lists every player's name
+    global_mb_stats_disabled: bool,
+    room_ids: RoomIdGenerator,
+    rate_limits: Arc<RoomRateLimits>,
+    max_rooms_per_ip: usize,
+    // creator IP of each room, for the per-IP room cap
+    room_creators: DashMap<String, IpAddr>,
+    ws_message_limits: WsMessageLimits,
+    // latest public room directory, pushed to SubscribePublicRooms sockets
+    public_rooms: Arc<watch::Sender<Vec<PublicRoomSummary>>>,
+    // persistent room id -> snapshot JSON last written to mb_persistent_rooms
+    persistent_rooms: DashMap<String, String>,
 }
 
 impl ServerState {
@@ -1109,9 +1285,12 @@ impl ServerState {
         let config = NormalizationConfig::from_env()?;
         let default_win_points_target = parse_default_win_points_from_env();
         let max_members = parse_max_members_from_env();
-        let most_beautiful_stats = Arc::new(MostBeautifulStatsStore::new(
-            parse_mb_stats_db_path_from_env(),
-        )?);
+        let room_ids = parse_room_id_generator_from_env();
+        println!("Room ids: {} possible codes", room_ids.id_space());
+        let most_beautiful_stats = Arc::new(
+            MostBeautifulStatsStore::new(parse_mb_stats_db_path_from_env())?
+                .with_background_writer(parse_mb_stats_writer_queue_from_env())?,
+        );
         let word_pack_presets = load_word_pack_presets(Path::new(WORD_PACKS_DIR))?;
         let default_word_pack = choose_default_word_pack(&word_pack_presets)?;
         let extra_image_dirs = get_extra_image_dirs();
@@ -1164,6 +1343,37 @@ impl ServerState {
             stella_word_pack_presets: Arc::new(word_pack_presets),
             default_win_points_target,
             max_members,
+            admin_token: parse_admin_token_from_env(),
+            name_retention_days: parse_mb_stats_name_retention_days_from_env(),
+            global_mb_stats_disabled: env_is_y(DISABLE_GLOBAL_MB_STATS_ENV),
+            room_ids,
+            rate_limits: Arc::new(RoomRateLimits {
+                http: IpRateLimiter::new(TokenBucketConfig::per_minute(parse_rate_limit_from_env(
+                    HTTP_RATE_LIMIT_ENV,
+                    DEFAULT_HTTP_RATE_LIMIT,
+                ))),
+                exists: IpRateLimiter::new(TokenBucketConfig::per_minute(
+                    parse_rate_limit_from_env(EXISTS_RATE_LIMIT_ENV, DEFAULT_EXISTS_RATE_LIMIT),
+                )),
+                create: IpRateLimiter::new(TokenBucketConfig::per_minute(
+                    parse_rate_limit_from_env(CREATE_RATE_LIMIT_ENV, DEFAULT_CREATE_RATE_LIMIT),
+                )),
+                join: IpRateLimiter::new(TokenBucketConfig::per_minute(parse_rate_limit_from_env(
+                    JOIN_RATE_LIMIT_ENV,
+                    DEFAULT_JOIN_RATE_LIMIT,
+                ))),
+                login: IpRateLimiter::new(TokenBucketConfig::per_minute(
+                    parse_rate_limit_from_env(LOGIN_RATE_LIMIT_ENV, DEFAULT_LOGIN_RATE_LIMIT),
+                )),
+            }),
+            max_rooms_per_ip: parse_rate_limit_from_env(
+                MAX_ROOMS_PER_IP_ENV,
+                DEFAULT_MAX_ROOMS_PER_IP,
+            ) as usize,
+            room_creators: DashMap::new(),
+            ws_message_limits: parse_ws_message_limits_from_env(),
+            public_rooms: Arc::new(watch::Sender::new(Vec::new())),
+            persistent_rooms: DashMap::new(),
         })
     }
 
@@ -1172,16 +1382,21 @@ impl ServerState {
         win_condition: WinCondition,
         creator_name: Option<String>,
         room_password: Option<String>,
+        unlisted: bool,
+        creator_ip: IpAddr,
     ) -> Result<ServerMsg> {
-        let mut room_id = generate_room_id(4);
+        let mut room_id = self.room_ids.generate();
 
         while (self.get_room(&room_id)).is_some() {
-            room_id = generate_room_id(4);
+            room_id = self.room_ids.generate();
         }
 
-        let room_password_hash = room_password
-            .as_ref()
-            .map(|password| hash_room_password(&room_id, password));
+        let room_password_hash = match room_password {
+            Some(password) => {
+                Some(tokio::task::spawn_blocking(move || hash_room_password(&password)).await?)
+            }
+            None => None,
+        };
         let room = Room::new(
             &room_id,
             self.base_deck.clone(),
@@ -1192,12 +1407,223 @@ impl ServerState {
             self.most_beautiful_stats.clone(),
             self.default_stella_word_pack.clone(),
             self.stella_word_pack_presets.clone(),
-        );
+        )
+        .with_unlisted(unlisted)
+        .with_message_limits(self.ws_message_limits);
         let msg = room.get_room_state().await;
+        self.room_creators.insert(room_id.clone(), creator_ip);
         self.rooms.insert(room_id.clone(), Arc::new(room));
         Ok(msg)
     }
 
+    async fn build_persistent_room(
+        &self,
+        room_id: &str,
+        owner_token_hash: &str,
+        snapshot: PersistentRoomSnapshot,
+    ) -> Room {
+        let room = Room::new(
+            room_id,
+            self.base_deck.clone(),
+            room::default_win_condition_for_game_mode(room::GameMode::DixitPlus),
+            None,
+            self.max_members,
+            None,
+            self.most_beautiful_stats.clone(),
+            self.default_stella_word_pack.clone(),
+            self.stella_word_pack_presets.clone(),
+        )
+        .with_message_limits(self.ws_message_limits)
+        .with_owner_token_hash(Some(owner_token_hash.to_string()));
+        room.restore_persistent_snapshot(snapshot).await;
+        room
+    }
+
+    // Returns the owner token, or None when the id is already taken.
+    async fn create_persistent_room(
+        &self,
+        room_id: &str,
+        room_password: Option<String>,
+        unlisted: bool,
+    ) -> Result<Option<String>> {
+        if self.get_room(room_id).is_some() {
+            return Ok(None);
+        }
+        let owner_token = generate_room_owner_token();
+        let owner_token_hash = hash_room_owner_token(&owner_token);
+        let room_password_hash = match room_password {
+            Some(password) => {
+                Some(tokio::task::spawn_blocking(move || hash_room_password(&password)).await?)
+            }
+            None => None,
+        };
+        let room = Room::new(
+            room_id,
+            self.base_deck.clone(),
+            room::default_win_condition_for_game_mode(room::GameMode::DixitPlus),
+            None,
+            self.max_members,
+            room_password_hash,
+            self.most_beautiful_stats.clone(),
+            self.default_stella_word_pack.clone(),
+            self.stella_word_pack_presets.clone(),
+        )
+        .with_unlisted(unlisted)
+        .with_message_limits(self.ws_message_limits)
+        .with_owner_token_hash(Some(owner_token_hash.clone()));
+        let snapshot = serde_json::to_string(&room.persistent_snapshot().await)?;
+        let room = Arc::new(room);
+        // A room created with the same id while hashing keeps its id. The owner row is only
+        // written once the id is reserved here.
+        match self.rooms.entry(room_id.to_string()) {
+            Entry::Occupied(_) => return Ok(None),
+            Entry::Vacant(entry) => {
+                entry.insert(room.clone());
+            }
+        }
+        let created = {
+            let (room_id, owner_token_hash, snapshot) = (
+                room_id.to_string(),
+                owner_token_hash.clone(),
+                snapshot.clone(),
+            );
+            self.run_stats_query(move |stats| {
+                stats.create_persistent_room(&room_id, &owner_token_hash, &snapshot, get_time_s())
+            })
+            .await
+        };
+        if !matches!(created, Ok(true)) {
+            self.rooms
+                .remove_if(room_id, |_, existing| Arc::ptr_eq(existing, &room));
+            return created.map(|_| None);
+        }
+        self.persistent_rooms.insert(room_id.to_string(), snapshot);
+        println!("Created persistent room {}", room_id);
+        Ok(Some(owner_token))
+    }
+
+    async fn restore_persistent_rooms(&self) -> Result<()> {
+        let records = self.most_beautiful_stats.persistent_rooms()?;
+        for record in &records {
+            let snapshot = match serde_json::from_str(&record.snapshot) {
+                Ok(snapshot) => snapshot,
+                Err(err) => {
+                    println!(
+                        "Skipping persistent room {} with an unreadable snapshot: {}",
+                        record.room_id, err
+                    );
+                    continue;
+                }
+            };
+            let room = self
+                .build_persistent_room(&record.room_id, &record.owner_token_hash, snapshot)
+                .await;
+            self.rooms.insert(record.room_id.clone(), Arc::new(room));
+            self.persistent_rooms
+                .insert(record.room_id.clone(), record.snapshot.clone());
+        }
+        if !records.is_empty() {
+            println!("Restored {} persistent rooms", records.len());
+        }
+        Ok(())
+    }
+
+    // Writes the snapshot of every persistent room that changed since the last save.
+    async fn save_persistent_rooms(&self) {
+        let room_ids: Vec<String> = self
+            .persistent_rooms
+            .iter()
+            .map(|entry| entry.key().clone())
+            .collect();
+        for room_id in room_ids {
+            if let Some(room) = self.get_room(&room_id) {
+                self.save_persistent_room(&room_id, &room).await;
+            }
+        }
+    }
+
+    async fn save_persistent_room(&self, room_id: &str, room: &Room) {
+        let snapshot = match serde_json::to_string(&room.persistent_snapshot().await) {
+            Ok(snapshot) => snapshot,
+            Err(err) => {
+                println!("Failed to serialize persistent room {}: {}", room_id, err);
+                return;
+            }
+        };
+        let changed = self
+            .persistent_rooms
+            .get(room_id)
+            .is_some_and(|saved| *saved != snapshot);
+        // released, or nothing changed
+        if !changed {
+            return;
+        }
+
+        let (save_id, saved_snapshot) = (room_id.to_string(), snapshot.clone());
+        match self
+            .run_stats_query(move |stats| {
+                stats.save_persistent_room_snapshot(&save_id, &saved_snapshot, get_time_s())
+            })
+            .await
+        {
+            Ok(()) => {
+                // a release while the write was queued leaves nothing to update
+                if let Some(mut saved) = self.persistent_rooms.get_mut(room_id) {
+                    *saved = snapshot;
+                }
+            }
+            Err(err) => println!("Failed to save persistent room {}: {}", room_id, err),
+        }
+    }
+
+    // Idle persistent rooms go back to a fresh lobby with the same settings and members instead
+    // of being removed.
+    async fn reset_persistent_room(&self, room_id: &str, room: &Room) {
+        let lookup_id = room_id.to_string();
+        let owner_token_hash = match self
+            .run_stats_query(move |stats| stats.persistent_room(&lookup_id))
+            .await
+        {
+            Ok(Some(record)) => record.owner_token_hash,
+            Ok(None) => return,
+            Err(err) => {
+                println!("Failed to load persistent room {}: {}", room_id, err);
+                return;
+            }
+        };
+        self.save_persistent_room(room_id, room).await;
+        let fresh = self
+            .build_persistent_room(room_id, &owner_token_hash, room.persistent_snapshot().await)
+            .await;
+        self.rooms.insert(room_id.to_string(), Arc::new(fresh));
+        println!("(gc) reset persistent room {}", room_id);
+    }
+
+    async fn release_persistent_room(&self, room_id: &str) -> Result<bool> {
+        let delete_id = room_id.to_string();
+        let released = self
+            .run_stats_query(move |stats| stats.delete_persistent_room(&delete_id))
+            .await?;
+        self.persistent_rooms.remove(room_id);
+        if let Some(room) = self.get_room(room_id) {
+            room.release_persistence().await?;
+        }
+        if released {
+            println!("Released persistent room {}", room_id);
+        }
+        Ok(released)
+    }
+
+    fn room_cap_reached(&self, ip: IpAddr) -> bool {
+        self.max_rooms_per_ip > 0
+            && self
+                .room_creators
+                .iter()
+                .filter(|entry| *entry.value() == ip && self.rooms.contains_key(entry.key()))
+                .count()
+                >= self.max_rooms_per_ip
+    }
+
     async fn join_room(
         &self,
         room_id: &str,
@@ -1205,9 +1631,12 @@ impl ServerState {
         name: &str,
         token: &str,
         room_password: Option<&str>,
+        invite: Option<&str>,
+        client_ip: IpAddr,
     ) -> Result<()> {
         if let Some(room) = self.get_room(room_id) {
-            room.on_connection(socket, name, token, room_password).await;
+            room.on_connection(socket, name, token, room_password, invite, client_ip)
+                .await;
         } else {
             socket.send(ServerMsg::InvalidRoomId {}.into()).await?;
             return Ok(());
@@ -1216,6 +1645,149 @@ impl ServerState {
         Ok(())
     }
 
+    async fn spectate_room(
+        &self,
+        room_id: &str,
+        socket: &mut WebSocket,
+        spectator_token: &str,
+    ) -> Result<()> {
+        if let Some(room) = self.get_room(room_id) {
+            room.on_spectator_connection(socket, spectator_token).await;
+        } else {
+            socket.send(ServerMsg::InvalidRoomId {}.into()).await?;
+        }
+
+        Ok(())
+    }
+
+    // Stats store calls block on SQLite, so they run on the blocking thread pool.
+    async fn run_stats_query<T, F>(&self, query: F) -> Result<T>
+    where
+        F: FnOnce(&MostBeautifulStatsStore) -> Result<T> + Send + 'static,
+        T: Send + 'static,
+    {
+        let stats = self.most_beautiful_stats.clone();
+        tokio::task::spawn_blocking(move || query(&stats)).await?
+    }
+
+    // Expired or unknown sessions and database errors all read as signed out.
+    async fn account_for_session(&self, session_token: &str) -> Option<Account> {
+        let session_token = session_token.trim().to_string();
+        if session_token.is_empty() {
+            return None;
+        }
+        self.run_stats_query(move |stats| stats.account_for_session(&session_token, get_time_s()))
+            .await
+            .unwrap_or_else(|err| {
+                println!("Failed to look up account session: {}", err);
+                None
+            })
+    }
+
+    fn is_admin_request(&self, headers: &HeaderMap) -> bool {
+        match (&self.admin_token, bearer_token(headers)) {
+            // digests first, so neither the contents nor the length of the token leak through timing
+            (Some(admin_token), Some(token)) => Sha256::digest(admin_token.as_bytes())
+                .ct_eq(&Sha256::digest(token.as_bytes()))
+                .into(),
+            _ => false,
+        }
+    }
+
+    // Admins may export anything. Moderators may export a room's games with the room's export
+    // token, sent as a bearer token or a `token` query parameter.
+    async fn can_export_stats(
+        &self,
+        headers: &HeaderMap,
+        query_token: Option<&str>,
+        room_id: Option<&str>,
+    ) -> bool {
+        if self.is_admin_request(headers) {
+            return true;
+        }
+        let (Some(room_id), Some(token)) = (room_id, bearer_token(headers).or(query_token)) else {
+            return false;
+        };
+        match self.get_room(room_id) {
+            Some(room) => room.stats_export_token_is_valid(token).await,
+            None => false,
+        }
+    }
+
+    // Applies to every stats endpoint. Unscoped stats are open unless disabled. Room-scoped stats
+    // need the admin token, or proof of membership in the room, or the room password.
+    async fn can_read_stats(
+        &self,
+        client_ip: IpAddr,
+        headers: &HeaderMap,
+        access: &StatsAccessQuery,
+        room_id: Option<&str>,
+    ) -> bool {
+        let Some(room_id) = room_id else {
+            return !self.global_mb_stats_disabled;
+        };
+        if self.is_admin_request(headers) {
+            return true;
+        }
+        // signed-in members send their account session instead of a browser token
+        let bearer = bearer_token(headers)
+            .or(access.token.as_deref())
+            .filter(|token| !is_account_room_token(token));
+        let account_token = match bearer {
+            Some(token) => self
+                .account_for_session(token)
+                .await
+                .map(|account| account.room_token()),
+            None => None,
+        };
+        match self.get_room(room_id) {
+            Some(room) => {
+                room.stats_read_is_allowed(
+                    client_ip,
+                    account_token.as_deref().or(bearer),
+                    access.room_auth_id.as_deref(),
+                    room_password_header(headers),
+                )
+                .await
+            }
+            None => false,
+        }
+    }
+
+    // Listed rooms a new player could join now: lobbies first, then fullest first.
+    async fn public_rooms(&self) -> Vec<PublicRoomSummary> {
+        let rooms: Vec<Arc<Room>> = self
+            .rooms
+            .iter()
+            .map(|entry| entry.value().clone())
+            .collect();
+        let mut listed = Vec::new();
+        for room in rooms {
+            if let Some(summary) = room.public_summary().await {
+                listed.push(summary);
+            }
+        }
+        listed.sort_by(|a, b| {
+            (b.stage == RoomStage::Joining)
+                .cmp(&(a.stage == RoomStage::Joining))
+                .then(b.player_count.cmp(&a.player_count))
+                .then_with(|| a.room_id.cmp(&b.room_id))
+        });
+        listed.truncate(MAX_PUBLIC_ROOMS_LISTED);
+        listed
+    }
+
+    async fn refresh_public_rooms(&self) {
+        let rooms = self.public_rooms().await;
+        self.public_rooms.send_if_modified(|current| {
+            if *current == rooms {
+                return false;
+            }
+            *current = rooms;
+            true
+        });
+    }
+
     fn get_room(&self, room_id: &str) -> Option<Arc<Room>> {
         self.rooms.get(room_id).map(|r| r.value().clone())
     }
@@ -1232,20 +1804,46 @@ impl ServerState {
             .collect()
     }
 
-    fn garbage_collect(&self) {
+    async fn garbage_collect(&self) {
         let mut to_remove = Vec::new();
+        let mut to_reset = Vec::new();
         for entry in &self.rooms {
             if entry.value().num_active() == 0
                 && get_time_s() - entry.value().last_access() > GC_ROOM_TIMEOUT_S
             {
-                to_remove.push(entry.key().clone());
+                if self.persistent_rooms.contains_key(entry.key()) {
+                    to_reset.push((entry.key().clone(), entry.value().clone()));
+                } else {
+                    to_remove.push(entry.key().clone());
+                }
+            }
+        }
+        for (room_id, room) in to_reset {
+            if room.stage().await != RoomStage::Joining {
+                self.reset_persistent_room(&room_id, &room).await;
             }
         }
 
+        self.rate_limits.prune(get_time_ms());
+
         println!("(gc) rooms to delete {:?}", to_remove);
         for room_id in to_remove {
             self.rooms.remove(&room_id);
         }
+        self.room_creators
+            .retain(|room_id, _| self.rooms.contains_key(room_id));
+    }
+
+    async fn apply_name_retention(&self, retention_days: u64) {
+        let cutoff_s = get_time_s().saturating_sub(retention_days * 24 * 60 * 60);
+        match self
+            .run_stats_query(move |stats| stats.anonymize_names_recorded_before(cutoff_s))
+            .await
+        {
+            Ok(0) => {}
+            Ok(changed) => println!("(retention) anonymized {} stats rows", changed),
+            Err(err) => println!("Failed to apply stats name retention: {}", err),
+        }
     }
 
     async fn run_room_maintenance(&self) {
@@ -1263,7 +1861,28 @@ impl ServerState {
 async fn garbage_collect(state: Arc<ServerState>) {
     loop {
         tokio::time::sleep(GARBAGE_COLLECT_INTERVAL).await;
-        state.garbage_collect();
+        state.garbage_collect().await;
+    }
+}
+
+async fn stats_retention(state: Arc<ServerState>, retention_days: u64) {
+    loop {
+        state.apply_name_retention(retention_days).await;
+        tokio::time::sleep(STATS_RETENTION_INTERVAL).await;
+    }
+}
+
+async fn public_room_directory(state: Arc<ServerState>) {
+    loop {
+        tokio::time::sleep(PUBLIC_ROOMS_REFRESH_INTERVAL).await;
+        state.refresh_public_rooms().await;
+    }
+}
+
+async fn persistent_room_saver(state: Arc<ServerState>) {
+    loop {
+        tokio::time::sleep(PERSISTENT_ROOM_SAVE_INTERVAL).await;
+        state.save_persistent_rooms().await;
     }
 }
 
@@ -1274,25 +1893,27 @@ async fn room_maintenance(state: Arc<ServerState>) {
     }
 }
 
-fn generate_room_id(length: usize) -> String {
-    let mut rng = rand::thread_rng();
-    let letters = Uniform::new_inclusive(b'a', b'z');
-    (0..length)
-        .map(|_| letters.sample(&mut rng) as char)
-        .collect()
-}
-
 #[tokio::main]
 async fn main() {
     let state = Arc::new(ServerState::new().unwrap());
+    state.restore_persistent_rooms().await.unwrap();
 
     tokio::spawn(garbage_collect(state.clone()));
     tokio::spawn(room_maintenance(state.clone()));
+    tokio::spawn(public_room_directory(state.clone()));
+    tokio::spawn(persistent_room_saver(state.clone()));
+    if let Some(retention_days) = state.name_retention_days {
+        tokio::spawn(stats_retention(state.clone(), retention_days));
+    }
 
     let cors = CorsLayer::new()
         .allow_origin(Any)
         .allow_methods([Method::GET, Method::POST])
-        .allow_headers([header::AUTHORIZATION, header::CONTENT_TYPE]);
+        .allow_headers([
+            header::AUTHORIZATION,
+            header::CONTENT_TYPE,
+            header::HeaderName::from_static(ROOM_PASSWORD_HEADER),
+        ]);
 
     let app = Router::new()
         .route("/ws", get(ws_handler))
@@ -1301,8 +1922,44 @@ async fn main() {
         .route("/create", post(create_room_handler))
         .route("/exists", post(exists_handler))
         .route("/stats", get(stats_handler))
+        .route("/rooms/public", get(public_rooms_handler))
+        .route(
+            "/rooms/persistent",
+            get(persistent_rooms_handler).post(create_persistent_room_handler),
+        )
+        .route(
+            "/rooms/persistent/:room_id/release",
+            post(release_persistent_room_handler),
+        )
         .route("/most-beautiful-stats", get(most_beautiful_stats_handler))
+        .route("/clues", get(clue_archive_handler))
+        .route("/card-stats", get(card_stats_handler))
+        .route("/player-stats/:player_hash", get(player_stats_handler))
+        .route("/head-to-head", get(head_to_head_handler))
+        .route("/stats-writer", get(stats_writer_handler))
+        .route("/ratings", get(rating_leaderboard_handler))
+        .route("/ratings/:player_hash", get(player_rating_history_handler))
+        .route("/export/games/:game_id", get(export_game_handler))
+        .route("/export/rooms/:room_id", get(export_room_handler))
+        .route("/export/players/:player_hash", get(export_player_handler))
+        .route("/games/:game_id/replay", get(game_replay_export_handler))
+        .route("/games/replay", post(game_replay_import_handler))
+        .route("/players/:player_hash/erase", post(erase_player_handler))
+        .route("/accounts/register", post(register_account_handler))
+        .route("/accounts/login", post(login_account_handler))
+        .route("/accounts/login-code", post(request_login_code_handler))
+        .route(
+            "/accounts/login-code/redeem",
+            post(redeem_login_code_handler),
+        )
+        .route("/accounts/logout", post(logout_account_handler))
+        .route("/accounts/me", get(account_me_handler))
+        .route("/accounts/merge", post(merge_account_history_handler))
         .route("/", get(root))
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            http_rate_limit,
+        ))
         .layer(TraceLayer::new_for_http())
         .layer(cors)
         .with_state(state);
@@ -1317,6 +1974,29 @@ async fn main() {
     .unwrap();
 }
 
+async fn http_rate_limit(
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    State(state): State<Arc<ServerState>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if !state
+        .rate_limits
+        .http
+        .check(client_ip(peer, request.headers()), get_time_ms())
+    {
+        return (
+            StatusCode::TOO_MANY_REQUESTS,
+            serde_json::to_string(&room::ServerMsg::ErrorMsg(
+                "Too many requests; slow down".to_string(),
+            ))
+            .unwrap(),
+        )
+            .into_response();
+    }
+    next.run(request).await
+}
+
 async fn card_handler(
     AxumPath(card_id): AxumPath<String>,
     State(state): State<Arc<ServerState>>,
@@ -1393,16 +2073,45 @@ async fn card_source_info_handler(
     .into_response()
 }
 
-async fn create_room_handler(State(state): State<Arc<ServerState>>, body: Bytes) -> String {
+async fn create_room_handler(
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    State(state): State<Arc<ServerState>>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> (StatusCode, String) {
+    let ip = client_ip(peer, &headers);
+    if !state.rate_limits.create.check(ip, get_time_ms()) {
+        return (
+            StatusCode::TOO_MANY_REQUESTS,
+            serde_json::to_string(&room::ServerMsg::ErrorMsg(
+                "Too many rooms created; try again in a minute".to_string(),
+            ))
+            .unwrap(),
+        );
+    }
+    if state.room_cap_reached(ip) {
+        return (
+            StatusCode::TOO_MANY_REQUESTS,
+            serde_json::to_string(&room::ServerMsg::ErrorMsg(format!(
+                "You already have {} open rooms; finish or leave one first",
+                state.max_rooms_per_ip
+            )))
+            .unwrap(),
+        );
+    }
+
     let room_config = match parse_create_room_win_condition(&body, state.default_win_points_target)
     {
         Ok(config) => config,
         Err(err) => {
             println!("Failed to parse create-room payload: {}", err);
-            return serde_json::to_string(&room::ServerMsg::ErrorMsg(
-                "Failed to create room".to_string(),
-            ))
-            .unwrap();
+            return (
+                StatusCode::BAD_REQUEST,
+                serde_json::to_string(&room::ServerMsg::ErrorMsg(
+                    "Failed to create room".to_string(),
+                ))
+                .unwrap(),
+            );
         }
     };
 
@@ -1411,17 +2120,22 @@ async fn create_room_handler(State(state): State<Arc<ServerState>>, body: Bytes)
             room_config.win_condition,
             room_config.creator_name,
             room_config.password,
+            room_config.unlisted,
+            ip,
         )
         .await;
 
     match room {
-        Ok(room_state) => serde_json::to_string(&room_state).unwrap(),
+        Ok(room_state) => (StatusCode::OK, serde_json::to_string(&room_state).unwrap()),
         Err(err) => {
             println!("Failed to create room: {}", err);
-            serde_json::to_string(&room::ServerMsg::ErrorMsg(
-                "Failed to create room".to_string(),
-            ))
-            .unwrap()
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                serde_json::to_string(&room::ServerMsg::ErrorMsg(
+                    "Failed to create room".to_string(),
+                ))
+                .unwrap(),
+            )
         }
     }
 }
@@ -1439,6 +2153,32 @@ mod tests {
         );
     }
 
+    #[test]
+    fn stats_room_password_comes_from_header_not_query() {
+        let uri: axum::http::Uri = "/ratings?room_id=abc&token=t-a&password=hunter2"
+            .parse()
+            .unwrap();
+        let Query(access) = Query::<StatsAccessQuery>::try_from_uri(&uri).unwrap();
+        assert_eq!(access.token.as_deref(), Some("t-a"));
+
+        let mut headers = HeaderMap::new();
+        assert_eq!(room_password_header(&headers), None);
+        headers.insert(ROOM_PASSWORD_HEADER, "hunter2".parse().unwrap());
+        assert_eq!(room_password_header(&headers), Some("hunter2"));
+    }
+
+    #[test]
+    fn create_room_reads_unlisted_flag() {
+        let config =
+            parse_create_room_win_condition(br#"{"unlisted":true}"#, DEFAULT_WIN_POINTS).unwrap();
+        assert!(config.unlisted);
+        assert!(
+            !parse_create_room_win_condition(&[], DEFAULT_WIN_POINTS)
+                .unwrap()
+                .unlisted
+        );
+    }
+
     #[test]
     fn create_room_defaults_to_single_dixit_cycle_when_win_condition_is_omitted() {
         let config =
@@ -1449,30 +2189,272 @@ mod tests {
             room::default_win_condition_for_game_mode(room::GameMode::DixitPlus)
         );
     }
-}
 
-async fn exists_handler(
-    State(state): State<Arc<ServerState>>,
-    Json(room_id): Json<String>,
-) -> &'static str {
-    if state.get_room(&room_id).is_some() {
-        "true"
-    } else {
-        "false"
+    #[test]
+    fn create_room_accepts_composite_win_condition_and_rejects_nesting() {
+        let config = parse_create_room_win_condition(
+            br#"{"win_condition":{"mode":"any_of","conditions":[{"mode":"points","target_points":30},{"mode":"time_limit","minutes":60}]}}"#,
+            DEFAULT_WIN_POINTS,
+        )
+        .unwrap();
+        assert_eq!(
+            config.win_condition,
+            WinCondition::AnyOf {
+                conditions: vec![
+                    WinCondition::Points { target_points: 30 },
+                    WinCondition::TimeLimit {
+                        minutes: 60,
+                        finish_cycle: false,
+                    },
+                ],
+            }
+        );
+
+        assert!(parse_create_room_win_condition(
+            br#"{"win_condition":{"mode":"all_of","conditions":[{"mode":"cards_finish"},{"mode":"any_of","conditions":[]}]}}"#,
+            DEFAULT_WIN_POINTS,
+        )
+        .is_err());
+    }
+}
+
+// `/exists` takes either a bare room id or `{ room_id, invite }` for unlisted rooms.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum ExistsRequest {
+    RoomId(String),
+    Invite {
+        room_id: String,
+        invite: Option<String>,
+    },
+}
+
+async fn exists_handler(
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    State(state): State<Arc<ServerState>>,
+    headers: HeaderMap,
+    Json(request): Json<ExistsRequest>,
+) -> (StatusCode, &'static str) {
+    if !state
+        .rate_limits
+        .exists
+        .check(client_ip(peer, &headers), get_time_ms())
+    {
+        return (StatusCode::TOO_MANY_REQUESTS, "false");
     }
+
+    let (room_id, invite) = match request {
+        ExistsRequest::RoomId(room_id) => (room_id, None),
+        ExistsRequest::Invite { room_id, invite } => (room_id, invite),
+    };
+    // unlisted rooms look the same as missing ones without their invite key
+    let exists = match state.get_room(&room_id.trim().to_lowercase()) {
+        Some(room) => room.invite_is_valid(invite.as_deref()).await,
+        None => false,
+    };
+    (StatusCode::OK, if exists { "true" } else { "false" })
 }
 
 async fn stats_handler(State(state): State<Arc<ServerState>>) -> String {
     serde_json::to_string(&state.stats()).unwrap()
 }
 
+async fn public_rooms_handler(
+    State(state): State<Arc<ServerState>>,
+) -> Json<Vec<PublicRoomSummary>> {
+    Json(state.public_rooms().await)
+}
+
+#[derive(Deserialize)]
+struct CreatePersistentRoomRequest {
+    room_id: String,
+    #[serde(default)]
+    password: Option<String>,
+    #[serde(default)]
+    unlisted: bool,
+}
+
+#[derive(Serialize)]
+struct CreatePersistentRoomResponse {
+    room_id: String,
+    // shown once; claims the room's owner seat with ClaimRoomOwner and releases the room
+    owner_token: String,
+}
+
+#[derive(Serialize)]
+struct PersistentRoomListing {
+    room_id: String,
+    created_at_s: u64,
+    updated_at_s: u64,
+}
+
+impl From<PersistentRoomRecord> for PersistentRoomListing {
+    fn from(record: PersistentRoomRecord) -> Self {
+        PersistentRoomListing {
+            room_id: record.room_id,
+            created_at_s: record.created_at_s,
+            updated_at_s: record.updated_at_s,
+        }
+    }
+}
+
+async fn create_persistent_room_handler(
+    State(state): State<Arc<ServerState>>,
+    headers: HeaderMap,
+    Json(request): Json<CreatePersistentRoomRequest>,
+) -> Result<Json<CreatePersistentRoomResponse>, (StatusCode, String)> {
+    if !state.is_admin_request(&headers) {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "Persistent rooms require the admin token".to_string(),
+        ));
+    }
+    let room_id =
+        normalize_custom_room_id(&request.room_id).map_err(|err| (StatusCode::BAD_REQUEST, err))?;
+    let password = request
+        .password
+        .map(|password| password.trim().to_string())
+        .filter(|password| !password.is_empty());
+    if password
+        .as_ref()
+        .is_some_and(|password| password.len() > MAX_ROOM_PASSWORD_LEN)
+    {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!(
+                "Room passwords must be at most {} bytes",
+                MAX_ROOM_PASSWORD_LEN
+            ),
+        ));
+    }
+
+    match state
+        .create_persistent_room(&room_id, password, request.unlisted)
+        .await
+    {
+        Ok(Some(owner_token)) => Ok(Json(CreatePersistentRoomResponse {
+            room_id,
+            owner_token,
+        })),
+        Ok(None) => Err((
+            StatusCode::CONFLICT,
+            format!("Room '{}' already exists", room_id),
+        )),
+        Err(err) => {
+            println!("Failed to create persistent room {}: {}", room_id, err);
+            Err((StatusCode::INTERNAL_SERVER_ERROR, String::new()))
+        }
+    }
+}
+
+async fn persistent_rooms_handler(
+    State(state): State<Arc<ServerState>>,
+    headers: HeaderMap,
+) -> Result<Json<Vec<PersistentRoomListing>>, (StatusCode, String)> {
+    if !state.is_admin_request(&headers) {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "Persistent rooms require the admin token".to_string(),
+        ));
+    }
+    match state
+        .run_stats_query(|stats| stats.persistent_rooms())
+        .await
+    {
+        Ok(records) => Ok(Json(records.into_iter().map(Into::into).collect())),
+        Err(err) => {
+            println!("Failed to list persistent rooms: {}", err);
+            Err((StatusCode::INTERNAL_SERVER_ERROR, String::new()))
+        }
+    }
+}
+
+// Accepts the admin token or the room's owner token. The room stays open until it is garbage
+// collected like any other.
+async fn release_persistent_room_handler(
+    AxumPath(room_id): AxumPath<String>,
+    State(state): State<Arc<ServerState>>,
+    headers: HeaderMap,
+) -> Result<Json<bool>, (StatusCode, String)> {
+    let room_id = room_id.trim().to_lowercase();
+    let authorized = state.is_admin_request(&headers)
+        || match bearer_token(&headers) {
+            Some(token) => {
+                let lookup_id = room_id.clone();
+                state
+                    .run_stats_query(move |stats| stats.persistent_room(&lookup_id))
+                    .await
+                    .unwrap_or_default()
+                    .is_some_and(|record| record.owner_token_hash == hash_room_owner_token(token))
+            }
+            None => false,
+        };
+    if !authorized {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "Releasing a room requires its owner token or the admin token".to_string(),
+        ));
+    }
+    match state.release_persistent_room(&room_id).await {
+        Ok(released) => Ok(Json(released)),
+        Err(err) => {
+            println!("Failed to release persistent room {}: {}", room_id, err);
+            Err((StatusCode::INTERNAL_SERVER_ERROR, String::new()))
+        }
+    }
+}
+
+// Sends the directory now and again whenever it changes, until the client goes away. Client
+// messages other than close are ignored.
+async fn subscribe_public_rooms(socket: &mut WebSocket, state: &ServerState) -> Result<()> {
+    state.refresh_public_rooms().await;
+    let mut directory = state.public_rooms.subscribe();
+    loop {
+        let rooms = directory.borrow_and_update().clone();
+        socket
+            .send(room::ServerMsg::PublicRooms { rooms }.into())
+            .await?;
+        loop {
+            tokio::select! {
+                changed = directory.changed() => {
+                    changed?;
+                    break;
+                }
+                msg = socket.recv() => match msg {
+                    Some(Ok(WsMessage::Ping(payload))) => {
+                        socket.send(WsMessage::Pong(payload)).await?;
+                    }
+                    Some(Ok(WsMessage::Close(_))) | Some(Err(_)) | None => return Ok(()),
+                    Some(Ok(_)) => {}
+                },
+            }
+        }
+    }
+}
+
 async fn most_beautiful_stats_handler(
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
     Query(query): Query<MostBeautifulStatsQuery>,
+    Query(access): Query<StatsAccessQuery>,
     State(state): State<Arc<ServerState>>,
+    headers: HeaderMap,
 ) -> Result<Json<MostBeautifulStatsResponse>, StatusCode> {
+    let room_id = query.room_id.as_deref().map(str::to_lowercase);
+    if !state
+        .can_read_stats(
+            client_ip(peer, &headers),
+            &headers,
+            &access,
+            room_id.as_deref(),
+        )
+        .await
+    {
+        return Err(StatusCode::FORBIDDEN);
+    }
+    let games = query.games.unwrap_or(0);
     state
-        .most_beautiful_stats
-        .filtered_stats(query.room_id.as_deref(), query.games.unwrap_or(0))
+        .run_stats_query(move |stats| stats.filtered_stats(room_id.as_deref(), games))
+        .await
         .map(Json)
         .map_err(|err| {
             println!(
@@ -1484,32 +2466,844 @@ async fn most_beautiful_stats_handler(
         })
 }
 
+async fn clue_archive_handler(
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    Query(query): Query<ClueArchiveQuery>,
+    Query(access): Query<StatsAccessQuery>,
+    State(state): State<Arc<ServerState>>,
+    headers: HeaderMap,
+) -> Result<Json<ClueArchiveResponse>, StatusCode> {
+    let non_empty = |value: Option<String>| {
+        value
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+    };
+    let search = ClueSearchQuery {
+        text: non_empty(query.q),
+        room_id: non_empty(query.room_id).map(|room_id| room_id.to_lowercase()),
+        storyteller_hash: non_empty(query.storyteller_hash),
+        since_s: query.since,
+        until_s: query.until,
+        limit: query.limit.unwrap_or(0),
+    };
+    if !state
+        .can_read_stats(
+            client_ip(peer, &headers),
+            &headers,
+            &access,
+            search.room_id.as_deref(),
+        )
+        .await
+    {
+        return Err(StatusCode::FORBIDDEN);
+    }
+    state
+        .run_stats_query(move |stats| stats.search_clues(&search))
+        .await
+        .map(Json)
+        .map_err(|err| {
+            println!(
+                "Failed to search clue archive in {}: {}",
+                state.most_beautiful_stats.path().display(),
+                err
+            );
+            StatusCode::INTERNAL_SERVER_ERROR
+        })
+}
+
+async fn card_stats_handler(
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    Query(query): Query<CardStatsQuery>,
+    Query(access): Query<StatsAccessQuery>,
+    State(state): State<Arc<ServerState>>,
+    headers: HeaderMap,
+) -> Result<Json<CardStatsResponse>, StatusCode> {
+    let room_id = query.room_id.as_deref().map(str::to_lowercase);
+    if !state
+        .can_read_stats(
+            client_ip(peer, &headers),
+            &headers,
+            &access,
+            room_id.as_deref(),
+        )
+        .await
+    {
+        return Err(StatusCode::FORBIDDEN);
+    }
+    state
+        .run_stats_query(move |stats| {
+            stats.card_stats(
+                room_id.as_deref(),
+                query.games.unwrap_or(0),
+                query.include_unplayed.unwrap_or(false),
+            )
+        })
+        .await
+        .map(Json)
+        .map_err(|err| {
+            println!(
+                "Failed to load card stats from {}: {}",
+                state.most_beautiful_stats.path().display(),
+                err
+            );
+            StatusCode::INTERNAL_SERVER_ERROR
+        })
+}
+
+async fn player_stats_handler(
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    AxumPath(player_hash): AxumPath<String>,
+    Query(query): Query<MostBeautifulStatsQuery>,
+    Query(access): Query<StatsAccessQuery>,
+    State(state): State<Arc<ServerState>>,
+    headers: HeaderMap,
+) -> Result<Json<PlayerProfileStats>, StatusCode> {
+    let room_id = query.room_id.as_deref().map(str::to_lowercase);
+    if !state
+        .can_read_stats(
+            client_ip(peer, &headers),
+            &headers,
+            &access,
+            room_id.as_deref(),
+        )
+        .await
+    {
+        return Err(StatusCode::FORBIDDEN);
+    }
+    let lookup_hash = player_hash.clone();
+    match state
+        .run_stats_query(move |stats| {
+            stats.player_profile_stats(&lookup_hash, room_id.as_deref(), query.games.unwrap_or(0))
+        })
+        .await
+    {
+        Ok(Some(stats)) => Ok(Json(stats)),
+        Ok(None) => Err(StatusCode::NOT_FOUND),
+        Err(err) => {
+            println!("Failed to load player stats for {}: {}", player_hash, err);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+async fn head_to_head_handler(
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    Query(query): Query<MostBeautifulStatsQuery>,
+    Query(access): Query<StatsAccessQuery>,
+    State(state): State<Arc<ServerState>>,
+    headers: HeaderMap,
+) -> Result<Json<HeadToHeadResponse>, StatusCode> {
+    let room_id = query.room_id.as_deref().map(str::to_lowercase);
+    if !state
+        .can_read_stats(
+            client_ip(peer, &headers),
+            &headers,
+            &access,
+            room_id.as_deref(),
+        )
+        .await
+    {
+        return Err(StatusCode::FORBIDDEN);
+    }
+    let games = query.games.unwrap_or(0);
+    state
+        .run_stats_query(move |stats| stats.head_to_head(room_id.as_deref(), games))
+        .await
+        .map(Json)
+        .map_err(|err| {
+            println!(
+                "Failed to load head-to-head stats from {}: {}",
+                state.most_beautiful_stats.path().display(),
+                err
+            );
+            StatusCode::INTERNAL_SERVER_ERROR
+        })
+}
+
+async fn stats_writer_handler(
+    State(state): State<Arc<ServerState>>,
+    headers: HeaderMap,
+) -> Result<Json<StatsWriterMetrics>, StatusCode> {
+    if !state.is_admin_request(&headers) {
+        return Err(StatusCode::FORBIDDEN);
+    }
+    Ok(Json(state.most_beautiful_stats.writer_metrics()))
+}
+
+async fn rating_leaderboard_handler(
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    Query(query): Query<RatingQuery>,
+    Query(access): Query<StatsAccessQuery>,
+    State(state): State<Arc<ServerState>>,
+    headers: HeaderMap,
+) -> Result<Json<PlayerRatingLeaderboard>, StatusCode> {
+    let room_id = query.room_id();
+    if !state
+        .can_read_stats(
+            client_ip(peer, &headers),
+            &headers,
+            &access,
+            room_id.as_deref(),
+        )
+        .await
+    {
+        return Err(StatusCode::FORBIDDEN);
+    }
+    let limit = query.limit.unwrap_or(0);
+    state
+        .run_stats_query(move |stats| stats.rating_leaderboard(room_id.as_deref(), limit))
+        .await
+        .map(Json)
+        .map_err(|err| {
+            println!(
+                "Failed to load rating leaderboard from {}: {}",
+                state.most_beautiful_stats.path().display(),
+                err
+            );
+            StatusCode::INTERNAL_SERVER_ERROR
+        })
+}
+
+async fn player_rating_history_handler(
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    AxumPath(player_hash): AxumPath<String>,
+    Query(query): Query<RatingQuery>,
+    Query(access): Query<StatsAccessQuery>,
+    State(state): State<Arc<ServerState>>,
+    headers: HeaderMap,
+) -> Result<Json<PlayerRatingHistory>, StatusCode> {
+    let room_id = query.room_id();
+    if !state
+        .can_read_stats(
+            client_ip(peer, &headers),
+            &headers,
+            &access,
+            room_id.as_deref(),
+        )
+        .await
+    {
+        return Err(StatusCode::FORBIDDEN);
+    }
+    let lookup_hash = player_hash.clone();
+    state
+        .run_stats_query(move |stats| stats.player_rating_history(&lookup_hash, room_id.as_deref()))
+        .await
+        .map(Json)
+        .map_err(|err| {
+            println!("Failed to load rating history for {}: {}", player_hash, err);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })
+}
+
+fn stats_export_response(
+    export: Result<StatsExportResponse>,
+    query: &StatsExportQuery,
+    file_stem: &str,
+) -> Response {
+    let csv_table = match query.format.as_deref().unwrap_or("json") {
+        "json" => None,
+        "csv" => match query.table.as_deref().and_then(StatsExportTable::parse) {
+            Some(table) => Some(table),
+            None => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    "CSV export needs table=rounds|cards|story_votes|beauty_votes|scores|\
+                     stella_rounds|stella_board_cards|stella_selections|stella_players",
+                )
+                    .into_response()
+            }
+        },
+        _ => return (StatusCode::BAD_REQUEST, "format must be json or csv").into_response(),
+    };
+    let export = match export {
+        Ok(export) => export,
+        Err(err) => {
+            println!("Failed to export stats for {}: {}", file_stem, err);
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+    let file_stem = file_stem
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || *c == '-' || *c == '_')
+        .collect::<String>();
+
+    let (content_type, file_name, body) = match csv_table {
+        None => (
+            "application/json",
+            format!("talespin-{file_stem}.json"),
+            serde_json::to_string(&export).map_err(anyhow::Error::from),
+        ),
+        Some(table) => (
+            "text/csv; charset=utf-8",
+            format!("talespin-{file_stem}-{}.csv", table.name()),
+            stats_export_csv(&export.games, table),
+        ),
+    };
+    match body {
+        Ok(body) => (
+            [
+                (header::CONTENT_TYPE, content_type.to_string()),
+                (
+                    header::CONTENT_DISPOSITION,
+                    format!("attachment; filename=\"{file_name}\""),
+                ),
+            ],
+            body,
+        )
+            .into_response(),
+        Err(err) => {
+            println!("Failed to encode stats export {}: {}", file_name, err);
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+async fn export_game_handler(
+    AxumPath(game_id): AxumPath<String>,
+    Query(query): Query<StatsExportQuery>,
+    State(state): State<Arc<ServerState>>,
+    headers: HeaderMap,
+) -> Response {
+    let lookup_id = game_id.clone();
+    let room_id = match state
+        .run_stats_query(move |stats| stats.game_room_id(&lookup_id))
+        .await
+    {
+        Ok(Some(room_id)) => room_id,
+        Ok(None) => return (StatusCode::NOT_FOUND, "Game not found").into_response(),
+        Err(err) => {
+            println!("Failed to look up game {} for export: {}", game_id, err);
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+    if !state
+        .can_export_stats(&headers, query.token.as_deref(), Some(&room_id))
+        .await
+    {
+        return (
+            StatusCode::FORBIDDEN,
+            "Stats export requires a moderator or admin token",
+        )
+            .into_response();
+    }
+    let lookup_id = game_id.clone();
+    let export = state
+        .run_stats_query(move |stats| stats.export_game_replay(&lookup_id))
+        .await
+        .map(|game| StatsExportResponse {
+            games: game.into_iter().collect(),
+        });
+    stats_export_response(export, &query, &format!("game-{game_id}"))
+}
+
+async fn export_room_handler(
+    AxumPath(room_id): AxumPath<String>,
+    Query(query): Query<StatsExportQuery>,
+    State(state): State<Arc<ServerState>>,
+    headers: HeaderMap,
+) -> Response {
+    let room_id = room_id.to_lowercase();
+    if !state
+        .can_export_stats(&headers, query.token.as_deref(), Some(&room_id))
+        .await
+    {
+        return (
+            StatusCode::FORBIDDEN,
+            "Stats export requires a moderator or admin token",
+        )
+            .into_response();
+    }
+    let lookup_id = room_id.clone();
+    let export = state
+        .run_stats_query(move |stats| stats.export_room_games(&lookup_id))
+        .await;
+    stats_export_response(export, &query, &format!("room-{room_id}"))
+}
+
+async fn export_player_handler(
+    AxumPath(player_hash): AxumPath<String>,
+    Query(query): Query<StatsExportQuery>,
+    State(state): State<Arc<ServerState>>,
+    headers: HeaderMap,
+) -> Response {
+    // moderator tokens only cover their own room, so they must pass room_id
+    let room_id = query.room_id.as_deref().map(str::to_lowercase);
+    if !state
+        .can_export_stats(&headers, query.token.as_deref(), room_id.as_deref())
+        .await
+    {
+        return (
+            StatusCode::FORBIDDEN,
+            "Stats export requires a moderator or admin token",
+        )
+            .into_response();
+    }
+    let lookup_hash = player_hash.clone();
+    let export = state
+        .run_stats_query(move |stats| stats.export_player_games(&lookup_hash, room_id.as_deref()))
+        .await;
+    stats_export_response(export, &query, &format!("player-{player_hash}"))
+}
+
+#[derive(Deserialize)]
+struct PlayerErasureQuery {
+    mode: Option<String>,
+}
+
+async fn erase_player_handler(
+    AxumPath(player_hash): AxumPath<String>,
+    Query(query): Query<PlayerErasureQuery>,
+    State(state): State<Arc<ServerState>>,
+    headers: HeaderMap,
+) -> Result<Json<PlayerErasureReport>, (StatusCode, String)> {
+    if !state.is_admin_request(&headers) {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "Player erasure requires the admin token".to_string(),
+        ));
+    }
+    let mode = match query.mode.as_deref() {
+        None => PlayerErasureMode::Anonymize,
+        Some(raw) => PlayerErasureMode::parse(raw).ok_or_else(|| {
+            (
+                StatusCode::BAD_REQUEST,
+                format!("Unknown erasure mode '{raw}'; expected anonymize or delete"),
+            )
+        })?,
+    };
+
+    let erased_hash = player_hash.clone();
+    match state
+        .run_stats_query(move |stats| stats.erase_player(&erased_hash, mode))
+        .await
+    {
+        Ok(report) => Ok(Json(report)),
+        Err(err) => {
+            println!("Failed to erase player {}: {}", player_hash, err);
+            Err((StatusCode::INTERNAL_SERVER_ERROR, String::new()))
+        }
+    }
+}
+
+type AccountResult<T> = Result<Json<T>, (StatusCode, String)>;
+
+#[derive(Deserialize)]
+struct AccountCredentials {
+    username: String,
+    #[serde(default)]
+    password: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct LoginCodeRequest {
+    username: String,
+    #[serde(default)]
+    code: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct AccountMergeRequest {
+    // the browser's room token; its player_hash history moves to the account
+    player_token: String,
+}
+
+fn account_internal_error(context: &str, err: anyhow::Error) -> (StatusCode, String) {
+    println!("{}: {}", context, err);
+    (StatusCode::INTERNAL_SERVER_ERROR, String::new())
+}
+
+fn check_login_rate_limit(
+    state: &ServerState,
+    peer: SocketAddr,
+    headers: &HeaderMap,
+) -> Result<(), (StatusCode, String)> {
+    if !state
+        .rate_limits
+        .login
+        .check(client_ip(peer, headers), get_time_ms())
+    {
+        return Err((
+            StatusCode::TOO_MANY_REQUESTS,
+            "Too many sign-in attempts; try again in a minute".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn start_account_session(
+    state: &ServerState,
+    account: Account,
+) -> AccountResult<AccountSessionResponse> {
+    let session_token = state
+        .most_beautiful_stats
+        .create_account_session(&account.account_id, get_time_s())
+        .map_err(|err| account_internal_error("Failed to create account session", err))?;
+    Ok(Json(AccountSessionResponse {
+        account,
+        session_token,
+    }))
+}
+
+async fn require_account(
+    state: &ServerState,
+    headers: &HeaderMap,
+) -> Result<Account, (StatusCode, String)> {
+    let account = match bearer_token(headers) {
+        Some(token) => state.account_for_session(token).await,
+        None => None,
+    };
+    account.ok_or_else(|| {
+        (
+            StatusCode::UNAUTHORIZED,
+            "Sign in again; the account session expired".to_string(),
+        )
+    })
+}
+
+async fn register_account_handler(
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    State(state): State<Arc<ServerState>>,
+    headers: HeaderMap,
+    Json(body): Json<AccountCredentials>,
+) -> AccountResult<AccountSessionResponse> {
+    check_login_rate_limit(&state, peer, &headers)?;
+    let username = normalize_username(&body.username).map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+    // accounts without a password sign in with login codes from the server log
+    let password_hash = match body.password.filter(|password| !password.is_empty()) {
+        Some(password) => {
+            validate_account_password(&password).map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+            Some(
+                tokio::task::spawn_blocking(move || hash_account_password(&password))
+                    .await
+                    .map_err(|err| account_internal_error("Failed to hash password", err.into()))?,
+            )
+        }
+        None => None,
+    };
+    let account = state
+        .most_beautiful_stats
+        .create_account(&username, password_hash.as_deref(), get_time_s())
+        .map_err(|err| account_internal_error("Failed to create account", err))?
+        .ok_or_else(|| {
+            (
+                StatusCode::CONFLICT,
+                format!("The username '{username}' is taken"),
+            )
+        })?;
+    println!("Registered account '{}'", account.username);
+    start_account_session(&state, account)
+}
+
+async fn login_account_handler(
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    State(state): State<Arc<ServerState>>,
+    headers: HeaderMap,
+    Json(body): Json<AccountCredentials>,
+) -> AccountResult<AccountSessionResponse> {
+    check_login_rate_limit(&state, peer, &headers)?;
+    let rejected = || {
+        (
+            StatusCode::UNAUTHORIZED,
+            "Incorrect username or password".to_string(),
+        )
+    };
+    let password = body.password.unwrap_or_default();
+    let username = body.username.clone();
+    let found = state
+        .run_stats_query(move |stats| stats.account_by_username(&username))
+        .await
+        .map_err(|err| account_internal_error("Failed to look up account", err))?;
+    let (account, password_hash) = match found {
+        Some((account, Some(password_hash))) => (Some(account), Some(password_hash)),
+        _ => (None, None),
+    };
+    // unknown usernames still pay for a full verify, so timing does not reveal which exist
+    let correct = tokio::task::spawn_blocking(move || match password_hash {
+        Some(password_hash) => verify_account_password(&password_hash, &password),
+        None => verify_missing_account_password(&password),
+    })
+    .await
+    .map_err(|err| account_internal_error("Failed to verify password", err.into()))?;
+    match account {
+        Some(account) if correct => start_account_session(&state, account),
+        _ => Err(rejected()),
+    }
+}
+
+// Prints a one-time code to the server log for whoever runs the server to pass on. The response
+// is the same whether or not the account exists.
+async fn request_login_code_handler(
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    State(state): State<Arc<ServerState>>,
+    headers: HeaderMap,
+    Json(body): Json<LoginCodeRequest>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    check_login_rate_limit(&state, peer, &headers)?;
+    let found = state
+        .most_beautiful_stats
+        .account_by_username(&body.username)
+        .map_err(|err| account_internal_error("Failed to look up account", err))?;
+    if let Some((account, _)) = found {
+        let code = generate_login_code();
+        state
+            .most_beautiful_stats
+            .store_login_code(
+                &account.account_id,
+                &hash_login_code(&code),
+                get_time_s() + LOGIN_CODE_TTL_S,
+            )
+            .map_err(|err| account_internal_error("Failed to store login code", err))?;
+        println!(
+            "Login code for account '{}': {} (expires in {} minutes)",
+            account.username,
+            code,
+            LOGIN_CODE_TTL_S / 60
+        );
+    }
+    Ok(StatusCode::ACCEPTED)
+}
+
+async fn redeem_login_code_handler(
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    State(state): State<Arc<ServerState>>,
+    headers: HeaderMap,
+    Json(body): Json<LoginCodeRequest>,
+) -> AccountResult<AccountSessionResponse> {
+    check_login_rate_limit(&state, peer, &headers)?;
+    let code = body.code.unwrap_or_default();
+    let account = state
+        .most_beautiful_stats
+        .redeem_login_code(&body.username, &hash_login_code(&code), get_time_s())
+        .map_err(|err| account_internal_error("Failed to redeem login code", err))?
+        .ok_or_else(|| {
+            (
+                StatusCode::UNAUTHORIZED,
+                "Incorrect or expired login code".to_string(),
+            )
+        })?;
+    start_account_session(&state, account)
+}
+
+async fn logout_account_handler(
+    State(state): State<Arc<ServerState>>,
+    headers: HeaderMap,
+) -> Result<StatusCode, (StatusCode, String)> {
+    if let Some(token) = bearer_token(&headers) {
+        state
+            .most_beautiful_stats
+            .delete_account_session(token)
+            .map_err(|err| account_internal_error("Failed to end account session", err))?;
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn account_me_handler(
+    State(state): State<Arc<ServerState>>,
+    headers: HeaderMap,
+) -> AccountResult<Account> {
+    require_account(&state, &headers).await.map(Json)
+}
+
+// Claims a browser's stats history for the signed-in account. Knowing the browser's room token
+// is the proof of ownership, so a player_hash alone is not enough.
+async fn merge_account_history_handler(
+    State(state): State<Arc<ServerState>>,
+    headers: HeaderMap,
+    Json(body): Json<AccountMergeRequest>,
+) -> AccountResult<AccountMergeReport> {
+    let account = require_account(&state, &headers).await?;
+    let player_token = body.player_token.trim();
+    if player_token.is_empty() || is_account_room_token(player_token) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "player_token must be a browser's player token".to_string(),
+        ));
+    }
+    let player_hash = player_hash_for_token(player_token);
+    let merged_account = account.clone();
+    let report = state
+        .run_stats_query(move |stats| {
+            stats.merge_player_into_account(&merged_account, &player_hash, get_time_s())
+        })
+        .await
+        .map_err(|err| account_internal_error("Failed to merge player history", err))?;
+    if report.merged {
+        println!(
+            "Merged {} stats rows into account '{}'",
+            report.rows_moved, account.username
+        );
+    }
+    Ok(Json(report))
+}
+
+async fn game_replay_export_handler(
+    AxumPath(game_id): AxumPath<String>,
+    State(state): State<Arc<ServerState>>,
+) -> Result<Json<GameReplay>, StatusCode> {
+    let lookup_id = game_id.clone();
+    match state
+        .run_stats_query(move |stats| stats.export_game_replay(&lookup_id))
+        .await
+    {
+        Ok(Some(replay)) => Ok(Json(replay)),
+        Ok(None) => Err(StatusCode::NOT_FOUND),
+        Err(err) => {
+            println!("Failed to export replay for game {}: {}", game_id, err);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+async fn game_replay_import_handler(
+    State(state): State<Arc<ServerState>>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> (StatusCode, String) {
+    if !state.is_admin_request(&headers) {
+        return (
+            StatusCode::FORBIDDEN,
+            "Replay import requires the admin token".to_string(),
+        );
+    }
+
+    let replay = match serde_json::from_slice::<GameReplay>(&body) {
+        Ok(replay) => replay,
+        Err(err) => return (StatusCode::BAD_REQUEST, format!("Invalid replay: {}", err)),
+    };
+    if let Err(err) = replay.validate() {
+        return (StatusCode::BAD_REQUEST, err);
+    }
+
+    let game_id = replay.game_id.clone();
+    match state
+        .run_stats_query(move |stats| stats.game_exists(&game_id))
+        .await
+    {
+        Ok(true) => {
+            return (
+                StatusCode::CONFLICT,
+                format!("Game {} already exists", replay.game_id),
+            )
+        }
+        Ok(false) => {}
+        Err(err) => {
+            println!("Failed to check replay game {}: {}", replay.game_id, err);
+            return (StatusCode::INTERNAL_SERVER_ERROR, String::new());
+        }
+    }
+
+    let game_id = replay.game_id.clone();
+    match state
+        .run_stats_query(move |stats| stats.import_game_replay(&replay))
+        .await
+    {
+        Ok(()) => (StatusCode::CREATED, game_id),
+        Err(err) => {
+            println!("Failed to import replay for game {}: {}", game_id, err);
+            (StatusCode::INTERNAL_SERVER_ERROR, String::new())
+        }
+    }
+}
+
 #[derive(Debug, Deserialize)]
 struct MostBeautifulStatsQuery {
     room_id: Option<String>,
     games: Option<usize>,
 }
 
+// Room-scoped stats credentials, read next to each endpoint's own query. A member token may also
+// be sent as a bearer token, and the room password only as the X-Room-Password header.
+#[derive(Debug, Deserialize)]
+struct StatsAccessQuery {
+    token: Option<String>,
+    room_auth_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CardStatsQuery {
+    room_id: Option<String>,
+    games: Option<usize>,
+    // also list known deck cards that never appeared in the selected games
+    include_unplayed: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StatsExportQuery {
+    // json (default) or csv
+    format: Option<String>,
+    // required for csv: rounds, cards, story_votes, beauty_votes or scores
+    table: Option<String>,
+    // moderator export token, as an alternative to the Authorization header
+    token: Option<String>,
+    room_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RatingQuery {
+    // omit for the global rating
+    room_id: Option<String>,
+    limit: Option<usize>,
+}
+
+impl RatingQuery {
+    fn room_id(&self) -> Option<String> {
+        self.room_id
+            .as_deref()
+            .map(|room_id| room_id.trim().to_lowercase())
+            .filter(|room_id| !room_id.is_empty())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ClueArchiveQuery {
+    q: Option<String>,
+    room_id: Option<String>,
+    storyteller_hash: Option<String>,
+    // unix seconds, inclusive
+    since: Option<u64>,
+    until: Option<u64>,
+    limit: Option<usize>,
+}
+
 async fn root() -> &'static str {
     "Hello, world!"
 }
 
 async fn ws_handler(
     ws: WebSocketUpgrade,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
     State(state): State<Arc<ServerState>>,
-) -> impl IntoResponse {
-    ws.on_upgrade(move |socket| handle_socket(socket, state))
+    headers: HeaderMap,
+) -> Response {
+    let ip = client_ip(peer, &headers);
+    if !state.rate_limits.join.check(ip, get_time_ms()) {
+        return (
+            StatusCode::TOO_MANY_REQUESTS,
+            "Too many connection attempts; try again in a minute",
+        )
+            .into_response();
+    }
+    // Rooms reject messages over the configured limit with a structured error; the transport cap
+    // only stops clients that ignore it.
+    let transport_cap = state.ws_message_limits.max_message_bytes.saturating_mul(2);
+    ws.max_message_size(transport_cap)
+        .max_frame_size(transport_cap)
+        .on_upgrade(move |socket| handle_socket(socket, state, ip))
 }
 
-async fn handle_socket(mut socket: WebSocket, state: Arc<ServerState>) {
-    let res = initialize_socket(&mut socket, state).await;
+async fn handle_socket(mut socket: WebSocket, state: Arc<ServerState>, client_ip: IpAddr) {
+    let res = initialize_socket(&mut socket, state, client_ip).await;
 
     if let Err(e) = res {
         println!("Error in initialize_socket: {}", e);
     }
 }
 
-async fn initialize_socket(socket: &mut WebSocket, state: Arc<ServerState>) -> Result<()> {
+async fn initialize_socket(
+    socket: &mut WebSocket,
+    state: Arc<ServerState>,
+    client_ip: IpAddr,
+) -> Result<()> {
     let msg = socket
         .recv()
         .await
@@ -1517,14 +3311,57 @@ async fn initialize_socket(socket: &mut WebSocket, state: Arc<ServerState>) -> R
 
     if let WsMessage::Text(s) = msg {
         if let Ok(msg) = serde_json::from_str(&s) {
-            if let room::ClientMsg::JoinRoom {
+            if let room::ClientMsg::JoinAsSpectator {
+                room_id,
+                spectator_token,
+            } = msg
+            {
+                if spectator_token.len() > 200 {
+                    socket
+                        .send(room::ServerMsg::ErrorMsg("Token too long".to_string()).into())
+                        .await?;
+                    return Err(anyhow!("Token too long"));
+                }
+
+                state
+                    .spectate_room(&room_id.to_lowercase(), socket, &spectator_token)
+                    .await?
+            } else if let room::ClientMsg::SubscribePublicRooms {} = msg {
+                subscribe_public_rooms(socket, &state).await?
+            } else if let room::ClientMsg::JoinRoom {
                 room_id,
                 name,
                 token,
                 room_password,
+                invite,
+                account_session,
             } = msg
             {
                 let name = canonical_member_name(&name);
+                if is_account_room_token(&token) {
+                    socket
+                        .send(room::ServerMsg::ErrorMsg("Invalid token".to_string()).into())
+                        .await?;
+                    return Err(anyhow!("Client sent an account room token"));
+                }
+                // Signed-in members share the account's room token across devices. Device
+                // migration joins (blank name) keep the room_auth_id they carry.
+                let account_session = account_session
+                    .as_deref()
+                    .map(str::trim)
+                    .filter(|session| !session.is_empty() && !name.is_empty());
+                let token = match account_session {
+                    Some(session) => match state.account_for_session(session).await {
+                        Some(account) => account.room_token(),
+                        None => {
+                            socket
+                                .send(room::ServerMsg::AccountSessionExpired {}.into())
+                                .await?;
+                            return Err(anyhow!("Expired account session"));
+                        }
+                    },
+                    None => token,
+                };
                 if name.len() > MAX_MEMBER_NAME_LEN {
                     socket
                         .send(room::ServerMsg::ErrorMsg("Name too long".to_string()).into())
@@ -1543,7 +3380,7 @@ async fn initialize_socket(socket: &mut WebSocket, state: Arc<ServerState>) -> R
                     .filter(|password| !password.is_empty());
                 if room_password
                     .as_ref()
-                    .map(|password| password.len() > 200)
+                    .map(|password| password.len() > MAX_ROOM_PASSWORD_LEN)
                     .unwrap_or(false)
                 {
                     socket
@@ -1561,6 +3398,8 @@ async fn initialize_socket(socket: &mut WebSocket, state: Arc<ServerState>) -> R
                         name,
                         &token,
                         room_password.as_deref(),
+                        invite.as_deref(),
+                        client_ip,
                     )
                     .await?
             }
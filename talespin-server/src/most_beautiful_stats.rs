@@ -1,10 +1,24 @@
+use crate::accounts::{
+    generate_account_id, generate_session_token, hash_session_token, player_hash_for_token,
+    Account, AccountMergeReport, ACCOUNT_SESSION_TTL_S, ACCOUNT_TOKEN_PREFIX,
+    LOGIN_CODE_MAX_ATTEMPTS,
+};
 use anyhow::{Context, Result};
 use rusqlite::{params, Connection, OptionalExtension};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap, HashSet},
+    ops::Deref,
     path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Condvar, Mutex, RwLock,
+    },
 };
+use tokio::sync::mpsc::{self, error::TrySendError};
+
+// stored in place of a display name for opted-out, erased, or expired players
+pub const ANONYMOUS_PLAYER_NAME: &str = "Anonymous";
 
 #[derive(Debug, Clone)]
 pub struct MostBeautifulVoteRecord {
@@ -55,7 +69,708 @@ pub struct MostBeautifulStatsResponse {
     pub players: Vec<MostBeautifulPlayerStats>,
 }
 
-#[derive(Debug, Clone)]
+const DEFAULT_CLUE_SEARCH_LIMIT: usize = 50;
+const MAX_CLUE_SEARCH_LIMIT: usize = 200;
+const MAX_PLAYER_FAVOURITE_VOTERS: usize = 10;
+
+pub const INITIAL_PLAYER_RATING: f64 = 1500.0;
+// Elo K-factor for one full game; each pairwise result gets K / (players - 1)
+const PLAYER_RATING_K_FACTOR: f64 = 32.0;
+const GLOBAL_RATING_SCOPE: &str = "global";
+const DEFAULT_RATING_LEADERBOARD_LIMIT: usize = 50;
+const MAX_RATING_LEADERBOARD_LIMIT: usize = 500;
+
+#[derive(Debug, Clone, Default)]
+pub struct ClueSearchQuery {
+    // free text matched against clues with FTS5; every word must appear
+    pub text: Option<String>,
+    pub room_id: Option<String>,
+    pub storyteller_hash: Option<String>,
+    pub since_s: Option<u64>,
+    pub until_s: Option<u64>,
+    pub limit: usize,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ClueArchiveEntry {
+    pub game_id: String,
+    pub room_id: String,
+    pub round_num: u16,
+    pub recorded_at_s: u64,
+    pub storyteller_hash: String,
+    pub storyteller_display_name: String,
+    pub clue: String,
+    pub storyteller_card_hash: Option<String>,
+    pub correct_guessers: u64,
+    pub guessers: u64,
+    pub clue_rating_average: Option<f64>,
+    pub clue_rating_count: u16,
+    pub clue_rating_max_stars: Option<u16>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ClueArchiveResponse {
+    pub clues: Vec<ClueArchiveEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct CardStats {
+    pub card_hash: String,
+    pub card_paths: Vec<String>,
+    pub appearances: u64,
+    pub storyteller_picks: u64,
+    // storyteller rounds where some, but not all, guessers found the card
+    pub storyteller_successes: u64,
+    // storyteller rounds where every guesser found the card (too obvious)
+    pub storyteller_all_guessed: u64,
+    // storyteller rounds where nobody found the card (too obscure)
+    pub storyteller_none_guessed: u64,
+    pub storyteller_success_rate: Option<f64>,
+    pub decoy_appearances: u64,
+    // story vote tokens the card attracted while it was a decoy
+    pub decoy_votes: u64,
+    pub beauty_votes: u64,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct CardStatsResponse {
+    pub cards: Vec<CardStats>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct PlayerAlias {
+    pub display_name: String,
+    pub first_seen_at_s: u64,
+    pub last_seen_at_s: u64,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct PlayerFavouriteVoter {
+    pub player_hash: String,
+    pub display_name: String,
+    // how many of this player's storyteller cards the voter found
+    pub correct_guesses: u64,
+    // how many of this player's storyteller rounds the voter guessed in
+    pub guesses: u64,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct PlayerProfileStats {
+    pub player_hash: String,
+    pub display_name: String,
+    pub aliases: Vec<PlayerAlias>,
+    pub games_played: u64,
+    pub games_completed: u64,
+    pub games_won: u64,
+    pub rounds_played: u64,
+    pub storyteller_rounds: u64,
+    // storyteller rounds where some, but not all, guessers found the card
+    pub storyteller_successes: u64,
+    pub storyteller_success_rate: Option<f64>,
+    pub guessing_rounds: u64,
+    pub correct_guesses: u64,
+    pub guessing_accuracy: Option<f64>,
+    pub decoy_cards: u64,
+    // decoys that drew at least one story vote
+    pub decoys_that_fooled: u64,
+    pub decoy_votes_attracted: u64,
+    pub decoy_effectiveness: Option<f64>,
+    pub clue_ratings_received: u64,
+    pub clue_rating_average: Option<f64>,
+    pub favourite_voters: Vec<PlayerFavouriteVoter>,
+    pub stella: PlayerStellaStats,
+}
+
+// Stella games in the same room/game-limit scope as the Dixit figures above
+#[derive(Debug, Clone, Default, Serialize, PartialEq, Eq)]
+pub struct PlayerStellaStats {
+    pub games_played: u64,
+    pub games_completed: u64,
+    pub games_won: u64,
+    pub rounds_played: u64,
+    pub cards_selected: u64,
+    // selections that scored when revealed
+    pub cards_scored: u64,
+    pub falls: u64,
+    pub rounds_in_the_dark: u64,
+    pub points: u64,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct HeadToHeadPlayer {
+    pub player_hash: String,
+    pub display_name: String,
+}
+
+// directed: how `guesser_hash` played against `target_hash`
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct HeadToHeadPair {
+    pub guesser_hash: String,
+    pub target_hash: String,
+    // target was storyteller and guesser voted
+    pub storyteller_rounds: u64,
+    pub correct_guesses: u64,
+    pub correct_guess_rate: Option<f64>,
+    // guesser voted while target had a decoy on the table
+    pub decoy_rounds: u64,
+    pub times_fooled: u64,
+    pub fooled_rate: Option<f64>,
+    pub beauty_votes_given: u64,
+    // share of the guesser's beauty vote tokens that went to the target's cards
+    pub beauty_affinity: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct HeadToHeadResponse {
+    pub players: Vec<HeadToHeadPlayer>,
+    pub pairs: Vec<HeadToHeadPair>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct PlayerRatingEntry {
+    pub player_hash: String,
+    pub display_name: String,
+    pub rating: f64,
+    pub games_rated: u64,
+    pub last_rated_at_s: u64,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct PlayerRatingLeaderboard {
+    pub scope: String,
+    pub players: Vec<PlayerRatingEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct PlayerRatingHistoryPoint {
+    pub game_id: String,
+    pub room_id: String,
+    pub recorded_at_s: u64,
+    // 1-based; tied players share a placement
+    pub placement: u16,
+    pub players_in_game: u16,
+    pub rating_before: f64,
+    pub rating_after: f64,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct PlayerRatingHistory {
+    pub scope: String,
+    pub player_hash: String,
+    pub display_name: String,
+    pub rating: Option<f64>,
+    pub history: Vec<PlayerRatingHistoryPoint>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlayerErasureMode {
+    // keep the rows for everyone else's stats, but under a fresh hash and no name
+    Anonymize,
+    // anonymize, then drop every row that only describes the player
+    Delete,
+}
+
+impl PlayerErasureMode {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "anonymize" => Some(Self::Anonymize),
+            "delete" => Some(Self::Delete),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Anonymize => "anonymize",
+            Self::Delete => "delete",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct PlayerErasureReport {
+    pub player_hash: String,
+    pub mode: String,
+    // hash now carried by the rows that were kept
+    pub replacement_hash: String,
+    pub rows_anonymized: u64,
+    pub rows_deleted: u64,
+}
+
+// Every column pair that ties a display name to a player. `rounds_table` is where the row's
+// recorded_at lives when the table has none of its own.
+struct PlayerNameColumn {
+    table: &'static str,
+    hash_column: &'static str,
+    name_column: &'static str,
+    rounds_table: Option<&'static str>,
+}
+
+const PLAYER_NAME_COLUMNS: &[PlayerNameColumn] = &[
+    PlayerNameColumn {
+        table: "mb_vote_events",
+        hash_column: "voter_hash",
+        name_column: "voter_display_name",
+        rounds_table: None,
+    },
+    PlayerNameColumn {
+        table: "mb_vote_events",
+        hash_column: "owner_hash",
+        name_column: "owner_display_name",
+        rounds_table: None,
+    },
+    PlayerNameColumn {
+        table: "mb_round_win_events",
+        hash_column: "winner_hash",
+        name_column: "winner_display_name",
+        rounds_table: None,
+    },
+    PlayerNameColumn {
+        table: "mb_game_rounds",
+        hash_column: "storyteller_hash",
+        name_column: "storyteller_display_name",
+        rounds_table: None,
+    },
+    PlayerNameColumn {
+        table: "mb_game_round_cards",
+        hash_column: "owner_hash",
+        name_column: "owner_display_name",
+        rounds_table: Some("mb_game_rounds"),
+    },
+    PlayerNameColumn {
+        table: "mb_game_round_cards",
+        hash_column: "submitted_by_hash",
+        name_column: "submitted_by_display_name",
+        rounds_table: Some("mb_game_rounds"),
+    },
+    PlayerNameColumn {
+        table: "mb_game_round_story_votes",
+        hash_column: "voter_hash",
+        name_column: "voter_display_name",
+        rounds_table: Some("mb_game_rounds"),
+    },
+    PlayerNameColumn {
+        table: "mb_game_round_beauty_votes",
+        hash_column: "voter_hash",
+        name_column: "voter_display_name",
+        rounds_table: Some("mb_game_rounds"),
+    },
+    PlayerNameColumn {
+        table: "mb_game_round_scores",
+        hash_column: "player_hash",
+        name_column: "player_display_name",
+        rounds_table: Some("mb_game_rounds"),
+    },
+    PlayerNameColumn {
+        table: "mb_stella_round_selections",
+        hash_column: "player_hash",
+        name_column: "player_display_name",
+        rounds_table: Some("mb_stella_rounds"),
+    },
+    PlayerNameColumn {
+        table: "mb_stella_round_players",
+        hash_column: "player_hash",
+        name_column: "player_display_name",
+        rounds_table: Some("mb_stella_rounds"),
+    },
+];
+
+// keyed by player hash but carry no display name
+const PLAYER_HASH_ONLY_TABLES: &[&str] = &[
+    "mb_player_ratings",
+    "mb_player_rating_history",
+    "mb_game_standings",
+];
+
+// (table, hash column) rows that describe only the erased player and go away in Delete mode;
+// everything else in PLAYER_NAME_COLUMNS is part of another player's history
+const PLAYER_OWNED_ROWS: &[(&str, &str)] = &[
+    ("mb_vote_events", "voter_hash"),
+    ("mb_round_win_events", "winner_hash"),
+    ("mb_game_round_story_votes", "voter_hash"),
+    ("mb_game_round_beauty_votes", "voter_hash"),
+    ("mb_game_round_scores", "player_hash"),
+    ("mb_stella_round_selections", "player_hash"),
+    ("mb_stella_round_players", "player_hash"),
+    ("mb_player_ratings", "player_hash"),
+    ("mb_player_rating_history", "player_hash"),
+    ("mb_game_standings", "player_hash"),
+    ("mb_players", "player_hash"),
+];
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct StatsExportResponse {
+    pub games: Vec<GameReplay>,
+}
+
+// one CSV file per table; every row carries game_id and round_num so files can be joined
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatsExportTable {
+    Rounds,
+    Cards,
+    StoryVotes,
+    BeautyVotes,
+    Scores,
+    StellaRounds,
+    StellaBoardCards,
+    StellaSelections,
+    StellaPlayers,
+}
+
+impl StatsExportTable {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "rounds" => Some(Self::Rounds),
+            "cards" => Some(Self::Cards),
+            "story_votes" => Some(Self::StoryVotes),
+            "beauty_votes" => Some(Self::BeautyVotes),
+            "scores" => Some(Self::Scores),
+            "stella_rounds" => Some(Self::StellaRounds),
+            "stella_board_cards" => Some(Self::StellaBoardCards),
+            "stella_selections" => Some(Self::StellaSelections),
+            "stella_players" => Some(Self::StellaPlayers),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Rounds => "rounds",
+            Self::Cards => "cards",
+            Self::StoryVotes => "story_votes",
+            Self::BeautyVotes => "beauty_votes",
+            Self::Scores => "scores",
+            Self::StellaRounds => "stella_rounds",
+            Self::StellaBoardCards => "stella_board_cards",
+            Self::StellaSelections => "stella_selections",
+            Self::StellaPlayers => "stella_players",
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct StatsExportRoundRow<'a> {
+    game_id: &'a str,
+    room_id: &'a str,
+    round_num: u16,
+    recorded_at_s: u64,
+    storyteller_hash: &'a str,
+    storyteller_display_name: &'a str,
+    clue: &'a str,
+    results_display_mode: &'a str,
+    clue_rating_count: Option<u16>,
+    clue_rating_sum: Option<u32>,
+    clue_rating_max_stars: Option<u16>,
+}
+
+#[derive(Serialize)]
+struct StatsExportCardRow<'a> {
+    game_id: &'a str,
+    round_num: u16,
+    card_hash: &'a str,
+    owner_hash: &'a str,
+    owner_display_name: &'a str,
+    submitted_by_hash: &'a str,
+    submitted_by_display_name: &'a str,
+    is_storyteller_card: bool,
+    center_order: u16,
+}
+
+#[derive(Serialize)]
+struct StatsExportVoteRow<'a> {
+    game_id: &'a str,
+    round_num: u16,
+    voter_hash: &'a str,
+    voter_display_name: &'a str,
+    card_hash: &'a str,
+    vote_count: u16,
+}
+
+#[derive(Serialize)]
+struct StatsExportScoreRow<'a> {
+    game_id: &'a str,
+    round_num: u16,
+    player_hash: &'a str,
+    player_display_name: &'a str,
+    story_delta: i32,
+    beauty_delta: i32,
+    total_after_round: u16,
+    beauty_total_after_round: u16,
+}
+
+#[derive(Serialize)]
+struct StatsExportStellaRoundRow<'a> {
+    game_id: &'a str,
+    room_id: &'a str,
+    round_num: u16,
+    recorded_at_s: u64,
+    clue_word: &'a str,
+}
+
+#[derive(Serialize)]
+struct StatsExportStellaBoardCardRow<'a> {
+    game_id: &'a str,
+    round_num: u16,
+    card_hash: &'a str,
+    board_order: u16,
+    reveal_order: Option<u16>,
+    points: Option<u16>,
+}
+
+#[derive(Serialize)]
+struct StatsExportStellaSelectionRow<'a> {
+    game_id: &'a str,
+    round_num: u16,
+    player_hash: &'a str,
+    player_display_name: &'a str,
+    card_hash: &'a str,
+}
+
+#[derive(Serialize)]
+struct StatsExportStellaPlayerRow<'a> {
+    game_id: &'a str,
+    round_num: u16,
+    player_hash: &'a str,
+    player_display_name: &'a str,
+    in_the_dark: bool,
+    fell: bool,
+    score_delta: u16,
+    total_after_round: u16,
+}
+
+pub fn stats_export_csv(games: &[GameReplay], table: StatsExportTable) -> Result<String> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    let mut wrote_row = false;
+    for game in games {
+        for round in &game.rounds {
+            let game_id = game.game_id.as_str();
+            let round_num = round.round_num;
+            match table {
+                StatsExportTable::Rounds => {
+                    writer.serialize(StatsExportRoundRow {
+                        game_id,
+                        room_id: &game.room_id,
+                        round_num,
+                        recorded_at_s: round.recorded_at_s,
+                        storyteller_hash: &round.storyteller_hash,
+                        storyteller_display_name: &round.storyteller_display_name,
+                        clue: &round.clue,
+                        results_display_mode: &round.results_display_mode,
+                        clue_rating_count: round.clue_rating.as_ref().map(|r| r.rating_count),
+                        clue_rating_sum: round.clue_rating.as_ref().map(|r| r.rating_sum),
+                        clue_rating_max_stars: round.clue_rating.as_ref().map(|r| r.max_stars),
+                    })?;
+                    wrote_row = true;
+                }
+                StatsExportTable::Cards => {
+                    for card in &round.center_cards {
+                        writer.serialize(StatsExportCardRow {
+                            game_id,
+                            round_num,
+                            card_hash: &card.card_hash,
+                            owner_hash: &card.owner_hash,
+                            owner_display_name: &card.owner_display_name,
+                            submitted_by_hash: &card.submitted_by_hash,
+                            submitted_by_display_name: &card.submitted_by_display_name,
+                            is_storyteller_card: card.is_storyteller_card,
+                            center_order: card.center_order,
+                        })?;
+                        wrote_row = true;
+                    }
+                }
+                StatsExportTable::StoryVotes | StatsExportTable::BeautyVotes => {
+                    let votes = if table == StatsExportTable::StoryVotes {
+                        &round.story_votes
+                    } else {
+                        &round.beauty_votes
+                    };
+                    for vote in votes {
+                        writer.serialize(StatsExportVoteRow {
+                            game_id,
+                            round_num,
+                            voter_hash: &vote.voter_hash,
+                            voter_display_name: &vote.voter_display_name,
+                            card_hash: &vote.card_hash,
+                            vote_count: vote.vote_count,
+                        })?;
+                        wrote_row = true;
+                    }
+                }
+                StatsExportTable::Scores => {
+                    for score in &round.scores {
+                        writer.serialize(StatsExportScoreRow {
+                            game_id,
+                            round_num,
+                            player_hash: &score.player_hash,
+                            player_display_name: &score.player_display_name,
+                            story_delta: score.story_delta,
+                            beauty_delta: score.beauty_delta,
+                            total_after_round: score.total_after_round,
+                            beauty_total_after_round: score.beauty_total_after_round,
+                        })?;
+                        wrote_row = true;
+                    }
+                }
+                StatsExportTable::StellaRounds
+                | StatsExportTable::StellaBoardCards
+                | StatsExportTable::StellaSelections
+                | StatsExportTable::StellaPlayers => {}
+            }
+        }
+        for round in &game.stella_rounds {
+            let game_id = game.game_id.as_str();
+            let round_num = round.round_num;
+            match table {
+                StatsExportTable::StellaRounds => {
+                    writer.serialize(StatsExportStellaRoundRow {
+                        game_id,
+                        room_id: &game.room_id,
+                        round_num,
+                        recorded_at_s: round.recorded_at_s,
+                        clue_word: &round.clue_word,
+                    })?;
+                    wrote_row = true;
+                }
+                StatsExportTable::StellaBoardCards => {
+                    for card in &round.board_cards {
+                        writer.serialize(StatsExportStellaBoardCardRow {
+                            game_id,
+                            round_num,
+                            card_hash: &card.card_hash,
+                            board_order: card.board_order,
+                            reveal_order: card.reveal_order,
+                            points: card.points,
+                        })?;
+                        wrote_row = true;
+                    }
+                }
+                StatsExportTable::StellaSelections => {
+                    for selection in &round.selections {
+                        writer.serialize(StatsExportStellaSelectionRow {
+                            game_id,
+                            round_num,
+                            player_hash: &selection.player_hash,
+                            player_display_name: &selection.player_display_name,
+                            card_hash: &selection.card_hash,
+                        })?;
+                        wrote_row = true;
+                    }
+                }
+                StatsExportTable::StellaPlayers => {
+                    for player in &round.players {
+                        writer.serialize(StatsExportStellaPlayerRow {
+                            game_id,
+                            round_num,
+                            player_hash: &player.player_hash,
+                            player_display_name: &player.player_display_name,
+                            in_the_dark: player.in_the_dark,
+                            fell: player.fell,
+                            score_delta: player.score_delta,
+                            total_after_round: player.total_after_round,
+                        })?;
+                        wrote_row = true;
+                    }
+                }
+                StatsExportTable::Rounds
+                | StatsExportTable::Cards
+                | StatsExportTable::StoryVotes
+                | StatsExportTable::BeautyVotes
+                | StatsExportTable::Scores => {}
+            }
+        }
+    }
+    // csv only writes headers alongside the first row, so an empty export gets them explicitly
+    if !wrote_row {
+        writer.write_record(stats_export_csv_headers(table))?;
+    }
+    let bytes = writer
+        .into_inner()
+        .map_err(|err| anyhow::anyhow!("Failed to flush stats export CSV: {err}"))?;
+    String::from_utf8(bytes).context("Stats export CSV is not valid UTF-8")
+}
+
+fn stats_export_csv_headers(table: StatsExportTable) -> &'static [&'static str] {
+    match table {
+        StatsExportTable::Rounds => &[
+            "game_id",
+            "room_id",
+            "round_num",
+            "recorded_at_s",
+            "storyteller_hash",
+            "storyteller_display_name",
+            "clue",
+            "results_display_mode",
+            "clue_rating_count",
+            "clue_rating_sum",
+            "clue_rating_max_stars",
+        ],
+        StatsExportTable::Cards => &[
+            "game_id",
+            "round_num",
+            "card_hash",
+            "owner_hash",
+            "owner_display_name",
+            "submitted_by_hash",
+            "submitted_by_display_name",
+            "is_storyteller_card",
+            "center_order",
+        ],
+        StatsExportTable::StoryVotes | StatsExportTable::BeautyVotes => &[
+            "game_id",
+            "round_num",
+            "voter_hash",
+            "voter_display_name",
+            "card_hash",
+            "vote_count",
+        ],
+        StatsExportTable::Scores => &[
+            "game_id",
+            "round_num",
+            "player_hash",
+            "player_display_name",
+            "story_delta",
+            "beauty_delta",
+            "total_after_round",
+            "beauty_total_after_round",
+        ],
+        StatsExportTable::StellaRounds => &[
+            "game_id",
+            "room_id",
+            "round_num",
+            "recorded_at_s",
+            "clue_word",
+        ],
+        StatsExportTable::StellaBoardCards => &[
+            "game_id",
+            "round_num",
+            "card_hash",
+            "board_order",
+            "reveal_order",
+            "points",
+        ],
+        StatsExportTable::StellaSelections => &[
+            "game_id",
+            "round_num",
+            "player_hash",
+            "player_display_name",
+            "card_hash",
+        ],
+        StatsExportTable::StellaPlayers => &[
+            "game_id",
+            "round_num",
+            "player_hash",
+            "player_display_name",
+            "in_the_dark",
+            "fell",
+            "score_delta",
+            "total_after_round",
+        ],
+    }
+}
+
+pub const GAME_REPLAY_FORMAT_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct MostBeautifulGameAuditCardRecord {
     pub card_hash: String,
     pub owner_hash: String,
@@ -66,7 +781,7 @@ pub struct MostBeautifulGameAuditCardRecord {
     pub center_order: u16,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct MostBeautifulGameAuditVoteRecord {
     pub voter_hash: String,
     pub voter_display_name: String,
@@ -74,16 +789,32 @@ pub struct MostBeautifulGameAuditVoteRecord {
     pub vote_count: u16,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct MostBeautifulGameAuditScoreRecord {
     pub player_hash: String,
     pub player_display_name: String,
-    pub story_delta: u16,
+    pub story_delta: i32,
     pub beauty_delta: i32,
     pub total_after_round: u16,
     pub beauty_total_after_round: u16,
 }
 
+// A member's place on the End screen, after tiebreaks, secret objective bonuses and handicaps.
+// Ratings are computed from these rather than from raw round totals.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameStandingRecord {
+    pub player_hash: String,
+    pub rank: u16,
+    pub points: u16,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MostBeautifulGameAuditClueRatingRecord {
+    pub rating_count: u16,
+    pub rating_sum: u32,
+    pub max_stars: u16,
+}
+
 #[derive(Debug, Clone)]
 pub struct MostBeautifulGameAuditRoundRecord {
     pub game_id: String,
@@ -99,71 +830,1115 @@ pub struct MostBeautifulGameAuditRoundRecord {
     pub story_votes: Vec<MostBeautifulGameAuditVoteRecord>,
     pub beauty_votes: Vec<MostBeautifulGameAuditVoteRecord>,
     pub score_log: Vec<MostBeautifulGameAuditScoreRecord>,
+    // None when the round had no clue-rating stage or nobody rated
+    pub clue_rating: Option<MostBeautifulGameAuditClueRatingRecord>,
+}
+
+// values stored in mb_games.game_mode, matching the room's serialized GameMode
+const GAME_MODE_DIXIT: &str = "dixit_plus";
+const GAME_MODE_STELLA: &str = "stella";
+
+fn default_replay_game_mode() -> String {
+    GAME_MODE_DIXIT.to_string()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct StellaGameAuditBoardCardRecord {
+    pub card_hash: String,
+    pub board_order: u16,
+    // position in the round's reveal sequence; None when the card was never revealed
+    pub reveal_order: Option<u16>,
+    // stella_card_points for revealed cards: 0 means the revealer fell
+    pub points: Option<u16>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct StellaGameAuditSelectionRecord {
+    pub player_hash: String,
+    pub player_display_name: String,
+    pub card_hash: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct StellaGameAuditPlayerRecord {
+    pub player_hash: String,
+    pub player_display_name: String,
+    pub in_the_dark: bool,
+    pub fell: bool,
+    pub score_delta: u16,
+    pub total_after_round: u16,
 }
 
 #[derive(Debug, Clone)]
-pub struct MostBeautifulStatsStore {
-    db_path: PathBuf,
+pub struct StellaGameAuditRoundRecord {
+    pub game_id: String,
+    pub room_id: String,
+    pub game_started_at_s: u64,
+    pub recorded_at_s: u64,
+    pub round_num: u16,
+    pub clue_word: String,
+    pub board_cards: Vec<StellaGameAuditBoardCardRecord>,
+    pub selections: Vec<StellaGameAuditSelectionRecord>,
+    pub players: Vec<StellaGameAuditPlayerRecord>,
 }
 
-impl MostBeautifulStatsStore {
-    pub fn new(db_path: impl Into<PathBuf>) -> Result<Self> {
-        let db_path = db_path.into();
-        if let Some(parent) = db_path.parent() {
-            std::fs::create_dir_all(parent).with_context(|| {
-                format!(
-                    "Failed to create Most Beautiful stats directory {}",
-                    parent.display()
-                )
-            })?;
+// self-contained, versioned export of one audited game
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct GameReplay {
+    pub format_version: u32,
+    pub game_id: String,
+    pub room_id: String,
+    // replays exported before Stella games were audited have no mode and are Dixit
+    #[serde(default = "default_replay_game_mode")]
+    pub game_mode: String,
+    pub started_at_s: u64,
+    pub ended_at_s: Option<u64>,
+    pub completed: bool,
+    // Dixit rounds; empty for Stella games
+    #[serde(default)]
+    pub rounds: Vec<GameReplayRound>,
+    // Stella rounds; empty for Dixit games
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stella_rounds: Vec<StellaReplayRound>,
+}
+
+// one round in stage order: clue, center cards, story votes, beauty votes, scores
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct GameReplayRound {
+    pub round_num: u16,
+    pub recorded_at_s: u64,
+    pub storyteller_hash: String,
+    pub storyteller_display_name: String,
+    pub clue: String,
+    pub results_display_mode: String,
+    // sorted by center_order, i.e. the order cards were shown during voting
+    pub center_cards: Vec<MostBeautifulGameAuditCardRecord>,
+    pub story_votes: Vec<MostBeautifulGameAuditVoteRecord>,
+    pub beauty_votes: Vec<MostBeautifulGameAuditVoteRecord>,
+    pub scores: Vec<MostBeautifulGameAuditScoreRecord>,
+    #[serde(default)]
+    pub clue_rating: Option<MostBeautifulGameAuditClueRatingRecord>,
+}
+
+// one Stella round: board in deal order, hidden selections, then who fell and what they scored
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct StellaReplayRound {
+    pub round_num: u16,
+    pub recorded_at_s: u64,
+    pub clue_word: String,
+    pub board_cards: Vec<StellaGameAuditBoardCardRecord>,
+    pub selections: Vec<StellaGameAuditSelectionRecord>,
+    pub players: Vec<StellaGameAuditPlayerRecord>,
+}
+
+impl GameReplay {
+    pub fn validate(&self) -> std::result::Result<(), String> {
+        if self.format_version != GAME_REPLAY_FORMAT_VERSION {
+            return Err(format!(
+                "Unsupported replay format version {} (expected {})",
+                self.format_version, GAME_REPLAY_FORMAT_VERSION
+            ));
+        }
+        if self.game_id.trim().is_empty() || self.room_id.trim().is_empty() {
+            return Err("Replay game_id and room_id must not be empty".to_string());
+        }
+        let round_nums = match self.game_mode.as_str() {
+            GAME_MODE_DIXIT if self.stella_rounds.is_empty() => self
+                .rounds
+                .iter()
+                .map(|round| round.round_num)
+                .collect::<Vec<_>>(),
+            GAME_MODE_STELLA if self.rounds.is_empty() => self
+                .stella_rounds
+                .iter()
+                .map(|round| round.round_num)
+                .collect::<Vec<_>>(),
+            GAME_MODE_DIXIT | GAME_MODE_STELLA => {
+                return Err("Replay rounds do not match its game_mode".to_string());
+            }
+            other => return Err(format!("Unsupported replay game_mode '{other}'")),
+        };
+        if round_nums.is_empty() {
+            return Err("Replay must contain at least one round".to_string());
+        }
+        match self.ended_at_s {
+            Some(ended_at_s) if ended_at_s < self.started_at_s => {
+                return Err("Replay ended_at_s is before started_at_s".to_string());
+            }
+            None if self.completed => {
+                return Err("Completed replay must have ended_at_s".to_string());
+            }
+            _ => {}
         }
 
-        let store = Self { db_path };
-        store.init()?;
-        Ok(store)
+        let mut previous_round_num = 0u16;
+        for round_num in round_nums {
+            if round_num <= previous_round_num {
+                return Err(format!(
+                    "Replay round numbers must start at 1 and increase (got {} after {})",
+                    round_num, previous_round_num
+                ));
+            }
+            previous_round_num = round_num;
+        }
+        for round in &self.rounds {
+            round.validate()?;
+        }
+        for round in &self.stella_rounds {
+            round.validate()?;
+        }
+        Ok(())
     }
 
-    pub fn path(&self) -> &Path {
-        &self.db_path
+    fn total_rounds(&self) -> u16 {
+        self.rounds
+            .last()
+            .map(|round| round.round_num)
+            .or_else(|| self.stella_rounds.last().map(|round| round.round_num))
+            .unwrap_or(0)
     }
+}
 
-    fn connect(&self) -> Result<Connection> {
-        let conn = Connection::open(&self.db_path).with_context(|| {
-            format!(
-                "Failed to open Most Beautiful stats database {}",
-                self.db_path.display()
-            )
-        })?;
-        conn.pragma_update(None, "journal_mode", "WAL")
-            .context("Failed to enable WAL mode for Most Beautiful stats")?;
-        conn.pragma_update(None, "foreign_keys", "ON")
-            .context("Failed to enable foreign keys for Most Beautiful stats")?;
-        conn.busy_timeout(std::time::Duration::from_secs(5))
-            .context("Failed to configure sqlite busy timeout")?;
-        Ok(conn)
+impl StellaReplayRound {
+    fn validate(&self) -> std::result::Result<(), String> {
+        let round_num = self.round_num;
+        let mut card_hashes = HashSet::new();
+        let mut board_orders = HashSet::new();
+        let mut reveal_orders = HashSet::new();
+        for card in &self.board_cards {
+            if !card_hashes.insert(card.card_hash.as_str())
+                || !board_orders.insert(card.board_order)
+            {
+                return Err(format!(
+                    "Round {round_num} has duplicate board cards or board positions"
+                ));
+            }
+            if let Some(reveal_order) = card.reveal_order {
+                if !reveal_orders.insert(reveal_order) {
+                    return Err(format!("Round {round_num} has duplicate reveal positions"));
+                }
+            }
+        }
+        if self
+            .selections
+            .iter()
+            .any(|selection| !card_hashes.contains(selection.card_hash.as_str()))
+        {
+            return Err(format!(
+                "Round {round_num} has a selection for a card that is not on the board"
+            ));
+        }
+        let mut players = HashSet::new();
+        if self
+            .players
+            .iter()
+            .any(|player| !players.insert(player.player_hash.as_str()))
+        {
+            return Err(format!("Round {round_num} lists a player more than once"));
+        }
+        Ok(())
     }
 
-    fn init(&self) -> Result<()> {
-        let conn = self.connect()?;
-        conn.execute_batch(
-            r#"
-            CREATE TABLE IF NOT EXISTS mb_players (
-                player_hash TEXT PRIMARY KEY,
-                latest_display_name TEXT NOT NULL,
-                first_seen_at INTEGER NOT NULL,
-                last_seen_at INTEGER NOT NULL
-            );
+    fn to_audit_record(&self, replay: &GameReplay) -> StellaGameAuditRoundRecord {
+        StellaGameAuditRoundRecord {
+            game_id: replay.game_id.clone(),
+            room_id: replay.room_id.clone(),
+            game_started_at_s: replay.started_at_s,
+            recorded_at_s: self.recorded_at_s,
+            round_num: self.round_num,
+            clue_word: self.clue_word.clone(),
+            board_cards: self.board_cards.clone(),
+            selections: self.selections.clone(),
+            players: self.players.clone(),
+        }
+    }
+}
 
-            CREATE TABLE IF NOT EXISTS mb_player_names (
-                player_hash TEXT NOT NULL,
-                display_name TEXT NOT NULL,
-                first_seen_at INTEGER NOT NULL,
-                last_seen_at INTEGER NOT NULL,
-                PRIMARY KEY (player_hash, display_name),
-                FOREIGN KEY (player_hash) REFERENCES mb_players(player_hash) ON DELETE CASCADE
-            );
+impl GameReplayRound {
+    fn validate(&self) -> std::result::Result<(), String> {
+        let round_num = self.round_num;
+        let storyteller_cards = self
+            .center_cards
+            .iter()
+            .filter(|card| card.is_storyteller_card)
+            .collect::<Vec<_>>();
+        if storyteller_cards.len() != 1 {
+            return Err(format!(
+                "Round {round_num} must have exactly one storyteller card"
+            ));
+        }
+        if storyteller_cards[0].owner_hash != self.storyteller_hash {
+            return Err(format!(
+                "Round {round_num} storyteller card is not owned by the storyteller"
+            ));
+        }
 
-            CREATE TABLE IF NOT EXISTS mb_card_paths (
+        let mut card_hashes = std::collections::HashSet::new();
+        let mut center_orders = std::collections::HashSet::new();
+        for card in &self.center_cards {
+            if !card_hashes.insert(card.card_hash.as_str())
+                || !center_orders.insert(card.center_order)
+            {
+                return Err(format!(
+                    "Round {round_num} has duplicate center cards or center positions"
+                ));
+            }
+        }
+        for vote in self.story_votes.iter().chain(self.beauty_votes.iter()) {
+            if vote.vote_count == 0 || !card_hashes.contains(vote.card_hash.as_str()) {
+                return Err(format!(
+                    "Round {round_num} has a vote for a card that is not in the center"
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn to_audit_record(&self, replay: &GameReplay) -> MostBeautifulGameAuditRoundRecord {
+        MostBeautifulGameAuditRoundRecord {
+            game_id: replay.game_id.clone(),
+            room_id: replay.room_id.clone(),
+            game_started_at_s: replay.started_at_s,
+            recorded_at_s: self.recorded_at_s,
+            round_num: self.round_num,
+            storyteller_hash: self.storyteller_hash.clone(),
+            storyteller_display_name: self.storyteller_display_name.clone(),
+            clue: self.clue.clone(),
+            results_display_mode: self.results_display_mode.clone(),
+            card_entries: self.center_cards.clone(),
+            story_votes: self.story_votes.clone(),
+            beauty_votes: self.beauty_votes.clone(),
+            score_log: self.scores.clone(),
+            clue_rating: self.clue_rating.clone(),
+        }
+    }
+}
+
+// story and beauty activity for one audited round, loaded in bulk for the stats endpoints
+#[derive(Debug, Default)]
+struct AuditRoundActivity {
+    storyteller_hash: String,
+    cards: Vec<MostBeautifulGameAuditCardRecord>,
+    story_votes: Vec<MostBeautifulGameAuditVoteRecord>,
+    beauty_votes: Vec<MostBeautifulGameAuditVoteRecord>,
+    scores: Vec<MostBeautifulGameAuditScoreRecord>,
+    clue_rating: Option<MostBeautifulGameAuditClueRatingRecord>,
+}
+
+impl AuditRoundActivity {
+    fn storyteller_card_hash(&self) -> Option<&str> {
+        self.cards
+            .iter()
+            .find(|card| card.is_storyteller_card)
+            .map(|card| card.card_hash.as_str())
+    }
+
+    fn guessers(&self) -> HashSet<&str> {
+        self.story_votes
+            .iter()
+            .map(|vote| vote.voter_hash.as_str())
+            .collect()
+    }
+
+    fn correct_guessers(&self) -> HashSet<&str> {
+        let Some(storyteller_card_hash) = self.storyteller_card_hash() else {
+            return HashSet::new();
+        };
+        self.story_votes
+            .iter()
+            .filter(|vote| vote.card_hash == storyteller_card_hash)
+            .map(|vote| vote.voter_hash.as_str())
+            .collect()
+    }
+
+    fn votes_for_card(votes: &[MostBeautifulGameAuditVoteRecord], card_hash: &str) -> u64 {
+        votes
+            .iter()
+            .filter(|vote| vote.card_hash == card_hash)
+            .map(|vote| u64::from(vote.vote_count))
+            .sum()
+    }
+}
+
+// Applied in order, one transaction each, with `PRAGMA user_version` recording the last one.
+// Statements use IF NOT EXISTS so databases created before versioning (user_version 0) only
+// gain what they are missing. Append new migrations; never edit a released one.
+struct StatsSchemaMigration {
+    version: u32,
+    description: &'static str,
+    apply: fn(&Connection) -> Result<()>,
+}
+
+const STATS_SCHEMA_MIGRATIONS: &[StatsSchemaMigration] = &[
+    StatsSchemaMigration {
+        version: 1,
+        description: "Most Beautiful events and Dixit game audit tables",
+        apply: MostBeautifulStatsStore::migrate_base_schema,
+    },
+    StatsSchemaMigration {
+        version: 2,
+        description: "clue ratings and clue search index",
+        apply: MostBeautifulStatsStore::migrate_clue_archive,
+    },
+    StatsSchemaMigration {
+        version: 3,
+        description: "player ratings",
+        apply: MostBeautifulStatsStore::migrate_player_ratings,
+    },
+    StatsSchemaMigration {
+        version: 4,
+        description: "game modes and Stella game audit tables",
+        apply: MostBeautifulStatsStore::migrate_stella_audit,
+    },
+    StatsSchemaMigration {
+        version: 5,
+        description: "player stats opt-outs",
+        apply: MostBeautifulStatsStore::migrate_player_opt_outs,
+    },
+    StatsSchemaMigration {
+        version: 6,
+        description: "optional player accounts",
+        apply: MostBeautifulStatsStore::migrate_accounts,
+    },
+    StatsSchemaMigration {
+        version: 7,
+        description: "persistent rooms",
+        apply: MostBeautifulStatsStore::migrate_persistent_rooms,
+    },
+    StatsSchemaMigration {
+        version: 8,
+        description: "final game standings",
+        apply: MostBeautifulStatsStore::migrate_game_standings,
+    },
+    StatsSchemaMigration {
+        version: 9,
+        description: "games with dropped audit writes",
+        apply: MostBeautifulStatsStore::migrate_missing_writes,
+    },
+];
+
+const STATS_SCHEMA_VERSION: u32 = 9;
+
+// last_used_at is only rewritten once it is this stale, so session lookups stay reads
+const ACCOUNT_SESSION_TOUCH_INTERVAL_S: u64 = 60 * 60;
+
+pub const DEFAULT_STATS_WRITER_QUEUE_CAPACITY: usize = 1024;
+// writes committed together in one transaction when the queue has a backlog
+const STATS_WRITER_MAX_BATCH: usize = 64;
+// connections shared by the read-only query endpoints
+const STATS_READ_POOL_SIZE: usize = 4;
+
+// A persistent room as stored; `snapshot` is the room's PersistentRoomSnapshot as JSON.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PersistentRoomRecord {
+    pub room_id: String,
+    pub owner_token_hash: String,
+    pub snapshot: String,
+    pub created_at_s: u64,
+    pub updated_at_s: u64,
+}
+
+#[derive(Debug, Clone)]
+enum StatsWrite {
+    Round(MostBeautifulRoundRecord),
+    GameAuditRound(Box<MostBeautifulGameAuditRoundRecord>),
+    StellaAuditRound(Box<StellaGameAuditRoundRecord>),
+    GameComplete {
+        game_id: String,
+        ended_at_s: u64,
+        total_rounds: u16,
+        standings: Vec<GameStandingRecord>,
+        // set when a round write for this game was dropped from a full queue
+        missing_writes: bool,
+    },
+    PlayerOptOut {
+        player_hash: String,
+        opted_out: bool,
+        recorded_at_s: u64,
+    },
+    // Maintenance writes below are sent with `submit_and_wait`. Running them on the writer
+    // thread orders them with every round write queued before or after.
+    ErasePlayer {
+        player_hash: String,
+        mode: PlayerErasureMode,
+        replacement_hash: String,
+    },
+    ExpireNames {
+        cutoff_s: u64,
+    },
+    MergePlayer {
+        account: Box<Account>,
+        player_hash: String,
+        merged_at_s: u64,
+    },
+    ImportReplay(Box<GameReplay>),
+    SavePersistentRoom {
+        room_id: String,
+        snapshot: String,
+        updated_at_s: u64,
+    },
+    DeletePersistentRoom {
+        room_id: String,
+    },
+}
+
+#[derive(Debug)]
+enum StatsWriteOutcome {
+    Applied,
+    RowsChanged(u64),
+    Erased(PlayerErasureReport),
+    Merged(AccountMergeReport),
+}
+
+impl StatsWrite {
+    fn apply(&self, tx: &rusqlite::Transaction<'_>) -> Result<StatsWriteOutcome> {
+        match self {
+            Self::Round(record) => MostBeautifulStatsStore::write_round_rows(tx, record)?,
+            Self::GameAuditRound(record) => {
+                MostBeautifulStatsStore::write_game_audit_round(tx, record)?
+            }
+            Self::StellaAuditRound(record) => {
+                MostBeautifulStatsStore::write_stella_audit_round(tx, record)?
+            }
+            Self::GameComplete {
+                game_id,
+                ended_at_s,
+                total_rounds,
+                standings,
+                missing_writes,
+            } => MostBeautifulStatsStore::write_game_complete(
+                tx,
+                game_id,
+                *ended_at_s,
+                *total_rounds,
+                standings,
+                *missing_writes,
+            )?,
+            Self::PlayerOptOut {
+                player_hash,
+                opted_out,
+                recorded_at_s,
+            } => MostBeautifulStatsStore::write_player_opt_out(
+                tx,
+                player_hash,
+                *opted_out,
+                *recorded_at_s,
+            )?,
+            Self::ErasePlayer {
+                player_hash,
+                mode,
+                replacement_hash,
+            } => {
+                return MostBeautifulStatsStore::write_player_erasure(
+                    tx,
+                    player_hash,
+                    *mode,
+                    replacement_hash,
+                )
+                .map(StatsWriteOutcome::Erased)
+            }
+            Self::ExpireNames { cutoff_s } => {
+                return MostBeautifulStatsStore::write_name_expiry(tx, *cutoff_s)
+                    .map(StatsWriteOutcome::RowsChanged)
+            }
+            Self::MergePlayer {
+                account,
+                player_hash,
+                merged_at_s,
+            } => {
+                return MostBeautifulStatsStore::write_account_merge(
+                    tx,
+                    account,
+                    player_hash,
+                    *merged_at_s,
+                )
+                .map(StatsWriteOutcome::Merged)
+            }
+            Self::ImportReplay(replay) => {
+                MostBeautifulStatsStore::write_game_replay_import(tx, replay)?
+            }
+            Self::SavePersistentRoom {
+                room_id,
+                snapshot,
+                updated_at_s,
+            } => {
+                tx.execute(
+                    "UPDATE mb_persistent_rooms SET snapshot = ?2, updated_at = ?3 WHERE room_id = ?1",
+                    params![room_id, snapshot, updated_at_s],
+                )
+                .context("Failed to save persistent room")?;
+            }
+            Self::DeletePersistentRoom { room_id } => {
+                let deleted = tx
+                    .execute(
+                        "DELETE FROM mb_persistent_rooms WHERE room_id = ?1",
+                        params![room_id],
+                    )
+                    .context("Failed to delete persistent room")?;
+                return Ok(StatsWriteOutcome::RowsChanged(deleted as u64));
+            }
+        }
+        Ok(StatsWriteOutcome::Applied)
+    }
+
+    // replaces the display name of every opted-out player before the write is queued
+    fn audit_game_id(&self) -> Option<&str> {
+        match self {
+            Self::GameAuditRound(record) => Some(&record.game_id),
+            Self::StellaAuditRound(record) => Some(&record.game_id),
+            _ => None,
+        }
+    }
+
+    fn redact_opted_out(&mut self, opted_out: &HashSet<String>) {
+        if opted_out.is_empty() {
+            return;
+        }
+        let redact = |hash: &str, name: &mut String| {
+            if opted_out.contains(hash) {
+                *name = ANONYMOUS_PLAYER_NAME.to_string();
+            }
+        };
+        match self {
+            Self::Round(record) => {
+                for vote in &mut record.votes {
+                    redact(&vote.voter_hash, &mut vote.voter_display_name);
+                    redact(&vote.owner_hash, &mut vote.owner_display_name);
+                }
+                for win in &mut record.wins {
+                    redact(&win.winner_hash, &mut win.winner_display_name);
+                }
+            }
+            Self::GameAuditRound(record) => {
+                redact(
+                    &record.storyteller_hash,
+                    &mut record.storyteller_display_name,
+                );
+                for card in &mut record.card_entries {
+                    redact(&card.owner_hash, &mut card.owner_display_name);
+                    redact(&card.submitted_by_hash, &mut card.submitted_by_display_name);
+                }
+                for vote in record
+                    .story_votes
+                    .iter_mut()
+                    .chain(record.beauty_votes.iter_mut())
+                {
+                    redact(&vote.voter_hash, &mut vote.voter_display_name);
+                }
+                for score in &mut record.score_log {
+                    redact(&score.player_hash, &mut score.player_display_name);
+                }
+            }
+            Self::StellaAuditRound(record) => {
+                for selection in &mut record.selections {
+                    redact(&selection.player_hash, &mut selection.player_display_name);
+                }
+                for player in &mut record.players {
+                    redact(&player.player_hash, &mut player.player_display_name);
+                }
+            }
+            Self::GameComplete { .. }
+            | Self::PlayerOptOut { .. }
+            | Self::ErasePlayer { .. }
+            | Self::ExpireNames { .. }
+            | Self::MergePlayer { .. }
+            | Self::ImportReplay(_)
+            | Self::SavePersistentRoom { .. }
+            | Self::DeletePersistentRoom { .. } => {}
+        }
+    }
+}
+
+enum StatsWriterMsg {
+    Write(StatsWrite),
+    // committed in its own transaction once every write queued before it is committed
+    Request(
+        StatsWrite,
+        std::sync::mpsc::Sender<Result<StatsWriteOutcome>>,
+    ),
+    // acknowledged once every write queued before it is committed
+    #[cfg(test)]
+    Flush(std::sync::mpsc::Sender<()>),
+}
+
+#[derive(Debug, Default)]
+struct StatsWriterCounters {
+    queue_depth: AtomicU64,
+    max_queue_depth: AtomicU64,
+    writes_committed: AtomicU64,
+    writes_failed: AtomicU64,
+    batches_committed: AtomicU64,
+    dropped_writes: AtomicU64,
+    last_batch_micros: AtomicU64,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct StatsWriterMetrics {
+    pub background_writer: bool,
+    pub queue_capacity: usize,
+    pub queue_depth: u64,
+    pub max_queue_depth: u64,
+    pub writes_committed: u64,
+    pub writes_failed: u64,
+    pub batches_committed: u64,
+    // writes discarded because the queue was full
+    pub dropped_writes: u64,
+    pub last_batch_ms: f64,
+}
+
+#[derive(Debug)]
+struct StatsWriter {
+    sender: mpsc::Sender<StatsWriterMsg>,
+    capacity: usize,
+    counters: Arc<StatsWriterCounters>,
+    // games that lost an audit round to a full queue, until they are marked complete
+    games_missing_writes: Mutex<HashSet<String>>,
+}
+
+impl StatsWriter {
+    // Never waits, since callers hold a room lock on a runtime worker. A full queue drops the
+    // write; the write is handed back only if the writer thread has exited.
+    fn enqueue(&self, write: StatsWrite) -> std::result::Result<(), StatsWrite> {
+        // counted before sending, since the writer may take the write before try_send returns
+        let depth = self.counters.queue_depth.fetch_add(1, Ordering::Relaxed) + 1;
+        let sent = self.sender.try_send(StatsWriterMsg::Write(write));
+        if sent.is_err() {
+            self.counters.queue_depth.fetch_sub(1, Ordering::Relaxed);
+        }
+        match sent {
+            Ok(()) => {
+                self.counters
+                    .max_queue_depth
+                    .fetch_max(depth, Ordering::Relaxed);
+                Ok(())
+            }
+            Err(TrySendError::Full(StatsWriterMsg::Write(write))) => {
+                if let Some(game_id) = write.audit_game_id() {
+                    if let Ok(mut games) = self.games_missing_writes.lock() {
+                        games.insert(game_id.to_string());
+                    }
+                }
+                let dropped = self.counters.dropped_writes.fetch_add(1, Ordering::Relaxed) + 1;
+                println!(
+                    "Most Beautiful stats writer queue is full; dropped a write ({} so far)",
+                    dropped
+                );
+                Ok(())
+            }
+            Err(TrySendError::Closed(StatsWriterMsg::Write(write))) => Err(write),
+            Err(TrySendError::Full(_)) | Err(TrySendError::Closed(_)) => {
+                unreachable!("enqueue only sends writes")
+            }
+        }
+    }
+
+    // True once per game that had an audit round dropped.
+    fn take_game_missing_writes(&self, game_id: &str) -> bool {
+        self.games_missing_writes
+            .lock()
+            .map(|mut games| games.remove(game_id))
+            .unwrap_or(false)
+    }
+
+    // Blocks the calling thread, so it must not run on a runtime worker.
+    fn send_blocking(&self, msg: StatsWriterMsg) -> Result<()> {
+        self.sender
+            .blocking_send(msg)
+            .map_err(|_| anyhow::anyhow!("Most Beautiful stats writer has stopped"))
+    }
+
+    fn run(
+        db_path: PathBuf,
+        mut receiver: mpsc::Receiver<StatsWriterMsg>,
+        counters: Arc<StatsWriterCounters>,
+    ) {
+        let mut conn = match MostBeautifulStatsStore::open_connection(&db_path) {
+            Ok(conn) => conn,
+            Err(err) => {
+                println!("Most Beautiful stats writer failed to open its connection: {err:#}");
+                return;
+            }
+        };
+        while let Some(first) = receiver.blocking_recv() {
+            let mut batch = vec![first];
+            while batch.len() < STATS_WRITER_MAX_BATCH {
+                match receiver.try_recv() {
+                    Ok(msg) => batch.push(msg),
+                    Err(_) => break,
+                }
+            }
+
+            let mut writes = Vec::with_capacity(batch.len());
+            for msg in batch {
+                match msg {
+                    StatsWriterMsg::Write(write) => {
+                        counters.queue_depth.fetch_sub(1, Ordering::Relaxed);
+                        writes.push(write);
+                    }
+                    StatsWriterMsg::Request(write, reply) => {
+                        Self::commit_pending(&mut conn, &mut writes, &counters);
+                        let result =
+                            MostBeautifulStatsStore::apply_in_transaction(&mut conn, &write);
+                        match &result {
+                            Ok(_) => counters.writes_committed.fetch_add(1, Ordering::Relaxed),
+                            Err(_) => counters.writes_failed.fetch_add(1, Ordering::Relaxed),
+                        };
+                        let _ = reply.send(result);
+                    }
+                    #[cfg(test)]
+                    StatsWriterMsg::Flush(ack) => {
+                        Self::commit_pending(&mut conn, &mut writes, &counters);
+                        let _ = ack.send(());
+                    }
+                }
+            }
+            Self::commit_pending(&mut conn, &mut writes, &counters);
+        }
+    }
+
+    fn commit_pending(
+        conn: &mut Connection,
+        writes: &mut Vec<StatsWrite>,
+        counters: &StatsWriterCounters,
+    ) {
+        if writes.is_empty() {
+            return;
+        }
+        let started = std::time::Instant::now();
+        Self::commit_batch(conn, writes, counters);
+        counters
+            .last_batch_micros
+            .store(started.elapsed().as_micros() as u64, Ordering::Relaxed);
+        writes.clear();
+    }
+
+    // One transaction for the whole batch. If it fails, each write is retried on its own so a
+    // single bad record cannot take the rest of the batch down with it.
+    fn commit_batch(conn: &mut Connection, writes: &[StatsWrite], counters: &StatsWriterCounters) {
+        let batch_result = (|| -> Result<()> {
+            let tx = conn
+                .transaction()
+                .context("Failed to open Most Beautiful stats batch transaction")?;
+            for write in writes {
+                write.apply(&tx)?;
+            }
+            tx.commit()
+                .context("Failed to commit Most Beautiful stats batch transaction")
+        })();
+        if batch_result.is_ok() {
+            counters
+                .writes_committed
+                .fetch_add(writes.len() as u64, Ordering::Relaxed);
+            counters.batches_committed.fetch_add(1, Ordering::Relaxed);
+            return;
+        }
+
+        for write in writes {
+            match MostBeautifulStatsStore::apply_in_transaction(conn, write) {
+                Ok(_) => {
+                    counters.writes_committed.fetch_add(1, Ordering::Relaxed);
+                    counters.batches_committed.fetch_add(1, Ordering::Relaxed);
+                }
+                Err(err) => {
+                    counters.writes_failed.fetch_add(1, Ordering::Relaxed);
+                    println!("Failed to write Most Beautiful stats: {err:#}");
+                }
+            }
+        }
+    }
+}
+
+// Fixed set of read-only connections; `get` waits for one to come back when all are in use.
+#[derive(Debug)]
+struct StatsReadPool {
+    conns: Mutex<Vec<Connection>>,
+    available: Condvar,
+}
+
+impl StatsReadPool {
+    fn open(db_path: &Path, size: usize) -> Result<Self> {
+        let conns = (0..size.max(1))
+            .map(|_| MostBeautifulStatsStore::open_connection(db_path))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self {
+            conns: Mutex::new(conns),
+            available: Condvar::new(),
+        })
+    }
+
+    fn get(&self) -> Result<PooledReadConnection<'_>> {
+        let poisoned = || anyhow::anyhow!("Most Beautiful stats read pool is poisoned");
+        let mut conns = self.conns.lock().map_err(|_| poisoned())?;
+        loop {
+            if let Some(conn) = conns.pop() {
+                return Ok(PooledReadConnection {
+                    pool: self,
+                    conn: Some(conn),
+                });
+            }
+            conns = self.available.wait(conns).map_err(|_| poisoned())?;
+        }
+    }
+}
+
+struct PooledReadConnection<'a> {
+    pool: &'a StatsReadPool,
+    conn: Option<Connection>,
+}
+
+impl Deref for PooledReadConnection<'_> {
+    type Target = Connection;
+
+    fn deref(&self) -> &Connection {
+        self.conn
+            .as_ref()
+            .expect("pooled connection is only taken on drop")
+    }
+}
+
+impl Drop for PooledReadConnection<'_> {
+    fn drop(&mut self) {
+        if let (Some(conn), Ok(mut conns)) = (self.conn.take(), self.pool.conns.lock()) {
+            conns.push(conn);
+            self.pool.available.notify_one();
+        }
+    }
+}
+
+// Queries open their connection from a small pool and block, so async callers should run them
+// on the blocking thread pool.
+#[derive(Debug, Clone)]
+pub struct MostBeautifulStatsStore {
+    db_path: PathBuf,
+    read_pool: Arc<StatsReadPool>,
+    writer: Option<Arc<StatsWriter>>,
+    // mirror of mb_player_opt_outs so names can be redacted before writes are queued
+    opted_out_players: Arc<RwLock<HashSet<String>>>,
+}
+
+impl MostBeautifulStatsStore {
+    pub fn new(db_path: impl Into<PathBuf>) -> Result<Self> {
+        let db_path = db_path.into();
+        if let Some(parent) = db_path.parent() {
+            std::fs::create_dir_all(parent).with_context(|| {
+                format!(
+                    "Failed to create Most Beautiful stats directory {}",
+                    parent.display()
+                )
+            })?;
+        }
+
+        Self::init(&db_path)?;
+        let read_pool = StatsReadPool::open(&db_path, STATS_READ_POOL_SIZE)?;
+        let opted_out_players = read_pool
+            .get()?
+            .prepare("SELECT player_hash FROM mb_player_opt_outs")
+            .and_then(|mut stmt| {
+                stmt.query_map([], |row| row.get::<_, String>(0))?
+                    .collect::<rusqlite::Result<HashSet<_>>>()
+            })
+            .context("Failed to load player stats opt-outs")?;
+        Ok(Self {
+            db_path,
+            read_pool: Arc::new(read_pool),
+            writer: None,
+            opted_out_players: Arc::new(RwLock::new(opted_out_players)),
+        })
+    }
+
+    // Moves round, audit and game-completion writes onto a dedicated thread with its own
+    // connection, so callers holding a room lock only pay for a channel send.
+    pub fn with_background_writer(mut self, queue_capacity: usize) -> Result<Self> {
+        let (sender, receiver) = mpsc::channel(queue_capacity.max(1));
+        let counters = Arc::new(StatsWriterCounters::default());
+        let db_path = self.db_path.clone();
+        let thread_counters = counters.clone();
+        std::thread::Builder::new()
+            .name("mb-stats-writer".to_string())
+            .spawn(move || StatsWriter::run(db_path, receiver, thread_counters))
+            .context("Failed to start Most Beautiful stats writer thread")?;
+        self.writer = Some(Arc::new(StatsWriter {
+            sender,
+            capacity: queue_capacity.max(1),
+            counters,
+            games_missing_writes: Mutex::new(HashSet::new()),
+        }));
+        Ok(self)
+    }
+
+    // Blocks until every write queued so far is committed. A no-op without a background writer.
+    // Maintenance writes go through `submit_and_wait` instead, which also orders them.
+    #[cfg(test)]
+    pub fn flush(&self) -> Result<()> {
+        let Some(writer) = &self.writer else {
+            return Ok(());
+        };
+        let (ack_tx, ack_rx) = std::sync::mpsc::channel();
+        writer.send_blocking(StatsWriterMsg::Flush(ack_tx))?;
+        ack_rx
+            .recv()
+            .context("Most Beautiful stats writer stopped before flushing")
+    }
+
+    pub fn writer_metrics(&self) -> StatsWriterMetrics {
+        let Some(writer) = &self.writer else {
+            return StatsWriterMetrics {
+                background_writer: false,
+                queue_capacity: 0,
+                queue_depth: 0,
+                max_queue_depth: 0,
+                writes_committed: 0,
+                writes_failed: 0,
+                batches_committed: 0,
+                dropped_writes: 0,
+                last_batch_ms: 0.0,
+            };
+        };
+        let counters = &writer.counters;
+        StatsWriterMetrics {
+            background_writer: true,
+            queue_capacity: writer.capacity,
+            queue_depth: counters.queue_depth.load(Ordering::Relaxed),
+            max_queue_depth: counters.max_queue_depth.load(Ordering::Relaxed),
+            writes_committed: counters.writes_committed.load(Ordering::Relaxed),
+            writes_failed: counters.writes_failed.load(Ordering::Relaxed),
+            batches_committed: counters.batches_committed.load(Ordering::Relaxed),
+            dropped_writes: counters.dropped_writes.load(Ordering::Relaxed),
+            last_batch_ms: counters.last_batch_micros.load(Ordering::Relaxed) as f64 / 1000.0,
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.db_path
+    }
+
+    fn connect(&self) -> Result<Connection> {
+        Self::open_connection(&self.db_path)
+    }
+
+    fn read_conn(&self) -> Result<PooledReadConnection<'_>> {
+        self.read_pool.get()
+    }
+
+    fn open_connection(db_path: &Path) -> Result<Connection> {
+        let conn = Connection::open(db_path).with_context(|| {
+            format!(
+                "Failed to open Most Beautiful stats database {}",
+                db_path.display()
+            )
+        })?;
+        conn.pragma_update(None, "journal_mode", "WAL")
+            .context("Failed to enable WAL mode for Most Beautiful stats")?;
+        conn.pragma_update(None, "foreign_keys", "ON")
+            .context("Failed to enable foreign keys for Most Beautiful stats")?;
+        conn.busy_timeout(std::time::Duration::from_secs(5))
+            .context("Failed to configure sqlite busy timeout")?;
+        Ok(conn)
+    }
+
+    fn init(db_path: &Path) -> Result<()> {
+        let mut conn = Self::open_connection(db_path)?;
+        let current_version = conn
+            .pragma_query_value(None, "user_version", |row| row.get::<_, u32>(0))
+            .context("Failed to read Most Beautiful stats schema version")?;
+        let latest_version = STATS_SCHEMA_VERSION;
+        if current_version > latest_version {
+            anyhow::bail!(
+                "Most Beautiful stats database {} has schema version {}, but this server only \
+                 supports up to {}; refusing to start against a newer schema",
+                db_path.display(),
+                current_version,
+                latest_version
+            );
+        }
+        if current_version == latest_version {
+            return Ok(());
+        }
+
+        let has_tables = conn
+            .query_row(
+                "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table'",
+                [],
+                |row| row.get::<_, u64>(0),
+            )
+            .context("Failed to inspect Most Beautiful stats database")?
+            > 0;
+        if has_tables {
+            let backup_path = Self::backup_before_migration(db_path, &conn, current_version)?;
+            println!(
+                "Backed up Most Beautiful stats database to {} before migrating from schema version {}",
+                backup_path.display(),
+                current_version
+            );
+        }
+
+        for migration in STATS_SCHEMA_MIGRATIONS
+            .iter()
+            .filter(|migration| migration.version > current_version)
+        {
+            let tx = conn
+                .transaction()
+                .context("Failed to open Most Beautiful stats migration transaction")?;
+            (migration.apply)(&tx).with_context(|| {
+                format!(
+                    "Failed to apply Most Beautiful stats migration {} ({})",
+                    migration.version, migration.description
+                )
+            })?;
+            tx.pragma_update(None, "user_version", migration.version)
+                .context("Failed to record Most Beautiful stats schema version")?;
+            tx.commit()
+                .context("Failed to commit Most Beautiful stats migration")?;
+        }
+        Ok(())
+    }
+
+    // consistent copy via VACUUM INTO, written next to the database
+    fn backup_before_migration(
+        db_path: &Path,
+        conn: &Connection,
+        from_version: u32,
+    ) -> Result<PathBuf> {
+        let now_s = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or(0);
+        let mut backup_name = db_path.as_os_str().to_owned();
+        backup_name.push(format!(".v{from_version}-{now_s}.bak"));
+        let backup_path = PathBuf::from(backup_name);
+        conn.execute(
+            "VACUUM INTO ?1",
+            params![backup_path.to_string_lossy().to_string()],
+        )
+        .with_context(|| {
+            format!(
+                "Failed to back up Most Beautiful stats database to {}",
+                backup_path.display()
+            )
+        })?;
+        Ok(backup_path)
+    }
+
+    fn migrate_base_schema(conn: &Connection) -> Result<()> {
+        conn.execute_batch(
+            r#"
+            CREATE TABLE IF NOT EXISTS mb_players (
+                player_hash TEXT PRIMARY KEY,
+                latest_display_name TEXT NOT NULL,
+                first_seen_at INTEGER NOT NULL,
+                last_seen_at INTEGER NOT NULL
+            );
+
+            CREATE TABLE IF NOT EXISTS mb_player_names (
+                player_hash TEXT NOT NULL,
+                display_name TEXT NOT NULL,
+                first_seen_at INTEGER NOT NULL,
+                last_seen_at INTEGER NOT NULL,
+                PRIMARY KEY (player_hash, display_name),
+                FOREIGN KEY (player_hash) REFERENCES mb_players(player_hash) ON DELETE CASCADE
+            );
+
+            CREATE TABLE IF NOT EXISTS mb_card_paths (
                 card_hash TEXT NOT NULL,
                 card_path TEXT NOT NULL,
                 first_seen_at INTEGER NOT NULL,
@@ -289,16 +2064,307 @@ impl MostBeautifulStatsStore {
                 ON mb_game_round_scores(player_hash);
             "#,
         )
-        .context("Failed to initialize Most Beautiful stats schema")?;
-        Ok(())
+        .context("Failed to create Most Beautiful stats base schema")
     }
 
-    fn upsert_player(
-        tx: &rusqlite::Transaction<'_>,
-        player_hash: &str,
-        display_name: &str,
-        recorded_at_s: u64,
-    ) -> Result<()> {
+    fn migrate_clue_archive(conn: &Connection) -> Result<()> {
+        conn.execute_batch(
+            r#"
+            CREATE TABLE IF NOT EXISTS mb_game_round_clue_ratings (
+                game_id TEXT NOT NULL,
+                round_num INTEGER NOT NULL,
+                rating_count INTEGER NOT NULL,
+                rating_sum INTEGER NOT NULL,
+                max_stars INTEGER NOT NULL,
+                PRIMARY KEY (game_id, round_num),
+                FOREIGN KEY (game_id, round_num)
+                    REFERENCES mb_game_rounds(game_id, round_num)
+                    ON DELETE CASCADE
+            );
+            "#,
+        )
+        .context("Failed to create clue rating table")?;
+        Self::init_clue_search(conn)
+    }
+
+    fn migrate_player_ratings(conn: &Connection) -> Result<()> {
+        conn.execute_batch(
+            r#"
+            CREATE TABLE IF NOT EXISTS mb_player_ratings (
+                scope TEXT NOT NULL,
+                player_hash TEXT NOT NULL,
+                rating REAL NOT NULL,
+                games_rated INTEGER NOT NULL,
+                last_rated_at INTEGER NOT NULL,
+                PRIMARY KEY (scope, player_hash)
+            );
+
+            CREATE TABLE IF NOT EXISTS mb_player_rating_history (
+                scope TEXT NOT NULL,
+                game_id TEXT NOT NULL,
+                player_hash TEXT NOT NULL,
+                recorded_at INTEGER NOT NULL,
+                placement INTEGER NOT NULL,
+                players_in_game INTEGER NOT NULL,
+                rating_before REAL NOT NULL,
+                rating_after REAL NOT NULL,
+                PRIMARY KEY (scope, game_id, player_hash),
+                FOREIGN KEY (game_id) REFERENCES mb_games(game_id) ON DELETE CASCADE
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_mb_player_rating_history_player
+                ON mb_player_rating_history(scope, player_hash, recorded_at);
+            "#,
+        )
+        .context("Failed to create player rating tables")
+    }
+
+    fn migrate_stella_audit(conn: &Connection) -> Result<()> {
+        let has_game_mode = conn
+            .prepare("SELECT 1 FROM pragma_table_info('mb_games') WHERE name = 'game_mode'")
+            .and_then(|mut stmt| stmt.exists([]))
+            .context("Failed to inspect mb_games columns")?;
+        if !has_game_mode {
+            conn.execute(
+                "ALTER TABLE mb_games ADD COLUMN game_mode TEXT NOT NULL DEFAULT 'dixit_plus'",
+                [],
+            )
+            .context("Failed to add mb_games.game_mode")?;
+        }
+
+        conn.execute_batch(
+            r#"
+            CREATE TABLE IF NOT EXISTS mb_stella_rounds (
+                game_id TEXT NOT NULL,
+                round_num INTEGER NOT NULL,
+                recorded_at INTEGER NOT NULL,
+                clue_word TEXT NOT NULL,
+                PRIMARY KEY (game_id, round_num),
+                FOREIGN KEY (game_id) REFERENCES mb_games(game_id) ON DELETE CASCADE
+            );
+
+            CREATE TABLE IF NOT EXISTS mb_stella_round_board_cards (
+                game_id TEXT NOT NULL,
+                round_num INTEGER NOT NULL,
+                card_hash TEXT NOT NULL,
+                board_order INTEGER NOT NULL,
+                reveal_order INTEGER,
+                points INTEGER,
+                PRIMARY KEY (game_id, round_num, card_hash),
+                FOREIGN KEY (game_id, round_num)
+                    REFERENCES mb_stella_rounds(game_id, round_num)
+                    ON DELETE CASCADE
+            );
+
+            CREATE TABLE IF NOT EXISTS mb_stella_round_selections (
+                game_id TEXT NOT NULL,
+                round_num INTEGER NOT NULL,
+                player_hash TEXT NOT NULL,
+                player_display_name TEXT NOT NULL,
+                card_hash TEXT NOT NULL,
+                PRIMARY KEY (game_id, round_num, player_hash, card_hash),
+                FOREIGN KEY (game_id, round_num)
+                    REFERENCES mb_stella_rounds(game_id, round_num)
+                    ON DELETE CASCADE
+            );
+
+            CREATE TABLE IF NOT EXISTS mb_stella_round_players (
+                game_id TEXT NOT NULL,
+                round_num INTEGER NOT NULL,
+                player_hash TEXT NOT NULL,
+                player_display_name TEXT NOT NULL,
+                in_the_dark INTEGER NOT NULL,
+                fell INTEGER NOT NULL,
+                score_delta INTEGER NOT NULL,
+                total_after_round INTEGER NOT NULL,
+                PRIMARY KEY (game_id, round_num, player_hash),
+                FOREIGN KEY (game_id, round_num)
+                    REFERENCES mb_stella_rounds(game_id, round_num)
+                    ON DELETE CASCADE
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_mb_games_mode ON mb_games(game_mode);
+            CREATE INDEX IF NOT EXISTS idx_mb_stella_selection_player
+                ON mb_stella_round_selections(player_hash);
+            CREATE INDEX IF NOT EXISTS idx_mb_stella_round_player
+                ON mb_stella_round_players(player_hash);
+            "#,
+        )
+        .context("Failed to create Stella game audit tables")
+    }
+
+    fn migrate_player_opt_outs(conn: &Connection) -> Result<()> {
+        conn.execute_batch(
+            r#"
+            CREATE TABLE IF NOT EXISTS mb_player_opt_outs (
+                player_hash TEXT PRIMARY KEY,
+                opted_out_at INTEGER NOT NULL
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_mb_game_card_submitter
+                ON mb_game_round_cards(submitted_by_hash);
+            CREATE INDEX IF NOT EXISTS idx_mb_round_win_winner_hash
+                ON mb_round_win_events(winner_hash);
+            CREATE INDEX IF NOT EXISTS idx_mb_player_rating_history_hash
+                ON mb_player_rating_history(player_hash);
+            "#,
+        )
+        .context("Failed to create player opt-out table")
+    }
+
+    fn migrate_accounts(conn: &Connection) -> Result<()> {
+        conn.execute_batch(
+            r#"
+            CREATE TABLE IF NOT EXISTS mb_accounts (
+                account_id TEXT PRIMARY KEY,
+                username TEXT NOT NULL UNIQUE COLLATE NOCASE,
+                password_hash TEXT,
+                player_hash TEXT NOT NULL UNIQUE,
+                created_at INTEGER NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS mb_account_sessions (
+                session_hash TEXT PRIMARY KEY,
+                account_id TEXT NOT NULL,
+                created_at INTEGER NOT NULL,
+                last_used_at INTEGER NOT NULL,
+                FOREIGN KEY (account_id) REFERENCES mb_accounts(account_id) ON DELETE CASCADE
+            );
+            CREATE TABLE IF NOT EXISTS mb_account_login_codes (
+                account_id TEXT PRIMARY KEY,
+                code_hash TEXT NOT NULL,
+                expires_at INTEGER NOT NULL,
+                attempts INTEGER NOT NULL DEFAULT 0,
+                FOREIGN KEY (account_id) REFERENCES mb_accounts(account_id) ON DELETE CASCADE
+            );
+            CREATE TABLE IF NOT EXISTS mb_account_merged_players (
+                player_hash TEXT PRIMARY KEY,
+                account_id TEXT NOT NULL,
+                merged_at INTEGER NOT NULL,
+                rows_moved INTEGER NOT NULL,
+                FOREIGN KEY (account_id) REFERENCES mb_accounts(account_id) ON DELETE CASCADE
+            );
+            CREATE INDEX IF NOT EXISTS idx_mb_account_sessions_account
+                ON mb_account_sessions(account_id);
+            "#,
+        )
+        .context("Failed to create account tables")
+    }
+
+    fn migrate_persistent_rooms(conn: &Connection) -> Result<()> {
+        conn.execute_batch(
+            r#"
+            CREATE TABLE IF NOT EXISTS mb_persistent_rooms (
+                room_id TEXT PRIMARY KEY,
+                owner_token_hash TEXT NOT NULL,
+                snapshot TEXT NOT NULL,
+                created_at INTEGER NOT NULL,
+                updated_at INTEGER NOT NULL
+            );
+            "#,
+        )
+        .context("Failed to create persistent room table")
+    }
+
+    fn migrate_game_standings(conn: &Connection) -> Result<()> {
+        conn.execute_batch(
+            r#"
+            CREATE TABLE IF NOT EXISTS mb_game_standings (
+                game_id TEXT NOT NULL,
+                player_hash TEXT NOT NULL,
+                rank INTEGER NOT NULL,
+                points INTEGER NOT NULL,
+                PRIMARY KEY (game_id, player_hash),
+                FOREIGN KEY (game_id) REFERENCES mb_games(game_id) ON DELETE CASCADE
+            );
+            CREATE INDEX IF NOT EXISTS idx_mb_game_standings_player
+                ON mb_game_standings(player_hash);
+            "#,
+        )
+        .context("Failed to create game standings table")
+    }
+
+    fn migrate_missing_writes(conn: &Connection) -> Result<()> {
+        let has_missing_writes = conn
+            .prepare("SELECT 1 FROM pragma_table_info('mb_games') WHERE name = 'missing_writes'")
+            .and_then(|mut stmt| stmt.exists([]))
+            .context("Failed to inspect mb_games columns")?;
+        if !has_missing_writes {
+            conn.execute(
+                "ALTER TABLE mb_games ADD COLUMN missing_writes INTEGER NOT NULL DEFAULT 0",
+                [],
+            )
+            .context("Failed to add mb_games.missing_writes")?;
+        }
+        Ok(())
+    }
+
+    // FTS5 index over mb_game_rounds.clue, kept in sync by triggers. Rows are keyed by
+    // (game_id, round_num) rather than rowid because mb_game_rounds rowids are not stable
+    // across VACUUM.
+    fn init_clue_search(conn: &Connection) -> Result<()> {
+        let index_exists = conn
+            .query_row(
+                "SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'mb_clue_search'",
+                [],
+                |_| Ok(()),
+            )
+            .optional()
+            .context("Failed to check for the clue search index")?
+            .is_some();
+
+        conn.execute_batch(
+            r#"
+            CREATE VIRTUAL TABLE IF NOT EXISTS mb_clue_search USING fts5(
+                clue,
+                game_id UNINDEXED,
+                round_num UNINDEXED
+            );
+
+            CREATE TRIGGER IF NOT EXISTS mb_game_rounds_clue_search_insert
+            AFTER INSERT ON mb_game_rounds
+            BEGIN
+                INSERT INTO mb_clue_search (clue, game_id, round_num)
+                VALUES (new.clue, new.game_id, new.round_num);
+            END;
+
+            CREATE TRIGGER IF NOT EXISTS mb_game_rounds_clue_search_update
+            AFTER UPDATE OF clue ON mb_game_rounds
+            BEGIN
+                DELETE FROM mb_clue_search
+                WHERE game_id = old.game_id AND round_num = old.round_num;
+                INSERT INTO mb_clue_search (clue, game_id, round_num)
+                VALUES (new.clue, new.game_id, new.round_num);
+            END;
+
+            CREATE TRIGGER IF NOT EXISTS mb_game_rounds_clue_search_delete
+            AFTER DELETE ON mb_game_rounds
+            BEGIN
+                DELETE FROM mb_clue_search
+                WHERE game_id = old.game_id AND round_num = old.round_num;
+            END;
+            "#,
+        )
+        .context("Failed to initialize clue search index")?;
+
+        if !index_exists {
+            conn.execute(
+                r#"
+                INSERT INTO mb_clue_search (clue, game_id, round_num)
+                SELECT clue, game_id, round_num FROM mb_game_rounds
+                "#,
+                [],
+            )
+            .context("Failed to backfill clue search index")?;
+        }
+        Ok(())
+    }
+
+    fn upsert_player(
+        tx: &rusqlite::Transaction<'_>,
+        player_hash: &str,
+        display_name: &str,
+        recorded_at_s: u64,
+    ) -> Result<()> {
         tx.execute(
             r#"
             INSERT INTO mb_players (player_hash, latest_display_name, first_seen_at, last_seen_at)
@@ -311,6 +2377,9 @@ impl MostBeautifulStatsStore {
         )
         .context("Failed to upsert Most Beautiful player")?;
 
+        if display_name == ANONYMOUS_PLAYER_NAME {
+            return Ok(());
+        }
         tx.execute(
             r#"
             INSERT INTO mb_player_names (player_hash, display_name, first_seen_at, last_seen_at)
@@ -356,12 +2425,13 @@ impl MostBeautifulStatsStore {
         if record.votes.is_empty() && record.wins.is_empty() {
             return Ok(());
         }
+        self.submit(StatsWrite::Round(record.clone()))
+    }
 
-        let mut conn = self.connect()?;
-        let tx = conn
-            .transaction()
-            .context("Failed to open Most Beautiful stats transaction")?;
-
+    fn write_round_rows(
+        tx: &rusqlite::Transaction<'_>,
+        record: &MostBeautifulRoundRecord,
+    ) -> Result<()> {
         for vote in &record.votes {
             Self::upsert_player(
                 &tx,
@@ -436,8 +2506,6 @@ impl MostBeautifulStatsStore {
             .context("Failed to insert Most Beautiful round-win event")?;
         }
 
-        tx.commit()
-            .context("Failed to commit Most Beautiful stats transaction")?;
         Ok(())
     }
 
@@ -445,11 +2513,13 @@ impl MostBeautifulStatsStore {
         &self,
         record: &MostBeautifulGameAuditRoundRecord,
     ) -> Result<()> {
-        let mut conn = self.connect()?;
-        let tx = conn
-            .transaction()
-            .context("Failed to open Most Beautiful game-audit transaction")?;
+        self.submit(StatsWrite::GameAuditRound(Box::new(record.clone())))
+    }
 
+    fn write_game_audit_round(
+        tx: &rusqlite::Transaction<'_>,
+        record: &MostBeautifulGameAuditRoundRecord,
+    ) -> Result<()> {
         tx.execute(
             r#"
             INSERT INTO mb_games (game_id, room_id, started_at, total_rounds)
@@ -468,8 +2538,15 @@ impl MostBeautifulStatsStore {
         )
         .context("Failed to upsert Most Beautiful game audit row")?;
 
+        Self::write_game_audit_round_rows(tx, record)
+    }
+
+    fn write_game_audit_round_rows(
+        tx: &rusqlite::Transaction<'_>,
+        record: &MostBeautifulGameAuditRoundRecord,
+    ) -> Result<()> {
         Self::upsert_player(
-            &tx,
+            tx,
             &record.storyteller_hash,
             &record.storyteller_display_name,
             record.recorded_at_s,
@@ -526,16 +2603,44 @@ impl MostBeautifulStatsStore {
             params![record.game_id, record.round_num],
         )
         .context("Failed to clear prior Most Beautiful game round scores")?;
+        tx.execute(
+            "DELETE FROM mb_game_round_clue_ratings WHERE game_id = ?1 AND round_num = ?2",
+            params![record.game_id, record.round_num],
+        )
+        .context("Failed to clear prior Most Beautiful game round clue rating")?;
+
+        if let Some(clue_rating) = &record.clue_rating {
+            tx.execute(
+                r#"
+                INSERT INTO mb_game_round_clue_ratings (
+                    game_id,
+                    round_num,
+                    rating_count,
+                    rating_sum,
+                    max_stars
+                )
+                VALUES (?1, ?2, ?3, ?4, ?5)
+                "#,
+                params![
+                    record.game_id,
+                    record.round_num,
+                    clue_rating.rating_count,
+                    clue_rating.rating_sum,
+                    clue_rating.max_stars
+                ],
+            )
+            .context("Failed to insert Most Beautiful game round clue rating")?;
+        }
 
         for card in &record.card_entries {
             Self::upsert_player(
-                &tx,
+                tx,
                 &card.owner_hash,
                 &card.owner_display_name,
                 record.recorded_at_s,
             )?;
             Self::upsert_player(
-                &tx,
+                tx,
                 &card.submitted_by_hash,
                 &card.submitted_by_display_name,
                 record.recorded_at_s,
@@ -571,13 +2676,13 @@ impl MostBeautifulStatsStore {
         }
 
         Self::insert_game_vote_records(
-            &tx,
+            tx,
             "mb_game_round_story_votes",
             record,
             &record.story_votes,
         )?;
         Self::insert_game_vote_records(
-            &tx,
+            tx,
             "mb_game_round_beauty_votes",
             record,
             &record.beauty_votes,
@@ -585,7 +2690,7 @@ impl MostBeautifulStatsStore {
 
         for score in &record.score_log {
             Self::upsert_player(
-                &tx,
+                tx,
                 &score.player_hash,
                 &score.player_display_name,
                 record.recorded_at_s,
@@ -618,8 +2723,6 @@ impl MostBeautifulStatsStore {
             .context("Failed to insert Most Beautiful game round score")?;
         }
 
-        tx.commit()
-            .context("Failed to commit Most Beautiful game-audit transaction")?;
         Ok(())
     }
 
@@ -661,1011 +2764,4859 @@ impl MostBeautifulStatsStore {
         Ok(())
     }
 
-    pub fn mark_game_complete(
-        &self,
-        game_id: &str,
-        ended_at_s: u64,
-        total_rounds: u16,
+    pub fn record_stella_audit_round(&self, record: &StellaGameAuditRoundRecord) -> Result<()> {
+        self.submit(StatsWrite::StellaAuditRound(Box::new(record.clone())))
+    }
+
+    fn write_stella_audit_round(
+        tx: &rusqlite::Transaction<'_>,
+        record: &StellaGameAuditRoundRecord,
     ) -> Result<()> {
-        let conn = self.connect()?;
-        conn.execute(
+        tx.execute(
             r#"
-            UPDATE mb_games
-            SET ended_at = ?2,
-                completed = 1,
-                total_rounds = MAX(total_rounds, ?3)
-            WHERE game_id = ?1
+            INSERT INTO mb_games (game_id, room_id, started_at, total_rounds, game_mode)
+            VALUES (?1, ?2, ?3, ?4, ?5)
+            ON CONFLICT(game_id) DO UPDATE SET
+                room_id = excluded.room_id,
+                started_at = MIN(started_at, excluded.started_at),
+                total_rounds = MAX(total_rounds, excluded.total_rounds),
+                game_mode = excluded.game_mode
             "#,
-            params![game_id, ended_at_s, total_rounds],
+            params![
+                record.game_id,
+                record.room_id,
+                record.game_started_at_s,
+                record.round_num,
+                GAME_MODE_STELLA
+            ],
         )
-        .context("Failed to mark Most Beautiful game audit complete")?;
-        Ok(())
-    }
-
-    #[cfg(test)]
-    pub fn aggregated_stats(&self) -> Result<MostBeautifulStatsResponse> {
-        let conn = self.connect()?;
-        let mut players = Self::load_players(&conn)?;
-        Self::load_vote_totals(&conn, &mut players)?;
-        Self::load_round_wins(&conn, &mut players)?;
-
-        let mut player_entries = players.into_values().collect::<Vec<_>>();
-        player_entries.sort_by(|a, b| {
-            b.rounds_won
-                .cmp(&a.rounds_won)
-                .then_with(|| b.votes_received.cmp(&a.votes_received))
-                .then_with(|| {
-                    a.display_name
-                        .to_lowercase()
-                        .cmp(&b.display_name.to_lowercase())
-                })
-                .then_with(|| a.player_hash.cmp(&b.player_hash))
-        });
-        for player in &mut player_entries {
-            player.voters.sort_by(|a, b| {
-                b.votes
-                    .cmp(&a.votes)
-                    .then_with(|| {
-                        a.display_name
-                            .to_lowercase()
-                            .cmp(&b.display_name.to_lowercase())
-                    })
-                    .then_with(|| a.player_hash.cmp(&b.player_hash))
-            });
-        }
+        .context("Failed to upsert Stella game audit row")?;
 
-        Ok(MostBeautifulStatsResponse {
-            players: player_entries,
-        })
+        Self::write_stella_audit_round_rows(tx, record)
     }
 
-    pub fn filtered_stats(
-        &self,
-        room_id: Option<&str>,
-        game_limit: usize,
-    ) -> Result<MostBeautifulStatsResponse> {
-        let conn = self.connect()?;
-        let game_ids = Self::game_ids_for_stats(&conn, room_id, game_limit)?;
-        if game_ids.is_empty() {
-            return Ok(MostBeautifulStatsResponse {
-                players: Vec::new(),
-            });
-        }
+    fn write_stella_audit_round_rows(
+        tx: &rusqlite::Transaction<'_>,
+        record: &StellaGameAuditRoundRecord,
+    ) -> Result<()> {
+        tx.execute(
+            r#"
+            INSERT INTO mb_stella_rounds (game_id, round_num, recorded_at, clue_word)
+            VALUES (?1, ?2, ?3, ?4)
+            ON CONFLICT(game_id, round_num) DO UPDATE SET
+                recorded_at = excluded.recorded_at,
+                clue_word = excluded.clue_word
+            "#,
+            params![
+                record.game_id,
+                record.round_num,
+                record.recorded_at_s,
+                record.clue_word
+            ],
+        )
+        .context("Failed to upsert Stella game round row")?;
 
-        let mut players = HashMap::new();
-        Self::load_filtered_vote_totals(&conn, &game_ids, &mut players)?;
-        Self::load_filtered_round_wins(&conn, &game_ids, &mut players)?;
+        for table_name in [
+            "mb_stella_round_board_cards",
+            "mb_stella_round_selections",
+            "mb_stella_round_players",
+        ] {
+            tx.execute(
+                &format!("DELETE FROM {table_name} WHERE game_id = ?1 AND round_num = ?2"),
+                params![record.game_id, record.round_num],
+            )
+            .with_context(|| format!("Failed to clear prior Stella rows from {table_name}"))?;
+        }
 
-        let mut player_entries = players
-            .into_values()
-            .filter(|player| player.votes_received > 0 || player.rounds_won > 0)
-            .collect::<Vec<_>>();
-        player_entries.sort_by(|a, b| {
-            b.rounds_won
-                .cmp(&a.rounds_won)
-                .then_with(|| b.votes_received.cmp(&a.votes_received))
-                .then_with(|| {
-                    a.display_name
-                        .to_lowercase()
-                        .cmp(&b.display_name.to_lowercase())
-                })
-                .then_with(|| a.player_hash.cmp(&b.player_hash))
-        });
-        player_entries.truncate(30);
-        for player in &mut player_entries {
-            player.voters.sort_by(|a, b| {
-                b.votes
-                    .cmp(&a.votes)
-                    .then_with(|| {
-                        a.display_name
-                            .to_lowercase()
-                            .cmp(&b.display_name.to_lowercase())
-                    })
-                    .then_with(|| a.player_hash.cmp(&b.player_hash))
-            });
+        for card in &record.board_cards {
+            tx.execute(
+                r#"
+                INSERT INTO mb_stella_round_board_cards (
+                    game_id, round_num, card_hash, board_order, reveal_order, points
+                )
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+                "#,
+                params![
+                    record.game_id,
+                    record.round_num,
+                    card.card_hash,
+                    card.board_order,
+                    card.reveal_order,
+                    card.points
+                ],
+            )
+            .context("Failed to insert Stella board card")?;
+        }
+        for selection in &record.selections {
+            tx.execute(
+                r#"
+                INSERT INTO mb_stella_round_selections (
+                    game_id, round_num, player_hash, player_display_name, card_hash
+                )
+                VALUES (?1, ?2, ?3, ?4, ?5)
+                "#,
+                params![
+                    record.game_id,
+                    record.round_num,
+                    selection.player_hash,
+                    selection.player_display_name,
+                    selection.card_hash
+                ],
+            )
+            .context("Failed to insert Stella selection")?;
+        }
+        for player in &record.players {
+            Self::upsert_player(
+                tx,
+                &player.player_hash,
+                &player.player_display_name,
+                record.recorded_at_s,
+            )?;
+            tx.execute(
+                r#"
+                INSERT INTO mb_stella_round_players (
+                    game_id, round_num, player_hash, player_display_name,
+                    in_the_dark, fell, score_delta, total_after_round
+                )
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+                "#,
+                params![
+                    record.game_id,
+                    record.round_num,
+                    player.player_hash,
+                    player.player_display_name,
+                    player.in_the_dark,
+                    player.fell,
+                    player.score_delta,
+                    player.total_after_round
+                ],
+            )
+            .context("Failed to insert Stella round player")?;
         }
+        Ok(())
+    }
 
-        Ok(MostBeautifulStatsResponse {
-            players: player_entries,
+    // Opting out anonymizes the names already stored for the player and every later write.
+    // Opting back in only affects future writes.
+    pub fn set_player_stats_opt_out(
+        &self,
+        player_hash: &str,
+        opted_out: bool,
+        recorded_at_s: u64,
+    ) -> Result<()> {
+        if let Ok(mut players) = self.opted_out_players.write() {
+            if opted_out {
+                players.insert(player_hash.to_string());
+            } else {
+                players.remove(player_hash);
+            }
+        }
+        self.submit(StatsWrite::PlayerOptOut {
+            player_hash: player_hash.to_string(),
+            opted_out,
+            recorded_at_s,
         })
     }
 
-    fn game_ids_for_stats(
-        conn: &Connection,
-        room_id: Option<&str>,
-        game_limit: usize,
-    ) -> Result<Vec<String>> {
-        let mut game_ids = Vec::new();
+    fn write_player_opt_out(
+        tx: &rusqlite::Transaction<'_>,
+        player_hash: &str,
+        opted_out: bool,
+        recorded_at_s: u64,
+    ) -> Result<()> {
+        if !opted_out {
+            tx.execute(
+                "DELETE FROM mb_player_opt_outs WHERE player_hash = ?1",
+                params![player_hash],
+            )
+            .context("Failed to clear player stats opt-out")?;
+            return Ok(());
+        }
+        tx.execute(
+            r#"
+            INSERT INTO mb_player_opt_outs (player_hash, opted_out_at)
+            VALUES (?1, ?2)
+            ON CONFLICT(player_hash) DO NOTHING
+            "#,
+            params![player_hash, recorded_at_s],
+        )
+        .context("Failed to record player stats opt-out")?;
+        Self::reassign_player_rows(tx, player_hash, player_hash)?;
+        Ok(())
+    }
 
-        if let Some(room_id) = room_id {
-            let current_room_game = conn
-                .query_row(
-                    r#"
-                    SELECT game_id
-                    FROM mb_games
-                    WHERE room_id = ?1 AND completed = 0
-                    ORDER BY started_at DESC, game_id DESC
-                    LIMIT 1
-                    "#,
-                    params![room_id],
-                    |row| row.get::<_, String>(0),
+    // Points every row for `player_hash` at `replacement_hash` with an anonymous name and drops
+    // the player's aliases. Passing the same hash twice only strips names.
+    fn reassign_player_rows(
+        tx: &rusqlite::Transaction<'_>,
+        player_hash: &str,
+        replacement_hash: &str,
+    ) -> Result<u64> {
+        let mut changed = 0u64;
+        for column in PLAYER_NAME_COLUMNS {
+            let query = format!(
+                "UPDATE {table} SET {hash} = ?2, {name} = ?3 WHERE {hash} = ?1",
+                table = column.table,
+                hash = column.hash_column,
+                name = column.name_column,
+            );
+            changed += tx
+                .execute(
+                    &query,
+                    params![player_hash, replacement_hash, ANONYMOUS_PLAYER_NAME],
                 )
-                .optional()
-                .context("Failed to load current-room Most Beautiful game")?;
-            if let Some(game_id) = current_room_game {
-                game_ids.push(game_id);
-            }
+                .with_context(|| format!("Failed to anonymize player rows in {}", column.table))?
+                as u64;
         }
-
-        let completed_limit = if game_limit == 0 {
-            None
-        } else {
-            Some(game_limit.saturating_sub(game_ids.len()))
-        };
-
-        if completed_limit != Some(0) {
-            let mut completed_query = r#"
-                SELECT game_id
-                FROM mb_games
-                WHERE completed = 1
-                ORDER BY COALESCE(ended_at, started_at) DESC, started_at DESC, game_id DESC
-            "#
-            .to_string();
-            let completed_ids = if let Some(limit) = completed_limit {
-                completed_query.push_str(" LIMIT ?1");
-                let mut stmt = conn
-                    .prepare(&completed_query)
-                    .context("Failed to prepare filtered Most Beautiful completed-games query")?;
-                let rows = stmt
-                    .query_map(params![u64::try_from(limit).unwrap_or(u64::MAX)], |row| {
-                        row.get::<_, String>(0)
-                    })
-                    .context("Failed to query filtered Most Beautiful completed games")?;
-                rows.collect::<rusqlite::Result<Vec<_>>>()
-                    .context("Failed to decode filtered Most Beautiful completed game row")?
-            } else {
-                let mut stmt = conn.prepare(&completed_query).context(
-                    "Failed to prepare all-history Most Beautiful completed-games query",
-                )?;
-                let rows = stmt
-                    .query_map([], |row| row.get::<_, String>(0))
-                    .context("Failed to query all-history Most Beautiful completed games")?;
-                rows.collect::<rusqlite::Result<Vec<_>>>()
-                    .context("Failed to decode all-history Most Beautiful completed game row")?
-            };
-            game_ids.extend(completed_ids);
+        if replacement_hash != player_hash {
+            for table in PLAYER_HASH_ONLY_TABLES {
+                changed += tx
+                    .execute(
+                        &format!("UPDATE {table} SET player_hash = ?2 WHERE player_hash = ?1"),
+                        params![player_hash, replacement_hash],
+                    )
+                    .with_context(|| format!("Failed to reassign player rows in {table}"))?
+                    as u64;
+            }
+            tx.execute(
+                "DELETE FROM mb_player_opt_outs WHERE player_hash = ?1",
+                params![player_hash],
+            )
+            .context("Failed to clear erased player opt-out")?;
         }
-
-        Ok(game_ids)
+        // aliases reference mb_players, so they go before its hash changes
+        changed += tx
+            .execute(
+                "DELETE FROM mb_player_names WHERE player_hash = ?1",
+                params![player_hash],
+            )
+            .context("Failed to delete player aliases")? as u64;
+        changed += tx
+            .execute(
+                r#"
+                UPDATE mb_players
+                SET player_hash = ?2, latest_display_name = ?3
+                WHERE player_hash = ?1
+                "#,
+                params![player_hash, replacement_hash, ANONYMOUS_PLAYER_NAME],
+            )
+            .context("Failed to anonymize player row")? as u64;
+        Ok(changed)
     }
 
-    fn load_filtered_vote_totals(
-        conn: &Connection,
-        game_ids: &[String],
-        players: &mut HashMap<String, MostBeautifulPlayerStats>,
-    ) -> Result<()> {
-        if game_ids.is_empty() {
-            return Ok(());
+    // Admin erasure across every mb_* table. Rows that other players' history depends on, such
+    // as rounds the player told or cards they owned, are always kept under the fresh hash so
+    // replays and everyone else's stats stay consistent.
+    pub fn erase_player(
+        &self,
+        player_hash: &str,
+        mode: PlayerErasureMode,
+    ) -> Result<PlayerErasureReport> {
+        if let Ok(mut players) = self.opted_out_players.write() {
+            players.remove(player_hash);
         }
 
-        let placeholders = std::iter::repeat("?")
-            .take(game_ids.len())
-            .collect::<Vec<_>>()
-            .join(", ");
-        let query = format!(
-            r#"
-            SELECT
-                cards.owner_hash,
-                MAX(cards.owner_display_name),
-                votes.voter_hash,
-                MAX(votes.voter_display_name),
-                SUM(votes.vote_count)
-            FROM mb_game_round_beauty_votes AS votes
-            INNER JOIN mb_game_round_cards AS cards
-                ON cards.game_id = votes.game_id
-                AND cards.round_num = votes.round_num
-                AND cards.card_hash = votes.card_hash
-            WHERE votes.game_id IN ({placeholders})
-            GROUP BY cards.owner_hash, votes.voter_hash
-            "#
+        let replacement_hash = format!(
+            "anon-{:016x}{:016x}",
+            rand::random::<u64>(),
+            rand::random::<u64>()
         );
-        let mut stmt = conn
-            .prepare(&query)
-            .context("Failed to prepare filtered Most Beautiful vote totals query")?;
-        let rows = stmt
-            .query_map(rusqlite::params_from_iter(game_ids.iter()), |row| {
-                Ok((
-                    row.get::<_, String>(0)?,
-                    row.get::<_, String>(1)?,
-                    row.get::<_, String>(2)?,
-                    row.get::<_, String>(3)?,
-                    row.get::<_, u64>(4)?,
-                ))
-            })
-            .context("Failed to query filtered Most Beautiful vote totals")?;
-        for row in rows {
-            let (owner_hash, owner_display_name, voter_hash, voter_display_name, votes_received) =
-                row.context("Failed to decode filtered Most Beautiful vote row")?;
-            let owner = players
-                .entry(owner_hash.clone())
-                .or_insert(MostBeautifulPlayerStats {
-                    player_hash: owner_hash.clone(),
-                    display_name: Self::player_name(conn, &owner_hash, &owner_display_name)?,
-                    votes_received: 0,
-                    rounds_won: 0,
-                    tie_round_wins: 0,
-                    decisive_round_wins: 0,
-                    voters: Vec::new(),
-                });
-            owner.votes_received += votes_received;
-            owner.voters.push(MostBeautifulVoterStats {
-                player_hash: voter_hash.clone(),
-                display_name: Self::player_name(conn, &voter_hash, &voter_display_name)?,
-                votes: votes_received,
-            });
+        match self.submit_and_wait(StatsWrite::ErasePlayer {
+            player_hash: player_hash.to_string(),
+            mode,
+            replacement_hash,
+        })? {
+            StatsWriteOutcome::Erased(report) => Ok(report),
+            outcome => anyhow::bail!("Unexpected player erasure outcome {outcome:?}"),
         }
-
-        Ok(())
     }
 
-    fn load_filtered_round_wins(
-        conn: &Connection,
-        game_ids: &[String],
-        players: &mut HashMap<String, MostBeautifulPlayerStats>,
-    ) -> Result<()> {
-        if game_ids.is_empty() {
-            return Ok(());
+    fn write_player_erasure(
+        tx: &rusqlite::Transaction<'_>,
+        player_hash: &str,
+        mode: PlayerErasureMode,
+        replacement_hash: &str,
+    ) -> Result<PlayerErasureReport> {
+        let changed = Self::reassign_player_rows(tx, player_hash, replacement_hash)?;
+        let mut rows_deleted = 0u64;
+        if mode == PlayerErasureMode::Delete {
+            for (table, hash_column) in PLAYER_OWNED_ROWS {
+                rows_deleted += tx
+                    .execute(
+                        &format!("DELETE FROM {table} WHERE {hash_column} = ?1"),
+                        params![replacement_hash],
+                    )
+                    .with_context(|| format!("Failed to delete erased player rows from {table}"))?
+                    as u64;
+            }
         }
 
-        let placeholders = std::iter::repeat("?")
-            .take(game_ids.len())
-            .collect::<Vec<_>>()
-            .join(", ");
-        let query = format!(
+        Ok(PlayerErasureReport {
+            player_hash: player_hash.to_string(),
+            mode: mode.name().to_string(),
+            replacement_hash: replacement_hash.to_string(),
+            rows_anonymized: changed.saturating_sub(rows_deleted),
+            rows_deleted,
+        })
+    }
+
+    // Retention policy: strips display names from rows recorded before `cutoff_s`, and from
+    // players not seen since then. Hashes are kept, so stats still add up.
+    pub fn anonymize_names_recorded_before(&self, cutoff_s: u64) -> Result<u64> {
+        match self.submit_and_wait(StatsWrite::ExpireNames { cutoff_s })? {
+            StatsWriteOutcome::RowsChanged(changed) => Ok(changed),
+            outcome => anyhow::bail!("Unexpected name retention outcome {outcome:?}"),
+        }
+    }
+
+    fn write_name_expiry(tx: &rusqlite::Transaction<'_>, cutoff_s: u64) -> Result<u64> {
+        let mut changed = 0u64;
+        for column in PLAYER_NAME_COLUMNS {
+            let query = match column.rounds_table {
+                None => format!(
+                    "UPDATE {table} SET {name} = ?1 WHERE {name} != ?1 AND recorded_at < ?2",
+                    table = column.table,
+                    name = column.name_column,
+                ),
+                Some(rounds_table) => format!(
+                    r#"
+                    UPDATE {table} SET {name} = ?1
+                    WHERE {name} != ?1
+                        AND EXISTS (
+                            SELECT 1 FROM {rounds_table} r
+                            WHERE r.game_id = {table}.game_id
+                                AND r.round_num = {table}.round_num
+                                AND r.recorded_at < ?2
+                        )
+                    "#,
+                    table = column.table,
+                    name = column.name_column,
+                ),
+            };
+            changed += tx
+                .execute(&query, params![ANONYMOUS_PLAYER_NAME, cutoff_s])
+                .with_context(|| format!("Failed to expire player names in {}", column.table))?
+                as u64;
+        }
+        changed += tx
+            .execute(
+                "DELETE FROM mb_player_names WHERE last_seen_at < ?1",
+                params![cutoff_s],
+            )
+            .context("Failed to expire player aliases")? as u64;
+        changed += tx
+            .execute(
+                r#"
+                UPDATE mb_players SET latest_display_name = ?1
+                WHERE latest_display_name != ?1 AND last_seen_at < ?2
+                "#,
+                params![ANONYMOUS_PLAYER_NAME, cutoff_s],
+            )
+            .context("Failed to expire player display names")? as u64;
+        Ok(changed)
+    }
+
+    fn account_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<Account> {
+        Ok(Account {
+            account_id: row.get(0)?,
+            username: row.get(1)?,
+            player_hash: row.get(2)?,
+            has_password: row.get::<_, Option<String>>(3)?.is_some(),
+            created_at_s: row.get(4)?,
+        })
+    }
+
+    // None when the username is taken, ignoring case.
+    pub fn create_account(
+        &self,
+        username: &str,
+        password_hash: Option<&str>,
+        created_at_s: u64,
+    ) -> Result<Option<Account>> {
+        let account_id = generate_account_id();
+        let player_hash = player_hash_for_token(&format!("{ACCOUNT_TOKEN_PREFIX}{account_id}"));
+        let inserted = self
+            .connect()?
+            .execute(
+                r#"
+                INSERT INTO mb_accounts (account_id, username, password_hash, player_hash, created_at)
+                VALUES (?1, ?2, ?3, ?4, ?5)
+                ON CONFLICT(username) DO NOTHING
+                "#,
+                params![account_id, username, password_hash, player_hash, created_at_s],
+            )
+            .context("Failed to create account")?;
+        Ok((inserted > 0).then(|| Account {
+            account_id,
+            username: username.to_string(),
+            player_hash,
+            has_password: password_hash.is_some(),
+            created_at_s,
+        }))
+    }
+
+    // The account and its password hash, if it has one.
+    pub fn account_by_username(&self, username: &str) -> Result<Option<(Account, Option<String>)>> {
+        self.read_conn()?
+            .query_row(
+                r#"
+                SELECT account_id, username, player_hash, password_hash, created_at
+                FROM mb_accounts WHERE username = ?1
+                "#,
+                params![username.trim()],
+                |row| Ok((Self::account_from_row(row)?, row.get(3)?)),
+            )
+            .optional()
+            .context("Failed to look up account")
+    }
+
+    pub fn create_account_session(&self, account_id: &str, now_s: u64) -> Result<String> {
+        let token = generate_session_token();
+        let conn = self.connect()?;
+        conn.execute(
+            "DELETE FROM mb_account_sessions WHERE last_used_at < ?1",
+            params![now_s.saturating_sub(ACCOUNT_SESSION_TTL_S)],
+        )
+        .context("Failed to expire account sessions")?;
+        conn.execute(
             r#"
-            SELECT
-                votes.game_id,
-                votes.round_num,
-                cards.owner_hash,
-                MAX(cards.owner_display_name),
-                SUM(votes.vote_count)
-            FROM mb_game_round_beauty_votes AS votes
-            INNER JOIN mb_game_round_cards AS cards
-                ON cards.game_id = votes.game_id
-                AND cards.round_num = votes.round_num
-                AND cards.card_hash = votes.card_hash
-            WHERE votes.game_id IN ({placeholders})
-            GROUP BY votes.game_id, votes.round_num, cards.owner_hash
-            ORDER BY votes.game_id, votes.round_num
-            "#
-        );
+            INSERT INTO mb_account_sessions (session_hash, account_id, created_at, last_used_at)
+            VALUES (?1, ?2, ?3, ?3)
+            "#,
+            params![hash_session_token(&token), account_id, now_s],
+        )
+        .context("Failed to create account session")?;
+        Ok(token)
+    }
+
+    pub fn account_for_session(&self, session_token: &str, now_s: u64) -> Result<Option<Account>> {
+        let session_hash = hash_session_token(session_token);
+        let found = self
+            .read_conn()?
+            .query_row(
+                r#"
+                SELECT a.account_id, a.username, a.player_hash, a.password_hash, a.created_at,
+                    s.last_used_at
+                FROM mb_account_sessions s
+                JOIN mb_accounts a ON a.account_id = s.account_id
+                WHERE s.session_hash = ?1 AND s.last_used_at >= ?2
+                "#,
+                params![session_hash, now_s.saturating_sub(ACCOUNT_SESSION_TTL_S)],
+                |row| Ok((Self::account_from_row(row)?, row.get::<_, u64>(5)?)),
+            )
+            .optional()
+            .context("Failed to look up account session")?;
+        let Some((account, last_used_at)) = found else {
+            return Ok(None);
+        };
+        if now_s.saturating_sub(last_used_at) >= ACCOUNT_SESSION_TOUCH_INTERVAL_S {
+            self.connect()?
+                .execute(
+                    "UPDATE mb_account_sessions SET last_used_at = ?2 WHERE session_hash = ?1",
+                    params![session_hash, now_s],
+                )
+                .context("Failed to refresh account session")?;
+        }
+        Ok(Some(account))
+    }
+
+    pub fn delete_account_session(&self, session_token: &str) -> Result<()> {
+        self.connect()?
+            .execute(
+                "DELETE FROM mb_account_sessions WHERE session_hash = ?1",
+                params![hash_session_token(session_token)],
+            )
+            .context("Failed to delete account session")?;
+        Ok(())
+    }
+
+    // Replaces any earlier code for the account.
+    pub fn store_login_code(
+        &self,
+        account_id: &str,
+        code_hash: &str,
+        expires_at_s: u64,
+    ) -> Result<()> {
+        self.connect()?
+            .execute(
+                r#"
+                INSERT INTO mb_account_login_codes (account_id, code_hash, expires_at, attempts)
+                VALUES (?1, ?2, ?3, 0)
+                ON CONFLICT(account_id) DO UPDATE SET
+                    code_hash = excluded.code_hash,
+                    expires_at = excluded.expires_at,
+                    attempts = 0
+                "#,
+                params![account_id, code_hash, expires_at_s],
+            )
+            .context("Failed to store login code")?;
+        Ok(())
+    }
+
+    // A code works once. Wrong guesses count against it, and it stops working after
+    // LOGIN_CODE_MAX_ATTEMPTS of them.
+    pub fn redeem_login_code(
+        &self,
+        username: &str,
+        code_hash: &str,
+        now_s: u64,
+    ) -> Result<Option<Account>> {
+        let mut conn = self.connect()?;
+        let tx = conn
+            .transaction()
+            .context("Failed to open login code transaction")?;
+        let found = tx
+            .query_row(
+                r#"
+                SELECT a.account_id, a.username, a.player_hash, a.password_hash, a.created_at,
+                    c.code_hash, c.expires_at, c.attempts
+                FROM mb_account_login_codes c
+                JOIN mb_accounts a ON a.account_id = c.account_id
+                WHERE a.username = ?1
+                "#,
+                params![username.trim()],
+                |row| {
+                    Ok((
+                        Self::account_from_row(row)?,
+                        row.get::<_, String>(5)?,
+                        row.get::<_, u64>(6)?,
+                        row.get::<_, u32>(7)?,
+                    ))
+                },
+            )
+            .optional()
+            .context("Failed to look up login code")?;
+        let Some((account, expected_hash, expires_at_s, attempts)) = found else {
+            return Ok(None);
+        };
+        let usable = expires_at_s > now_s && attempts < LOGIN_CODE_MAX_ATTEMPTS;
+        let redeemed = usable && expected_hash == code_hash;
+        if redeemed || !usable {
+            tx.execute(
+                "DELETE FROM mb_account_login_codes WHERE account_id = ?1",
+                params![account.account_id],
+            )
+            .context("Failed to clear login code")?;
+        } else {
+            tx.execute(
+                "UPDATE mb_account_login_codes SET attempts = attempts + 1 WHERE account_id = ?1",
+                params![account.account_id],
+            )
+            .context("Failed to count login code attempt")?;
+        }
+        tx.commit()
+            .context("Failed to commit login code transaction")?;
+        Ok(redeemed.then_some(account))
+    }
+
+    // Moves a browser player_hash's history onto the account's player_hash, keeping names.
+    // Rows that would collide with the account's own rows for the same game and round stay
+    // under the old hash. A rating the account already has in a scope wins over the merged one.
+    // Opt-outs carry over.
+    pub fn merge_player_into_account(
+        &self,
+        account: &Account,
+        player_hash: &str,
+        merged_at_s: u64,
+    ) -> Result<AccountMergeReport> {
+        let report = match self.submit_and_wait(StatsWrite::MergePlayer {
+            account: Box::new(account.clone()),
+            player_hash: player_hash.to_string(),
+            merged_at_s,
+        })? {
+            StatsWriteOutcome::Merged(report) => report,
+            outcome => anyhow::bail!("Unexpected account merge outcome {outcome:?}"),
+        };
+        if report.merged {
+            if let Ok(mut players) = self.opted_out_players.write() {
+                if players.remove(player_hash) {
+                    players.insert(account.player_hash.clone());
+                }
+            }
+        }
+        Ok(report)
+    }
+
+    fn write_account_merge(
+        tx: &rusqlite::Transaction<'_>,
+        account: &Account,
+        player_hash: &str,
+        merged_at_s: u64,
+    ) -> Result<AccountMergeReport> {
+        let report = |rows_moved: u64, merged: bool| AccountMergeReport {
+            account_id: account.account_id.clone(),
+            merged_player_hash: player_hash.to_string(),
+            rows_moved,
+            merged,
+        };
+        if player_hash == account.player_hash {
+            return Ok(report(0, false));
+        }
+
+        let target = account.player_hash.as_str();
+        let already_merged = tx
+            .query_row(
+                "SELECT 1 FROM mb_account_merged_players WHERE player_hash = ?1",
+                params![player_hash],
+                |_| Ok(()),
+            )
+            .optional()
+            .context("Failed to check earlier account merges")?
+            .is_some();
+        if already_merged {
+            return Ok(report(0, false));
+        }
+        let is_account_hash = tx
+            .query_row(
+                "SELECT 1 FROM mb_accounts WHERE player_hash = ?1",
+                params![player_hash],
+                |_| Ok(()),
+            )
+            .optional()
+            .context("Failed to check account player hashes")?
+            .is_some();
+        if is_account_hash {
+            anyhow::bail!("player_hash belongs to another account");
+        }
+
+        let mut rows_moved = 0u64;
+        for column in PLAYER_NAME_COLUMNS {
+            rows_moved += tx
+                .execute(
+                    &format!(
+                        "UPDATE OR IGNORE {table} SET {hash} = ?2 WHERE {hash} = ?1",
+                        table = column.table,
+                        hash = column.hash_column,
+                    ),
+                    params![player_hash, target],
+                )
+                .with_context(|| format!("Failed to merge player rows in {}", column.table))?
+                as u64;
+        }
+        for table in PLAYER_HASH_ONLY_TABLES {
+            rows_moved += tx
+                .execute(
+                    &format!(
+                        "UPDATE OR IGNORE {table} SET player_hash = ?2 WHERE player_hash = ?1"
+                    ),
+                    params![player_hash, target],
+                )
+                .with_context(|| format!("Failed to merge player rows in {table}"))?
+                as u64;
+        }
+        tx.execute(
+            "DELETE FROM mb_player_ratings WHERE player_hash = ?1",
+            params![player_hash],
+        )
+        .context("Failed to drop superseded player ratings")?;
+
+        tx.execute(
+            r#"
+            INSERT INTO mb_players (player_hash, latest_display_name, first_seen_at, last_seen_at)
+            SELECT ?2, latest_display_name, first_seen_at, last_seen_at
+            FROM mb_players WHERE player_hash = ?1
+            ON CONFLICT(player_hash) DO UPDATE SET
+                latest_display_name = CASE
+                    WHEN excluded.last_seen_at > mb_players.last_seen_at
+                        THEN excluded.latest_display_name
+                    ELSE mb_players.latest_display_name
+                END,
+                first_seen_at = MIN(mb_players.first_seen_at, excluded.first_seen_at),
+                last_seen_at = MAX(mb_players.last_seen_at, excluded.last_seen_at)
+            "#,
+            params![player_hash, target],
+        )
+        .context("Failed to merge player row")?;
+        tx.execute(
+            r#"
+            INSERT INTO mb_player_names (player_hash, display_name, first_seen_at, last_seen_at)
+            SELECT ?2, display_name, first_seen_at, last_seen_at
+            FROM mb_player_names WHERE player_hash = ?1
+            ON CONFLICT(player_hash, display_name) DO UPDATE SET
+                first_seen_at = MIN(mb_player_names.first_seen_at, excluded.first_seen_at),
+                last_seen_at = MAX(mb_player_names.last_seen_at, excluded.last_seen_at)
+            "#,
+            params![player_hash, target],
+        )
+        .context("Failed to merge player aliases")?;
+        tx.execute(
+            "DELETE FROM mb_player_names WHERE player_hash = ?1",
+            params![player_hash],
+        )
+        .context("Failed to delete merged player aliases")?;
+        tx.execute(
+            "DELETE FROM mb_players WHERE player_hash = ?1",
+            params![player_hash],
+        )
+        .context("Failed to delete merged player row")?;
+
+        let opted_out = tx
+            .execute(
+                "DELETE FROM mb_player_opt_outs WHERE player_hash = ?1",
+                params![player_hash],
+            )
+            .context("Failed to move player stats opt-out")?
+            > 0;
+        if opted_out {
+            Self::write_player_opt_out(tx, target, true, merged_at_s)?;
+        } else {
+            let target_opted_out = tx
+                .query_row(
+                    "SELECT 1 FROM mb_player_opt_outs WHERE player_hash = ?1",
+                    params![target],
+                    |_| Ok(()),
+                )
+                .optional()
+                .context("Failed to check account stats opt-out")?
+                .is_some();
+            if target_opted_out {
+                // strips the names that just arrived
+                Self::reassign_player_rows(tx, target, target)?;
+            }
+        }
+
+        tx.execute(
+            r#"
+            INSERT INTO mb_account_merged_players (player_hash, account_id, merged_at, rows_moved)
+            VALUES (?1, ?2, ?3, ?4)
+            "#,
+            params![player_hash, account.account_id, merged_at_s, rows_moved],
+        )
+        .context("Failed to record account merge")?;
+        Ok(report(rows_moved, true))
+    }
+
+    // False when the room id is already persistent.
+    pub fn create_persistent_room(
+        &self,
+        room_id: &str,
+        owner_token_hash: &str,
+        snapshot: &str,
+        created_at_s: u64,
+    ) -> Result<bool> {
+        let inserted = self
+            .connect()?
+            .execute(
+                r#"
+                INSERT INTO mb_persistent_rooms
+                    (room_id, owner_token_hash, snapshot, created_at, updated_at)
+                VALUES (?1, ?2, ?3, ?4, ?4)
+                ON CONFLICT(room_id) DO NOTHING
+                "#,
+                params![room_id, owner_token_hash, snapshot, created_at_s],
+            )
+            .context("Failed to create persistent room")?;
+        Ok(inserted > 0)
+    }
+
+    // Runs on the writer thread and blocks until it is committed.
+    pub fn save_persistent_room_snapshot(
+        &self,
+        room_id: &str,
+        snapshot: &str,
+        updated_at_s: u64,
+    ) -> Result<()> {
+        self.submit_and_wait(StatsWrite::SavePersistentRoom {
+            room_id: room_id.to_string(),
+            snapshot: snapshot.to_string(),
+            updated_at_s,
+        })
+        .map(|_| ())
+    }
+
+    fn persistent_room_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<PersistentRoomRecord> {
+        Ok(PersistentRoomRecord {
+            room_id: row.get(0)?,
+            owner_token_hash: row.get(1)?,
+            snapshot: row.get(2)?,
+            created_at_s: row.get(3)?,
+            updated_at_s: row.get(4)?,
+        })
+    }
+
+    pub fn persistent_rooms(&self) -> Result<Vec<PersistentRoomRecord>> {
+        let conn = self.read_conn()?;
         let mut stmt = conn
-            .prepare(&query)
-            .context("Failed to prepare filtered Most Beautiful round-wins query")?;
+            .prepare(
+                r#"
+                SELECT room_id, owner_token_hash, snapshot, created_at, updated_at
+                FROM mb_persistent_rooms ORDER BY room_id
+                "#,
+            )
+            .context("Failed to prepare persistent room query")?;
         let rows = stmt
-            .query_map(rusqlite::params_from_iter(game_ids.iter()), |row| {
-                Ok((
-                    row.get::<_, String>(0)?,
-                    row.get::<_, u16>(1)?,
-                    row.get::<_, String>(2)?,
-                    row.get::<_, String>(3)?,
-                    row.get::<_, u64>(4)?,
-                ))
-            })
-            .context("Failed to query filtered Most Beautiful round wins")?;
-        let mut rounds = HashMap::<(String, u16), Vec<(String, String, u64)>>::new();
-        for row in rows {
-            let (game_id, round_num, owner_hash, owner_display_name, vote_total) =
-                row.context("Failed to decode filtered Most Beautiful round-win row")?;
-            rounds.entry((game_id, round_num)).or_default().push((
-                owner_hash,
-                owner_display_name,
-                vote_total,
-            ));
+            .query_map([], Self::persistent_room_from_row)
+            .context("Failed to query persistent rooms")?;
+        rows.collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to read persistent rooms")
+    }
+
+    pub fn persistent_room(&self, room_id: &str) -> Result<Option<PersistentRoomRecord>> {
+        self.read_conn()?
+            .query_row(
+                r#"
+                SELECT room_id, owner_token_hash, snapshot, created_at, updated_at
+                FROM mb_persistent_rooms WHERE room_id = ?1
+                "#,
+                params![room_id],
+                Self::persistent_room_from_row,
+            )
+            .optional()
+            .context("Failed to look up persistent room")
+    }
+
+    // False when the room id was not persistent. Runs on the writer thread and blocks until it
+    // is committed.
+    pub fn delete_persistent_room(&self, room_id: &str) -> Result<bool> {
+        match self.submit_and_wait(StatsWrite::DeletePersistentRoom {
+            room_id: room_id.to_string(),
+        })? {
+            StatsWriteOutcome::RowsChanged(deleted) => Ok(deleted > 0),
+            outcome => anyhow::bail!("Unexpected persistent room delete outcome {outcome:?}"),
+        }
+    }
+
+    // `standings` is the End screen order. Without it, ratings fall back to final round totals.
+    pub fn mark_game_complete(
+        &self,
+        game_id: &str,
+        ended_at_s: u64,
+        total_rounds: u16,
+        standings: &[GameStandingRecord],
+    ) -> Result<()> {
+        let missing_writes = self
+            .writer
+            .as_ref()
+            .is_some_and(|writer| writer.take_game_missing_writes(game_id));
+        self.submit(StatsWrite::GameComplete {
+            game_id: game_id.to_string(),
+            ended_at_s,
+            total_rounds,
+            standings: standings.to_vec(),
+            missing_writes,
+        })
+    }
+
+    // A game with dropped round writes is closed but left incomplete, so it is never rated or
+    // exported as a replay with gaps in it.
+    fn write_game_complete(
+        tx: &rusqlite::Transaction<'_>,
+        game_id: &str,
+        ended_at_s: u64,
+        total_rounds: u16,
+        standings: &[GameStandingRecord],
+        missing_writes: bool,
+    ) -> Result<()> {
+        if missing_writes {
+            tx.execute(
+                r#"
+                UPDATE mb_games
+                SET ended_at = ?2,
+                    missing_writes = 1,
+                    total_rounds = MAX(total_rounds, ?3)
+                WHERE game_id = ?1
+                "#,
+                params![game_id, ended_at_s, total_rounds],
+            )
+            .context("Failed to mark Most Beautiful game audit incomplete")?;
+            return Ok(());
         }
+        tx.execute(
+            r#"
+            UPDATE mb_games
+            SET ended_at = ?2,
+                completed = 1,
+                total_rounds = MAX(total_rounds, ?3)
+            WHERE game_id = ?1
+            "#,
+            params![game_id, ended_at_s, total_rounds],
+        )
+        .context("Failed to mark Most Beautiful game audit complete")?;
+        for standing in standings {
+            tx.execute(
+                r#"
+                INSERT INTO mb_game_standings (game_id, player_hash, rank, points)
+                SELECT ?1, ?2, ?3, ?4 WHERE EXISTS (SELECT 1 FROM mb_games WHERE game_id = ?1)
+                ON CONFLICT(game_id, player_hash) DO UPDATE SET
+                    rank = excluded.rank,
+                    points = excluded.points
+                "#,
+                params![
+                    game_id,
+                    standing.player_hash,
+                    standing.rank,
+                    standing.points
+                ],
+            )
+            .context("Failed to record final game standing")?;
+        }
+        Self::apply_game_ratings(tx, game_id, ended_at_s)
+    }
+
+    // Queues the write on the background writer when there is one, otherwise writes it now.
+    // A full queue drops the write rather than stalling the room lock the caller holds.
+    fn submit(&self, mut write: StatsWrite) -> Result<()> {
+        if let Ok(opted_out) = self.opted_out_players.read() {
+            write.redact_opted_out(&opted_out);
+        }
+        let write = match &self.writer {
+            Some(writer) => match writer.enqueue(write) {
+                Ok(()) => return Ok(()),
+                Err(write) => {
+                    println!(
+                        "Most Beautiful stats writer is gone; writing to {} directly",
+                        self.db_path.display()
+                    );
+                    write
+                }
+            },
+            None => write,
+        };
+        Self::apply_in_transaction(&mut self.connect()?, &write).map(|_| ())
+    }
+
+    // Runs the write after everything queued before it and waits for the result. Blocks, so
+    // async callers go through the blocking thread pool.
+    fn submit_and_wait(&self, write: StatsWrite) -> Result<StatsWriteOutcome> {
+        let Some(writer) = &self.writer else {
+            return Self::apply_in_transaction(&mut self.connect()?, &write);
+        };
+        let (reply_tx, reply_rx) = std::sync::mpsc::channel();
+        writer.send_blocking(StatsWriterMsg::Request(write, reply_tx))?;
+        reply_rx
+            .recv()
+            .context("Most Beautiful stats writer stopped before replying")?
+    }
+
+    fn apply_in_transaction(
+        conn: &mut Connection,
+        write: &StatsWrite,
+    ) -> Result<StatsWriteOutcome> {
+        let tx = conn
+            .transaction()
+            .context("Failed to open Most Beautiful stats transaction")?;
+        let outcome = write.apply(&tx)?;
+        tx.commit()
+            .context("Failed to commit Most Beautiful stats transaction")?;
+        Ok(outcome)
+    }
+
+    fn room_rating_scope(room_id: &str) -> String {
+        format!("room:{room_id}")
+    }
+
+    // Updates the global and room-scoped ratings from the game's End screen standings, or from
+    // final round totals for games recorded without them. A game is rated at most once, so
+    // marking it complete again is a no-op.
+    fn apply_game_ratings(
+        tx: &rusqlite::Transaction<'_>,
+        game_id: &str,
+        recorded_at_s: u64,
+    ) -> Result<()> {
+        let already_rated = tx
+            .query_row(
+                "SELECT 1 FROM mb_player_rating_history WHERE game_id = ?1 LIMIT 1",
+                params![game_id],
+                |_| Ok(()),
+            )
+            .optional()
+            .context("Failed to check whether game is already rated")?
+            .is_some();
+        if already_rated {
+            return Ok(());
+        }
+        let Some((room_id, game_mode)) = tx
+            .query_row(
+                "SELECT room_id, game_mode FROM mb_games WHERE game_id = ?1",
+                params![game_id],
+                |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)),
+            )
+            .optional()
+            .context("Failed to load game room for rating")?
+        else {
+            return Ok(());
+        };
+        let standings = Self::final_standings_for_rating(tx, game_id, &game_mode)?;
+        if standings.len() < 2 {
+            return Ok(());
+        }
+        let players_in_game = standings.len() as u16;
+        let placements = standings
+            .iter()
+            .map(|(_, placement)| *placement)
+            .collect::<Vec<_>>();
+
+        for scope in [
+            GLOBAL_RATING_SCOPE.to_string(),
+            Self::room_rating_scope(&room_id),
+        ] {
+            let mut ratings_before = Vec::with_capacity(standings.len());
+            for (player_hash, _) in &standings {
+                let rating = tx
+                    .query_row(
+                        "SELECT rating FROM mb_player_ratings WHERE scope = ?1 AND player_hash = ?2",
+                        params![scope, player_hash],
+                        |row| row.get::<_, f64>(0),
+                    )
+                    .optional()
+                    .context("Failed to load player rating")?
+                    .unwrap_or(INITIAL_PLAYER_RATING);
+                ratings_before.push(rating);
+            }
+
+            let ratings_after = elo_ratings_after_game(&ratings_before, &placements);
+            for (idx, (player_hash, _)) in standings.iter().enumerate() {
+                tx.execute(
+                    r#"
+                    INSERT INTO mb_player_ratings (
+                        scope,
+                        player_hash,
+                        rating,
+                        games_rated,
+                        last_rated_at
+                    )
+                    VALUES (?1, ?2, ?3, 1, ?4)
+                    ON CONFLICT(scope, player_hash) DO UPDATE SET
+                        rating = excluded.rating,
+                        games_rated = games_rated + 1,
+                        last_rated_at = excluded.last_rated_at
+                    "#,
+                    params![scope, player_hash, ratings_after[idx], recorded_at_s],
+                )
+                .context("Failed to upsert player rating")?;
+                tx.execute(
+                    r#"
+                    INSERT INTO mb_player_rating_history (
+                        scope,
+                        game_id,
+                        player_hash,
+                        recorded_at,
+                        placement,
+                        players_in_game,
+                        rating_before,
+                        rating_after
+                    )
+                    VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+                    "#,
+                    params![
+                        scope,
+                        game_id,
+                        player_hash,
+                        recorded_at_s,
+                        placements[idx],
+                        players_in_game,
+                        ratings_before[idx],
+                        ratings_after[idx]
+                    ],
+                )
+                .context("Failed to insert player rating history")?;
+            }
+        }
+
+        Ok(())
+    }
+
+    // (player_hash, placement) pairs; equal placements are draws.
+    fn final_standings_for_rating(
+        tx: &rusqlite::Transaction<'_>,
+        game_id: &str,
+        game_mode: &str,
+    ) -> Result<Vec<(String, u16)>> {
+        let recorded = tx
+            .prepare(
+                r#"
+                SELECT player_hash, rank FROM mb_game_standings
+                WHERE game_id = ?1
+                ORDER BY rank, player_hash
+                "#,
+            )
+            .and_then(|mut stmt| {
+                stmt.query_map(params![game_id], |row| {
+                    Ok((row.get::<_, String>(0)?, row.get::<_, u16>(1)?))
+                })?
+                .collect::<rusqlite::Result<Vec<_>>>()
+            })
+            .context("Failed to load final game standings")?;
+        if !recorded.is_empty() {
+            return Ok(recorded);
+        }
+
+        let scores_table = if game_mode == GAME_MODE_STELLA {
+            "mb_stella_round_players"
+        } else {
+            "mb_game_round_scores"
+        };
+        let mut totals_stmt = tx
+            .prepare(&format!(
+                r#"
+                SELECT s.player_hash, s.total_after_round
+                FROM {scores_table} s
+                JOIN (
+                    SELECT player_hash, MAX(round_num) AS last_round
+                    FROM {scores_table}
+                    WHERE game_id = ?1
+                    GROUP BY player_hash
+                ) latest
+                    ON latest.player_hash = s.player_hash AND latest.last_round = s.round_num
+                WHERE s.game_id = ?1
+                ORDER BY s.player_hash
+                "#
+            ))
+            .context("Failed to prepare final standings query")?;
+        let totals = totals_stmt
+            .query_map(params![game_id], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, u16>(1)?))
+            })
+            .context("Failed to query final standings")?
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to decode final standings row")?;
+        Ok(totals
+            .iter()
+            .map(|(player_hash, total)| {
+                let placement = 1 + totals
+                    .iter()
+                    .filter(|(_, other_total)| other_total > total)
+                    .count() as u16;
+                (player_hash.clone(), placement)
+            })
+            .collect())
+    }
+
+    fn rating_scope(room_id: Option<&str>) -> String {
+        room_id
+            .map(Self::room_rating_scope)
+            .unwrap_or_else(|| GLOBAL_RATING_SCOPE.to_string())
+    }
+
+    pub fn rating_leaderboard(
+        &self,
+        room_id: Option<&str>,
+        limit: usize,
+    ) -> Result<PlayerRatingLeaderboard> {
+        let conn = self.read_conn()?;
+        let scope = Self::rating_scope(room_id);
+        let limit = if limit == 0 {
+            DEFAULT_RATING_LEADERBOARD_LIMIT
+        } else {
+            limit.min(MAX_RATING_LEADERBOARD_LIMIT)
+        };
+        let mut stmt = conn
+            .prepare(
+                r#"
+                SELECT player_hash, rating, games_rated, last_rated_at
+                FROM mb_player_ratings
+                WHERE scope = ?1
+                ORDER BY rating DESC, games_rated DESC, player_hash
+                LIMIT ?2
+                "#,
+            )
+            .context("Failed to prepare rating leaderboard query")?;
+        let rows = stmt
+            .query_map(params![scope, limit as i64], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, f64>(1)?,
+                    row.get::<_, u64>(2)?,
+                    row.get::<_, u64>(3)?,
+                ))
+            })
+            .context("Failed to query rating leaderboard")?;
+        let mut players = Vec::new();
+        for row in rows {
+            let (player_hash, rating, games_rated, last_rated_at_s) =
+                row.context("Failed to decode rating leaderboard row")?;
+            players.push(PlayerRatingEntry {
+                display_name: Self::player_name(&conn, &player_hash, &player_hash)?,
+                player_hash,
+                rating,
+                games_rated,
+                last_rated_at_s,
+            });
+        }
+        Ok(PlayerRatingLeaderboard { scope, players })
+    }
+
+    pub fn player_rating_history(
+        &self,
+        player_hash: &str,
+        room_id: Option<&str>,
+    ) -> Result<PlayerRatingHistory> {
+        let conn = self.read_conn()?;
+        let scope = Self::rating_scope(room_id);
+        let rating = conn
+            .query_row(
+                "SELECT rating FROM mb_player_ratings WHERE scope = ?1 AND player_hash = ?2",
+                params![scope, player_hash],
+                |row| row.get::<_, f64>(0),
+            )
+            .optional()
+            .context("Failed to load player rating")?;
+        let mut stmt = conn
+            .prepare(
+                r#"
+                SELECT
+                    h.game_id,
+                    g.room_id,
+                    h.recorded_at,
+                    h.placement,
+                    h.players_in_game,
+                    h.rating_before,
+                    h.rating_after
+                FROM mb_player_rating_history h
+                JOIN mb_games g ON g.game_id = h.game_id
+                WHERE h.scope = ?1 AND h.player_hash = ?2
+                ORDER BY h.recorded_at, h.game_id
+                "#,
+            )
+            .context("Failed to prepare player rating history query")?;
+        let history = stmt
+            .query_map(params![scope, player_hash], |row| {
+                Ok(PlayerRatingHistoryPoint {
+                    game_id: row.get(0)?,
+                    room_id: row.get(1)?,
+                    recorded_at_s: row.get(2)?,
+                    placement: row.get(3)?,
+                    players_in_game: row.get(4)?,
+                    rating_before: row.get(5)?,
+                    rating_after: row.get(6)?,
+                })
+            })
+            .context("Failed to query player rating history")?
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to decode player rating history row")?;
+        Ok(PlayerRatingHistory {
+            scope,
+            player_hash: player_hash.to_string(),
+            display_name: Self::player_name(&conn, player_hash, player_hash)?,
+            rating,
+            history,
+        })
+    }
+
+    pub fn export_game_replay(&self, game_id: &str) -> Result<Option<GameReplay>> {
+        let conn = self.read_conn()?;
+        Self::load_game_replay(&conn, game_id)
+    }
+
+    pub fn game_room_id(&self, game_id: &str) -> Result<Option<String>> {
+        let conn = self.read_conn()?;
+        conn.query_row(
+            "SELECT room_id FROM mb_games WHERE game_id = ?1",
+            params![game_id],
+            |row| row.get(0),
+        )
+        .optional()
+        .context("Failed to load game room")
+    }
+
+    fn load_game_replays(conn: &Connection, game_ids: &[String]) -> Result<Vec<GameReplay>> {
+        let mut games = Vec::with_capacity(game_ids.len());
+        for game_id in game_ids {
+            if let Some(game) = Self::load_game_replay(conn, game_id)? {
+                games.push(game);
+            }
+        }
+        Ok(games)
+    }
+
+    pub fn export_room_games(&self, room_id: &str) -> Result<StatsExportResponse> {
+        let conn = self.read_conn()?;
+        let mut stmt = conn
+            .prepare("SELECT game_id FROM mb_games WHERE room_id = ?1 ORDER BY started_at, game_id")
+            .context("Failed to prepare room export games query")?;
+        let game_ids = stmt
+            .query_map(params![room_id], |row| row.get::<_, String>(0))
+            .context("Failed to query room export games")?
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to decode room export game row")?;
+        Ok(StatsExportResponse {
+            games: Self::load_game_replays(&conn, &game_ids)?,
+        })
+    }
+
+    // every game the player took part in, optionally limited to one room
+    pub fn export_player_games(
+        &self,
+        player_hash: &str,
+        room_id: Option<&str>,
+    ) -> Result<StatsExportResponse> {
+        let conn = self.read_conn()?;
+        let mut stmt = conn
+            .prepare(
+                r#"
+                SELECT game_id
+                FROM mb_games
+                WHERE game_id IN (
+                    SELECT game_id FROM mb_game_rounds WHERE storyteller_hash = ?1
+                    UNION
+                    SELECT game_id FROM mb_game_round_cards
+                    WHERE owner_hash = ?1 OR submitted_by_hash = ?1
+                    UNION
+                    SELECT game_id FROM mb_game_round_story_votes WHERE voter_hash = ?1
+                    UNION
+                    SELECT game_id FROM mb_game_round_beauty_votes WHERE voter_hash = ?1
+                    UNION
+                    SELECT game_id FROM mb_game_round_scores WHERE player_hash = ?1
+                    UNION
+                    SELECT game_id FROM mb_stella_round_players WHERE player_hash = ?1
+                    UNION
+                    SELECT game_id FROM mb_stella_round_selections WHERE player_hash = ?1
+                )
+                AND (?2 IS NULL OR room_id = ?2)
+                ORDER BY started_at, game_id
+                "#,
+            )
+            .context("Failed to prepare player export games query")?;
+        let game_ids = stmt
+            .query_map(params![player_hash, room_id], |row| row.get::<_, String>(0))
+            .context("Failed to query player export games")?
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to decode player export game row")?;
+        Ok(StatsExportResponse {
+            games: Self::load_game_replays(&conn, &game_ids)?,
+        })
+    }
+
+    fn load_game_replay(conn: &Connection, game_id: &str) -> Result<Option<GameReplay>> {
+        let Some((room_id, game_mode, started_at_s, ended_at_s, completed)) = conn
+            .query_row(
+                r#"
+                SELECT room_id, game_mode, started_at, ended_at, completed
+                FROM mb_games
+                WHERE game_id = ?1 AND missing_writes = 0
+                "#,
+                params![game_id],
+                |row| {
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        row.get::<_, String>(1)?,
+                        row.get::<_, u64>(2)?,
+                        row.get::<_, Option<u64>>(3)?,
+                        row.get::<_, bool>(4)?,
+                    ))
+                },
+            )
+            .optional()
+            .context("Failed to load game for replay export")?
+        else {
+            return Ok(None);
+        };
+
+        let mut round_stmt = conn
+            .prepare(
+                r#"
+                SELECT
+                    round_num,
+                    recorded_at,
+                    storyteller_hash,
+                    storyteller_display_name,
+                    clue,
+                    results_display_mode
+                FROM mb_game_rounds
+                WHERE game_id = ?1
+                ORDER BY round_num
+                "#,
+            )
+            .context("Failed to prepare replay rounds query")?;
+        let round_rows = round_stmt
+            .query_map(params![game_id], |row| {
+                Ok(GameReplayRound {
+                    round_num: row.get(0)?,
+                    recorded_at_s: row.get(1)?,
+                    storyteller_hash: row.get(2)?,
+                    storyteller_display_name: row.get(3)?,
+                    clue: row.get(4)?,
+                    results_display_mode: row.get(5)?,
+                    center_cards: Vec::new(),
+                    story_votes: Vec::new(),
+                    beauty_votes: Vec::new(),
+                    scores: Vec::new(),
+                    clue_rating: None,
+                })
+            })
+            .context("Failed to query replay rounds")?;
+        let mut rounds = round_rows
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to decode replay round row")?;
+
+        for round in &mut rounds {
+            let mut card_stmt = conn
+                .prepare(
+                    r#"
+                    SELECT
+                        card_hash,
+                        owner_hash,
+                        owner_display_name,
+                        submitted_by_hash,
+                        submitted_by_display_name,
+                        is_storyteller_card,
+                        center_order
+                    FROM mb_game_round_cards
+                    WHERE game_id = ?1 AND round_num = ?2
+                    ORDER BY center_order, card_hash
+                    "#,
+                )
+                .context("Failed to prepare replay cards query")?;
+            round.center_cards = card_stmt
+                .query_map(params![game_id, round.round_num], |row| {
+                    Ok(MostBeautifulGameAuditCardRecord {
+                        card_hash: row.get(0)?,
+                        owner_hash: row.get(1)?,
+                        owner_display_name: row.get(2)?,
+                        submitted_by_hash: row.get(3)?,
+                        submitted_by_display_name: row.get(4)?,
+                        is_storyteller_card: row.get(5)?,
+                        center_order: row.get(6)?,
+                    })
+                })
+                .context("Failed to query replay cards")?
+                .collect::<rusqlite::Result<Vec<_>>>()
+                .context("Failed to decode replay card row")?;
+            round.story_votes = Self::load_game_vote_records(
+                conn,
+                "mb_game_round_story_votes",
+                game_id,
+                round.round_num,
+            )?;
+            round.beauty_votes = Self::load_game_vote_records(
+                conn,
+                "mb_game_round_beauty_votes",
+                game_id,
+                round.round_num,
+            )?;
+
+            let mut score_stmt = conn
+                .prepare(
+                    r#"
+                    SELECT
+                        player_hash,
+                        player_display_name,
+                        story_delta,
+                        beauty_delta,
+                        total_after_round,
+                        beauty_total_after_round
+                    FROM mb_game_round_scores
+                    WHERE game_id = ?1 AND round_num = ?2
+                    ORDER BY player_hash
+                    "#,
+                )
+                .context("Failed to prepare replay scores query")?;
+            round.scores = score_stmt
+                .query_map(params![game_id, round.round_num], |row| {
+                    Ok(MostBeautifulGameAuditScoreRecord {
+                        player_hash: row.get(0)?,
+                        player_display_name: row.get(1)?,
+                        story_delta: row.get(2)?,
+                        beauty_delta: row.get(3)?,
+                        total_after_round: row.get(4)?,
+                        beauty_total_after_round: row.get(5)?,
+                    })
+                })
+                .context("Failed to query replay scores")?
+                .collect::<rusqlite::Result<Vec<_>>>()
+                .context("Failed to decode replay score row")?;
+            round.clue_rating = Self::load_game_clue_rating(conn, game_id, round.round_num)?;
+        }
+
+        let stella_rounds = Self::load_stella_replay_rounds(conn, game_id)?;
+
+        Ok(Some(GameReplay {
+            format_version: GAME_REPLAY_FORMAT_VERSION,
+            game_id: game_id.to_string(),
+            room_id,
+            game_mode,
+            started_at_s,
+            ended_at_s,
+            completed,
+            rounds,
+            stella_rounds,
+        }))
+    }
+
+    fn load_stella_replay_rounds(
+        conn: &Connection,
+        game_id: &str,
+    ) -> Result<Vec<StellaReplayRound>> {
+        let mut round_stmt = conn
+            .prepare(
+                r#"
+                SELECT round_num, recorded_at, clue_word
+                FROM mb_stella_rounds
+                WHERE game_id = ?1
+                ORDER BY round_num
+                "#,
+            )
+            .context("Failed to prepare Stella replay rounds query")?;
+        let mut rounds = round_stmt
+            .query_map(params![game_id], |row| {
+                Ok(StellaReplayRound {
+                    round_num: row.get(0)?,
+                    recorded_at_s: row.get(1)?,
+                    clue_word: row.get(2)?,
+                    board_cards: Vec::new(),
+                    selections: Vec::new(),
+                    players: Vec::new(),
+                })
+            })
+            .context("Failed to query Stella replay rounds")?
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to decode Stella replay round row")?;
+
+        let mut card_stmt = conn
+            .prepare(
+                r#"
+                SELECT card_hash, board_order, reveal_order, points
+                FROM mb_stella_round_board_cards
+                WHERE game_id = ?1 AND round_num = ?2
+                ORDER BY board_order, card_hash
+                "#,
+            )
+            .context("Failed to prepare Stella replay board query")?;
+        let mut selection_stmt = conn
+            .prepare(
+                r#"
+                SELECT player_hash, player_display_name, card_hash
+                FROM mb_stella_round_selections
+                WHERE game_id = ?1 AND round_num = ?2
+                ORDER BY player_hash, card_hash
+                "#,
+            )
+            .context("Failed to prepare Stella replay selections query")?;
+        let mut player_stmt = conn
+            .prepare(
+                r#"
+                SELECT
+                    player_hash,
+                    player_display_name,
+                    in_the_dark,
+                    fell,
+                    score_delta,
+                    total_after_round
+                FROM mb_stella_round_players
+                WHERE game_id = ?1 AND round_num = ?2
+                ORDER BY player_hash
+                "#,
+            )
+            .context("Failed to prepare Stella replay players query")?;
+        for round in &mut rounds {
+            round.board_cards = card_stmt
+                .query_map(params![game_id, round.round_num], |row| {
+                    Ok(StellaGameAuditBoardCardRecord {
+                        card_hash: row.get(0)?,
+                        board_order: row.get(1)?,
+                        reveal_order: row.get(2)?,
+                        points: row.get(3)?,
+                    })
+                })
+                .context("Failed to query Stella replay board")?
+                .collect::<rusqlite::Result<Vec<_>>>()
+                .context("Failed to decode Stella replay board card row")?;
+            round.selections = selection_stmt
+                .query_map(params![game_id, round.round_num], |row| {
+                    Ok(StellaGameAuditSelectionRecord {
+                        player_hash: row.get(0)?,
+                        player_display_name: row.get(1)?,
+                        card_hash: row.get(2)?,
+                    })
+                })
+                .context("Failed to query Stella replay selections")?
+                .collect::<rusqlite::Result<Vec<_>>>()
+                .context("Failed to decode Stella replay selection row")?;
+            round.players = player_stmt
+                .query_map(params![game_id, round.round_num], |row| {
+                    Ok(StellaGameAuditPlayerRecord {
+                        player_hash: row.get(0)?,
+                        player_display_name: row.get(1)?,
+                        in_the_dark: row.get(2)?,
+                        fell: row.get(3)?,
+                        score_delta: row.get(4)?,
+                        total_after_round: row.get(5)?,
+                    })
+                })
+                .context("Failed to query Stella replay players")?
+                .collect::<rusqlite::Result<Vec<_>>>()
+                .context("Failed to decode Stella replay player row")?;
+        }
+        Ok(rounds)
+    }
+
+    fn load_game_vote_records(
+        conn: &Connection,
+        table_name: &str,
+        game_id: &str,
+        round_num: u16,
+    ) -> Result<Vec<MostBeautifulGameAuditVoteRecord>> {
+        let query = format!(
+            "SELECT voter_hash, voter_display_name, card_hash, vote_count FROM {} WHERE game_id = ?1 AND round_num = ?2 ORDER BY voter_hash, card_hash",
+            table_name
+        );
+        let mut stmt = conn
+            .prepare(&query)
+            .with_context(|| format!("Failed to prepare replay votes query for {}", table_name))?;
+        let rows = stmt
+            .query_map(params![game_id, round_num], |row| {
+                Ok(MostBeautifulGameAuditVoteRecord {
+                    voter_hash: row.get(0)?,
+                    voter_display_name: row.get(1)?,
+                    card_hash: row.get(2)?,
+                    vote_count: row.get(3)?,
+                })
+            })
+            .with_context(|| format!("Failed to query replay votes from {}", table_name))?;
+        rows.collect::<rusqlite::Result<Vec<_>>>()
+            .with_context(|| format!("Failed to decode replay vote row from {}", table_name))
+    }
+
+    fn load_game_clue_rating(
+        conn: &Connection,
+        game_id: &str,
+        round_num: u16,
+    ) -> Result<Option<MostBeautifulGameAuditClueRatingRecord>> {
+        conn.query_row(
+            r#"
+            SELECT rating_count, rating_sum, max_stars
+            FROM mb_game_round_clue_ratings
+            WHERE game_id = ?1 AND round_num = ?2
+            "#,
+            params![game_id, round_num],
+            |row| {
+                Ok(MostBeautifulGameAuditClueRatingRecord {
+                    rating_count: row.get(0)?,
+                    rating_sum: row.get(1)?,
+                    max_stars: row.get(2)?,
+                })
+            },
+        )
+        .optional()
+        .context("Failed to load Most Beautiful game round clue rating")
+    }
+
+    pub fn game_exists(&self, game_id: &str) -> Result<bool> {
+        let conn = self.read_conn()?;
+        let exists = conn
+            .query_row(
+                "SELECT 1 FROM mb_games WHERE game_id = ?1",
+                params![game_id],
+                |_| Ok(()),
+            )
+            .optional()
+            .context("Failed to look up game for replay import")?
+            .is_some();
+        Ok(exists)
+    }
+
+    // callers should check `GameReplay::validate` and `game_exists` first to report precise errors
+    pub fn import_game_replay(&self, replay: &GameReplay) -> Result<()> {
+        replay.validate().map_err(anyhow::Error::msg)?;
+        self.submit_and_wait(StatsWrite::ImportReplay(Box::new(replay.clone())))
+            .map(|_| ())
+    }
+
+    fn write_game_replay_import(tx: &rusqlite::Transaction<'_>, replay: &GameReplay) -> Result<()> {
+        tx.execute(
+            r#"
+            INSERT INTO mb_games (
+                game_id, room_id, started_at, ended_at, completed, total_rounds, game_mode
+            )
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+            "#,
+            params![
+                replay.game_id,
+                replay.room_id,
+                replay.started_at_s,
+                replay.ended_at_s,
+                i64::from(replay.completed),
+                replay.total_rounds(),
+                replay.game_mode
+            ],
+        )
+        .context("Failed to insert imported game row")?;
+        for round in &replay.rounds {
+            Self::write_game_audit_round_rows(tx, &round.to_audit_record(replay))?;
+        }
+        for round in &replay.stella_rounds {
+            Self::write_stella_audit_round_rows(tx, &round.to_audit_record(replay))?;
+        }
+        Ok(())
+    }
+
+    pub fn search_clues(&self, query: &ClueSearchQuery) -> Result<ClueArchiveResponse> {
+        let conn = self.read_conn()?;
+        let match_query = query
+            .text
+            .as_deref()
+            .and_then(Self::clue_search_match_query);
+        if query.text.is_some() && match_query.is_none() {
+            return Ok(ClueArchiveResponse { clues: Vec::new() });
+        }
+        let limit = if query.limit == 0 {
+            DEFAULT_CLUE_SEARCH_LIMIT
+        } else {
+            query.limit.min(MAX_CLUE_SEARCH_LIMIT)
+        };
+
+        let (search_join, search_filter, search_order) = if match_query.is_some() {
+            (
+                "JOIN mb_clue_search ON mb_clue_search.game_id = r.game_id AND mb_clue_search.round_num = r.round_num",
+                "AND mb_clue_search MATCH ?5",
+                "mb_clue_search.rank,",
+            )
+        } else {
+            ("", "AND ?5 IS NULL", "")
+        };
+        let sql = format!(
+            r#"
+            SELECT
+                r.game_id,
+                g.room_id,
+                r.round_num,
+                r.recorded_at,
+                r.storyteller_hash,
+                r.storyteller_display_name,
+                r.clue,
+                (
+                    SELECT c.card_hash
+                    FROM mb_game_round_cards c
+                    WHERE c.game_id = r.game_id
+                        AND c.round_num = r.round_num
+                        AND c.is_storyteller_card = 1
+                    LIMIT 1
+                ),
+                (
+                    SELECT COUNT(DISTINCT v.voter_hash)
+                    FROM mb_game_round_story_votes v
+                    JOIN mb_game_round_cards c
+                        ON c.game_id = v.game_id
+                        AND c.round_num = v.round_num
+                        AND c.card_hash = v.card_hash
+                    WHERE v.game_id = r.game_id
+                        AND v.round_num = r.round_num
+                        AND c.is_storyteller_card = 1
+                ),
+                (
+                    SELECT COUNT(DISTINCT v.voter_hash)
+                    FROM mb_game_round_story_votes v
+                    WHERE v.game_id = r.game_id AND v.round_num = r.round_num
+                ),
+                cr.rating_count,
+                cr.rating_sum,
+                cr.max_stars
+            FROM mb_game_rounds r
+            JOIN mb_games g ON g.game_id = r.game_id
+            LEFT JOIN mb_game_round_clue_ratings cr
+                ON cr.game_id = r.game_id AND cr.round_num = r.round_num
+            {search_join}
+            WHERE (?1 IS NULL OR g.room_id = ?1)
+                AND (?2 IS NULL OR r.storyteller_hash = ?2)
+                AND (?3 IS NULL OR r.recorded_at >= ?3)
+                AND (?4 IS NULL OR r.recorded_at <= ?4)
+                {search_filter}
+            ORDER BY {search_order} r.recorded_at DESC, r.game_id, r.round_num
+            LIMIT ?6
+            "#
+        );
+
+        let mut stmt = conn
+            .prepare(&sql)
+            .context("Failed to prepare clue archive query")?;
+        let rows = stmt
+            .query_map(
+                params![
+                    query.room_id,
+                    query.storyteller_hash,
+                    query.since_s,
+                    query.until_s,
+                    match_query,
+                    u64::try_from(limit).unwrap_or(u64::MAX)
+                ],
+                |row| {
+                    let rating_count = row.get::<_, Option<u16>>(10)?.unwrap_or(0);
+                    let rating_sum = row.get::<_, Option<u32>>(11)?.unwrap_or(0);
+                    Ok(ClueArchiveEntry {
+                        game_id: row.get(0)?,
+                        room_id: row.get(1)?,
+                        round_num: row.get(2)?,
+                        recorded_at_s: row.get(3)?,
+                        storyteller_hash: row.get(4)?,
+                        storyteller_display_name: row.get(5)?,
+                        clue: row.get(6)?,
+                        storyteller_card_hash: row.get(7)?,
+                        correct_guessers: row.get(8)?,
+                        guessers: row.get(9)?,
+                        clue_rating_average: (rating_count > 0)
+                            .then(|| f64::from(rating_sum) / f64::from(rating_count)),
+                        clue_rating_count: rating_count,
+                        clue_rating_max_stars: row.get(12)?,
+                    })
+                },
+            )
+            .context("Failed to query clue archive")?;
+        let clues = rows
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to decode clue archive row")?;
+
+        Ok(ClueArchiveResponse { clues })
+    }
+
+    // Quote every word so user input can never be parsed as FTS5 query syntax.
+    fn clue_search_match_query(text: &str) -> Option<String> {
+        let terms = text
+            .split_whitespace()
+            .map(|word| format!("\"{}\"", word.replace('"', "\"\"")))
+            .collect::<Vec<_>>();
+        if terms.is_empty() {
+            None
+        } else {
+            Some(terms.join(" "))
+        }
+    }
+
+    #[cfg(test)]
+    pub fn aggregated_stats(&self) -> Result<MostBeautifulStatsResponse> {
+        let conn = self.read_conn()?;
+        let mut players = Self::load_players(&conn)?;
+        Self::load_vote_totals(&conn, &mut players)?;
+        Self::load_round_wins(&conn, &mut players)?;
+
+        let mut player_entries = players.into_values().collect::<Vec<_>>();
+        player_entries.sort_by(|a, b| {
+            b.rounds_won
+                .cmp(&a.rounds_won)
+                .then_with(|| b.votes_received.cmp(&a.votes_received))
+                .then_with(|| {
+                    a.display_name
+                        .to_lowercase()
+                        .cmp(&b.display_name.to_lowercase())
+                })
+                .then_with(|| a.player_hash.cmp(&b.player_hash))
+        });
+        for player in &mut player_entries {
+            player.voters.sort_by(|a, b| {
+                b.votes
+                    .cmp(&a.votes)
+                    .then_with(|| {
+                        a.display_name
+                            .to_lowercase()
+                            .cmp(&b.display_name.to_lowercase())
+                    })
+                    .then_with(|| a.player_hash.cmp(&b.player_hash))
+            });
+        }
+
+        Ok(MostBeautifulStatsResponse {
+            players: player_entries,
+        })
+    }
+
+    pub fn filtered_stats(
+        &self,
+        room_id: Option<&str>,
+        game_limit: usize,
+    ) -> Result<MostBeautifulStatsResponse> {
+        let conn = self.read_conn()?;
+        let game_ids = Self::game_ids_for_stats(&conn, GAME_MODE_DIXIT, room_id, game_limit)?;
+        if game_ids.is_empty() {
+            return Ok(MostBeautifulStatsResponse {
+                players: Vec::new(),
+            });
+        }
+
+        let mut players = HashMap::new();
+        Self::load_filtered_vote_totals(&conn, &game_ids, &mut players)?;
+        Self::load_filtered_round_wins(&conn, &game_ids, &mut players)?;
+
+        let mut player_entries = players
+            .into_values()
+            .filter(|player| player.votes_received > 0 || player.rounds_won > 0)
+            .collect::<Vec<_>>();
+        player_entries.sort_by(|a, b| {
+            b.rounds_won
+                .cmp(&a.rounds_won)
+                .then_with(|| b.votes_received.cmp(&a.votes_received))
+                .then_with(|| {
+                    a.display_name
+                        .to_lowercase()
+                        .cmp(&b.display_name.to_lowercase())
+                })
+                .then_with(|| a.player_hash.cmp(&b.player_hash))
+        });
+        player_entries.truncate(30);
+        for player in &mut player_entries {
+            player.voters.sort_by(|a, b| {
+                b.votes
+                    .cmp(&a.votes)
+                    .then_with(|| {
+                        a.display_name
+                            .to_lowercase()
+                            .cmp(&b.display_name.to_lowercase())
+                    })
+                    .then_with(|| a.player_hash.cmp(&b.player_hash))
+            });
+        }
+
+        Ok(MostBeautifulStatsResponse {
+            players: player_entries,
+        })
+    }
+
+    fn game_ids_for_stats(
+        conn: &Connection,
+        game_mode: &str,
+        room_id: Option<&str>,
+        game_limit: usize,
+    ) -> Result<Vec<String>> {
+        let mut game_ids = Vec::new();
+
+        if let Some(room_id) = room_id {
+            let current_room_game = conn
+                .query_row(
+                    r#"
+                    SELECT game_id
+                    FROM mb_games
+                    WHERE room_id = ?1 AND completed = 0 AND missing_writes = 0 AND game_mode = ?2
+                    ORDER BY started_at DESC, game_id DESC
+                    LIMIT 1
+                    "#,
+                    params![room_id, game_mode],
+                    |row| row.get::<_, String>(0),
+                )
+                .optional()
+                .context("Failed to load current-room Most Beautiful game")?;
+            if let Some(game_id) = current_room_game {
+                game_ids.push(game_id);
+            }
+        }
+
+        let completed_limit = if game_limit == 0 {
+            None
+        } else {
+            Some(game_limit.saturating_sub(game_ids.len()))
+        };
+
+        if completed_limit != Some(0) {
+            let mut completed_query = r#"
+                SELECT game_id
+                FROM mb_games
+                WHERE completed = 1 AND game_mode = ?1
+                ORDER BY COALESCE(ended_at, started_at) DESC, started_at DESC, game_id DESC
+            "#
+            .to_string();
+            let completed_ids = if let Some(limit) = completed_limit {
+                completed_query.push_str(" LIMIT ?2");
+                let mut stmt = conn
+                    .prepare(&completed_query)
+                    .context("Failed to prepare filtered Most Beautiful completed-games query")?;
+                let rows = stmt
+                    .query_map(
+                        params![game_mode, u64::try_from(limit).unwrap_or(u64::MAX)],
+                        |row| row.get::<_, String>(0),
+                    )
+                    .context("Failed to query filtered Most Beautiful completed games")?;
+                rows.collect::<rusqlite::Result<Vec<_>>>()
+                    .context("Failed to decode filtered Most Beautiful completed game row")?
+            } else {
+                let mut stmt = conn.prepare(&completed_query).context(
+                    "Failed to prepare all-history Most Beautiful completed-games query",
+                )?;
+                let rows = stmt
+                    .query_map(params![game_mode], |row| row.get::<_, String>(0))
+                    .context("Failed to query all-history Most Beautiful completed games")?;
+                rows.collect::<rusqlite::Result<Vec<_>>>()
+                    .context("Failed to decode all-history Most Beautiful completed game row")?
+            };
+            game_ids.extend(completed_ids);
+        }
+
+        Ok(game_ids)
+    }
+
+    // keyed by (game_id, round_num)
+    fn load_round_activity(
+        conn: &Connection,
+        game_ids: &[String],
+    ) -> Result<BTreeMap<(String, u16), AuditRoundActivity>> {
+        let mut rounds = BTreeMap::new();
+        if game_ids.is_empty() {
+            return Ok(rounds);
+        }
+
+        let placeholders = std::iter::repeat("?")
+            .take(game_ids.len())
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let mut round_stmt = conn
+            .prepare(&format!(
+                r#"
+                SELECT
+                    r.game_id,
+                    r.round_num,
+                    r.storyteller_hash,
+                    cr.rating_count,
+                    cr.rating_sum,
+                    cr.max_stars
+                FROM mb_game_rounds r
+                LEFT JOIN mb_game_round_clue_ratings cr
+                    ON cr.game_id = r.game_id AND cr.round_num = r.round_num
+                WHERE r.game_id IN ({placeholders})
+                "#
+            ))
+            .context("Failed to prepare round activity query")?;
+        let round_rows = round_stmt
+            .query_map(rusqlite::params_from_iter(game_ids.iter()), |row| {
+                let clue_rating = match (
+                    row.get::<_, Option<u16>>(3)?,
+                    row.get::<_, Option<u32>>(4)?,
+                    row.get::<_, Option<u16>>(5)?,
+                ) {
+                    (Some(rating_count), Some(rating_sum), Some(max_stars)) => {
+                        Some(MostBeautifulGameAuditClueRatingRecord {
+                            rating_count,
+                            rating_sum,
+                            max_stars,
+                        })
+                    }
+                    _ => None,
+                };
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, u16>(1)?,
+                    AuditRoundActivity {
+                        storyteller_hash: row.get(2)?,
+                        clue_rating,
+                        ..Default::default()
+                    },
+                ))
+            })
+            .context("Failed to query round activity")?;
+        for row in round_rows {
+            let (game_id, round_num, round) = row.context("Failed to decode round activity row")?;
+            rounds.insert((game_id, round_num), round);
+        }
+
+        let mut card_stmt = conn
+            .prepare(&format!(
+                r#"
+                SELECT
+                    game_id,
+                    round_num,
+                    card_hash,
+                    owner_hash,
+                    owner_display_name,
+                    submitted_by_hash,
+                    submitted_by_display_name,
+                    is_storyteller_card,
+                    center_order
+                FROM mb_game_round_cards
+                WHERE game_id IN ({placeholders})
+                ORDER BY game_id, round_num, center_order
+                "#
+            ))
+            .context("Failed to prepare round activity cards query")?;
+        let card_rows = card_stmt
+            .query_map(rusqlite::params_from_iter(game_ids.iter()), |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, u16>(1)?,
+                    MostBeautifulGameAuditCardRecord {
+                        card_hash: row.get(2)?,
+                        owner_hash: row.get(3)?,
+                        owner_display_name: row.get(4)?,
+                        submitted_by_hash: row.get(5)?,
+                        submitted_by_display_name: row.get(6)?,
+                        is_storyteller_card: row.get(7)?,
+                        center_order: row.get(8)?,
+                    },
+                ))
+            })
+            .context("Failed to query round activity cards")?;
+        for row in card_rows {
+            let (game_id, round_num, card) =
+                row.context("Failed to decode round activity card row")?;
+            if let Some(round) = rounds.get_mut(&(game_id, round_num)) {
+                round.cards.push(card);
+            }
+        }
+
+        for (table_name, is_story) in [
+            ("mb_game_round_story_votes", true),
+            ("mb_game_round_beauty_votes", false),
+        ] {
+            let mut vote_stmt = conn
+                .prepare(&format!(
+                    "SELECT game_id, round_num, voter_hash, voter_display_name, card_hash, vote_count FROM {table_name} WHERE game_id IN ({placeholders})"
+                ))
+                .with_context(|| {
+                    format!("Failed to prepare round activity votes query for {table_name}")
+                })?;
+            let vote_rows = vote_stmt
+                .query_map(rusqlite::params_from_iter(game_ids.iter()), |row| {
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        row.get::<_, u16>(1)?,
+                        MostBeautifulGameAuditVoteRecord {
+                            voter_hash: row.get(2)?,
+                            voter_display_name: row.get(3)?,
+                            card_hash: row.get(4)?,
+                            vote_count: row.get(5)?,
+                        },
+                    ))
+                })
+                .with_context(|| {
+                    format!("Failed to query round activity votes from {table_name}")
+                })?;
+            for row in vote_rows {
+                let (game_id, round_num, vote) = row.with_context(|| {
+                    format!("Failed to decode round activity vote row from {table_name}")
+                })?;
+                if let Some(round) = rounds.get_mut(&(game_id, round_num)) {
+                    if is_story {
+                        round.story_votes.push(vote);
+                    } else {
+                        round.beauty_votes.push(vote);
+                    }
+                }
+            }
+        }
+
+        let mut score_stmt = conn
+            .prepare(&format!(
+                r#"
+                SELECT
+                    game_id,
+                    round_num,
+                    player_hash,
+                    player_display_name,
+                    story_delta,
+                    beauty_delta,
+                    total_after_round,
+                    beauty_total_after_round
+                FROM mb_game_round_scores
+                WHERE game_id IN ({placeholders})
+                "#
+            ))
+            .context("Failed to prepare round activity scores query")?;
+        let score_rows = score_stmt
+            .query_map(rusqlite::params_from_iter(game_ids.iter()), |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, u16>(1)?,
+                    MostBeautifulGameAuditScoreRecord {
+                        player_hash: row.get(2)?,
+                        player_display_name: row.get(3)?,
+                        story_delta: row.get(4)?,
+                        beauty_delta: row.get(5)?,
+                        total_after_round: row.get(6)?,
+                        beauty_total_after_round: row.get(7)?,
+                    },
+                ))
+            })
+            .context("Failed to query round activity scores")?;
+        for row in score_rows {
+            let (game_id, round_num, score) =
+                row.context("Failed to decode round activity score row")?;
+            if let Some(round) = rounds.get_mut(&(game_id, round_num)) {
+                round.scores.push(score);
+            }
+        }
+
+        Ok(rounds)
+    }
+
+    pub fn card_stats(
+        &self,
+        room_id: Option<&str>,
+        game_limit: usize,
+        include_unplayed: bool,
+    ) -> Result<CardStatsResponse> {
+        let conn = self.read_conn()?;
+        let game_ids = Self::game_ids_for_stats(&conn, GAME_MODE_DIXIT, room_id, game_limit)?;
+        let rounds = Self::load_round_activity(&conn, &game_ids)?;
+
+        let mut cards: HashMap<String, CardStats> = HashMap::new();
+        let new_card_stats = |card_hash: &str| CardStats {
+            card_hash: card_hash.to_string(),
+            card_paths: Vec::new(),
+            appearances: 0,
+            storyteller_picks: 0,
+            storyteller_successes: 0,
+            storyteller_all_guessed: 0,
+            storyteller_none_guessed: 0,
+            storyteller_success_rate: None,
+            decoy_appearances: 0,
+            decoy_votes: 0,
+            beauty_votes: 0,
+        };
+
+        for round in rounds.values() {
+            let guessers = round.guessers().len();
+            let correct_guessers = round.correct_guessers().len();
+            for card in &round.cards {
+                let stats = cards
+                    .entry(card.card_hash.clone())
+                    .or_insert_with(|| new_card_stats(&card.card_hash));
+                stats.appearances += 1;
+                stats.beauty_votes +=
+                    AuditRoundActivity::votes_for_card(&round.beauty_votes, &card.card_hash);
+                if card.is_storyteller_card {
+                    stats.storyteller_picks += 1;
+                    if guessers == 0 {
+                        // nobody voted, so the round says nothing about the card
+                    } else if correct_guessers == 0 {
+                        stats.storyteller_none_guessed += 1;
+                    } else if correct_guessers == guessers {
+                        stats.storyteller_all_guessed += 1;
+                    } else {
+                        stats.storyteller_successes += 1;
+                    }
+                } else {
+                    stats.decoy_appearances += 1;
+                    stats.decoy_votes +=
+                        AuditRoundActivity::votes_for_card(&round.story_votes, &card.card_hash);
+                }
+            }
+        }
+
+        let mut path_stmt = conn
+            .prepare("SELECT card_hash, card_path FROM mb_card_paths ORDER BY card_path")
+            .context("Failed to prepare card paths query")?;
+        let path_rows = path_stmt
+            .query_map([], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+            })
+            .context("Failed to query card paths")?;
+        for row in path_rows {
+            let (card_hash, card_path) = row.context("Failed to decode card path row")?;
+            if include_unplayed {
+                cards
+                    .entry(card_hash.clone())
+                    .or_insert_with(|| new_card_stats(&card_hash));
+            }
+            if let Some(stats) = cards.get_mut(&card_hash) {
+                stats.card_paths.push(card_path);
+            }
+        }
+
+        let mut card_entries = cards.into_values().collect::<Vec<_>>();
+        for stats in &mut card_entries {
+            if stats.storyteller_picks > 0 {
+                stats.storyteller_success_rate =
+                    Some(stats.storyteller_successes as f64 / stats.storyteller_picks as f64);
+            }
+        }
+        card_entries.sort_by(|a, b| {
+            b.appearances
+                .cmp(&a.appearances)
+                .then_with(|| a.card_hash.cmp(&b.card_hash))
+        });
+
+        Ok(CardStatsResponse {
+            cards: card_entries,
+        })
+    }
+
+    fn head_to_head_pair<'a>(
+        pairs: &'a mut BTreeMap<(String, String), HeadToHeadPair>,
+        guesser_hash: &str,
+        target_hash: &str,
+    ) -> &'a mut HeadToHeadPair {
+        pairs
+            .entry((guesser_hash.to_string(), target_hash.to_string()))
+            .or_insert_with(|| HeadToHeadPair {
+                guesser_hash: guesser_hash.to_string(),
+                target_hash: target_hash.to_string(),
+                storyteller_rounds: 0,
+                correct_guesses: 0,
+                correct_guess_rate: None,
+                decoy_rounds: 0,
+                times_fooled: 0,
+                fooled_rate: None,
+                beauty_votes_given: 0,
+                beauty_affinity: None,
+            })
+    }
+
+    pub fn head_to_head(
+        &self,
+        room_id: Option<&str>,
+        game_limit: usize,
+    ) -> Result<HeadToHeadResponse> {
+        let conn = self.read_conn()?;
+        let game_ids = Self::game_ids_for_stats(&conn, GAME_MODE_DIXIT, room_id, game_limit)?;
+        let rounds = Self::load_round_activity(&conn, &game_ids)?;
+
+        let mut pairs = BTreeMap::new();
+        let mut beauty_votes_cast: HashMap<String, u64> = HashMap::new();
+        let mut fallback_names: HashMap<String, String> = HashMap::new();
+
+        for round in rounds.values() {
+            for card in &round.cards {
+                fallback_names
+                    .entry(card.owner_hash.clone())
+                    .or_insert_with(|| card.owner_display_name.clone());
+            }
+            for vote in round.story_votes.iter().chain(&round.beauty_votes) {
+                fallback_names
+                    .entry(vote.voter_hash.clone())
+                    .or_insert_with(|| vote.voter_display_name.clone());
+            }
+
+            let correct = round.correct_guessers();
+            for guesser_hash in round.guessers() {
+                if guesser_hash != round.storyteller_hash {
+                    let pair =
+                        Self::head_to_head_pair(&mut pairs, guesser_hash, &round.storyteller_hash);
+                    pair.storyteller_rounds += 1;
+                    if correct.contains(guesser_hash) {
+                        pair.correct_guesses += 1;
+                    }
+                }
+
+                let decoy_owners = round
+                    .cards
+                    .iter()
+                    .filter(|card| !card.is_storyteller_card && card.owner_hash != guesser_hash)
+                    .map(|card| card.owner_hash.as_str())
+                    .collect::<HashSet<_>>();
+                for owner_hash in decoy_owners {
+                    let fooled = round.story_votes.iter().any(|vote| {
+                        vote.voter_hash == guesser_hash
+                            && round.cards.iter().any(|card| {
+                                card.card_hash == vote.card_hash
+                                    && card.owner_hash == owner_hash
+                                    && !card.is_storyteller_card
+                            })
+                    });
+                    let pair = Self::head_to_head_pair(&mut pairs, guesser_hash, owner_hash);
+                    pair.decoy_rounds += 1;
+                    if fooled {
+                        pair.times_fooled += 1;
+                    }
+                }
+            }
+
+            for vote in &round.beauty_votes {
+                let vote_count = u64::from(vote.vote_count);
+                *beauty_votes_cast
+                    .entry(vote.voter_hash.clone())
+                    .or_default() += vote_count;
+                let Some(card) = round
+                    .cards
+                    .iter()
+                    .find(|card| card.card_hash == vote.card_hash)
+                else {
+                    continue;
+                };
+                if card.owner_hash != vote.voter_hash {
+                    Self::head_to_head_pair(&mut pairs, &vote.voter_hash, &card.owner_hash)
+                        .beauty_votes_given += vote_count;
+                }
+            }
+        }
+
+        let ratio = |numerator: u64, denominator: u64| {
+            (denominator > 0).then(|| numerator as f64 / denominator as f64)
+        };
+        let mut player_hashes = HashSet::new();
+        let pairs = pairs
+            .into_values()
+            .map(|mut pair| {
+                pair.correct_guess_rate = ratio(pair.correct_guesses, pair.storyteller_rounds);
+                pair.fooled_rate = ratio(pair.times_fooled, pair.decoy_rounds);
+                pair.beauty_affinity = ratio(
+                    pair.beauty_votes_given,
+                    beauty_votes_cast
+                        .get(&pair.guesser_hash)
+                        .copied()
+                        .unwrap_or(0),
+                );
+                player_hashes.insert(pair.guesser_hash.clone());
+                player_hashes.insert(pair.target_hash.clone());
+                pair
+            })
+            .collect::<Vec<_>>();
+
+        let mut players = Vec::with_capacity(player_hashes.len());
+        for player_hash in player_hashes {
+            let fallback = fallback_names
+                .get(&player_hash)
+                .cloned()
+                .unwrap_or_else(|| player_hash.clone());
+            players.push(HeadToHeadPlayer {
+                display_name: Self::player_name(&conn, &player_hash, &fallback)?,
+                player_hash,
+            });
+        }
+        players.sort_by(|a, b| {
+            a.display_name
+                .cmp(&b.display_name)
+                .then_with(|| a.player_hash.cmp(&b.player_hash))
+        });
+
+        Ok(HeadToHeadResponse { players, pairs })
+    }
+
+    fn completed_game_ids(conn: &Connection, game_ids: &[String]) -> Result<HashSet<String>> {
+        if game_ids.is_empty() {
+            return Ok(HashSet::new());
+        }
+        let placeholders = std::iter::repeat("?")
+            .take(game_ids.len())
+            .collect::<Vec<_>>()
+            .join(", ");
+        let mut stmt = conn
+            .prepare(&format!(
+                "SELECT game_id FROM mb_games WHERE completed = 1 AND game_id IN ({placeholders})"
+            ))
+            .context("Failed to prepare completed games query")?;
+        let rows = stmt
+            .query_map(rusqlite::params_from_iter(game_ids.iter()), |row| {
+                row.get::<_, String>(0)
+            })
+            .context("Failed to query completed games")?;
+        rows.collect::<rusqlite::Result<HashSet<_>>>()
+            .context("Failed to decode completed game row")
+    }
+
+    fn player_aliases(conn: &Connection, player_hash: &str) -> Result<Vec<PlayerAlias>> {
+        let mut stmt = conn
+            .prepare(
+                r#"
+                SELECT display_name, first_seen_at, last_seen_at
+                FROM mb_player_names
+                WHERE player_hash = ?1
+                ORDER BY last_seen_at DESC, display_name
+                "#,
+            )
+            .context("Failed to prepare player aliases query")?;
+        let rows = stmt
+            .query_map(params![player_hash], |row| {
+                Ok(PlayerAlias {
+                    display_name: row.get(0)?,
+                    first_seen_at_s: row.get(1)?,
+                    last_seen_at_s: row.get(2)?,
+                })
+            })
+            .context("Failed to query player aliases")?;
+        rows.collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to decode player alias row")
+    }
+
+    // None when the hash has never been seen in the stats database
+    pub fn player_profile_stats(
+        &self,
+        player_hash: &str,
+        room_id: Option<&str>,
+        game_limit: usize,
+    ) -> Result<Option<PlayerProfileStats>> {
+        let conn = self.read_conn()?;
+        let aliases = Self::player_aliases(&conn, player_hash)?;
+        let game_ids = Self::game_ids_for_stats(&conn, GAME_MODE_DIXIT, room_id, game_limit)?;
+        let completed_games = Self::completed_game_ids(&conn, &game_ids)?;
+        let rounds = Self::load_round_activity(&conn, &game_ids)?;
+        let stella_game_ids =
+            Self::game_ids_for_stats(&conn, GAME_MODE_STELLA, room_id, game_limit)?;
+        let stella = Self::player_stella_stats(&conn, player_hash, &stella_game_ids)?;
+
+        let mut games_played = HashSet::new();
+        // game_id -> player_hash -> (round_num, total) from each player's last scored round
+        let mut final_totals: HashMap<&str, HashMap<&str, (u16, u16)>> = HashMap::new();
+        let mut fallback_name = None;
+        let mut rounds_played = 0;
+        let mut storyteller_rounds = 0;
+        let mut storyteller_successes = 0;
+        let mut guessing_rounds = 0;
+        let mut correct_guesses = 0;
+        let mut decoy_cards = 0;
+        let mut decoys_that_fooled = 0;
+        let mut decoy_votes_attracted = 0;
+        let mut clue_ratings_received = 0;
+        let mut clue_rating_sum = 0;
+        let mut favourite_voters: HashMap<String, PlayerFavouriteVoter> = HashMap::new();
+
+        for ((game_id, round_num), round) in &rounds {
+            for score in &round.scores {
+                let totals = final_totals.entry(game_id.as_str()).or_default();
+                let entry = totals
+                    .entry(score.player_hash.as_str())
+                    .or_insert((*round_num, score.total_after_round));
+                if *round_num >= entry.0 {
+                    *entry = (*round_num, score.total_after_round);
+                }
+                if score.player_hash == player_hash {
+                    fallback_name.get_or_insert_with(|| score.player_display_name.clone());
+                }
+            }
+
+            let played_round = round
+                .scores
+                .iter()
+                .any(|score| score.player_hash == player_hash)
+                || round.storyteller_hash == player_hash
+                || round
+                    .cards
+                    .iter()
+                    .any(|card| card.owner_hash == player_hash)
+                || round
+                    .story_votes
+                    .iter()
+                    .any(|vote| vote.voter_hash == player_hash);
+            if !played_round {
+                continue;
+            }
+            games_played.insert(game_id.as_str());
+            rounds_played += 1;
+
+            let guessers = round.guessers();
+            let correct = round.correct_guessers();
+            if round.storyteller_hash == player_hash {
+                storyteller_rounds += 1;
+                if !correct.is_empty() && correct.len() < guessers.len() {
+                    storyteller_successes += 1;
+                }
+                if let Some(rating) = &round.clue_rating {
+                    clue_ratings_received += u64::from(rating.rating_count);
+                    clue_rating_sum += u64::from(rating.rating_sum);
+                }
+                // a voter with several tokens in one round still counts once
+                let mut counted_voters = HashSet::new();
+                for vote in &round.story_votes {
+                    if !counted_voters.insert(vote.voter_hash.as_str()) {
+                        continue;
+                    }
+                    let voter = favourite_voters
+                        .entry(vote.voter_hash.clone())
+                        .or_insert_with(|| PlayerFavouriteVoter {
+                            player_hash: vote.voter_hash.clone(),
+                            display_name: vote.voter_display_name.clone(),
+                            correct_guesses: 0,
+                            guesses: 0,
+                        });
+                    voter.guesses += 1;
+                    if correct.contains(vote.voter_hash.as_str()) {
+                        voter.correct_guesses += 1;
+                    }
+                }
+                continue;
+            }
+
+            if guessers.contains(player_hash) {
+                guessing_rounds += 1;
+                if correct.contains(player_hash) {
+                    correct_guesses += 1;
+                }
+            }
+            for card in round
+                .cards
+                .iter()
+                .filter(|card| card.owner_hash == player_hash && !card.is_storyteller_card)
+            {
+                decoy_cards += 1;
+                let votes = AuditRoundActivity::votes_for_card(&round.story_votes, &card.card_hash);
+                decoy_votes_attracted += votes;
+                if votes > 0 {
+                    decoys_that_fooled += 1;
+                }
+            }
+        }
+
+        if aliases.is_empty() && games_played.is_empty() && stella.games_played == 0 {
+            return Ok(None);
+        }
+
+        let games_won = games_played
+            .iter()
+            .filter(|game_id| completed_games.contains(**game_id))
+            .filter(|game_id| {
+                let Some(totals) = final_totals.get(**game_id) else {
+                    return false;
+                };
+                let best = totals.values().map(|(_, total)| *total).max();
+                best.is_some() && totals.get(player_hash).map(|(_, total)| *total) == best
+            })
+            .count() as u64;
+        let games_completed = games_played
+            .iter()
+            .filter(|game_id| completed_games.contains(**game_id))
+            .count() as u64;
+
+        let mut favourite_voters = favourite_voters.into_values().collect::<Vec<_>>();
+        for voter in &mut favourite_voters {
+            voter.display_name = Self::player_name(&conn, &voter.player_hash, &voter.display_name)?;
+        }
+        favourite_voters.sort_by(|a, b| {
+            b.correct_guesses
+                .cmp(&a.correct_guesses)
+                .then_with(|| a.guesses.cmp(&b.guesses))
+                .then_with(|| a.display_name.cmp(&b.display_name))
+        });
+        favourite_voters.truncate(MAX_PLAYER_FAVOURITE_VOTERS);
+
+        let ratio = |numerator: u64, denominator: u64| {
+            (denominator > 0).then(|| numerator as f64 / denominator as f64)
+        };
+        let fallback_name = fallback_name
+            .or_else(|| aliases.first().map(|alias| alias.display_name.clone()))
+            .unwrap_or_else(|| player_hash.to_string());
+
+        Ok(Some(PlayerProfileStats {
+            player_hash: player_hash.to_string(),
+            display_name: Self::player_name(&conn, player_hash, &fallback_name)?,
+            aliases,
+            games_played: games_played.len() as u64,
+            games_completed,
+            games_won,
+            rounds_played,
+            storyteller_rounds,
+            storyteller_successes,
+            storyteller_success_rate: ratio(storyteller_successes, storyteller_rounds),
+            guessing_rounds,
+            correct_guesses,
+            guessing_accuracy: ratio(correct_guesses, guessing_rounds),
+            decoy_cards,
+            decoys_that_fooled,
+            decoy_votes_attracted,
+            decoy_effectiveness: ratio(decoys_that_fooled, decoy_cards),
+            clue_ratings_received,
+            clue_rating_average: ratio(clue_rating_sum, clue_ratings_received),
+            favourite_voters,
+            stella,
+        }))
+    }
+
+    fn player_stella_stats(
+        conn: &Connection,
+        player_hash: &str,
+        game_ids: &[String],
+    ) -> Result<PlayerStellaStats> {
+        let mut stats = PlayerStellaStats::default();
+        if game_ids.is_empty() {
+            return Ok(stats);
+        }
+        let completed_games = Self::completed_game_ids(conn, game_ids)?;
+        let placeholders = std::iter::repeat("?")
+            .take(game_ids.len())
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let mut player_stmt = conn
+            .prepare(&format!(
+                r#"
+                SELECT game_id, round_num, player_hash, in_the_dark, fell, score_delta,
+                    total_after_round
+                FROM mb_stella_round_players
+                WHERE game_id IN ({placeholders})
+                "#
+            ))
+            .context("Failed to prepare Stella player stats query")?;
+        let rows = player_stmt
+            .query_map(rusqlite::params_from_iter(game_ids.iter()), |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, u16>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, bool>(3)?,
+                    row.get::<_, bool>(4)?,
+                    row.get::<_, u16>(5)?,
+                    row.get::<_, u16>(6)?,
+                ))
+            })
+            .context("Failed to query Stella player stats")?
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to decode Stella player stats row")?;
+
+        let mut games_played = HashSet::new();
+        // game_id -> player_hash -> (round_num, total) from each player's last round
+        let mut final_totals: HashMap<&str, HashMap<&str, (u16, u16)>> = HashMap::new();
+        for (game_id, round_num, hash, in_the_dark, fell, score_delta, total) in &rows {
+            let entry = final_totals
+                .entry(game_id.as_str())
+                .or_default()
+                .entry(hash.as_str())
+                .or_insert((*round_num, *total));
+            if *round_num >= entry.0 {
+                *entry = (*round_num, *total);
+            }
+            if hash != player_hash {
+                continue;
+            }
+            games_played.insert(game_id.as_str());
+            stats.rounds_played += 1;
+            stats.rounds_in_the_dark += u64::from(*in_the_dark);
+            stats.falls += u64::from(*fell);
+            stats.points += u64::from(*score_delta);
+        }
+        for game_id in &games_played {
+            if !completed_games.contains(*game_id) {
+                continue;
+            }
+            stats.games_completed += 1;
+            let Some(totals) = final_totals.get(game_id) else {
+                continue;
+            };
+            let best = totals.values().map(|(_, total)| *total).max();
+            if best.is_some() && totals.get(player_hash).map(|(_, total)| *total) == best {
+                stats.games_won += 1;
+            }
+        }
+        stats.games_played = games_played.len() as u64;
+
+        let (cards_selected, cards_scored) = conn
+            .query_row(
+                &format!(
+                    r#"
+                    SELECT COUNT(*), COALESCE(SUM(c.points > 0), 0)
+                    FROM mb_stella_round_selections s
+                    LEFT JOIN mb_stella_round_board_cards c
+                        ON c.game_id = s.game_id
+                        AND c.round_num = s.round_num
+                        AND c.card_hash = s.card_hash
+                    WHERE s.player_hash = ? AND s.game_id IN ({placeholders})
+                    "#
+                ),
+                rusqlite::params_from_iter(
+                    std::iter::once(player_hash).chain(game_ids.iter().map(String::as_str)),
+                ),
+                |row| Ok((row.get::<_, u64>(0)?, row.get::<_, u64>(1)?)),
+            )
+            .context("Failed to query Stella selection stats")?;
+        stats.cards_selected = cards_selected;
+        stats.cards_scored = cards_scored;
+        Ok(stats)
+    }
+
+    fn load_filtered_vote_totals(
+        conn: &Connection,
+        game_ids: &[String],
+        players: &mut HashMap<String, MostBeautifulPlayerStats>,
+    ) -> Result<()> {
+        if game_ids.is_empty() {
+            return Ok(());
+        }
+
+        let placeholders = std::iter::repeat("?")
+            .take(game_ids.len())
+            .collect::<Vec<_>>()
+            .join(", ");
+        let query = format!(
+            r#"
+            SELECT
+                cards.owner_hash,
+                MAX(cards.owner_display_name),
+                votes.voter_hash,
+                MAX(votes.voter_display_name),
+                SUM(votes.vote_count)
+            FROM mb_game_round_beauty_votes AS votes
+            INNER JOIN mb_game_round_cards AS cards
+                ON cards.game_id = votes.game_id
+                AND cards.round_num = votes.round_num
+                AND cards.card_hash = votes.card_hash
+            WHERE votes.game_id IN ({placeholders})
+            GROUP BY cards.owner_hash, votes.voter_hash
+            "#
+        );
+        let mut stmt = conn
+            .prepare(&query)
+            .context("Failed to prepare filtered Most Beautiful vote totals query")?;
+        let rows = stmt
+            .query_map(rusqlite::params_from_iter(game_ids.iter()), |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, String>(3)?,
+                    row.get::<_, u64>(4)?,
+                ))
+            })
+            .context("Failed to query filtered Most Beautiful vote totals")?;
+        for row in rows {
+            let (owner_hash, owner_display_name, voter_hash, voter_display_name, votes_received) =
+                row.context("Failed to decode filtered Most Beautiful vote row")?;
+            let owner = players
+                .entry(owner_hash.clone())
+                .or_insert(MostBeautifulPlayerStats {
+                    player_hash: owner_hash.clone(),
+                    display_name: Self::player_name(conn, &owner_hash, &owner_display_name)?,
+                    votes_received: 0,
+                    rounds_won: 0,
+                    tie_round_wins: 0,
+                    decisive_round_wins: 0,
+                    voters: Vec::new(),
+                });
+            owner.votes_received += votes_received;
+            owner.voters.push(MostBeautifulVoterStats {
+                player_hash: voter_hash.clone(),
+                display_name: Self::player_name(conn, &voter_hash, &voter_display_name)?,
+                votes: votes_received,
+            });
+        }
+
+        Ok(())
+    }
+
+    fn load_filtered_round_wins(
+        conn: &Connection,
+        game_ids: &[String],
+        players: &mut HashMap<String, MostBeautifulPlayerStats>,
+    ) -> Result<()> {
+        if game_ids.is_empty() {
+            return Ok(());
+        }
+
+        let placeholders = std::iter::repeat("?")
+            .take(game_ids.len())
+            .collect::<Vec<_>>()
+            .join(", ");
+        let query = format!(
+            r#"
+            SELECT
+                votes.game_id,
+                votes.round_num,
+                cards.owner_hash,
+                MAX(cards.owner_display_name),
+                SUM(votes.vote_count)
+            FROM mb_game_round_beauty_votes AS votes
+            INNER JOIN mb_game_round_cards AS cards
+                ON cards.game_id = votes.game_id
+                AND cards.round_num = votes.round_num
+                AND cards.card_hash = votes.card_hash
+            WHERE votes.game_id IN ({placeholders})
+            GROUP BY votes.game_id, votes.round_num, cards.owner_hash
+            ORDER BY votes.game_id, votes.round_num
+            "#
+        );
+        let mut stmt = conn
+            .prepare(&query)
+            .context("Failed to prepare filtered Most Beautiful round-wins query")?;
+        let rows = stmt
+            .query_map(rusqlite::params_from_iter(game_ids.iter()), |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, u16>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, String>(3)?,
+                    row.get::<_, u64>(4)?,
+                ))
+            })
+            .context("Failed to query filtered Most Beautiful round wins")?;
+        let mut rounds = HashMap::<(String, u16), Vec<(String, String, u64)>>::new();
+        for row in rows {
+            let (game_id, round_num, owner_hash, owner_display_name, vote_total) =
+                row.context("Failed to decode filtered Most Beautiful round-win row")?;
+            rounds.entry((game_id, round_num)).or_default().push((
+                owner_hash,
+                owner_display_name,
+                vote_total,
+            ));
+        }
+
+        for winner_entries in rounds.into_values() {
+            let highest_vote_total = winner_entries
+                .iter()
+                .map(|(_, _, vote_total)| *vote_total)
+                .max()
+                .unwrap_or(0);
+            if highest_vote_total == 0 {
+                continue;
+            }
+            let winners = winner_entries
+                .into_iter()
+                .filter(|(_, _, vote_total)| *vote_total == highest_vote_total)
+                .collect::<Vec<_>>();
+            let is_tie = winners.len() > 1;
+            for (winner_hash, winner_display_name, _) in winners {
+                let player =
+                    players
+                        .entry(winner_hash.clone())
+                        .or_insert(MostBeautifulPlayerStats {
+                            player_hash: winner_hash.clone(),
+                            display_name: Self::player_name(
+                                conn,
+                                &winner_hash,
+                                &winner_display_name,
+                            )?,
+                            votes_received: 0,
+                            rounds_won: 0,
+                            tie_round_wins: 0,
+                            decisive_round_wins: 0,
+                            voters: Vec::new(),
+                        });
+                player.rounds_won += 1;
+                if is_tie {
+                    player.tie_round_wins += 1;
+                } else {
+                    player.decisive_round_wins += 1;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    #[cfg(test)]
+    fn load_players(conn: &Connection) -> Result<HashMap<String, MostBeautifulPlayerStats>> {
+        let mut stmt = conn
+            .prepare(
+                "SELECT player_hash, latest_display_name FROM mb_players ORDER BY latest_display_name, player_hash",
+            )
+            .context("Failed to prepare Most Beautiful players query")?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok(MostBeautifulPlayerStats {
+                    player_hash: row.get(0)?,
+                    display_name: row.get(1)?,
+                    votes_received: 0,
+                    rounds_won: 0,
+                    tie_round_wins: 0,
+                    decisive_round_wins: 0,
+                    voters: Vec::new(),
+                })
+            })
+            .context("Failed to query Most Beautiful players")?;
+
+        let mut players = HashMap::new();
+        for row in rows {
+            let player = row.context("Failed to decode Most Beautiful player row")?;
+            players.insert(player.player_hash.clone(), player);
+        }
+        Ok(players)
+    }
+
+    fn player_name(conn: &Connection, player_hash: &str, fallback: &str) -> Result<String> {
+        conn.query_row(
+            "SELECT latest_display_name FROM mb_players WHERE player_hash = ?1",
+            params![player_hash],
+            |row| row.get(0),
+        )
+        .optional()
+        .map(|name| name.unwrap_or_else(|| fallback.to_string()))
+        .context("Failed to load Most Beautiful player display name")
+    }
+
+    #[cfg(test)]
+    fn load_vote_totals(
+        conn: &Connection,
+        players: &mut HashMap<String, MostBeautifulPlayerStats>,
+    ) -> Result<()> {
+        let mut totals_stmt = conn
+            .prepare(
+                r#"
+                SELECT owner_hash, COALESCE(SUM(vote_count), 0)
+                FROM mb_vote_events
+                GROUP BY owner_hash
+                "#,
+            )
+            .context("Failed to prepare Most Beautiful vote totals query")?;
+        let vote_rows = totals_stmt
+            .query_map([], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, u64>(1)?))
+            })
+            .context("Failed to query Most Beautiful vote totals")?;
+        for row in vote_rows {
+            let (owner_hash, total_votes) =
+                row.context("Failed to decode Most Beautiful votes row")?;
+            players
+                .entry(owner_hash.clone())
+                .or_insert(MostBeautifulPlayerStats {
+                    player_hash: owner_hash.clone(),
+                    display_name: Self::player_name(conn, &owner_hash, &owner_hash)?,
+                    votes_received: 0,
+                    rounds_won: 0,
+                    tie_round_wins: 0,
+                    decisive_round_wins: 0,
+                    voters: Vec::new(),
+                })
+                .votes_received = total_votes;
+        }
+
+        let mut breakdown_stmt = conn
+            .prepare(
+                r#"
+                SELECT owner_hash, voter_hash, SUM(vote_count), MAX(voter_display_name)
+                FROM mb_vote_events
+                GROUP BY owner_hash, voter_hash
+                "#,
+            )
+            .context("Failed to prepare Most Beautiful vote breakdown query")?;
+        let breakdown_rows = breakdown_stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, u64>(2)?,
+                    row.get::<_, String>(3)?,
+                ))
+            })
+            .context("Failed to query Most Beautiful vote breakdown")?;
+        for row in breakdown_rows {
+            let (owner_hash, voter_hash, votes, voter_display_name) =
+                row.context("Failed to decode Most Beautiful vote breakdown row")?;
+            let owner = players
+                .entry(owner_hash.clone())
+                .or_insert(MostBeautifulPlayerStats {
+                    player_hash: owner_hash.clone(),
+                    display_name: Self::player_name(conn, &owner_hash, &owner_hash)?,
+                    votes_received: 0,
+                    rounds_won: 0,
+                    tie_round_wins: 0,
+                    decisive_round_wins: 0,
+                    voters: Vec::new(),
+                });
+            owner.voters.push(MostBeautifulVoterStats {
+                player_hash: voter_hash.clone(),
+                display_name: Self::player_name(conn, &voter_hash, &voter_display_name)?,
+                votes,
+            });
+        }
+
+        Ok(())
+    }
+
+    #[cfg(test)]
+    fn load_round_wins(
+        conn: &Connection,
+        players: &mut HashMap<String, MostBeautifulPlayerStats>,
+    ) -> Result<()> {
+        let mut stmt = conn
+            .prepare(
+                r#"
+                SELECT winner_hash, is_tie, COUNT(*)
+                FROM mb_round_win_events
+                GROUP BY winner_hash, is_tie
+                "#,
+            )
+            .context("Failed to prepare Most Beautiful round wins query")?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, bool>(1)?,
+                    row.get::<_, u64>(2)?,
+                ))
+            })
+            .context("Failed to query Most Beautiful round wins")?;
+        for row in rows {
+            let (winner_hash, is_tie, count) =
+                row.context("Failed to decode Most Beautiful round wins row")?;
+            let player = players
+                .entry(winner_hash.clone())
+                .or_insert(MostBeautifulPlayerStats {
+                    player_hash: winner_hash.clone(),
+                    display_name: Self::player_name(conn, &winner_hash, &winner_hash)?,
+                    votes_received: 0,
+                    rounds_won: 0,
+                    tie_round_wins: 0,
+                    decisive_round_wins: 0,
+                    voters: Vec::new(),
+                });
+            player.rounds_won += count;
+            if is_tie {
+                player.tie_round_wins += count;
+            } else {
+                player.decisive_round_wins += count;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// Multiplayer Elo: every pair of players is scored as one match from their placements (a tie is
+// half a win), and each player's total change is scaled down by the number of opponents.
+fn elo_ratings_after_game(ratings: &[f64], placements: &[u16]) -> Vec<f64> {
+    let opponents = ratings.len().saturating_sub(1);
+    if opponents == 0 {
+        return ratings.to_vec();
+    }
+    let k = PLAYER_RATING_K_FACTOR / opponents as f64;
+    ratings
+        .iter()
+        .enumerate()
+        .map(|(idx, rating)| {
+            let mut delta = 0.0;
+            for (other_idx, other_rating) in ratings.iter().enumerate() {
+                if other_idx == idx {
+                    continue;
+                }
+                let expected = 1.0 / (1.0 + 10f64.powf((other_rating - rating) / 400.0));
+                let actual = match placements[idx].cmp(&placements[other_idx]) {
+                    std::cmp::Ordering::Less => 1.0,
+                    std::cmp::Ordering::Equal => 0.5,
+                    std::cmp::Ordering::Greater => 0.0,
+                };
+                delta += k * (actual - expected);
+            }
+            rating + delta
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::accounts::hash_login_code;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    fn temp_db_path() -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "talespin-most-beautiful-{}-{}.sqlite3",
+            std::process::id(),
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_nanos()
+        ))
+    }
+
+    fn audit_card(
+        card_hash: &str,
+        owner_hash: &str,
+        owner_display_name: &str,
+        is_storyteller_card: bool,
+        center_order: u16,
+    ) -> MostBeautifulGameAuditCardRecord {
+        MostBeautifulGameAuditCardRecord {
+            card_hash: card_hash.to_string(),
+            owner_hash: owner_hash.to_string(),
+            owner_display_name: owner_display_name.to_string(),
+            submitted_by_hash: owner_hash.to_string(),
+            submitted_by_display_name: owner_display_name.to_string(),
+            is_storyteller_card,
+            center_order,
+        }
+    }
+
+    fn audit_vote(
+        voter_hash: &str,
+        voter_display_name: &str,
+        card_hash: &str,
+        vote_count: u16,
+    ) -> MostBeautifulGameAuditVoteRecord {
+        MostBeautifulGameAuditVoteRecord {
+            voter_hash: voter_hash.to_string(),
+            voter_display_name: voter_display_name.to_string(),
+            card_hash: card_hash.to_string(),
+            vote_count,
+        }
+    }
+
+    fn audit_score(
+        player_hash: &str,
+        player_display_name: &str,
+        story_delta: i32,
+        beauty_delta: i32,
+        total_after_round: u16,
+        beauty_total_after_round: u16,
+    ) -> MostBeautifulGameAuditScoreRecord {
+        MostBeautifulGameAuditScoreRecord {
+            player_hash: player_hash.to_string(),
+            player_display_name: player_display_name.to_string(),
+            story_delta,
+            beauty_delta,
+            total_after_round,
+            beauty_total_after_round,
+        }
+    }
+
+    #[test]
+    fn store_aggregates_votes_and_round_wins_across_rooms() -> Result<()> {
+        let path = temp_db_path();
+        let store = MostBeautifulStatsStore::new(&path)?;
+        store.register_card_paths(
+            1,
+            &HashMap::from([
+                ("card-a".to_string(), PathBuf::from("/tmp/card-a.avif")),
+                ("card-b".to_string(), PathBuf::from("/tmp/card-b.avif")),
+            ]),
+        )?;
+        store.register_card_paths(
+            2,
+            &HashMap::from([(
+                "card-a".to_string(),
+                PathBuf::from("/tmp/alternate-card-a.avif"),
+            )]),
+        )?;
+
+        store.record_round(&MostBeautifulRoundRecord {
+            recorded_at_s: 1,
+            room_id: "room-a".to_string(),
+            round_num: 1,
+            votes: vec![
+                MostBeautifulVoteRecord {
+                    voter_hash: "voter-a".to_string(),
+                    voter_display_name: "Voter A".to_string(),
+                    owner_hash: "owner-a".to_string(),
+                    owner_display_name: "Owner A".to_string(),
+                    card_hash: "card-a".to_string(),
+                    vote_count: 2,
+                },
+                MostBeautifulVoteRecord {
+                    voter_hash: "voter-b".to_string(),
+                    voter_display_name: "Voter B".to_string(),
+                    owner_hash: "owner-a".to_string(),
+                    owner_display_name: "Owner A".to_string(),
+                    card_hash: "card-a".to_string(),
+                    vote_count: 1,
+                },
+            ],
+            wins: vec![MostBeautifulRoundWinRecord {
+                winner_hash: "owner-a".to_string(),
+                winner_display_name: "Owner A".to_string(),
+                is_tie: false,
+            }],
+        })?;
+
+        store.record_round(&MostBeautifulRoundRecord {
+            recorded_at_s: 2,
+            room_id: "room-b".to_string(),
+            round_num: 3,
+            votes: vec![MostBeautifulVoteRecord {
+                voter_hash: "voter-a".to_string(),
+                voter_display_name: "Voter A Renamed".to_string(),
+                owner_hash: "owner-b".to_string(),
+                owner_display_name: "Owner B".to_string(),
+                card_hash: "card-b".to_string(),
+                vote_count: 1,
+            }],
+            wins: vec![
+                MostBeautifulRoundWinRecord {
+                    winner_hash: "owner-a".to_string(),
+                    winner_display_name: "Owner A".to_string(),
+                    is_tie: true,
+                },
+                MostBeautifulRoundWinRecord {
+                    winner_hash: "owner-b".to_string(),
+                    winner_display_name: "Owner B".to_string(),
+                    is_tie: true,
+                },
+            ],
+        })?;
+
+        let stats = store.aggregated_stats()?;
+        assert_eq!(
+            stats.players.len(),
+            4,
+            "players and voters should all be retained"
+        );
+
+        let owner_a = stats
+            .players
+            .iter()
+            .find(|entry| entry.player_hash == "owner-a")
+            .context("missing owner-a stats")?;
+        assert_eq!(owner_a.display_name, "Owner A");
+        assert_eq!(owner_a.votes_received, 3);
+        assert_eq!(owner_a.rounds_won, 2);
+        assert_eq!(owner_a.tie_round_wins, 1);
+        assert_eq!(owner_a.decisive_round_wins, 1);
+        assert_eq!(owner_a.voters.len(), 2);
+        assert_eq!(owner_a.voters[0].display_name, "Voter A Renamed");
+        assert_eq!(owner_a.voters[0].votes, 2);
+
+        let owner_b = stats
+            .players
+            .iter()
+            .find(|entry| entry.player_hash == "owner-b")
+            .context("missing owner-b stats")?;
+        assert_eq!(owner_b.votes_received, 1);
+        assert_eq!(owner_b.rounds_won, 1);
+        assert_eq!(owner_b.tie_round_wins, 1);
+        assert_eq!(owner_b.decisive_round_wins, 0);
+
+        let conn = store.connect()?;
+        let mut card_path_stmt = conn.prepare(
+            "SELECT card_path FROM mb_card_paths WHERE card_hash = ?1 ORDER BY card_path",
+        )?;
+        let card_paths = card_path_stmt
+            .query_map(params!["card-a"], |row| row.get::<_, String>(0))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        assert_eq!(
+            card_paths,
+            vec![
+                "/tmp/alternate-card-a.avif".to_string(),
+                "/tmp/card-a.avif".to_string()
+            ]
+        );
+
+        let stored_vote_card_hash: Option<String> = conn
+            .query_row(
+                "SELECT card_hash FROM mb_vote_events WHERE owner_hash = ?1 LIMIT 1",
+                params!["owner-a"],
+                |row| row.get(0),
+            )
+            .optional()?;
+        assert_eq!(stored_vote_card_hash.as_deref(), Some("card-a"));
+
+        std::fs::remove_file(&path).ok();
+        Ok(())
+    }
+
+    #[test]
+    fn store_persists_game_audit_rounds_and_completion() -> Result<()> {
+        let path = temp_db_path();
+        let store = MostBeautifulStatsStore::new(&path)?;
+
+        store.record_game_audit_round(&MostBeautifulGameAuditRoundRecord {
+            game_id: "game-1".to_string(),
+            room_id: "room-a".to_string(),
+            game_started_at_s: 10,
+            recorded_at_s: 12,
+            round_num: 1,
+            storyteller_hash: "story-h".to_string(),
+            storyteller_display_name: "Story".to_string(),
+            clue: "moon".to_string(),
+            results_display_mode: "combined".to_string(),
+            card_entries: vec![
+                MostBeautifulGameAuditCardRecord {
+                    card_hash: "card-a".to_string(),
+                    owner_hash: "story-h".to_string(),
+                    owner_display_name: "Story".to_string(),
+                    submitted_by_hash: "story-h".to_string(),
+                    submitted_by_display_name: "Story".to_string(),
+                    is_storyteller_card: true,
+                    center_order: 0,
+                },
+                MostBeautifulGameAuditCardRecord {
+                    card_hash: "card-b".to_string(),
+                    owner_hash: "p2-h".to_string(),
+                    owner_display_name: "P2".to_string(),
+                    submitted_by_hash: "p2-h".to_string(),
+                    submitted_by_display_name: "P2".to_string(),
+                    is_storyteller_card: false,
+                    center_order: 1,
+                },
+            ],
+            story_votes: vec![MostBeautifulGameAuditVoteRecord {
+                voter_hash: "p2-h".to_string(),
+                voter_display_name: "P2".to_string(),
+                card_hash: "card-a".to_string(),
+                vote_count: 1,
+            }],
+            beauty_votes: vec![MostBeautifulGameAuditVoteRecord {
+                voter_hash: "story-h".to_string(),
+                voter_display_name: "Story".to_string(),
+                card_hash: "card-b".to_string(),
+                vote_count: 1,
+            }],
+            score_log: vec![
+                MostBeautifulGameAuditScoreRecord {
+                    player_hash: "story-h".to_string(),
+                    player_display_name: "Story".to_string(),
+                    story_delta: 3,
+                    beauty_delta: 0,
+                    total_after_round: 3,
+                    beauty_total_after_round: 0,
+                },
+                MostBeautifulGameAuditScoreRecord {
+                    player_hash: "p2-h".to_string(),
+                    player_display_name: "P2".to_string(),
+                    story_delta: 3,
+                    beauty_delta: 2,
+                    total_after_round: 5,
+                    beauty_total_after_round: 2,
+                },
+            ],
+            clue_rating: None,
+        })?;
+        store.mark_game_complete("game-1", 20, 1, &[])?;
+
+        let conn = store.connect()?;
+        let completed: (bool, u64, u64) = conn.query_row(
+            "SELECT completed, ended_at, total_rounds FROM mb_games WHERE game_id = ?1",
+            params!["game-1"],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )?;
+        assert_eq!(completed, (true, 20, 1));
+
+        let card_count: u64 = conn.query_row(
+            "SELECT COUNT(*) FROM mb_game_round_cards WHERE game_id = ?1 AND round_num = 1",
+            params!["game-1"],
+            |row| row.get(0),
+        )?;
+        assert_eq!(card_count, 2);
+
+        let beauty_vote_count: u64 = conn.query_row(
+            "SELECT COUNT(*) FROM mb_game_round_beauty_votes WHERE game_id = ?1 AND round_num = 1",
+            params!["game-1"],
+            |row| row.get(0),
+        )?;
+        assert_eq!(beauty_vote_count, 1);
+
+        let p2_score: (u16, u16) = conn.query_row(
+            "SELECT total_after_round, beauty_total_after_round FROM mb_game_round_scores WHERE game_id = ?1 AND round_num = 1 AND player_hash = ?2",
+            params!["game-1", "p2-h"],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?;
+        assert_eq!(p2_score, (5, 2));
+
+        std::fs::remove_file(&path).ok();
+        Ok(())
+    }
+
+    #[test]
+    fn filtered_stats_prioritize_current_room_in_progress_then_latest_completed_games() -> Result<()>
+    {
+        let path = temp_db_path();
+        let store = MostBeautifulStatsStore::new(&path)?;
+
+        store.record_game_audit_round(&MostBeautifulGameAuditRoundRecord {
+            game_id: "game-current".to_string(),
+            room_id: "room-a".to_string(),
+            game_started_at_s: 300,
+            recorded_at_s: 301,
+            round_num: 1,
+            storyteller_hash: "story-current".to_string(),
+            storyteller_display_name: "Story Current".to_string(),
+            clue: "current".to_string(),
+            results_display_mode: "combined".to_string(),
+            card_entries: vec![
+                audit_card("current-story", "story-current", "Story Current", true, 0),
+                audit_card("current-win-1", "owner-current", "Owner Current", false, 1),
+                audit_card("current-win-2", "owner-current", "Owner Current", false, 2),
+                audit_card(
+                    "current-other",
+                    "owner-current-2",
+                    "Owner Current 2",
+                    false,
+                    3,
+                ),
+            ],
+            story_votes: vec![],
+            beauty_votes: vec![
+                audit_vote("voter-current-a", "Voter Current A", "current-win-1", 1),
+                audit_vote("voter-current-b", "Voter Current B", "current-win-2", 1),
+                audit_vote("voter-current-c", "Voter Current C", "current-other", 1),
+            ],
+            score_log: vec![
+                audit_score("owner-current", "Owner Current", 0, 2, 2, 2),
+                audit_score("owner-current-2", "Owner Current 2", 0, 1, 1, 1),
+            ],
+            clue_rating: None,
+        })?;
+
+        store.record_game_audit_round(&MostBeautifulGameAuditRoundRecord {
+            game_id: "game-completed-new".to_string(),
+            room_id: "room-b".to_string(),
+            game_started_at_s: 200,
+            recorded_at_s: 201,
+            round_num: 1,
+            storyteller_hash: "story-new".to_string(),
+            storyteller_display_name: "Story New".to_string(),
+            clue: "new".to_string(),
+            results_display_mode: "combined".to_string(),
+            card_entries: vec![
+                audit_card("new-story", "story-new", "Story New", true, 0),
+                audit_card("new-win", "owner-new", "Owner New", false, 1),
+                audit_card("new-other", "owner-new-2", "Owner New 2", false, 2),
+            ],
+            story_votes: vec![],
+            beauty_votes: vec![
+                audit_vote("voter-new-a", "Voter New A", "new-win", 2),
+                audit_vote("voter-new-b", "Voter New B", "new-win", 1),
+                audit_vote("voter-new-c", "Voter New C", "new-other", 1),
+            ],
+            score_log: vec![
+                audit_score("owner-new", "Owner New", 0, 3, 3, 3),
+                audit_score("owner-new-2", "Owner New 2", 0, 1, 1, 1),
+            ],
+            clue_rating: None,
+        })?;
+        store.mark_game_complete("game-completed-new", 250, 1, &[])?;
+
+        store.record_game_audit_round(&MostBeautifulGameAuditRoundRecord {
+            game_id: "game-completed-old".to_string(),
+            room_id: "room-c".to_string(),
+            game_started_at_s: 100,
+            recorded_at_s: 101,
+            round_num: 1,
+            storyteller_hash: "story-old".to_string(),
+            storyteller_display_name: "Story Old".to_string(),
+            clue: "old".to_string(),
+            results_display_mode: "combined".to_string(),
+            card_entries: vec![
+                audit_card("old-story", "story-old", "Story Old", true, 0),
+                audit_card("old-win", "owner-old", "Owner Old", false, 1),
+                audit_card("old-other", "owner-old-2", "Owner Old 2", false, 2),
+            ],
+            story_votes: vec![],
+            beauty_votes: vec![
+                audit_vote("voter-old-a", "Voter Old A", "old-win", 3),
+                audit_vote("voter-old-b", "Voter Old B", "old-win", 1),
+            ],
+            score_log: vec![audit_score("owner-old", "Owner Old", 0, 4, 4, 4)],
+            clue_rating: None,
+        })?;
+        store.mark_game_complete("game-completed-old", 150, 1, &[])?;
+
+        let current_only = store.filtered_stats(Some("room-a"), 1)?;
+        assert_eq!(
+            current_only
+                .players
+                .iter()
+                .map(|player| player.player_hash.as_str())
+                .collect::<Vec<_>>(),
+            vec!["owner-current", "owner-current-2"],
+            "N=1 should prefer the current room's in-progress game"
+        );
+        assert_eq!(current_only.players[0].votes_received, 2);
+        assert_eq!(current_only.players[0].rounds_won, 1);
+
+        let current_plus_latest_completed = store.filtered_stats(Some("room-a"), 2)?;
+        assert_eq!(
+            current_plus_latest_completed
+                .players
+                .iter()
+                .map(|player| player.player_hash.as_str())
+                .collect::<Vec<_>>(),
+            vec![
+                "owner-new",
+                "owner-current",
+                "owner-current-2",
+                "owner-new-2"
+            ],
+            "N=2 should include the current room plus the latest completed global game"
+        );
+
+        let latest_completed_only = store.filtered_stats(Some("room-z"), 1)?;
+        assert_eq!(
+            latest_completed_only
+                .players
+                .iter()
+                .map(|player| player.player_hash.as_str())
+                .collect::<Vec<_>>(),
+            vec!["owner-new", "owner-new-2"],
+            "without a current-room exception, N=1 should use the latest completed global game"
+        );
+
+        std::fs::remove_file(&path).ok();
+        Ok(())
+    }
+
+    #[test]
+    fn filtered_stats_limit_results_to_top_30_players() -> Result<()> {
+        let path = temp_db_path();
+        let store = MostBeautifulStatsStore::new(&path)?;
+
+        for index in 0..35 {
+            let game_id = format!("game-{index}");
+            let winner_hash = format!("owner-{index:02}");
+            let winner_name = format!("Owner {index:02}");
+            let other_hash = format!("other-{index:02}");
+            let other_name = format!("Other {index:02}");
+            let winner_card = format!("winner-card-{index:02}");
+            let other_card = format!("other-card-{index:02}");
+            let story_hash = format!("story-{index:02}");
+            let story_name = format!("Story {index:02}");
+            store.record_game_audit_round(&MostBeautifulGameAuditRoundRecord {
+                game_id: game_id.clone(),
+                room_id: format!("room-{index:02}"),
+                game_started_at_s: index as u64,
+                recorded_at_s: index as u64 + 1,
+                round_num: 1,
+                storyteller_hash: story_hash.clone(),
+                storyteller_display_name: story_name.clone(),
+                clue: format!("clue-{index:02}"),
+                results_display_mode: "combined".to_string(),
+                card_entries: vec![
+                    audit_card(
+                        &format!("story-card-{index:02}"),
+                        &story_hash,
+                        &story_name,
+                        true,
+                        0,
+                    ),
+                    audit_card(&winner_card, &winner_hash, &winner_name, false, 1),
+                    audit_card(&other_card, &other_hash, &other_name, false, 2),
+                ],
+                story_votes: vec![],
+                beauty_votes: vec![audit_vote(
+                    &format!("voter-{index:02}"),
+                    &format!("Voter {index:02}"),
+                    &winner_card,
+                    1,
+                )],
+                score_log: vec![audit_score(&winner_hash, &winner_name, 0, 1, 1, 1)],
+                clue_rating: None,
+            })?;
+            store.mark_game_complete(&game_id, index as u64 + 2, 1, &[])?;
+        }
+
+        let stats = store.filtered_stats(Some("room-none"), 0)?;
+        assert_eq!(
+            stats.players.len(),
+            30,
+            "filtered stats should cap the ranking panel to the top 30 players"
+        );
+
+        std::fs::remove_file(&path).ok();
+        Ok(())
+    }
+
+    fn replay_round_record(game_id: &str, round_num: u16) -> MostBeautifulGameAuditRoundRecord {
+        MostBeautifulGameAuditRoundRecord {
+            game_id: game_id.to_string(),
+            room_id: "room-a".to_string(),
+            game_started_at_s: 10,
+            recorded_at_s: 10 + u64::from(round_num),
+            round_num,
+            storyteller_hash: "story-h".to_string(),
+            storyteller_display_name: "Story".to_string(),
+            clue: format!("clue {round_num}"),
+            results_display_mode: "combined".to_string(),
+            card_entries: vec![
+                audit_card("card-b", "p2-h", "P2", false, 0),
+                audit_card("card-a", "story-h", "Story", true, 1),
+            ],
+            story_votes: vec![audit_vote("p2-h", "P2", "card-a", 1)],
+            beauty_votes: vec![audit_vote("story-h", "Story", "card-b", 1)],
+            score_log: vec![
+                audit_score("story-h", "Story", 3, 0, 3, 0),
+                audit_score("p2-h", "P2", 3, 1, 3, 1),
+            ],
+            clue_rating: None,
+        }
+    }
+
+    #[test]
+    fn game_replay_round_trips_through_export_and_import() -> Result<()> {
+        let source_path = temp_db_path();
+        let source = MostBeautifulStatsStore::new(&source_path)?;
+        source.record_game_audit_round(&replay_round_record("game-1", 1))?;
+        source.record_game_audit_round(&replay_round_record("game-1", 2))?;
+        source.mark_game_complete("game-1", 20, 2, &[])?;
+
+        let replay = source
+            .export_game_replay("game-1")?
+            .context("missing exported replay")?;
+        assert!(source.export_game_replay("missing")?.is_none());
+        assert_eq!(replay.format_version, GAME_REPLAY_FORMAT_VERSION);
+        assert!(replay.completed);
+        assert_eq!(replay.rounds.len(), 2);
+        assert_eq!(
+            replay.rounds[0]
+                .center_cards
+                .iter()
+                .map(|card| card.card_hash.as_str())
+                .collect::<Vec<_>>(),
+            vec!["card-b", "card-a"],
+            "center cards should keep their voting order"
+        );
+        replay.validate().map_err(anyhow::Error::msg)?;
+
+        let json = serde_json::to_string(&replay)?;
+        let parsed: GameReplay = serde_json::from_str(&json)?;
+        let target_path = temp_db_path();
+        let target = MostBeautifulStatsStore::new(&target_path)?;
+        assert!(!target.game_exists("game-1")?);
+        target.import_game_replay(&parsed)?;
+        assert!(target.game_exists("game-1")?);
+        assert_eq!(target.export_game_replay("game-1")?, Some(replay));
+        assert!(
+            target.import_game_replay(&parsed).is_err(),
+            "importing the same game twice should fail"
+        );
+
+        std::fs::remove_file(&source_path).ok();
+        std::fs::remove_file(&target_path).ok();
+        Ok(())
+    }
+
+    #[test]
+    fn game_replay_validation_rejects_inconsistent_documents() -> Result<()> {
+        let path = temp_db_path();
+        let store = MostBeautifulStatsStore::new(&path)?;
+        store.record_game_audit_round(&replay_round_record("game-1", 1))?;
+        let replay = store
+            .export_game_replay("game-1")?
+            .context("missing exported replay")?;
+        assert!(replay.validate().is_ok());
+
+        let mut wrong_version = replay.clone();
+        wrong_version.format_version += 1;
+        assert!(wrong_version.validate().is_err());
+
+        let mut stray_vote = replay.clone();
+        stray_vote.rounds[0].story_votes[0].card_hash = "card-z".to_string();
+        assert!(stray_vote.validate().is_err());
+
+        let mut two_storyteller_cards = replay.clone();
+        two_storyteller_cards.rounds[0].center_cards[0].is_storyteller_card = true;
+        assert!(two_storyteller_cards.validate().is_err());
+
+        let mut repeated_round = replay.clone();
+        repeated_round.rounds.push(replay.rounds[0].clone());
+        assert!(repeated_round.validate().is_err());
+
+        let mut completed_without_end = replay;
+        completed_without_end.completed = true;
+        assert!(completed_without_end.validate().is_err());
+
+        std::fs::remove_file(&path).ok();
+        Ok(())
+    }
+
+    #[test]
+    fn clue_archive_searches_full_text_and_filters() -> Result<()> {
+        let path = temp_db_path();
+        let store = MostBeautifulStatsStore::new(&path)?;
+
+        let mut legendary = replay_round_record("game-1", 1);
+        legendary.clue = "The lighthouse keeper's regret".to_string();
+        legendary.story_votes = vec![
+            audit_vote("p2-h", "P2", "card-a", 1),
+            audit_vote("p3-h", "P3", "card-b", 1),
+        ];
+        legendary.clue_rating = Some(MostBeautifulGameAuditClueRatingRecord {
+            rating_count: 2,
+            rating_sum: 9,
+            max_stars: 5,
+        });
+        store.record_game_audit_round(&legendary)?;
+        store.record_game_audit_round(&replay_round_record("game-1", 2))?;
+        let mut other_room = replay_round_record("game-2", 1);
+        other_room.room_id = "room-b".to_string();
+        other_room.storyteller_hash = "other-h".to_string();
+        other_room.card_entries = vec![
+            audit_card("card-b", "p2-h", "P2", false, 0),
+            audit_card("card-a", "other-h", "Other", true, 1),
+        ];
+        other_room.clue = "Lighthouse in fog".to_string();
+        other_room.recorded_at_s = 100;
+        store.record_game_audit_round(&other_room)?;
+
+        let search = |query: ClueSearchQuery| -> Result<Vec<(String, u16)>> {
+            Ok(store
+                .search_clues(&query)?
+                .clues
+                .into_iter()
+                .map(|entry| (entry.game_id, entry.round_num))
+                .collect())
+        };
+
+        let hits = store.search_clues(&ClueSearchQuery {
+            text: Some("lighthouse".to_string()),
+            ..Default::default()
+        })?;
+        assert_eq!(hits.clues.len(), 2);
+        let entry = hits
+            .clues
+            .iter()
+            .find(|entry| entry.game_id == "game-1")
+            .context("missing legendary clue")?;
+        assert_eq!(entry.storyteller_card_hash.as_deref(), Some("card-a"));
+        assert_eq!((entry.correct_guessers, entry.guessers), (1, 2));
+        assert_eq!(entry.clue_rating_average, Some(4.5));
+        assert_eq!(entry.clue_rating_max_stars, Some(5));
+
+        assert_eq!(
+            search(ClueSearchQuery {
+                text: Some("lighthouse".to_string()),
+                room_id: Some("room-b".to_string()),
+                ..Default::default()
+            })?,
+            vec![("game-2".to_string(), 1)]
+        );
+        assert_eq!(
+            search(ClueSearchQuery {
+                storyteller_hash: Some("story-h".to_string()),
+                ..Default::default()
+            })?,
+            vec![("game-1".to_string(), 2), ("game-1".to_string(), 1)],
+            "without text, newest clues come first"
+        );
+        assert_eq!(
+            search(ClueSearchQuery {
+                since_s: Some(50),
+                ..Default::default()
+            })?,
+            vec![("game-2".to_string(), 1)]
+        );
+        assert!(
+            search(ClueSearchQuery {
+                text: Some("\"fog OR (".to_string()),
+                ..Default::default()
+            })?
+            .is_empty(),
+            "query syntax in user input should be matched literally"
+        );
+
+        let mut renamed = replay_round_record("game-1", 2);
+        renamed.clue = "Harbor lights".to_string();
+        store.record_game_audit_round(&renamed)?;
+        assert_eq!(
+            search(ClueSearchQuery {
+                text: Some("harbor".to_string()),
+                ..Default::default()
+            })?,
+            vec![("game-1".to_string(), 2)],
+            "re-recording a round should reindex its clue"
+        );
+
+        std::fs::remove_file(&path).ok();
+        Ok(())
+    }
+
+    #[test]
+    fn card_stats_aggregate_appearances_and_votes() -> Result<()> {
+        let path = temp_db_path();
+        let store = MostBeautifulStatsStore::new(&path)?;
+        store.register_card_paths(
+            1,
+            &HashMap::from([
+                ("card-a".to_string(), PathBuf::from("deck/a.jpg")),
+                ("card-z".to_string(), PathBuf::from("deck/z.jpg")),
+            ]),
+        )?;
+
+        let mut obvious = replay_round_record("game-1", 1);
+        obvious.story_votes = vec![
+            audit_vote("p2-h", "P2", "card-a", 1),
+            audit_vote("p3-h", "P3", "card-a", 1),
+        ];
+        store.record_game_audit_round(&obvious)?;
+        let mut split = replay_round_record("game-1", 2);
+        split.story_votes = vec![
+            audit_vote("p2-h", "P2", "card-a", 1),
+            audit_vote("p3-h", "P3", "card-b", 2),
+        ];
+        store.record_game_audit_round(&split)?;
+        store.mark_game_complete("game-1", 20, 2, &[])?;
+
+        let stats = store.card_stats(None, 0, false)?;
+        assert_eq!(stats.cards.len(), 2);
+        let card_a = stats
+            .cards
+            .iter()
+            .find(|card| card.card_hash == "card-a")
+            .context("missing card-a stats")?;
+        assert_eq!(card_a.card_paths, vec!["deck/a.jpg".to_string()]);
+        assert_eq!(card_a.appearances, 2);
+        assert_eq!(card_a.storyteller_picks, 2);
+        assert_eq!(card_a.storyteller_all_guessed, 1);
+        assert_eq!(card_a.storyteller_successes, 1);
+        assert_eq!(card_a.storyteller_success_rate, Some(0.5));
+        assert_eq!(card_a.decoy_appearances, 0);
+
+        let card_b = stats
+            .cards
+            .iter()
+            .find(|card| card.card_hash == "card-b")
+            .context("missing card-b stats")?;
+        assert!(card_b.card_paths.is_empty());
+        assert_eq!(card_b.decoy_appearances, 2);
+        assert_eq!(card_b.decoy_votes, 2);
+        assert_eq!(card_b.beauty_votes, 2);
+        assert_eq!(card_b.storyteller_success_rate, None);
+
+        let with_unplayed = store.card_stats(None, 0, true)?;
+        let card_z = with_unplayed
+            .cards
+            .iter()
+            .find(|card| card.card_hash == "card-z")
+            .context("unplayed deck cards should be listed on request")?;
+        assert_eq!(card_z.appearances, 0);
+        assert_eq!(
+            with_unplayed
+                .cards
+                .last()
+                .map(|card| card.card_hash.as_str()),
+            Some("card-z")
+        );
+
+        std::fs::remove_file(&path).ok();
+        Ok(())
+    }
+
+    #[test]
+    fn player_profile_stats_cover_storytelling_guessing_and_decoys() -> Result<()> {
+        let path = temp_db_path();
+        let store = MostBeautifulStatsStore::new(&path)?;
+
+        let mut rated = replay_round_record("game-1", 1);
+        rated.clue_rating = Some(MostBeautifulGameAuditClueRatingRecord {
+            rating_count: 2,
+            rating_sum: 9,
+            max_stars: 5,
+        });
+        store.record_game_audit_round(&rated)?;
+        let mut split = replay_round_record("game-1", 2);
+        split.story_votes = vec![
+            audit_vote("p2-h", "P2", "card-a", 1),
+            audit_vote("p3-h", "P3", "card-b", 1),
+        ];
+        store.record_game_audit_round(&split)?;
+        let mut guessing = replay_round_record("game-1", 3);
+        guessing.storyteller_hash = "p2-h".to_string();
+        guessing.storyteller_display_name = "P2 New".to_string();
+        guessing.card_entries = vec![
+            audit_card("card-c", "p2-h", "P2 New", true, 0),
+            audit_card("card-d", "story-h", "Story", false, 1),
+        ];
+        guessing.story_votes = vec![
+            audit_vote("story-h", "Story", "card-c", 1),
+            audit_vote("p3-h", "P3", "card-d", 1),
+        ];
+        guessing.score_log = vec![
+            audit_score("story-h", "Story", 10, 0, 10, 0),
+            audit_score("p2-h", "P2 New", 5, 0, 5, 0),
+        ];
+        store.record_game_audit_round(&guessing)?;
+        store.mark_game_complete("game-1", 20, 3, &[])?;
+
+        let profile = store
+            .player_profile_stats("story-h", None, 0)?
+            .context("missing storyteller profile")?;
+        assert_eq!(
+            (
+                profile.games_played,
+                profile.games_completed,
+                profile.games_won
+            ),
+            (1, 1, 1)
+        );
+        assert_eq!(profile.rounds_played, 3);
+        assert_eq!(
+            (profile.storyteller_rounds, profile.storyteller_successes),
+            (2, 1)
+        );
+        assert_eq!(profile.storyteller_success_rate, Some(0.5));
+        assert_eq!((profile.guessing_rounds, profile.correct_guesses), (1, 1));
+        assert_eq!(
+            (
+                profile.decoy_cards,
+                profile.decoys_that_fooled,
+                profile.decoy_votes_attracted
+            ),
+            (1, 1, 1)
+        );
+        assert_eq!(profile.clue_rating_average, Some(4.5));
+        assert_eq!(
+            profile
+                .favourite_voters
+                .iter()
+                .map(|voter| (
+                    voter.display_name.as_str(),
+                    voter.correct_guesses,
+                    voter.guesses
+                ))
+                .collect::<Vec<_>>(),
+            vec![("P2 New", 2, 2), ("P3", 0, 1)]
+        );
+
+        let renamed = store
+            .player_profile_stats("p2-h", None, 0)?
+            .context("missing renamed player profile")?;
+        assert_eq!(renamed.display_name, "P2 New");
+        assert_eq!(
+            renamed
+                .aliases
+                .iter()
+                .map(|alias| alias.display_name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["P2 New", "P2"]
+        );
+        assert_eq!(renamed.games_won, 0);
+        assert!(store.player_profile_stats("missing-h", None, 0)?.is_none());
+
+        std::fs::remove_file(&path).ok();
+        Ok(())
+    }
+
+    #[test]
+    fn head_to_head_tracks_guesses_decoys_and_beauty_affinity() -> Result<()> {
+        let path = temp_db_path();
+        let store = MostBeautifulStatsStore::new(&path)?;
+
+        store.record_game_audit_round(&replay_round_record("game-1", 1))?;
+        let mut fooled = replay_round_record("game-1", 2);
+        fooled.story_votes = vec![
+            audit_vote("p2-h", "P2", "card-a", 1),
+            audit_vote("p3-h", "P3", "card-b", 1),
+        ];
+        store.record_game_audit_round(&fooled)?;
+        store.mark_game_complete("game-1", 20, 2, &[])?;
+
+        let mut later = replay_round_record("game-2", 1);
+        later.storyteller_hash = "p3-h".to_string();
+        later.storyteller_display_name = "P3".to_string();
+        later.card_entries = vec![
+            audit_card("card-b", "p2-h", "P2", false, 0),
+            audit_card("card-a", "p3-h", "P3", true, 1),
+        ];
+        later.story_votes = vec![audit_vote("story-h", "Story", "card-b", 1)];
+        store.record_game_audit_round(&later)?;
+        store.mark_game_complete("game-2", 30, 1, &[])?;
+
+        let pair = |response: &HeadToHeadResponse, guesser: &str, target: &str| {
+            response
+                .pairs
+                .iter()
+                .find(|pair| pair.guesser_hash == guesser && pair.target_hash == target)
+                .cloned()
+        };
+
+        let all = store.head_to_head(None, 0)?;
+        assert_eq!(
+            all.players
+                .iter()
+                .map(|player| player.display_name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["P2", "P3", "Story"]
+        );
+        let p2_on_story = pair(&all, "p2-h", "story-h").context("missing p2 -> story pair")?;
+        assert_eq!(
+            (p2_on_story.storyteller_rounds, p2_on_story.correct_guesses),
+            (2, 2)
+        );
+        assert_eq!(p2_on_story.correct_guess_rate, Some(1.0));
+        let p3_on_p2 = pair(&all, "p3-h", "p2-h").context("missing p3 -> p2 pair")?;
+        assert_eq!((p3_on_p2.decoy_rounds, p3_on_p2.times_fooled), (1, 1));
+        let story_on_p2 = pair(&all, "story-h", "p2-h").context("missing story -> p2 pair")?;
+        assert_eq!(story_on_p2.beauty_votes_given, 3);
+        assert_eq!(story_on_p2.beauty_affinity, Some(1.0));
+        assert_eq!((story_on_p2.decoy_rounds, story_on_p2.times_fooled), (1, 1));
+        assert!(
+            pair(&all, "p2-h", "p2-h").is_none(),
+            "players should never be paired with themselves"
+        );
+
+        let latest = store.head_to_head(None, 1)?;
+        assert!(pair(&latest, "p2-h", "story-h").is_none());
+        let story_on_p3 = pair(&latest, "story-h", "p3-h").context("missing story -> p3 pair")?;
+        assert_eq!(
+            (story_on_p3.storyteller_rounds, story_on_p3.correct_guesses),
+            (1, 0)
+        );
+
+        std::fs::remove_file(&path).ok();
+        Ok(())
+    }
+
+    #[test]
+    fn elo_ratings_are_zero_sum_and_respect_ties() {
+        let after = elo_ratings_after_game(&[1500.0, 1500.0, 1500.0], &[1, 2, 2]);
+        assert!(after[0] > 1500.0);
+        assert!(after[1] < 1500.0);
+        assert!((after[1] - after[2]).abs() < 1e-9);
+        assert!((after.iter().sum::<f64>() - 4500.0).abs() < 1e-9);
+
+        let upset = elo_ratings_after_game(&[1700.0, 1300.0], &[2, 1]);
+        let expected_win = elo_ratings_after_game(&[1700.0, 1300.0], &[1, 2]);
+        assert!(upset[1] - 1300.0 > expected_win[0] - 1700.0);
+    }
+
+    #[test]
+    fn completing_a_game_updates_global_and_room_ratings_once() -> Result<()> {
+        let path = temp_db_path();
+        let store = MostBeautifulStatsStore::new(&path)?;
+
+        let mut first = replay_round_record("game-1", 1);
+        first.score_log = vec![
+            audit_score("story-h", "Story", 3, 0, 3, 0),
+            audit_score("p2-h", "P2", 0, 0, 0, 0),
+        ];
+        store.record_game_audit_round(&first)?;
+        store.mark_game_complete("game-1", 20, 1, &[])?;
+        store.mark_game_complete("game-1", 21, 1, &[])?;
+
+        let mut other_room = replay_round_record("game-2", 1);
+        other_room.room_id = "room-b".to_string();
+        other_room.score_log = vec![
+            audit_score("story-h", "Story", 0, 0, 0, 0),
+            audit_score("p2-h", "P2", 3, 0, 3, 0),
+        ];
+        store.record_game_audit_round(&other_room)?;
+        store.mark_game_complete("game-2", 30, 1, &[])?;
+
+        let global = store.rating_leaderboard(None, 0)?;
+        assert_eq!(global.scope, "global");
+        assert_eq!(global.players.len(), 2);
+        assert!(global.players.iter().all(|player| player.games_rated == 2));
+
+        let room_a = store.rating_leaderboard(Some("room-a"), 0)?;
+        assert_eq!(room_a.scope, "room:room-a");
+        assert_eq!(room_a.players[0].player_hash, "story-h");
+        assert_eq!(room_a.players[0].rating, INITIAL_PLAYER_RATING + 16.0);
+        assert!(room_a.players.iter().all(|player| player.games_rated == 1));
+
+        let history = store.player_rating_history("story-h", None)?;
+        assert_eq!(
+            history
+                .history
+                .iter()
+                .map(|point| (point.game_id.as_str(), point.placement))
+                .collect::<Vec<_>>(),
+            vec![("game-1", 1), ("game-2", 2)],
+            "re-marking a game complete should not rate it twice"
+        );
+        assert_eq!(history.rating, Some(history.history[1].rating_after));
+        assert_eq!(
+            history.history[1].rating_before,
+            history.history[0].rating_after
+        );
+        assert!(store
+            .player_rating_history("missing-h", None)?
+            .rating
+            .is_none());
+
+        std::fs::remove_file(&path).ok();
+        Ok(())
+    }
 
-        for winner_entries in rounds.into_values() {
-            let highest_vote_total = winner_entries
-                .iter()
-                .map(|(_, _, vote_total)| *vote_total)
-                .max()
-                .unwrap_or(0);
-            if highest_vote_total == 0 {
-                continue;
-            }
-            let winners = winner_entries
-                .into_iter()
-                .filter(|(_, _, vote_total)| *vote_total == highest_vote_total)
-                .collect::<Vec<_>>();
-            let is_tie = winners.len() > 1;
-            for (winner_hash, winner_display_name, _) in winners {
-                let player =
-                    players
-                        .entry(winner_hash.clone())
-                        .or_insert(MostBeautifulPlayerStats {
-                            player_hash: winner_hash.clone(),
-                            display_name: Self::player_name(
-                                conn,
-                                &winner_hash,
-                                &winner_display_name,
-                            )?,
-                            votes_received: 0,
-                            rounds_won: 0,
-                            tie_round_wins: 0,
-                            decisive_round_wins: 0,
-                            voters: Vec::new(),
-                        });
-                player.rounds_won += 1;
-                if is_tie {
-                    player.tie_round_wins += 1;
-                } else {
-                    player.decisive_round_wins += 1;
-                }
-            }
-        }
+    #[test]
+    fn ratings_follow_recorded_standings_over_round_totals() -> Result<()> {
+        let path = temp_db_path();
+        let store = MostBeautifulStatsStore::new(&path)?;
+
+        // Story leads on round totals, but P2's secret objective bonus wins the game
+        let mut round = replay_round_record("game-1", 1);
+        round.score_log = vec![
+            audit_score("story-h", "Story", 3, 0, 3, 0),
+            audit_score("p2-h", "P2", 2, 0, 2, 0),
+        ];
+        store.record_game_audit_round(&round)?;
+        store.mark_game_complete(
+            "game-1",
+            20,
+            1,
+            &[
+                GameStandingRecord {
+                    player_hash: "p2-h".to_string(),
+                    rank: 1,
+                    points: 5,
+                },
+                GameStandingRecord {
+                    player_hash: "story-h".to_string(),
+                    rank: 2,
+                    points: 3,
+                },
+            ],
+        )?;
 
+        let p2 = store.player_rating_history("p2-h", None)?;
+        assert_eq!(p2.history[0].placement, 1);
+        assert_eq!(p2.rating, Some(INITIAL_PLAYER_RATING + 16.0));
+        let story = store.player_rating_history("story-h", None)?;
+        assert_eq!(story.history[0].placement, 2);
+        assert_eq!(story.rating, Some(INITIAL_PLAYER_RATING - 16.0));
+
+        std::fs::remove_file(&path).ok();
         Ok(())
     }
 
-    #[cfg(test)]
-    fn load_players(conn: &Connection) -> Result<HashMap<String, MostBeautifulPlayerStats>> {
-        let mut stmt = conn
-            .prepare(
-                "SELECT player_hash, latest_display_name FROM mb_players ORDER BY latest_display_name, player_hash",
+    #[test]
+    fn stats_exports_select_games_and_render_csv_tables() -> Result<()> {
+        let path = temp_db_path();
+        let store = MostBeautifulStatsStore::new(&path)?;
+
+        let mut quoted = replay_round_record("game-1", 1);
+        quoted.clue = "commas, \"quotes\"".to_string();
+        store.record_game_audit_round(&quoted)?;
+        let mut other_room = replay_round_record("game-2", 1);
+        other_room.room_id = "room-b".to_string();
+        other_room.story_votes = Vec::new();
+        other_room.score_log = vec![audit_score("story-h", "Story", 0, 0, 0, 0)];
+        other_room.card_entries = vec![audit_card("card-a", "story-h", "Story", true, 0)];
+        other_room.beauty_votes = Vec::new();
+        store.record_game_audit_round(&other_room)?;
+
+        assert_eq!(store.game_room_id("game-2")?.as_deref(), Some("room-b"));
+        assert_eq!(store.export_room_games("room-a")?.games.len(), 1);
+        let p2_games = store.export_player_games("p2-h", None)?;
+        assert_eq!(
+            p2_games
+                .games
+                .iter()
+                .map(|game| game.game_id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["game-1"]
+        );
+        assert_eq!(store.export_player_games("story-h", None)?.games.len(), 2);
+        assert!(store
+            .export_player_games("story-h", Some("room-c"))?
+            .games
+            .is_empty());
+
+        let rounds = stats_export_csv(&p2_games.games, StatsExportTable::Rounds)?;
+        let mut lines = rounds.lines();
+        assert_eq!(
+            lines.next(),
+            Some(
+                "game_id,room_id,round_num,recorded_at_s,storyteller_hash,\
+                 storyteller_display_name,clue,results_display_mode,clue_rating_count,\
+                 clue_rating_sum,clue_rating_max_stars"
             )
-            .context("Failed to prepare Most Beautiful players query")?;
-        let rows = stmt
-            .query_map([], |row| {
-                Ok(MostBeautifulPlayerStats {
-                    player_hash: row.get(0)?,
-                    display_name: row.get(1)?,
-                    votes_received: 0,
-                    rounds_won: 0,
-                    tie_round_wins: 0,
-                    decisive_round_wins: 0,
-                    voters: Vec::new(),
-                })
+        );
+        assert_eq!(
+            lines.next(),
+            Some("game-1,room-a,1,11,story-h,Story,\"commas, \"\"quotes\"\"\",combined,,,")
+        );
+        let votes = stats_export_csv(&p2_games.games, StatsExportTable::StoryVotes)?;
+        assert_eq!(votes.lines().nth(1), Some("game-1,1,p2-h,P2,card-a,1"));
+        let empty = stats_export_csv(&[], StatsExportTable::Scores)?;
+        assert_eq!(
+            empty.trim_end(),
+            "game_id,round_num,player_hash,player_display_name,story_delta,beauty_delta,\
+             total_after_round,beauty_total_after_round"
+        );
+        assert_eq!(
+            StatsExportTable::parse("Beauty_Votes"),
+            Some(StatsExportTable::BeautyVotes)
+        );
+        assert_eq!(StatsExportTable::parse("players"), None);
+
+        std::fs::remove_file(&path).ok();
+        Ok(())
+    }
+
+    fn migration_backups(path: &Path) -> Vec<PathBuf> {
+        let prefix = format!(
+            "{}.v",
+            path.file_name().unwrap_or_default().to_string_lossy()
+        );
+        std::fs::read_dir(path.parent().unwrap_or(Path::new(".")))
+            .map(|entries| {
+                entries
+                    .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                    .filter(|candidate| {
+                        candidate
+                            .file_name()
+                            .map(|name| name.to_string_lossy().starts_with(&prefix))
+                            .unwrap_or(false)
+                    })
+                    .collect()
             })
-            .context("Failed to query Most Beautiful players")?;
+            .unwrap_or_default()
+    }
 
-        let mut players = HashMap::new();
-        for row in rows {
-            let player = row.context("Failed to decode Most Beautiful player row")?;
-            players.insert(player.player_hash.clone(), player);
+    #[test]
+    fn schema_migrations_are_contiguous_and_end_at_current_version() {
+        for (idx, migration) in STATS_SCHEMA_MIGRATIONS.iter().enumerate() {
+            assert_eq!(migration.version, idx as u32 + 1);
         }
-        Ok(players)
+        assert_eq!(
+            STATS_SCHEMA_MIGRATIONS
+                .last()
+                .map(|migration| migration.version),
+            Some(STATS_SCHEMA_VERSION)
+        );
     }
 
-    fn player_name(conn: &Connection, player_hash: &str, fallback: &str) -> Result<String> {
-        conn.query_row(
-            "SELECT latest_display_name FROM mb_players WHERE player_hash = ?1",
-            params![player_hash],
-            |row| row.get(0),
-        )
-        .optional()
-        .map(|name| name.unwrap_or_else(|| fallback.to_string()))
-        .context("Failed to load Most Beautiful player display name")
+    #[test]
+    fn fresh_database_starts_at_current_schema_without_backup() -> Result<()> {
+        let path = temp_db_path();
+        let store = MostBeautifulStatsStore::new(&path)?;
+        let version: u32 = store
+            .connect()?
+            .pragma_query_value(None, "user_version", |row| row.get(0))?;
+        assert_eq!(version, STATS_SCHEMA_VERSION);
+        assert!(migration_backups(&path).is_empty());
+
+        MostBeautifulStatsStore::new(&path)?;
+        assert!(
+            migration_backups(&path).is_empty(),
+            "reopening an up-to-date database should not back it up"
+        );
+
+        std::fs::remove_file(&path).ok();
+        Ok(())
     }
 
-    #[cfg(test)]
-    fn load_vote_totals(
-        conn: &Connection,
-        players: &mut HashMap<String, MostBeautifulPlayerStats>,
-    ) -> Result<()> {
-        let mut totals_stmt = conn
-            .prepare(
+    #[test]
+    fn unversioned_database_is_backed_up_and_migrated() -> Result<()> {
+        let path = temp_db_path();
+        {
+            let conn = Connection::open(&path)?;
+            MostBeautifulStatsStore::migrate_base_schema(&conn)?;
+            conn.execute(
+                "INSERT INTO mb_games (game_id, room_id, started_at) VALUES ('game-1', 'room-a', 10)",
+                [],
+            )?;
+            conn.execute(
                 r#"
-                SELECT owner_hash, COALESCE(SUM(vote_count), 0)
-                FROM mb_vote_events
-                GROUP BY owner_hash
+                INSERT INTO mb_game_rounds (
+                    game_id, round_num, recorded_at, storyteller_hash,
+                    storyteller_display_name, clue, results_display_mode
+                )
+                VALUES ('game-1', 1, 11, 'story-h', 'Story', 'Old lighthouse', 'combined')
                 "#,
-            )
-            .context("Failed to prepare Most Beautiful vote totals query")?;
-        let vote_rows = totals_stmt
-            .query_map([], |row| {
-                Ok((row.get::<_, String>(0)?, row.get::<_, u64>(1)?))
-            })
-            .context("Failed to query Most Beautiful vote totals")?;
-        for row in vote_rows {
-            let (owner_hash, total_votes) =
-                row.context("Failed to decode Most Beautiful votes row")?;
-            players
-                .entry(owner_hash.clone())
-                .or_insert(MostBeautifulPlayerStats {
-                    player_hash: owner_hash.clone(),
-                    display_name: Self::player_name(conn, &owner_hash, &owner_hash)?,
-                    votes_received: 0,
-                    rounds_won: 0,
-                    tie_round_wins: 0,
-                    decisive_round_wins: 0,
-                    voters: Vec::new(),
-                })
-                .votes_received = total_votes;
+                [],
+            )?;
         }
 
-        let mut breakdown_stmt = conn
-            .prepare(
-                r#"
-                SELECT owner_hash, voter_hash, SUM(vote_count), MAX(voter_display_name)
-                FROM mb_vote_events
-                GROUP BY owner_hash, voter_hash
-                "#,
-            )
-            .context("Failed to prepare Most Beautiful vote breakdown query")?;
-        let breakdown_rows = breakdown_stmt
-            .query_map([], |row| {
-                Ok((
-                    row.get::<_, String>(0)?,
-                    row.get::<_, String>(1)?,
-                    row.get::<_, u64>(2)?,
-                    row.get::<_, String>(3)?,
-                ))
-            })
-            .context("Failed to query Most Beautiful vote breakdown")?;
-        for row in breakdown_rows {
-            let (owner_hash, voter_hash, votes, voter_display_name) =
-                row.context("Failed to decode Most Beautiful vote breakdown row")?;
-            let owner = players
-                .entry(owner_hash.clone())
-                .or_insert(MostBeautifulPlayerStats {
-                    player_hash: owner_hash.clone(),
-                    display_name: Self::player_name(conn, &owner_hash, &owner_hash)?,
-                    votes_received: 0,
-                    rounds_won: 0,
-                    tie_round_wins: 0,
-                    decisive_round_wins: 0,
-                    voters: Vec::new(),
-                });
-            owner.voters.push(MostBeautifulVoterStats {
-                player_hash: voter_hash.clone(),
-                display_name: Self::player_name(conn, &voter_hash, &voter_display_name)?,
-                votes,
-            });
+        let store = MostBeautifulStatsStore::new(&path)?;
+        let version: u32 = store
+            .connect()?
+            .pragma_query_value(None, "user_version", |row| row.get(0))?;
+        assert_eq!(version, STATS_SCHEMA_VERSION);
+        assert_eq!(
+            store
+                .search_clues(&ClueSearchQuery {
+                    text: Some("lighthouse".to_string()),
+                    ..Default::default()
+                })?
+                .clues
+                .len(),
+            1,
+            "migrating should backfill the clue search index"
+        );
+
+        let backups = migration_backups(&path);
+        assert_eq!(backups.len(), 1);
+        let backup = Connection::open(&backups[0])?;
+        let backup_version: u32 =
+            backup.pragma_query_value(None, "user_version", |row| row.get(0))?;
+        assert_eq!(backup_version, 0);
+        let backup_games: u32 =
+            backup.query_row("SELECT COUNT(*) FROM mb_games", [], |row| row.get(0))?;
+        assert_eq!(backup_games, 1);
+
+        for backup_path in backups {
+            std::fs::remove_file(backup_path).ok();
+        }
+        std::fs::remove_file(&path).ok();
+        Ok(())
+    }
+
+    #[test]
+    fn newer_schema_version_is_refused() -> Result<()> {
+        let path = temp_db_path();
+        {
+            let conn = Connection::open(&path)?;
+            conn.pragma_update(None, "user_version", STATS_SCHEMA_VERSION + 1)?;
         }
+        let err = MostBeautifulStatsStore::new(&path).expect_err("newer schema should be refused");
+        assert!(err.to_string().contains("refusing to start"));
+        assert!(migration_backups(&path).is_empty());
 
+        std::fs::remove_file(&path).ok();
         Ok(())
     }
 
-    #[cfg(test)]
-    fn load_round_wins(
-        conn: &Connection,
-        players: &mut HashMap<String, MostBeautifulPlayerStats>,
-    ) -> Result<()> {
-        let mut stmt = conn
-            .prepare(
-                r#"
-                SELECT winner_hash, is_tie, COUNT(*)
-                FROM mb_round_win_events
-                GROUP BY winner_hash, is_tie
-                "#,
-            )
-            .context("Failed to prepare Most Beautiful round wins query")?;
-        let rows = stmt
-            .query_map([], |row| {
-                Ok((
-                    row.get::<_, String>(0)?,
-                    row.get::<_, bool>(1)?,
-                    row.get::<_, u64>(2)?,
-                ))
-            })
-            .context("Failed to query Most Beautiful round wins")?;
-        for row in rows {
-            let (winner_hash, is_tie, count) =
-                row.context("Failed to decode Most Beautiful round wins row")?;
-            let player = players
-                .entry(winner_hash.clone())
-                .or_insert(MostBeautifulPlayerStats {
-                    player_hash: winner_hash.clone(),
-                    display_name: Self::player_name(conn, &winner_hash, &winner_hash)?,
-                    votes_received: 0,
-                    rounds_won: 0,
-                    tie_round_wins: 0,
-                    decisive_round_wins: 0,
-                    voters: Vec::new(),
-                });
-            player.rounds_won += count;
-            if is_tie {
-                player.tie_round_wins += count;
-            } else {
-                player.decisive_round_wins += count;
-            }
+    #[test]
+    fn background_writer_batches_writes_and_flushes_before_reads() -> Result<()> {
+        let path = temp_db_path();
+        let store = MostBeautifulStatsStore::new(&path)?.with_background_writer(8)?;
+        assert!(store.writer_metrics().background_writer);
+
+        for round_num in 1..=5 {
+            store.record_game_audit_round(&replay_round_record("game-1", round_num))?;
         }
+        store.mark_game_complete("game-1", 99, 5, &[])?;
+        store.flush()?;
 
+        let replay = store
+            .export_game_replay("game-1")?
+            .expect("flushed game should be readable");
+        assert!(replay.completed);
+        assert_eq!(replay.rounds.len(), 5);
+
+        let metrics = store.writer_metrics();
+        assert_eq!(metrics.queue_capacity, 8);
+        assert_eq!(metrics.queue_depth, 0);
+        assert_eq!(metrics.writes_committed, 6);
+        assert_eq!(metrics.writes_failed, 0);
+        assert_eq!(metrics.dropped_writes, 0);
+        assert!(metrics.batches_committed >= 1);
+
+        let direct = MostBeautifulStatsStore::new(&path)?;
+        assert!(!direct.writer_metrics().background_writer);
+        direct.flush()?;
+
+        std::fs::remove_file(&path).ok();
         Ok(())
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::time::{SystemTime, UNIX_EPOCH};
+    #[test]
+    fn full_writer_queue_drops_writes_without_blocking() {
+        // nothing drains the receiver, so the queue stays full after one write
+        let (sender, _receiver) = mpsc::channel(1);
+        let writer = StatsWriter {
+            sender,
+            capacity: 1,
+            counters: Arc::new(StatsWriterCounters::default()),
+            games_missing_writes: Mutex::new(HashSet::new()),
+        };
+        let write = || StatsWrite::GameComplete {
+            game_id: "game-1".to_string(),
+            ended_at_s: 10,
+            total_rounds: 1,
+            standings: Vec::new(),
+            missing_writes: false,
+        };
 
-    fn temp_db_path() -> PathBuf {
-        std::env::temp_dir().join(format!(
-            "talespin-most-beautiful-{}-{}.sqlite3",
-            std::process::id(),
-            SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .unwrap_or_default()
-                .as_nanos()
-        ))
+        assert!(writer.enqueue(write()).is_ok());
+        assert!(writer.enqueue(write()).is_ok());
+        assert!(writer.enqueue(write()).is_ok());
+        assert_eq!(writer.counters.queue_depth.load(Ordering::Relaxed), 1);
+        assert_eq!(writer.counters.dropped_writes.load(Ordering::Relaxed), 2);
     }
 
-    fn audit_card(
-        card_hash: &str,
-        owner_hash: &str,
-        owner_display_name: &str,
-        is_storyteller_card: bool,
-        center_order: u16,
-    ) -> MostBeautifulGameAuditCardRecord {
-        MostBeautifulGameAuditCardRecord {
-            card_hash: card_hash.to_string(),
-            owner_hash: owner_hash.to_string(),
-            owner_display_name: owner_display_name.to_string(),
-            submitted_by_hash: owner_hash.to_string(),
-            submitted_by_display_name: owner_display_name.to_string(),
-            is_storyteller_card,
-            center_order,
-        }
+    #[test]
+    fn games_with_dropped_audit_rounds_are_not_rated_or_exported() -> Result<()> {
+        let path = temp_db_path();
+        let mut store = MostBeautifulStatsStore::new(&path)?;
+        store.record_game_audit_round(&replay_round_record("game-1", 1))?;
+
+        // nothing drains the receiver: round 2 fills the queue and round 3 is dropped
+        let (sender, receiver) = mpsc::channel(1);
+        store.writer = Some(Arc::new(StatsWriter {
+            sender,
+            capacity: 1,
+            counters: Arc::new(StatsWriterCounters::default()),
+            games_missing_writes: Mutex::new(HashSet::new()),
+        }));
+        store.record_game_audit_round(&replay_round_record("game-1", 2))?;
+        store.record_game_audit_round(&replay_round_record("game-1", 3))?;
+        assert_eq!(store.writer_metrics().dropped_writes, 1);
+
+        // with the writer gone, completion falls back to a direct write
+        drop(receiver);
+        store.mark_game_complete("game-1", 30, 3, &[])?;
+
+        let conn = store.connect()?;
+        let (completed, missing_writes, ended_at): (bool, bool, Option<u64>) = conn.query_row(
+            "SELECT completed, missing_writes, ended_at FROM mb_games WHERE game_id = 'game-1'",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )?;
+        assert_eq!(
+            (completed, missing_writes, ended_at),
+            (false, true, Some(30))
+        );
+        assert!(store.export_game_replay("game-1")?.is_none());
+        assert!(store.rating_leaderboard(None, 0)?.players.is_empty());
+
+        std::fs::remove_file(&path).ok();
+        Ok(())
     }
 
-    fn audit_vote(
-        voter_hash: &str,
-        voter_display_name: &str,
-        card_hash: &str,
-        vote_count: u16,
-    ) -> MostBeautifulGameAuditVoteRecord {
-        MostBeautifulGameAuditVoteRecord {
-            voter_hash: voter_hash.to_string(),
-            voter_display_name: voter_display_name.to_string(),
-            card_hash: card_hash.to_string(),
-            vote_count,
-        }
+    #[test]
+    fn maintenance_writes_run_in_order_with_queued_writes() -> Result<()> {
+        let path = temp_db_path();
+        let store = MostBeautifulStatsStore::new(&path)?.with_background_writer(16)?;
+        store.record_game_audit_round(&replay_round_record("game-1", 1))?;
+        store.record_game_audit_round(&replay_round_record("game-1", 2))?;
+
+        // no flush: the erasure is queued behind both rounds
+        let report = store.erase_player("p2-h", PlayerErasureMode::Anonymize)?;
+        assert!(report.rows_anonymized > 0);
+        assert_eq!(count_rows_named(&store, "P2")?, 0);
+
+        let replay = store.export_game_replay("game-1")?.expect("game recorded");
+        assert_eq!(replay.rounds.len(), 2);
+        let metrics = store.writer_metrics();
+        assert_eq!(metrics.writes_committed, 3);
+        assert_eq!(metrics.writes_failed, 0);
+
+        std::fs::remove_file(&path).ok();
+        Ok(())
     }
 
-    fn audit_score(
-        player_hash: &str,
-        player_display_name: &str,
-        story_delta: u16,
-        beauty_delta: i32,
-        total_after_round: u16,
-        beauty_total_after_round: u16,
-    ) -> MostBeautifulGameAuditScoreRecord {
-        MostBeautifulGameAuditScoreRecord {
-            player_hash: player_hash.to_string(),
-            player_display_name: player_display_name.to_string(),
-            story_delta,
-            beauty_delta,
-            total_after_round,
-            beauty_total_after_round,
+    fn stella_round_record(game_id: &str, round_num: u16) -> StellaGameAuditRoundRecord {
+        let player = |hash: &str, name: &str, fell: bool, delta: u16, total: u16| {
+            StellaGameAuditPlayerRecord {
+                player_hash: hash.to_string(),
+                player_display_name: name.to_string(),
+                in_the_dark: false,
+                fell,
+                score_delta: delta,
+                total_after_round: total,
+            }
+        };
+        let selection = |hash: &str, name: &str, card: &str| StellaGameAuditSelectionRecord {
+            player_hash: hash.to_string(),
+            player_display_name: name.to_string(),
+            card_hash: card.to_string(),
+        };
+        StellaGameAuditRoundRecord {
+            game_id: game_id.to_string(),
+            room_id: "room-s".to_string(),
+            game_started_at_s: 10,
+            recorded_at_s: 10 + u64::from(round_num),
+            round_num,
+            clue_word: format!("word-{round_num}"),
+            board_cards: vec![
+                StellaGameAuditBoardCardRecord {
+                    card_hash: "s-a".to_string(),
+                    board_order: 0,
+                    reveal_order: Some(0),
+                    points: Some(2),
+                },
+                StellaGameAuditBoardCardRecord {
+                    card_hash: "s-b".to_string(),
+                    board_order: 1,
+                    reveal_order: Some(1),
+                    points: Some(0),
+                },
+            ],
+            selections: vec![
+                selection("p1-h", "P1", "s-a"),
+                selection("p2-h", "P2", "s-a"),
+                selection("p2-h", "P2", "s-b"),
+            ],
+            players: vec![
+                player("p1-h", "P1", false, 2, 2 * round_num),
+                player("p2-h", "P2", true, 0, 0),
+            ],
         }
     }
 
     #[test]
-    fn store_aggregates_votes_and_round_wins_across_rooms() -> Result<()> {
+    fn stella_games_replay_rate_and_report_separately_from_dixit() -> Result<()> {
         let path = temp_db_path();
         let store = MostBeautifulStatsStore::new(&path)?;
-        store.register_card_paths(
-            1,
-            &HashMap::from([
-                ("card-a".to_string(), PathBuf::from("/tmp/card-a.avif")),
-                ("card-b".to_string(), PathBuf::from("/tmp/card-b.avif")),
-            ]),
-        )?;
-        store.register_card_paths(
-            2,
-            &HashMap::from([(
-                "card-a".to_string(),
-                PathBuf::from("/tmp/alternate-card-a.avif"),
-            )]),
-        )?;
+        store.record_game_audit_round(&replay_round_record("dixit-1", 1))?;
+        store.mark_game_complete("dixit-1", 50, 1, &[])?;
+        store.record_stella_audit_round(&stella_round_record("stella-1", 1))?;
+        store.record_stella_audit_round(&stella_round_record("stella-1", 2))?;
+        store.mark_game_complete("stella-1", 60, 2, &[])?;
 
-        store.record_round(&MostBeautifulRoundRecord {
-            recorded_at_s: 1,
-            room_id: "room-a".to_string(),
-            round_num: 1,
-            votes: vec![
-                MostBeautifulVoteRecord {
-                    voter_hash: "voter-a".to_string(),
-                    voter_display_name: "Voter A".to_string(),
-                    owner_hash: "owner-a".to_string(),
-                    owner_display_name: "Owner A".to_string(),
-                    card_hash: "card-a".to_string(),
-                    vote_count: 2,
-                },
-                MostBeautifulVoteRecord {
-                    voter_hash: "voter-b".to_string(),
-                    voter_display_name: "Voter B".to_string(),
-                    owner_hash: "owner-a".to_string(),
-                    owner_display_name: "Owner A".to_string(),
-                    card_hash: "card-a".to_string(),
-                    vote_count: 1,
-                },
-            ],
-            wins: vec![MostBeautifulRoundWinRecord {
-                winner_hash: "owner-a".to_string(),
-                winner_display_name: "Owner A".to_string(),
-                is_tie: false,
-            }],
-        })?;
+        let replay = store
+            .export_game_replay("stella-1")?
+            .expect("Stella game should be recorded");
+        assert_eq!(replay.game_mode, GAME_MODE_STELLA);
+        assert!(replay.completed);
+        assert!(replay.rounds.is_empty());
+        assert_eq!(replay.stella_rounds.len(), 2);
+        assert_eq!(replay.stella_rounds[1].clue_word, "word-2");
+        assert_eq!(replay.validate(), Ok(()));
 
-        store.record_round(&MostBeautifulRoundRecord {
-            recorded_at_s: 2,
-            room_id: "room-b".to_string(),
-            round_num: 3,
-            votes: vec![MostBeautifulVoteRecord {
-                voter_hash: "voter-a".to_string(),
-                voter_display_name: "Voter A Renamed".to_string(),
-                owner_hash: "owner-b".to_string(),
-                owner_display_name: "Owner B".to_string(),
-                card_hash: "card-b".to_string(),
-                vote_count: 1,
-            }],
-            wins: vec![
-                MostBeautifulRoundWinRecord {
-                    winner_hash: "owner-a".to_string(),
-                    winner_display_name: "Owner A".to_string(),
-                    is_tie: true,
-                },
-                MostBeautifulRoundWinRecord {
-                    winner_hash: "owner-b".to_string(),
-                    winner_display_name: "Owner B".to_string(),
-                    is_tie: true,
-                },
-            ],
-        })?;
+        let ratings = store.rating_leaderboard(Some("room-s"), 0)?;
+        assert_eq!(ratings.players.len(), 2);
+        assert_eq!(ratings.players[0].player_hash, "p1-h");
 
-        let stats = store.aggregated_stats()?;
+        let profile = store
+            .player_profile_stats("p2-h", None, 0)?
+            .expect("P2 has played");
         assert_eq!(
-            stats.players.len(),
-            4,
-            "players and voters should all be retained"
+            profile.games_played, 1,
+            "Dixit figures exclude Stella games"
+        );
+        assert_eq!(
+            profile.stella,
+            PlayerStellaStats {
+                games_played: 1,
+                games_completed: 1,
+                games_won: 0,
+                rounds_played: 2,
+                cards_selected: 4,
+                cards_scored: 2,
+                falls: 2,
+                rounds_in_the_dark: 0,
+                points: 0,
+            }
         );
 
-        let owner_a = stats
-            .players
-            .iter()
-            .find(|entry| entry.player_hash == "owner-a")
-            .context("missing owner-a stats")?;
-        assert_eq!(owner_a.display_name, "Owner A");
-        assert_eq!(owner_a.votes_received, 3);
-        assert_eq!(owner_a.rounds_won, 2);
-        assert_eq!(owner_a.tie_round_wins, 1);
-        assert_eq!(owner_a.decisive_round_wins, 1);
-        assert_eq!(owner_a.voters.len(), 2);
-        assert_eq!(owner_a.voters[0].display_name, "Voter A Renamed");
-        assert_eq!(owner_a.voters[0].votes, 2);
-
-        let owner_b = stats
-            .players
-            .iter()
-            .find(|entry| entry.player_hash == "owner-b")
-            .context("missing owner-b stats")?;
-        assert_eq!(owner_b.votes_received, 1);
-        assert_eq!(owner_b.rounds_won, 1);
-        assert_eq!(owner_b.tie_round_wins, 1);
-        assert_eq!(owner_b.decisive_round_wins, 0);
-
-        let conn = store.connect()?;
-        let mut card_path_stmt = conn.prepare(
-            "SELECT card_path FROM mb_card_paths WHERE card_hash = ?1 ORDER BY card_path",
+        let csv = stats_export_csv(
+            std::slice::from_ref(&replay),
+            StatsExportTable::StellaPlayers,
         )?;
-        let card_paths = card_path_stmt
-            .query_map(params!["card-a"], |row| row.get::<_, String>(0))?
-            .collect::<rusqlite::Result<Vec<_>>>()?;
         assert_eq!(
-            card_paths,
-            vec![
-                "/tmp/alternate-card-a.avif".to_string(),
-                "/tmp/card-a.avif".to_string()
-            ]
+            csv.lines().nth(1),
+            Some("stella-1,1,p1-h,P1,false,false,2,2")
+        );
+        assert_eq!(
+            stats_export_csv(std::slice::from_ref(&replay), StatsExportTable::Scores)?
+                .lines()
+                .count(),
+            1
+        );
+        assert_eq!(
+            store
+                .export_player_games("p2-h", Some("room-s"))?
+                .games
+                .len(),
+            1
         );
 
-        let stored_vote_card_hash: Option<String> = conn
-            .query_row(
-                "SELECT card_hash FROM mb_vote_events WHERE owner_hash = ?1 LIMIT 1",
-                params!["owner-a"],
-                |row| row.get(0),
-            )
-            .optional()?;
-        assert_eq!(stored_vote_card_hash.as_deref(), Some("card-a"));
+        let mut imported = replay.clone();
+        imported.game_id = "stella-imported".to_string();
+        store.import_game_replay(&imported)?;
+        let reloaded = store
+            .export_game_replay("stella-imported")?
+            .expect("imported Stella game should load");
+        assert_eq!(reloaded.stella_rounds, replay.stella_rounds);
+
+        let mut mismatched = replay;
+        mismatched.game_mode = GAME_MODE_DIXIT.to_string();
+        assert!(mismatched.validate().is_err());
 
         std::fs::remove_file(&path).ok();
         Ok(())
     }
 
+    fn count_rows_named(store: &MostBeautifulStatsStore, display_name: &str) -> Result<i64> {
+        let conn = store.connect()?;
+        let mut total = 0;
+        for column in PLAYER_NAME_COLUMNS {
+            total += conn.query_row(
+                &format!(
+                    "SELECT COUNT(*) FROM {} WHERE {} = ?1",
+                    column.table, column.name_column
+                ),
+                params![display_name],
+                |row| row.get::<_, i64>(0),
+            )?;
+        }
+        Ok(total)
+    }
+
     #[test]
-    fn store_persists_game_audit_rounds_and_completion() -> Result<()> {
+    fn erase_player_anonymizes_or_deletes_rows_across_tables() -> Result<()> {
         let path = temp_db_path();
-        let store = MostBeautifulStatsStore::new(&path)?;
-
-        store.record_game_audit_round(&MostBeautifulGameAuditRoundRecord {
-            game_id: "game-1".to_string(),
-            room_id: "room-a".to_string(),
-            game_started_at_s: 10,
-            recorded_at_s: 12,
-            round_num: 1,
-            storyteller_hash: "story-h".to_string(),
-            storyteller_display_name: "Story".to_string(),
-            clue: "moon".to_string(),
-            results_display_mode: "combined".to_string(),
-            card_entries: vec![
-                MostBeautifulGameAuditCardRecord {
-                    card_hash: "card-a".to_string(),
-                    owner_hash: "story-h".to_string(),
-                    owner_display_name: "Story".to_string(),
-                    submitted_by_hash: "story-h".to_string(),
-                    submitted_by_display_name: "Story".to_string(),
-                    is_storyteller_card: true,
-                    center_order: 0,
-                },
-                MostBeautifulGameAuditCardRecord {
-                    card_hash: "card-b".to_string(),
-                    owner_hash: "p2-h".to_string(),
-                    owner_display_name: "P2".to_string(),
-                    submitted_by_hash: "p2-h".to_string(),
-                    submitted_by_display_name: "P2".to_string(),
-                    is_storyteller_card: false,
-                    center_order: 1,
-                },
-            ],
-            story_votes: vec![MostBeautifulGameAuditVoteRecord {
+        let store = MostBeautifulStatsStore::new(&path)?;
+        store.record_round(&MostBeautifulRoundRecord {
+            recorded_at_s: 11,
+            room_id: "room-a".to_string(),
+            round_num: 1,
+            votes: vec![MostBeautifulVoteRecord {
                 voter_hash: "p2-h".to_string(),
                 voter_display_name: "P2".to_string(),
+                owner_hash: "story-h".to_string(),
+                owner_display_name: "Story".to_string(),
                 card_hash: "card-a".to_string(),
                 vote_count: 1,
             }],
-            beauty_votes: vec![MostBeautifulGameAuditVoteRecord {
-                voter_hash: "story-h".to_string(),
-                voter_display_name: "Story".to_string(),
-                card_hash: "card-b".to_string(),
-                vote_count: 1,
+            wins: vec![MostBeautifulRoundWinRecord {
+                winner_hash: "story-h".to_string(),
+                winner_display_name: "Story".to_string(),
+                is_tie: false,
             }],
-            score_log: vec![
-                MostBeautifulGameAuditScoreRecord {
-                    player_hash: "story-h".to_string(),
-                    player_display_name: "Story".to_string(),
-                    story_delta: 3,
-                    beauty_delta: 0,
-                    total_after_round: 3,
-                    beauty_total_after_round: 0,
-                },
-                MostBeautifulGameAuditScoreRecord {
-                    player_hash: "p2-h".to_string(),
-                    player_display_name: "P2".to_string(),
-                    story_delta: 3,
-                    beauty_delta: 2,
-                    total_after_round: 5,
-                    beauty_total_after_round: 2,
-                },
-            ],
         })?;
-        store.mark_game_complete("game-1", 20, 1)?;
-
-        let conn = store.connect()?;
-        let completed: (bool, u64, u64) = conn.query_row(
-            "SELECT completed, ended_at, total_rounds FROM mb_games WHERE game_id = ?1",
-            params!["game-1"],
-            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
-        )?;
-        assert_eq!(completed, (true, 20, 1));
+        store.record_game_audit_round(&replay_round_record("game-1", 1))?;
+        store.mark_game_complete("game-1", 20, 1, &[])?;
+        store.record_stella_audit_round(&stella_round_record("stella-1", 1))?;
+        store.mark_game_complete("stella-1", 30, 1, &[])?;
 
-        let card_count: u64 = conn.query_row(
-            "SELECT COUNT(*) FROM mb_game_round_cards WHERE game_id = ?1 AND round_num = 1",
-            params!["game-1"],
-            |row| row.get(0),
-        )?;
-        assert_eq!(card_count, 2);
+        let report = store.erase_player("p2-h", PlayerErasureMode::Anonymize)?;
+        assert_eq!(report.mode, "anonymize");
+        assert_eq!(report.rows_deleted, 0);
+        assert!(report.replacement_hash.starts_with("anon-"));
+        assert!(report.rows_anonymized > 0);
+        assert_eq!(count_rows_named(&store, "P2")?, 0);
+        assert!(store.player_profile_stats("p2-h", None, 0)?.is_none());
+        assert!(store
+            .rating_leaderboard(None, 0)?
+            .players
+            .iter()
+            .all(|player| player.player_hash != "p2-h"));
 
-        let beauty_vote_count: u64 = conn.query_row(
-            "SELECT COUNT(*) FROM mb_game_round_beauty_votes WHERE game_id = ?1 AND round_num = 1",
-            params!["game-1"],
-            |row| row.get(0),
-        )?;
-        assert_eq!(beauty_vote_count, 1);
+        let replay = store
+            .export_game_replay("game-1")?
+            .expect("anonymized game should still replay");
+        let card = &replay.rounds[0].center_cards[0];
+        assert_eq!(card.owner_hash, report.replacement_hash);
+        assert_eq!(card.owner_display_name, ANONYMOUS_PLAYER_NAME);
+        assert_eq!(replay.rounds[0].scores.len(), 2);
 
-        let p2_score: (u16, u16) = conn.query_row(
-            "SELECT total_after_round, beauty_total_after_round FROM mb_game_round_scores WHERE game_id = ?1 AND round_num = 1 AND player_hash = ?2",
-            params!["game-1", "p2-h"],
-            |row| Ok((row.get(0)?, row.get(1)?)),
-        )?;
-        assert_eq!(p2_score, (5, 2));
+        let report = store.erase_player("story-h", PlayerErasureMode::Delete)?;
+        assert!(report.rows_deleted > 0);
+        assert_eq!(count_rows_named(&store, "Story")?, 0);
+        let replay = store
+            .export_game_replay("game-1")?
+            .expect("rounds told by an erased player are kept");
+        let round = &replay.rounds[0];
+        assert_eq!(round.storyteller_hash, report.replacement_hash);
+        assert_eq!(round.storyteller_display_name, ANONYMOUS_PLAYER_NAME);
+        assert_eq!(round.center_cards.len(), 2, "other players' cards stay");
+        assert!(round.beauty_votes.is_empty());
+        assert_eq!(round.scores.len(), 1);
+        assert_eq!(round.story_votes.len(), 1);
+        assert!(store
+            .aggregated_stats()?
+            .players
+            .iter()
+            .all(|player| player.rounds_won == 0));
 
         std::fs::remove_file(&path).ok();
         Ok(())
     }
 
     #[test]
-    fn filtered_stats_prioritize_current_room_in_progress_then_latest_completed_games() -> Result<()>
-    {
+    fn retention_and_opt_out_hide_display_names() -> Result<()> {
         let path = temp_db_path();
-        let store = MostBeautifulStatsStore::new(&path)?;
+        let store = MostBeautifulStatsStore::new(&path)?.with_background_writer(4)?;
+        store.record_game_audit_round(&replay_round_record("game-1", 1))?;
+        store.record_game_audit_round(&replay_round_record("game-1", 2))?;
 
-        store.record_game_audit_round(&MostBeautifulGameAuditRoundRecord {
-            game_id: "game-current".to_string(),
-            room_id: "room-a".to_string(),
-            game_started_at_s: 300,
-            recorded_at_s: 301,
-            round_num: 1,
-            storyteller_hash: "story-current".to_string(),
-            storyteller_display_name: "Story Current".to_string(),
-            clue: "current".to_string(),
-            results_display_mode: "combined".to_string(),
-            card_entries: vec![
-                audit_card("current-story", "story-current", "Story Current", true, 0),
-                audit_card("current-win-1", "owner-current", "Owner Current", false, 1),
-                audit_card("current-win-2", "owner-current", "Owner Current", false, 2),
-                audit_card(
-                    "current-other",
-                    "owner-current-2",
-                    "Owner Current 2",
-                    false,
-                    3,
-                ),
-            ],
-            story_votes: vec![],
-            beauty_votes: vec![
-                audit_vote("voter-current-a", "Voter Current A", "current-win-1", 1),
-                audit_vote("voter-current-b", "Voter Current B", "current-win-2", 1),
-                audit_vote("voter-current-c", "Voter Current C", "current-other", 1),
-            ],
-            score_log: vec![
-                audit_score("owner-current", "Owner Current", 0, 2, 2, 2),
-                audit_score("owner-current-2", "Owner Current 2", 0, 1, 1, 1),
-            ],
-        })?;
+        assert!(store.anonymize_names_recorded_before(12)? > 0);
+        let replay = store.export_game_replay("game-1")?.expect("game recorded");
+        assert_eq!(
+            replay.rounds[0].storyteller_display_name,
+            ANONYMOUS_PLAYER_NAME
+        );
+        assert_eq!(replay.rounds[0].storyteller_hash, "story-h");
+        assert_eq!(replay.rounds[1].storyteller_display_name, "Story");
 
-        store.record_game_audit_round(&MostBeautifulGameAuditRoundRecord {
-            game_id: "game-completed-new".to_string(),
-            room_id: "room-b".to_string(),
-            game_started_at_s: 200,
-            recorded_at_s: 201,
-            round_num: 1,
-            storyteller_hash: "story-new".to_string(),
-            storyteller_display_name: "Story New".to_string(),
-            clue: "new".to_string(),
-            results_display_mode: "combined".to_string(),
-            card_entries: vec![
-                audit_card("new-story", "story-new", "Story New", true, 0),
-                audit_card("new-win", "owner-new", "Owner New", false, 1),
-                audit_card("new-other", "owner-new-2", "Owner New 2", false, 2),
-            ],
-            story_votes: vec![],
-            beauty_votes: vec![
-                audit_vote("voter-new-a", "Voter New A", "new-win", 2),
-                audit_vote("voter-new-b", "Voter New B", "new-win", 1),
-                audit_vote("voter-new-c", "Voter New C", "new-other", 1),
-            ],
-            score_log: vec![
-                audit_score("owner-new", "Owner New", 0, 3, 3, 3),
-                audit_score("owner-new-2", "Owner New 2", 0, 1, 1, 1),
-            ],
-        })?;
-        store.mark_game_complete("game-completed-new", 250, 1)?;
+        store.set_player_stats_opt_out("p2-h", true, 20)?;
+        store.record_game_audit_round(&replay_round_record("game-1", 3))?;
+        store.flush()?;
+        drop(store);
 
-        store.record_game_audit_round(&MostBeautifulGameAuditRoundRecord {
-            game_id: "game-completed-old".to_string(),
-            room_id: "room-c".to_string(),
-            game_started_at_s: 100,
-            recorded_at_s: 101,
-            round_num: 1,
-            storyteller_hash: "story-old".to_string(),
-            storyteller_display_name: "Story Old".to_string(),
-            clue: "old".to_string(),
-            results_display_mode: "combined".to_string(),
-            card_entries: vec![
-                audit_card("old-story", "story-old", "Story Old", true, 0),
-                audit_card("old-win", "owner-old", "Owner Old", false, 1),
-                audit_card("old-other", "owner-old-2", "Owner Old 2", false, 2),
-            ],
-            story_votes: vec![],
-            beauty_votes: vec![
-                audit_vote("voter-old-a", "Voter Old A", "old-win", 3),
-                audit_vote("voter-old-b", "Voter Old B", "old-win", 1),
-            ],
-            score_log: vec![audit_score("owner-old", "Owner Old", 0, 4, 4, 4)],
-        })?;
-        store.mark_game_complete("game-completed-old", 150, 1)?;
+        // the opt-out survives a restart
+        let store = MostBeautifulStatsStore::new(&path)?;
+        store.record_game_audit_round(&replay_round_record("game-1", 4))?;
+        store.set_player_stats_opt_out("p2-h", false, 30)?;
+        store.record_game_audit_round(&replay_round_record("game-1", 5))?;
 
-        let current_only = store.filtered_stats(Some("room-a"), 1)?;
+        let replay = store.export_game_replay("game-1")?.expect("game recorded");
+        let p2_names = replay
+            .rounds
+            .iter()
+            .map(|round| round.story_votes[0].voter_display_name.as_str())
+            .collect::<Vec<_>>();
         assert_eq!(
-            current_only
-                .players
-                .iter()
-                .map(|player| player.player_hash.as_str())
-                .collect::<Vec<_>>(),
-            vec!["owner-current", "owner-current-2"],
-            "N=1 should prefer the current room's in-progress game"
+            p2_names,
+            vec![
+                ANONYMOUS_PLAYER_NAME,
+                ANONYMOUS_PLAYER_NAME,
+                ANONYMOUS_PLAYER_NAME,
+                ANONYMOUS_PLAYER_NAME,
+                "P2"
+            ]
         );
-        assert_eq!(current_only.players[0].votes_received, 2);
-        assert_eq!(current_only.players[0].rounds_won, 1);
+        assert!(replay
+            .rounds
+            .iter()
+            .all(|round| round.story_votes[0].voter_hash == "p2-h"));
+        assert_eq!(replay.rounds[2].storyteller_display_name, "Story");
 
-        let current_plus_latest_completed = store.filtered_stats(Some("room-a"), 2)?;
+        std::fs::remove_file(&path).ok();
+        Ok(())
+    }
+
+    #[test]
+    fn accounts_sign_in_with_sessions_or_one_time_codes() -> Result<()> {
+        let path = temp_db_path();
+        let store = MostBeautifulStatsStore::new(&path)?;
+        let account = store
+            .create_account("Ada", None, 10)?
+            .expect("username is free");
+        assert!(store.create_account("ada", None, 11)?.is_none());
+        let (found, password_hash) = store.account_by_username("ADA")?.expect("case-insensitive");
+        assert_eq!(found, account);
+        assert!(password_hash.is_none());
+
+        let session = store.create_account_session(&account.account_id, 100)?;
         assert_eq!(
-            current_plus_latest_completed
-                .players
-                .iter()
-                .map(|player| player.player_hash.as_str())
-                .collect::<Vec<_>>(),
-            vec![
-                "owner-new",
-                "owner-current",
-                "owner-current-2",
-                "owner-new-2"
-            ],
-            "N=2 should include the current room plus the latest completed global game"
+            store.account_for_session(&session, 200)?,
+            Some(account.clone())
         );
+        assert!(store
+            .account_for_session(&session, 101 + ACCOUNT_SESSION_TTL_S)?
+            .is_none());
+        store.delete_account_session(&session)?;
+        assert!(store.account_for_session(&session, 200)?.is_none());
 
-        let latest_completed_only = store.filtered_stats(Some("room-z"), 1)?;
+        store.store_login_code(&account.account_id, &hash_login_code("GOODCODE"), 500)?;
+        assert!(store
+            .redeem_login_code("ada", &hash_login_code("badcode1"), 400)?
+            .is_none());
         assert_eq!(
-            latest_completed_only
-                .players
-                .iter()
-                .map(|player| player.player_hash.as_str())
-                .collect::<Vec<_>>(),
-            vec!["owner-new", "owner-new-2"],
-            "without a current-room exception, N=1 should use the latest completed global game"
+            store.redeem_login_code("ada", &hash_login_code("goodcode"), 400)?,
+            Some(account.clone())
+        );
+        assert!(
+            store
+                .redeem_login_code("ada", &hash_login_code("goodcode"), 400)?
+                .is_none(),
+            "codes work once"
         );
 
+        store.store_login_code(&account.account_id, &hash_login_code("GOODCODE"), 500)?;
+        for _ in 0..LOGIN_CODE_MAX_ATTEMPTS {
+            store.redeem_login_code("ada", &hash_login_code("badcode1"), 400)?;
+        }
+        assert!(store
+            .redeem_login_code("ada", &hash_login_code("goodcode"), 400)?
+            .is_none());
+
+        store.store_login_code(&account.account_id, &hash_login_code("GOODCODE"), 500)?;
+        assert!(store
+            .redeem_login_code("ada", &hash_login_code("goodcode"), 500)?
+            .is_none());
+
         std::fs::remove_file(&path).ok();
         Ok(())
     }
 
     #[test]
-    fn filtered_stats_limit_results_to_top_30_players() -> Result<()> {
+    fn merging_a_player_moves_history_onto_the_account() -> Result<()> {
         let path = temp_db_path();
         let store = MostBeautifulStatsStore::new(&path)?;
+        let account = store
+            .create_account("ada", Some("hash"), 10)?
+            .expect("username is free");
+        store.record_game_audit_round(&replay_round_record("game-1", 1))?;
+        store.mark_game_complete("game-1", 20, 1, &[])?;
+        store.set_player_stats_opt_out("p2-h", true, 30)?;
 
-        for index in 0..35 {
-            let game_id = format!("game-{index}");
-            let winner_hash = format!("owner-{index:02}");
-            let winner_name = format!("Owner {index:02}");
-            let other_hash = format!("other-{index:02}");
-            let other_name = format!("Other {index:02}");
-            let winner_card = format!("winner-card-{index:02}");
-            let other_card = format!("other-card-{index:02}");
-            let story_hash = format!("story-{index:02}");
-            let story_name = format!("Story {index:02}");
-            store.record_game_audit_round(&MostBeautifulGameAuditRoundRecord {
-                game_id: game_id.clone(),
-                room_id: format!("room-{index:02}"),
-                game_started_at_s: index as u64,
-                recorded_at_s: index as u64 + 1,
-                round_num: 1,
-                storyteller_hash: story_hash.clone(),
-                storyteller_display_name: story_name.clone(),
-                clue: format!("clue-{index:02}"),
-                results_display_mode: "combined".to_string(),
-                card_entries: vec![
-                    audit_card(
-                        &format!("story-card-{index:02}"),
-                        &story_hash,
-                        &story_name,
-                        true,
-                        0,
-                    ),
-                    audit_card(&winner_card, &winner_hash, &winner_name, false, 1),
-                    audit_card(&other_card, &other_hash, &other_name, false, 2),
-                ],
-                story_votes: vec![],
-                beauty_votes: vec![audit_vote(
-                    &format!("voter-{index:02}"),
-                    &format!("Voter {index:02}"),
-                    &winner_card,
-                    1,
-                )],
-                score_log: vec![audit_score(&winner_hash, &winner_name, 0, 1, 1, 1)],
-            })?;
-            store.mark_game_complete(&game_id, index as u64 + 2, 1)?;
-        }
+        let report = store.merge_player_into_account(&account, "p2-h", 40)?;
+        assert!(report.merged);
+        assert!(report.rows_moved > 0);
+        assert!(store.player_profile_stats("p2-h", None, 0)?.is_none());
+        let replay = store.export_game_replay("game-1")?.expect("game recorded");
+        let vote = &replay.rounds[0].story_votes[0];
+        assert_eq!(vote.voter_hash, account.player_hash);
+        assert_eq!(vote.voter_display_name, ANONYMOUS_PLAYER_NAME);
+        let opted_out: Vec<String> = store
+            .read_conn()?
+            .prepare("SELECT player_hash FROM mb_player_opt_outs")?
+            .query_map([], |row| row.get(0))?
+            .collect::<rusqlite::Result<_>>()?;
+        assert_eq!(opted_out, vec![account.player_hash.clone()]);
 
-        let stats = store.filtered_stats(Some("room-none"), 0)?;
+        assert!(
+            !store
+                .merge_player_into_account(&account, "p2-h", 50)?
+                .merged
+        );
+        let other = store
+            .create_account("grace", None, 10)?
+            .expect("username is free");
+        assert!(store
+            .merge_player_into_account(&account, &other.player_hash, 50)
+            .is_err());
+
+        std::fs::remove_file(&path).ok();
+        Ok(())
+    }
+
+    #[test]
+    fn persistent_rooms_are_created_once_saved_and_deleted() -> Result<()> {
+        let path = temp_db_path();
+        let store = MostBeautifulStatsStore::new(&path)?;
+        assert!(store.create_persistent_room("thursday-club", "owner-h", "{}", 10)?);
+        assert!(!store.create_persistent_room("thursday-club", "other-h", "{}", 11)?);
+
+        store.save_persistent_room_snapshot("thursday-club", r#"{"creator":"ada"}"#, 20)?;
+        drop(store);
+        let store = MostBeautifulStatsStore::new(&path)?;
         assert_eq!(
-            stats.players.len(),
-            30,
-            "filtered stats should cap the ranking panel to the top 30 players"
+            store.persistent_rooms()?,
+            vec![PersistentRoomRecord {
+                room_id: "thursday-club".to_string(),
+                owner_token_hash: "owner-h".to_string(),
+                snapshot: r#"{"creator":"ada"}"#.to_string(),
+                created_at_s: 10,
+                updated_at_s: 20,
+            }]
         );
 
+        assert_eq!(
+            store
+                .persistent_room("thursday-club")?
+                .map(|record| record.owner_token_hash),
+            Some("owner-h".to_string())
+        );
+        assert_eq!(store.persistent_room("friday-club")?, None);
+
+        assert!(store.delete_persistent_room("thursday-club")?);
+        assert!(!store.delete_persistent_room("thursday-club")?);
+        assert!(store.persistent_rooms()?.is_empty());
+        assert_eq!(store.persistent_room("thursday-club")?, None);
+
         std::fs::remove_file(&path).ok();
         Ok(())
     }
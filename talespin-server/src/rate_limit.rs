@@ -0,0 +1,216 @@
+use axum::http::HeaderMap;
+use dashmap::DashMap;
+use std::net::{IpAddr, SocketAddr};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TokenBucketConfig {
+    // burst size
+    pub capacity: f64,
+    pub refill_per_s: f64,
+}
+
+impl TokenBucketConfig {
+    // `limit` requests per minute with bursts of up to `limit`; 0 means unlimited
+    pub fn per_minute(limit: u32) -> Option<Self> {
+        (limit > 0).then(|| Self {
+            capacity: f64::from(limit),
+            refill_per_s: f64::from(limit) / 60.0,
+        })
+    }
+
+    pub fn per_second(rate: u32, burst: u32) -> Option<Self> {
+        (rate > 0).then(|| Self {
+            capacity: f64::from(burst.max(1)),
+            refill_per_s: f64::from(rate),
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TokenBucket {
+    config: TokenBucketConfig,
+    tokens: f64,
+    updated_at_ms: u64,
+}
+
+impl TokenBucket {
+    pub fn new(config: TokenBucketConfig, now_ms: u64) -> Self {
+        Self {
+            config,
+            tokens: config.capacity,
+            updated_at_ms: now_ms,
+        }
+    }
+
+    fn refill(&mut self, now_ms: u64) {
+        let elapsed_s = now_ms.saturating_sub(self.updated_at_ms) as f64 / 1000.0;
+        self.tokens =
+            (self.tokens + elapsed_s * self.config.refill_per_s).min(self.config.capacity);
+        self.updated_at_ms = now_ms;
+    }
+
+    // Takes one token if available. Rejected attempts cost nothing.
+    pub fn try_take(&mut self, now_ms: u64) -> bool {
+        self.refill(now_ms);
+        if self.tokens < 1.0 {
+            return false;
+        }
+        self.tokens -= 1.0;
+        true
+    }
+
+    fn is_full(&mut self, now_ms: u64) -> bool {
+        self.refill(now_ms);
+        self.tokens >= self.config.capacity
+    }
+}
+
+pub const DEFAULT_WS_MESSAGE_RATE: u32 = 15;
+pub const DEFAULT_WS_MESSAGE_BURST: u32 = 60;
+// room for a maximum-size custom Stella word pack plus JSON overhead
+pub const DEFAULT_WS_MAX_MESSAGE_BYTES: usize = 4 * 1024 * 1024;
+
+// Limits on what a single websocket connection may send.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WsMessageLimits {
+    pub rate: Option<TokenBucketConfig>,
+    pub max_message_bytes: usize,
+}
+
+impl Default for WsMessageLimits {
+    fn default() -> Self {
+        Self {
+            rate: TokenBucketConfig::per_second(DEFAULT_WS_MESSAGE_RATE, DEFAULT_WS_MESSAGE_BURST),
+            max_message_bytes: DEFAULT_WS_MAX_MESSAGE_BYTES,
+        }
+    }
+}
+
+// One token bucket per client IP. A missing config disables the limiter.
+#[derive(Debug)]
+pub struct IpRateLimiter {
+    config: Option<TokenBucketConfig>,
+    buckets: DashMap<IpAddr, TokenBucket>,
+}
+
+impl IpRateLimiter {
+    pub fn new(config: Option<TokenBucketConfig>) -> Self {
+        Self {
+            config,
+            buckets: DashMap::new(),
+        }
+    }
+
+    // Counts the request and returns whether it is allowed.
+    pub fn check(&self, ip: IpAddr, now_ms: u64) -> bool {
+        let Some(config) = self.config else {
+            return true;
+        };
+        self.buckets
+            .entry(ip)
+            .or_insert_with(|| TokenBucket::new(config, now_ms))
+            .try_take(now_ms)
+    }
+
+    // full buckets behave exactly like new ones, so they can be dropped
+    pub fn prune(&self, now_ms: u64) {
+        self.buckets.retain(|_, bucket| !bucket.is_full(now_ms));
+    }
+}
+
+// Per-IP limits: `http` covers every route, the others are extra limits on the endpoints that
+// reveal or create rooms, or check account credentials.
+#[derive(Debug)]
+pub struct RoomRateLimits {
+    pub http: IpRateLimiter,
+    pub exists: IpRateLimiter,
+    pub create: IpRateLimiter,
+    pub join: IpRateLimiter,
+    pub login: IpRateLimiter,
+}
+
+impl RoomRateLimits {
+    pub fn prune(&self, now_ms: u64) {
+        self.http.prune(now_ms);
+        self.exists.prune(now_ms);
+        self.create.prune(now_ms);
+        self.join.prune(now_ms);
+        self.login.prune(now_ms);
+    }
+}
+
+// The peer address, or the last X-Forwarded-For hop when the peer is a local reverse proxy.
+// Remote peers cannot spoof their address this way.
+pub fn client_ip(peer: SocketAddr, headers: &HeaderMap) -> IpAddr {
+    if !peer.ip().is_loopback() {
+        return peer.ip();
+    }
+    headers
+        .get("x-forwarded-for")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.rsplit(',').next())
+        .and_then(|hop| hop.trim().parse().ok())
+        .unwrap_or(peer.ip())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    #[test]
+    fn token_bucket_allows_bursts_then_refills_over_time() {
+        let config = TokenBucketConfig::per_second(2, 3).unwrap();
+        let mut bucket = TokenBucket::new(config, 1_000);
+
+        assert!((0..3).all(|_| bucket.try_take(1_000)));
+        assert!(!bucket.try_take(1_000));
+        assert!(!bucket.try_take(1_400), "0.8 tokens is not enough");
+        assert!(bucket.try_take(1_500));
+        assert!(bucket.try_take(10_000));
+        assert!((0..2).all(|_| bucket.try_take(10_000)));
+        assert!(!bucket.try_take(10_000), "refills stop at the burst size");
+
+        assert!(TokenBucketConfig::per_second(0, 10).is_none());
+        assert!(TokenBucketConfig::per_minute(0).is_none());
+    }
+
+    #[test]
+    fn ip_limiter_tracks_each_ip_and_prunes_idle_buckets() {
+        let limiter = IpRateLimiter::new(TokenBucketConfig::per_minute(2));
+        let a: IpAddr = "10.0.0.1".parse().unwrap();
+        let b: IpAddr = "10.0.0.2".parse().unwrap();
+
+        assert!(limiter.check(a, 0));
+        assert!(limiter.check(a, 0));
+        assert!(!limiter.check(a, 1_000));
+        assert!(limiter.check(b, 1_000), "limits are per IP");
+        assert!(limiter.check(a, 30_000), "one token refills every 30s");
+
+        limiter.prune(30_000);
+        assert_eq!(limiter.buckets.len(), 2);
+        limiter.prune(120_000);
+        assert!(limiter.buckets.is_empty());
+
+        let disabled = IpRateLimiter::new(None);
+        assert!((0..100).all(|_| disabled.check(a, 0)));
+    }
+
+    #[test]
+    fn forwarded_for_is_only_trusted_from_loopback_peers() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "x-forwarded-for",
+            HeaderValue::from_static("1.1.1.1, 203.0.113.7"),
+        );
+        let local: SocketAddr = "127.0.0.1:5000".parse().unwrap();
+        let remote: SocketAddr = "198.51.100.4:5000".parse().unwrap();
+
+        assert_eq!(
+            client_ip(local, &headers),
+            "203.0.113.7".parse::<IpAddr>().unwrap()
+        );
+        assert_eq!(client_ip(remote, &headers), remote.ip());
+        assert_eq!(client_ip(local, &HeaderMap::new()), local.ip());
+    }
+}
@@ -1,22 +1,32 @@
 use anyhow::{anyhow, Context, Result};
+use argon2::{
+    password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
+    Argon2,
+};
 use axum::{extract::ws::Message as WsMessage, extract::ws::WebSocket};
 use rand::{seq::SliceRandom, Rng};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::{
-    collections::{BTreeSet, HashMap, HashSet},
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque},
+    net::IpAddr,
     sync::{
         atomic::{AtomicU64, Ordering},
-        Arc,
+        Arc, Mutex,
     },
+    time::Duration,
 };
 use tokio::sync::{broadcast, mpsc, RwLock, RwLockWriteGuard};
 
+use crate::accounts::player_hash_for_token;
 use crate::most_beautiful_stats::{
-    MostBeautifulGameAuditCardRecord, MostBeautifulGameAuditRoundRecord,
-    MostBeautifulGameAuditScoreRecord, MostBeautifulGameAuditVoteRecord, MostBeautifulRoundRecord,
-    MostBeautifulRoundWinRecord, MostBeautifulStatsStore, MostBeautifulVoteRecord,
+    GameStandingRecord, MostBeautifulGameAuditCardRecord, MostBeautifulGameAuditClueRatingRecord,
+    MostBeautifulGameAuditRoundRecord, MostBeautifulGameAuditScoreRecord,
+    MostBeautifulGameAuditVoteRecord, MostBeautifulRoundRecord, MostBeautifulRoundWinRecord,
+    MostBeautifulStatsStore, MostBeautifulVoteRecord, StellaGameAuditBoardCardRecord,
+    StellaGameAuditPlayerRecord, StellaGameAuditRoundRecord, StellaGameAuditSelectionRecord,
 };
+use crate::rate_limit::{TokenBucket, WsMessageLimits};
 
 const DEFAULT_MODERATOR_ABSENCE_PROMOTION_DELAY_S: u64 = 8 * 60;
 const DEFAULT_CARDS_PER_HAND: u16 = 26;
@@ -53,6 +63,32 @@ const MAX_BEAUTY_POINTS_BONUS: u16 = 10;
 const DEFAULT_STORYTELLER_SUCCESS_POINTS: u16 = 3;
 const MIN_STORYTELLER_SUCCESS_POINTS: u16 = 0;
 const MAX_STORYTELLER_SUCCESS_POINTS: u16 = 10;
+const DEFAULT_STORYTELLER_PASS_PENALTY_POINTS: u16 = 1;
+const MIN_STORYTELLER_PASS_PENALTY_POINTS: u16 = 0;
+const MAX_STORYTELLER_PASS_PENALTY_POINTS: u16 = 10;
+const MAX_HANDICAP_OFFSET_POINTS: u16 = 20;
+const DEFAULT_CATCH_UP_BONUS_ENABLED: bool = false;
+const DEFAULT_CATCH_UP_BONUS_POINTS: u16 = 1;
+const MIN_CATCH_UP_BONUS_POINTS: u16 = 1;
+const MAX_CATCH_UP_BONUS_POINTS: u16 = 5;
+const DEFAULT_CATCH_UP_DEFICIT_POINTS: u16 = 5;
+const MIN_CATCH_UP_DEFICIT_POINTS: u16 = 1;
+const MAX_CATCH_UP_DEFICIT_POINTS: u16 = 50;
+const DEFAULT_SECRET_OBJECTIVES_ENABLED: bool = false;
+const DEFAULT_SECRET_OBJECTIVE_BONUS_POINTS: u16 = 3;
+const MIN_SECRET_OBJECTIVE_BONUS_POINTS: u16 = 1;
+const MAX_SECRET_OBJECTIVE_BONUS_POINTS: u16 = 10;
+const CHAT_HISTORY_LIMIT: usize = 100;
+const MAX_CHAT_MESSAGE_CHARS: usize = 500;
+const CHAT_RATE_LIMIT_MAX_ACTIONS: usize = 5;
+const CHAT_RATE_LIMIT_WINDOW_MS: u64 = 10_000;
+const CHAT_REACTIONS: [&str; 6] = ["👍", "😂", "😮", "😍", "🤔", "👏"];
+const DEFAULT_CHAT_STAGE_RESTRICTIONS_ENABLED: bool = true;
+const DEFAULT_SPECTATOR_DELAY_S: u16 = 0;
+const MIN_SPECTATOR_DELAY_S: u16 = 0;
+const MAX_SPECTATOR_DELAY_S: u16 = 600;
+const STATS_EXPORT_TOKEN_TTL_S: u64 = 60 * 60;
+const MAX_SUDDEN_DEATH_ROUNDS: u16 = 3;
 const DEFAULT_DOUBLE_VOTE_BONUS_POINTS: u16 = 2;
 const MIN_DOUBLE_VOTE_BONUS_POINTS: u16 = 0;
 const MAX_DOUBLE_VOTE_BONUS_POINTS: u16 = 10;
@@ -68,6 +104,16 @@ const DEFAULT_CLUE_RATING_MAX_STARS: u16 = 5;
 const MIN_CLUE_RATING_MAX_STARS: u16 = 1;
 const MAX_CLUE_RATING_MAX_STARS: u16 = 10;
 pub(crate) const MAX_MEMBER_NAME_LEN: usize = 30;
+pub(crate) const MAX_ROOM_PASSWORD_LEN: usize = 200;
+pub(crate) const MAX_PUBLIC_ROOM_TITLE_LEN: usize = 60;
+// enough for BCP 47 tags like "pt-BR" or a language name
+pub(crate) const MAX_PUBLIC_ROOM_LANGUAGE_LEN: usize = 35;
+// wrong room passwords an IP may submit before backoff starts
+const ROOM_PASSWORD_FREE_ATTEMPTS: u32 = 3;
+const ROOM_PASSWORD_BACKOFF_BASE_MS: u64 = 1_000;
+const ROOM_PASSWORD_BACKOFF_MAX_MS: u64 = 5 * 60 * 1_000;
+// an IP's failure count resets after this long without a wrong password
+const ROOM_PASSWORD_FAILURE_RESET_MS: u64 = 60 * 60 * 1_000;
 
 pub(crate) fn canonical_member_name(name: &str) -> &str {
     name.trim()
@@ -80,6 +126,159 @@ pub enum GameMode {
     Stella,
 }
 
+// moderator-set details shown in the public room directory
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct PublicRoomListing {
+    pub title: String,
+    pub language: String,
+}
+
+impl PublicRoomListing {
+    fn normalized(&self) -> Result<Self, &'static str> {
+        let title = self.title.trim();
+        let language = self.language.trim();
+        if title.is_empty() || language.is_empty() {
+            return Err("Public rooms need a title and a language");
+        }
+        if title.chars().count() > MAX_PUBLIC_ROOM_TITLE_LEN {
+            return Err("Public room title too long");
+        }
+        if language.chars().count() > MAX_PUBLIC_ROOM_LANGUAGE_LEN {
+            return Err("Public room language too long");
+        }
+        Ok(Self {
+            title: title.to_string(),
+            language: language.to_string(),
+        })
+    }
+}
+
+// one open table in /rooms/public and ServerMsg::PublicRooms
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct PublicRoomSummary {
+    pub room_id: String,
+    pub title: String,
+    pub language: String,
+    pub game_mode: GameMode,
+    pub stage: RoomStage,
+    pub player_count: usize,
+    pub observer_count: usize,
+    pub max_members: usize,
+    pub has_password: bool,
+}
+
+// What a persistent room keeps when it resets to its lobby or the server restarts. Stored as JSON
+// in mb_persistent_rooms.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct PersistentRoomSnapshot {
+    pub creator: Option<String>,
+    pub members: Vec<PersistentRoomMember>,
+    pub moderators: Vec<String>,
+    pub room_password_hash: Option<String>,
+    pub invite_key: Option<String>,
+    pub public_listing: Option<PublicRoomListing>,
+    // PersistentRoomSettings; keys missing from older snapshots keep the current defaults
+    pub settings: serde_json::Value,
+}
+
+// Members come back as disconnected players. Only a hash of their token is kept, which is also
+// their stats player_hash.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct PersistentRoomMember {
+    pub name: String,
+    pub token_hash: String,
+    pub room_auth_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct PersistentRoomSettings {
+    game_mode: GameMode,
+    win_condition: WinCondition,
+    moderator_absence_promotion_delay_s: u64,
+    allow_new_players_midgame: bool,
+    storyteller_loss_complement: u16,
+    storyteller_loss_complement_auto: bool,
+    storyteller_pool_enabled: bool,
+    // sorted so unchanged settings serialize identically
+    storyteller_pool_member_auth_ids: Vec<String>,
+    storyteller_success_points: u16,
+    storyteller_pass_penalty: StorytellerPassPenalty,
+    storyteller_pass_penalty_points: u16,
+    tiebreak_rules: Vec<TiebreakRule>,
+    member_handicap_offsets: HashMap<String, u16>,
+    catch_up_bonus_enabled: bool,
+    catch_up_bonus_points: u16,
+    catch_up_deficit_points: u16,
+    secret_objectives_enabled: bool,
+    secret_objective_bonus_points: u16,
+    chat_stage_restrictions_enabled: bool,
+    spectator_delay_s: u16,
+    double_vote_bonus_normal_points: u16,
+    double_vote_bonus_too_many_wrong_points: u16,
+    double_vote_bonus_too_many_wrong_follows_normal: bool,
+    double_vote_bonus_too_many_correct_points: u16,
+    double_vote_bonus_too_many_correct_follows_normal: bool,
+    votes_per_guesser: u16,
+    votes_per_guesser_auto: bool,
+    beauty_enabled: bool,
+    beauty_votes_per_player: u16,
+    beauty_votes_per_player_auto: bool,
+    beauty_allow_duplicate_votes: bool,
+    beauty_split_points_on_tie: bool,
+    beauty_points_bonus: u16,
+    beauty_scoring_mode: BeautyScoringMode,
+    beauty_vote_points_divisor_mode: BeautyVotePointsDivisorMode,
+    beauty_vote_points_divisor_tenths: u16,
+    beauty_vote_points_divisor_player_count_base: u16,
+    beauty_results_display_mode: BeautyResultsDisplayMode,
+    show_previous_results_during_storyteller_choosing: bool,
+    randomize_voting_card_order_per_viewer: bool,
+    cards_per_hand: u16,
+    nominations_per_guesser: u16,
+    bonus_correct_guess_on_threshold_correct_loss: bool,
+    bonus_threshold_loss_toggles_apply_to_all_storyteller_loss_rounds: bool,
+    show_voting_card_numbers: bool,
+    round_start_discard_all_unpinned: bool,
+    round_start_discard_count: u16,
+    hint_choosing_timer_enabled: bool,
+    hint_choosing_timer_duration_s: u16,
+    card_choosing_timer_enabled: bool,
+    card_choosing_timer_duration_s: u16,
+    voting_timer_enabled: bool,
+    voting_timer_duration_s: u16,
+    beauty_timer_enabled: bool,
+    beauty_timer_duration_s: u16,
+    clue_rating_enabled: bool,
+    clue_rating_max_stars: u16,
+    clue_rating_timer_enabled: bool,
+    clue_rating_timer_duration_s: u16,
+    force_hint_choosing_timer: bool,
+    force_card_choosing_timer: bool,
+    force_voting_timer: bool,
+    force_beauty_timer: bool,
+    force_clue_rating_timer: bool,
+    voting_wrong_card_disable_distribution: Vec<f64>,
+    stella_word_pack: Vec<String>,
+    stella_board_size: u16,
+    stella_selection_min: u16,
+    stella_selection_max: u16,
+    stella_queue_during_association: bool,
+    stella_queued_reveal_mode: StellaQueuedRevealMode,
+    stella_scout_timer_enabled: bool,
+    stella_scout_timer_duration_s: u16,
+    force_stella_scout_timer: bool,
+    copy_card_url_on_hold: bool,
+    leaderboard_view_mode_default: LeaderboardViewMode,
+}
+
+// why the server closed a member connection; sent in ServerMsg::Disconnected
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DisconnectReason {
+    RateLimited,
+    MessageTooLarge,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum StellaQueuedRevealMode {
@@ -110,7 +309,7 @@ pub enum PreviousDixitResultsView {
         active_card: String,
         beauty_results_display_mode: BeautyResultsDisplayMode,
         point_change: HashMap<String, i32>,
-        storyteller_point_change: HashMap<String, u16>,
+        storyteller_point_change: HashMap<String, i32>,
         beauty_point_change: HashMap<String, i32>,
         beauty_vote_totals: HashMap<String, u16>,
         beauty_winning_cards: Vec<String>,
@@ -137,6 +336,76 @@ pub struct RoundDeltaScores {
     beauty_point_change: HashMap<String, i32>,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum StorytellerPassPenalty {
+    None,
+    LosePoints,
+    CountsAsTurn,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TiebreakRule {
+    StorytellerSuccesses,
+    BeautyPoints,
+    ClueRating,
+    SuddenDeath,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct FinalStanding {
+    rank: u16,
+    name: String,
+    points: u16,
+    // first tiebreak rule that separated this member from an equal-points member ranked above
+    decided_by: Option<TiebreakRule>,
+    // observers with leftover points are ranked below every active player
+    observer: bool,
+    secret_objective: Option<SecretObjective>,
+    // bonus already included in points for a completed secret objective
+    secret_objective_bonus: u16,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SecretObjectiveKind {
+    // at least two players vote for one of your decoy cards in a single round
+    DecoyDoubleFool,
+    // own a Most Beautiful winning card in two rounds
+    BeautyWinnerTwice,
+    // succeed as storyteller with exactly one correct guesser
+    PreciseStoryteller,
+}
+
+impl SecretObjectiveKind {
+    fn target(self) -> u16 {
+        match self {
+            SecretObjectiveKind::DecoyDoubleFool => 1,
+            SecretObjectiveKind::BeautyWinnerTwice => 2,
+            SecretObjectiveKind::PreciseStoryteller => 1,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct ChatMessage {
+    id: u64,
+    sender: String,
+    text: String,
+    sent_at_ms: u64,
+    // emoji -> members who reacted with it
+    reactions: BTreeMap<String, BTreeSet<String>>,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct SecretObjective {
+    kind: SecretObjectiveKind,
+    progress: u16,
+    target: u16,
+    completed: bool,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum BeautyScoringMode {
@@ -184,6 +453,9 @@ pub struct DixitEndRoundHistoryEntry {
     player_clue_ratings: HashMap<String, u16>,
     total_after_round: HashMap<String, u16>,
     beauty_total_after_round: HashMap<String, u16>,
+    handicap_after_round: HashMap<String, u16>,
+    // points taken from storytellers who passed earlier in the round
+    pass_penalty_deltas: HashMap<String, u16>,
     results_display_mode: BeautyResultsDisplayMode,
 }
 
@@ -195,6 +467,7 @@ pub struct LeaderboardRoundHistoryEntry {
     beauty_deltas: HashMap<String, i32>,
     total_after_round: HashMap<String, u16>,
     beauty_total_after_round: HashMap<String, u16>,
+    raw_total_after_round: HashMap<String, u16>,
 }
 
 #[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
@@ -216,15 +489,36 @@ pub struct StellaWordPackPreset {
     pub words: Vec<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 #[serde(tag = "mode", rename_all = "snake_case")]
 pub enum WinCondition {
-    Points { target_points: u16 },
-    Cycles { target_cycles: u16 },
+    Points {
+        target_points: u16,
+    },
+    Cycles {
+        target_cycles: u16,
+    },
     CardsFinish,
-    FixedRounds { target_rounds: u16 },
+    FixedRounds {
+        target_rounds: u16,
+    },
+    // checked at round boundaries once the game has run for `minutes`
+    TimeLimit {
+        minutes: u16,
+        #[serde(default)]
+        finish_cycle: bool,
+    },
+    AnyOf {
+        conditions: Vec<WinCondition>,
+    },
+    AllOf {
+        conditions: Vec<WinCondition>,
+    },
 }
 
+pub(crate) const MAX_WIN_CONDITION_TIME_LIMIT_MINUTES: u16 = 24 * 60;
+pub(crate) const MAX_COMPOSITE_WIN_CONDITIONS: usize = 4;
+
 pub(crate) const DEFAULT_DIXIT_WIN_CONDITION: WinCondition =
     WinCondition::Cycles { target_cycles: 1 };
 pub(crate) const DEFAULT_STELLA_WIN_CONDITION: WinCondition =
@@ -239,12 +533,86 @@ pub(crate) fn default_win_condition_for_game_mode(game_mode: GameMode) -> WinCon
 
 pub(crate) fn win_condition_supported_by_game_mode(
     game_mode: GameMode,
-    win_condition: WinCondition,
+    win_condition: &WinCondition,
 ) -> bool {
-    !matches!(
-        (game_mode, win_condition),
-        (GameMode::Stella, WinCondition::Cycles { .. })
-    )
+    match win_condition {
+        WinCondition::Cycles { .. } => !matches!(game_mode, GameMode::Stella),
+        WinCondition::TimeLimit { finish_cycle, .. } => {
+            !(*finish_cycle && matches!(game_mode, GameMode::Stella))
+        }
+        WinCondition::AnyOf { conditions } | WinCondition::AllOf { conditions } => conditions
+            .iter()
+            .all(|condition| win_condition_supported_by_game_mode(game_mode, condition)),
+        WinCondition::Points { .. }
+        | WinCondition::CardsFinish
+        | WinCondition::FixedRounds { .. } => true,
+    }
+}
+
+pub(crate) fn validate_win_condition(win_condition: WinCondition) -> Result<WinCondition> {
+    match win_condition {
+        WinCondition::Points { target_points } => {
+            if target_points == 0 {
+                return Err(anyhow!("target_points must be >= 1"));
+            }
+            Ok(WinCondition::Points { target_points })
+        }
+        WinCondition::Cycles { target_cycles } => {
+            if target_cycles == 0 {
+                return Err(anyhow!("target_cycles must be >= 1"));
+            }
+            Ok(WinCondition::Cycles { target_cycles })
+        }
+        WinCondition::FixedRounds { target_rounds } => {
+            if target_rounds == 0 {
+                return Err(anyhow!("target_rounds must be >= 1"));
+            }
+            Ok(WinCondition::FixedRounds { target_rounds })
+        }
+        WinCondition::CardsFinish => Ok(WinCondition::CardsFinish),
+        WinCondition::TimeLimit {
+            minutes,
+            finish_cycle,
+        } => {
+            if minutes == 0 || minutes > MAX_WIN_CONDITION_TIME_LIMIT_MINUTES {
+                return Err(anyhow!(
+                    "minutes must be between 1 and {}",
+                    MAX_WIN_CONDITION_TIME_LIMIT_MINUTES
+                ));
+            }
+            Ok(WinCondition::TimeLimit {
+                minutes,
+                finish_cycle,
+            })
+        }
+        WinCondition::AnyOf { conditions } => Ok(WinCondition::AnyOf {
+            conditions: validate_composite_win_conditions(conditions)?,
+        }),
+        WinCondition::AllOf { conditions } => Ok(WinCondition::AllOf {
+            conditions: validate_composite_win_conditions(conditions)?,
+        }),
+    }
+}
+
+fn validate_composite_win_conditions(conditions: Vec<WinCondition>) -> Result<Vec<WinCondition>> {
+    if conditions.len() < 2 || conditions.len() > MAX_COMPOSITE_WIN_CONDITIONS {
+        return Err(anyhow!(
+            "composite win conditions need 2 to {} conditions",
+            MAX_COMPOSITE_WIN_CONDITIONS
+        ));
+    }
+    conditions
+        .into_iter()
+        .map(|condition| {
+            if matches!(
+                condition,
+                WinCondition::AnyOf { .. } | WinCondition::AllOf { .. }
+            ) {
+                return Err(anyhow!("composite win conditions cannot be nested"));
+            }
+            validate_win_condition(condition)
+        })
+        .collect()
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -259,6 +627,9 @@ pub enum ServerMsg {
         name: String,
         room_auth_id: String,
     },
+    JoinedAsSpectator {
+        delay_s: u16,
+    },
     MemberMigrateLink {
         player: String,
         room_auth_id: String,
@@ -266,6 +637,21 @@ pub enum ServerMsg {
     CurrentInfoMarkdown {
         markdown: String,
     },
+    StatsExportToken {
+        token: String,
+        expires_at_s: u64,
+    },
+    StatsOptOut {
+        opt_out: bool,
+    },
+    // reply to SetRoomPassword
+    RoomPasswordUpdated {
+        has_password: bool,
+    },
+    // public room directory for SubscribePublicRooms; resent whenever it changes
+    PublicRooms {
+        rooms: Vec<PublicRoomSummary>,
+    },
     RoomState {
         room_id: String,
         game_mode: GameMode,
@@ -280,6 +666,7 @@ pub enum ServerMsg {
         cards_remaining: u32,
         deck_refill_count: u32,
         win_condition: WinCondition,
+        game_started_at_s: Option<u64>,
         allow_new_players_midgame: bool,
         paused_reason: Option<String>,
         storyteller_loss_complement: u16,
@@ -292,6 +679,41 @@ pub enum ServerMsg {
         storyteller_success_points: u16,
         storyteller_success_points_min: u16,
         storyteller_success_points_max: u16,
+        storyteller_pass_penalty: StorytellerPassPenalty,
+        storyteller_pass_penalty_points: u16,
+        storyteller_pass_penalty_points_min: u16,
+        storyteller_pass_penalty_points_max: u16,
+        tiebreak_rules: Vec<TiebreakRule>,
+        sudden_death_rounds_played: u16,
+        handicap_offsets: HashMap<String, u16>,
+        handicap_offset_max: u16,
+        handicap_points: HashMap<String, u16>,
+        catch_up_bonus_enabled: bool,
+        catch_up_bonus_points: u16,
+        catch_up_bonus_points_min: u16,
+        catch_up_bonus_points_max: u16,
+        catch_up_deficit_points: u16,
+        catch_up_deficit_points_min: u16,
+        catch_up_deficit_points_max: u16,
+        secret_objectives_enabled: bool,
+        secret_objective_bonus_points: u16,
+        secret_objective_bonus_points_min: u16,
+        secret_objective_bonus_points_max: u16,
+        // every member's objective, revealed only once the game has ended
+        revealed_secret_objectives: HashMap<String, SecretObjective>,
+        chat_muted_members: Vec<String>,
+        chat_stage_restrictions_enabled: bool,
+        spectator_token: Option<String>,
+        // unlisted rooms only; members add it to invite links
+        invite_key: Option<String>,
+        has_password: bool,
+        public_listing: Option<PublicRoomListing>,
+        // long-lived room that resets to its lobby instead of being garbage collected
+        persistent: bool,
+        spectator_delay_s: u16,
+        spectator_delay_s_min: u16,
+        spectator_delay_s_max: u16,
+        spectator_count: usize,
         double_vote_bonus_normal_points: u16,
         double_vote_bonus_too_many_wrong_points: u16,
         double_vote_bonus_too_many_wrong_follows_normal: bool,
@@ -449,7 +871,7 @@ pub enum ServerMsg {
         player_to_current_cards: HashMap<String, Vec<String>>,
         active_card: String,
         point_change: HashMap<String, i32>,
-        storyteller_point_change: HashMap<String, u16>,
+        storyteller_point_change: HashMap<String, i32>,
         beauty_point_change: HashMap<String, i32>,
         beauty_vote_totals: HashMap<String, u16>,
         beauty_winning_cards: Vec<String>,
@@ -512,7 +934,50 @@ pub enum ServerMsg {
         reason: String,
     },
     InvalidRoomId {},
-    EndGame {},
+    // JoinRoom carried an unknown or expired account session
+    AccountSessionExpired {},
+    // sent right before the server closes the socket
+    Disconnected {
+        reason: DisconnectReason,
+        message: String,
+    },
+    SecretObjective {
+        objective: SecretObjective,
+    },
+    Chat {
+        message: ChatMessage,
+    },
+    ChatHistory {
+        messages: Vec<ChatMessage>,
+    },
+    ChatDeleted {
+        message_id: u64,
+    },
+    EndGame {
+        standings: Vec<FinalStanding>,
+        // audited Dixit game id, usable with the replay export endpoint
+        game_id: Option<String>,
+    },
+}
+
+impl ServerMsg {
+    // spectator links may be public, so they never carry the unlisted room's invite key
+    fn without_invite_key(mut self) -> Self {
+        if let Self::RoomState { invite_key, .. } = &mut self {
+            *invite_key = None;
+        }
+        self
+    }
+
+    fn without_spectator_token(mut self) -> Self {
+        if let Self::RoomState {
+            spectator_token, ..
+        } = &mut self
+        {
+            *spectator_token = None;
+        }
+        self
+    }
 }
 
 impl From<ServerMsg> for WsMessage {
@@ -535,6 +1000,25 @@ pub enum ClientMsg {
         player: String,
     },
     RequestCurrentInfo {},
+    RequestStatsExportToken {},
+    // keeps the sender's display name out of Most Beautiful stats
+    SetStatsOptOut {
+        opt_out: bool,
+    },
+    // moderators only; None or a blank password removes it
+    SetRoomPassword {
+        password: Option<String>,
+    },
+    // moderators only; None takes the room out of the public directory
+    SetPublicListing {
+        listing: Option<PublicRoomListing>,
+    },
+    // first message on a socket that only watches the public room directory
+    SubscribePublicRooms {},
+    // persistent rooms only; the owner token makes the sender the room's creator and a moderator
+    ClaimRoomOwner {
+        owner_token: String,
+    },
     RaiseScoreToActiveMin {
         player: String,
     },
@@ -576,6 +1060,56 @@ pub enum ClientMsg {
     SetStorytellerSuccessPoints {
         points: u16,
     },
+    SetStorytellerPassPenalty {
+        penalty: StorytellerPassPenalty,
+    },
+    SetStorytellerPassPenaltyPoints {
+        points: u16,
+    },
+    SetHandicapOffset {
+        player: String,
+        points: u16,
+    },
+    SetTiebreakRules {
+        rules: Vec<TiebreakRule>,
+    },
+    SetCatchUpBonusEnabled {
+        enabled: bool,
+    },
+    SetCatchUpBonusPoints {
+        points: u16,
+    },
+    SetCatchUpDeficitPoints {
+        points: u16,
+    },
+    SetSecretObjectivesEnabled {
+        enabled: bool,
+    },
+    SetSecretObjectiveBonusPoints {
+        points: u16,
+    },
+    SendChat {
+        text: String,
+    },
+    React {
+        message_id: u64,
+        emoji: String,
+    },
+    SetChatMuted {
+        member: String,
+        muted: bool,
+    },
+    DeleteChatMessage {
+        message_id: u64,
+    },
+    SetChatStageRestrictionsEnabled {
+        enabled: bool,
+    },
+    CreateSpectatorLink {},
+    RevokeSpectatorLink {},
+    SetSpectatorDelay {
+        seconds: u16,
+    },
     SetDoubleVoteBonusNormalPoints {
         points: u16,
     },
@@ -755,6 +1289,7 @@ pub enum ClientMsg {
     ForceStartNextRound {},
     ForceEndGame {},
     ResetClue {},
+    PassStoryteller {},
     RefreshHands {},
     ResumeGame {},
     RequestJoinFromObserver {},
@@ -763,6 +1298,16 @@ pub enum ClientMsg {
         name: String,
         token: String,
         room_password: Option<String>,
+        // invite key from the full invite link; required to join unlisted rooms
+        #[serde(default)]
+        invite: Option<String>,
+        // signed-in players join as their account instead of this browser's token
+        #[serde(default)]
+        account_session: Option<String>,
+    },
+    JoinAsSpectator {
+        room_id: String,
+        spectator_token: String,
     },
     CreateRoom {
         name: String,
@@ -888,6 +1433,15 @@ struct RoomState {
     next_generation: u64,
     // optional room password hash (never broadcast)
     room_password_hash: Option<String>,
+    // set for unlisted rooms; non-members need it to find or join the room
+    invite_key: Option<String>,
+    // listed in /rooms/public while set; never set together with invite_key
+    public_listing: Option<PublicRoomListing>,
+    // set for persistent rooms; hash of the owner token handed out when the room was created
+    owner_token_hash: Option<String>,
+    // members restored from a persistent room snapshot who have not rejoined yet, keyed by name;
+    // stands in for name_tokens until they do
+    restored_member_token_hashes: HashMap<String, String>,
     // moderators can toggle this after the game starts
     allow_new_players_midgame: bool,
     // user-facing pause reason for RoomStage::Paused
@@ -904,6 +1458,48 @@ struct RoomState {
     storyteller_pool_member_auth_ids: HashSet<String>,
     // storyteller reward in successful rounds
     storyteller_success_points: u16,
+    // consequence applied when the storyteller passes their turn
+    storyteller_pass_penalty: StorytellerPassPenalty,
+    // points deducted by the lose-points pass penalty
+    storyteller_pass_penalty_points: u16,
+    // ordered tiebreakers applied to equal final scores
+    tiebreak_rules: Vec<TiebreakRule>,
+    // extra rounds already played this game because the leaders were still tied
+    sudden_death_rounds_played: u16,
+    // successful (non-loss) storyteller rounds for each member in the current game
+    member_to_storyteller_successes: HashMap<String, u16>,
+    // moderator-set starting point offsets applied when a Dixit game starts, keyed by member name
+    member_handicap_offsets: HashMap<String, u16>,
+    // handicap points awarded this game (starting offset plus catch-up bonuses), keyed by member name
+    member_handicap_points: HashMap<String, u16>,
+    // whether trailing players earn a catch-up bonus on correct guesses
+    catch_up_bonus_enabled: bool,
+    // catch-up bonus per correct guess
+    catch_up_bonus_points: u16,
+    // how far behind the leader a player must be before the catch-up bonus applies
+    catch_up_deficit_points: u16,
+    // whether players are dealt a private bonus objective when a Dixit game starts
+    secret_objectives_enabled: bool,
+    // final-standings bonus for a completed secret objective
+    secret_objective_bonus_points: u16,
+    // objective dealt to each player in the current game, keyed by member name
+    member_secret_objectives: HashMap<String, SecretObjective>,
+    // most recent chat messages, oldest first, capped at CHAT_HISTORY_LIMIT
+    chat_history: VecDeque<ChatMessage>,
+    next_chat_message_id: u64,
+    // members a moderator has muted in chat
+    chat_muted_members: HashSet<String>,
+    // whether chat is paused for members who could leak hints in the current stage
+    chat_stage_restrictions_enabled: bool,
+    // timestamps of each member's recent chat messages and reactions, for rate limiting
+    chat_recent_actions_ms: HashMap<String, VecDeque<u64>>,
+    // token for the read-only spectator link, None while spectating is disabled
+    spectator_token: Option<String>,
+    // how far spectator connections lag behind the live room
+    spectator_delay_s: u16,
+    // bearer token that lets moderators export this room's audit history over HTTP
+    stats_export_token: Option<String>,
+    stats_export_token_expires_at_s: u64,
     // extra points for guessers with 2+ correct vote tokens in normal rounds
     double_vote_bonus_normal_points: u16,
     // extra points for guessers with 2+ correct vote tokens in storyteller-loss rounds with too many wrong guesses
@@ -1050,6 +1646,8 @@ struct RoomState {
     storyteller_point_change: HashMap<String, u16>,
     // cached beauty-stage point change for the current round
     beauty_point_change: HashMap<String, i32>,
+    // points lost this round by storytellers who passed with the lose-points penalty
+    storyteller_pass_penalty_change: HashMap<String, u16>,
     // accumulated beauty points already applied to each member this game
     member_to_beauty_points: HashMap<String, u16>,
     // sum of recorded storyteller-round clue averages for each member in the current game
@@ -1066,10 +1664,10 @@ struct RoomState {
     vote_divisor_segment_start_history_index: Option<usize>,
     // per-round owner vote totals keyed by the history entry they came from
     vote_divisor_round_vote_totals_by_history_index: HashMap<usize, HashMap<String, u32>>,
-    // in-progress/current Dixit game id for persisted audit history
-    current_dixit_game_id: Option<String>,
-    // timestamp when the current Dixit game began
-    current_dixit_game_started_at_s: Option<u64>,
+    // in-progress/current Dixit or Stella game id for persisted audit history
+    current_game_id: Option<String>,
+    // timestamp when the current audited game began
+    current_game_started_at_s: Option<u64>,
     // condensed per-round history used by the end-game Dixit leaderboard
     dixit_end_round_history: Vec<DixitEndRoundHistoryEntry>,
     // previous Dixit results snapshot shown during the following storyteller-choosing stage
@@ -1126,6 +1724,8 @@ struct RoomState {
     player_to_disabled_voting_cards: HashMap<String, Vec<String>>,
     // configured win condition for this room
     win_condition: WinCondition,
+    // wall-clock start of the current game, used by time-limit win conditions
+    game_started_at_s: Option<u64>,
     // increments whenever draw deck is refilled from base deck
     deck_refill_count: u32,
     // room-wide default leaderboard mode for Dixit rooms
@@ -1141,6 +1741,8 @@ pub struct Room {
     state: RwLock<RoomState>,
     // send updates to everyone in the room
     broadcast: broadcast::Sender<ServerMsg>,
+    // public-only updates for spectator connections, which are not room members
+    spectator_broadcast: broadcast::Sender<ServerMsg>,
     // keep pointer to the base deck for refills
     base_deck: Arc<Vec<String>>,
     // default word pack used when a room has no active Resonance pack
@@ -1151,6 +1753,10 @@ pub struct Room {
     most_beautiful_stats: Arc<MostBeautifulStatsStore>,
     // cap for players + observers in a room
     max_members: usize,
+    // per-connection limits on incoming member messages
+    message_limits: WsMessageLimits,
+    // wrong room password attempts per client IP
+    password_failures: Mutex<HashMap<IpAddr, RoomPasswordFailures>>,
     // last access in seconds
     last_access: AtomicU64,
 }
@@ -1171,12 +1777,62 @@ pub fn get_time_ms() -> u64 {
         .unwrap_or(u64::MAX)
 }
 
-pub fn hash_room_password(room_id: &str, password: &str) -> String {
-    let mut hasher = Sha256::new();
-    hasher.update(room_id.as_bytes());
-    hasher.update([0]);
-    hasher.update(password.as_bytes());
-    format!("{:x}", hasher.finalize())
+pub(crate) fn generate_room_owner_token() -> String {
+    format!(
+        "{:032x}{:032x}",
+        rand::random::<u128>(),
+        rand::random::<u128>()
+    )
+}
+
+// Owner tokens are long and random, so a fast hash is enough.
+pub(crate) fn hash_room_owner_token(owner_token: &str) -> String {
+    player_hash_for_token(owner_token.trim())
+}
+
+// Argon2id PHC string with a random salt, so equal passwords in two rooms hash differently.
+// Takes tens of milliseconds by design; call it off the async runtime.
+pub fn hash_room_password(password: &str) -> String {
+    let salt = SaltString::encode_b64(&rand::random::<[u8; 16]>())
+        .expect("16 salt bytes are within the PHC salt length limits");
+    Argon2::default()
+        .hash_password(password.as_bytes(), &salt)
+        .expect("default argon2 params accept any password length")
+        .to_string()
+}
+
+// The hash output comparison inside verify_password is constant-time.
+pub fn verify_room_password(password_hash: &str, password: &str) -> bool {
+    PasswordHash::new(password_hash).is_ok_and(|parsed| {
+        Argon2::default()
+            .verify_password(password.as_bytes(), &parsed)
+            .is_ok()
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RoomPasswordCheck {
+    Correct,
+    Incorrect,
+    RetryAfter { seconds: u64 },
+}
+
+#[derive(Debug, Clone, Copy)]
+struct RoomPasswordFailures {
+    count: u32,
+    last_failure_ms: u64,
+}
+
+impl RoomPasswordFailures {
+    // doubles with every failure past the free attempts
+    fn retry_at_ms(&self) -> u64 {
+        if self.count < ROOM_PASSWORD_FREE_ATTEMPTS {
+            return 0;
+        }
+        let doublings = (self.count - ROOM_PASSWORD_FREE_ATTEMPTS).min(20);
+        self.last_failure_ms
+            + (ROOM_PASSWORD_BACKOFF_BASE_MS << doublings).min(ROOM_PASSWORD_BACKOFF_MAX_MS)
+    }
 }
 
 impl Room {
@@ -1222,6 +1878,10 @@ impl Room {
             connection_generation: HashMap::new(),
             next_generation: 0,
             room_password_hash,
+            invite_key: None,
+            public_listing: None,
+            owner_token_hash: None,
+            restored_member_token_hashes: HashMap::new(),
             allow_new_players_midgame: true,
             paused_reason: None,
             paused_needs_storyteller_selection: false,
@@ -1230,6 +1890,28 @@ impl Room {
             storyteller_pool_enabled: false,
             storyteller_pool_member_auth_ids: HashSet::new(),
             storyteller_success_points: DEFAULT_STORYTELLER_SUCCESS_POINTS,
+            storyteller_pass_penalty: StorytellerPassPenalty::None,
+            storyteller_pass_penalty_points: DEFAULT_STORYTELLER_PASS_PENALTY_POINTS,
+            tiebreak_rules: Vec::new(),
+            sudden_death_rounds_played: 0,
+            member_to_storyteller_successes: HashMap::new(),
+            member_handicap_offsets: HashMap::new(),
+            member_handicap_points: HashMap::new(),
+            catch_up_bonus_enabled: DEFAULT_CATCH_UP_BONUS_ENABLED,
+            catch_up_bonus_points: DEFAULT_CATCH_UP_BONUS_POINTS,
+            catch_up_deficit_points: DEFAULT_CATCH_UP_DEFICIT_POINTS,
+            secret_objectives_enabled: DEFAULT_SECRET_OBJECTIVES_ENABLED,
+            secret_objective_bonus_points: DEFAULT_SECRET_OBJECTIVE_BONUS_POINTS,
+            member_secret_objectives: HashMap::new(),
+            chat_history: VecDeque::new(),
+            next_chat_message_id: 0,
+            chat_muted_members: HashSet::new(),
+            chat_stage_restrictions_enabled: DEFAULT_CHAT_STAGE_RESTRICTIONS_ENABLED,
+            spectator_token: None,
+            spectator_delay_s: DEFAULT_SPECTATOR_DELAY_S,
+            stats_export_token: None,
+            stats_export_token_expires_at_s: 0,
+            chat_recent_actions_ms: HashMap::new(),
             double_vote_bonus_normal_points: DEFAULT_DOUBLE_VOTE_BONUS_POINTS,
             double_vote_bonus_too_many_wrong_points: DEFAULT_DOUBLE_VOTE_BONUS_POINTS,
             double_vote_bonus_too_many_wrong_follows_normal: true,
@@ -1303,6 +1985,7 @@ impl Room {
             forced_random_vote_cards: HashMap::new(),
             storyteller_point_change: HashMap::new(),
             beauty_point_change: HashMap::new(),
+            storyteller_pass_penalty_change: HashMap::new(),
             member_to_beauty_points: HashMap::new(),
             member_to_clue_rating_average_sum: HashMap::new(),
             member_to_clue_rating_rounds: HashMap::new(),
@@ -1311,8 +1994,8 @@ impl Room {
             vote_divisor_completed_rounds: 0,
             vote_divisor_segment_start_history_index: None,
             vote_divisor_round_vote_totals_by_history_index: HashMap::new(),
-            current_dixit_game_id: None,
-            current_dixit_game_started_at_s: None,
+            current_game_id: None,
+            current_game_started_at_s: None,
             dixit_end_round_history: Vec::new(),
             previous_dixit_results: None,
             stella_leaderboard_round_history: Vec::new(),
@@ -1341,25 +2024,35 @@ impl Room {
             player_to_disabled_voting_cards: HashMap::new(),
             round: 0,
             win_condition,
+            game_started_at_s: None,
             deck_refill_count: 0,
             leaderboard_view_mode_default: LeaderboardViewMode::Combined,
             leaderboard_view_mode_default_version: 0,
         };
 
         let (tx, _) = broadcast::channel(10);
+        let (spectator_tx, _) = broadcast::channel(10);
 
         Self {
             state: RwLock::new(state),
             broadcast: tx,
+            spectator_broadcast: spectator_tx,
             base_deck,
             default_stella_word_pack,
             stella_word_pack_presets,
             most_beautiful_stats,
             max_members,
+            message_limits: WsMessageLimits::default(),
+            password_failures: Mutex::new(HashMap::new()),
             last_access: AtomicU64::new(get_time_s()),
         }
     }
 
+    pub fn with_message_limits(mut self, message_limits: WsMessageLimits) -> Self {
+        self.message_limits = message_limits;
+        self
+    }
+
     fn is_creator(&self, state: &RwLockWriteGuard<RoomState>, name: &str) -> bool {
         state.creator.as_deref() == Some(name)
     }
@@ -1604,6 +2297,28 @@ impl Room {
         )
     }
 
+    fn storyteller_pass_penalty_points_bounds(&self) -> (u16, u16) {
+        (
+            MIN_STORYTELLER_PASS_PENALTY_POINTS,
+            MAX_STORYTELLER_PASS_PENALTY_POINTS,
+        )
+    }
+
+    fn catch_up_bonus_points_bounds(&self) -> (u16, u16) {
+        (MIN_CATCH_UP_BONUS_POINTS, MAX_CATCH_UP_BONUS_POINTS)
+    }
+
+    fn catch_up_deficit_points_bounds(&self) -> (u16, u16) {
+        (MIN_CATCH_UP_DEFICIT_POINTS, MAX_CATCH_UP_DEFICIT_POINTS)
+    }
+
+    fn secret_objective_bonus_points_bounds(&self) -> (u16, u16) {
+        (
+            MIN_SECRET_OBJECTIVE_BONUS_POINTS,
+            MAX_SECRET_OBJECTIVE_BONUS_POINTS,
+        )
+    }
+
     fn double_vote_bonus_points_bounds(&self) -> (u16, u16) {
         (MIN_DOUBLE_VOTE_BONUS_POINTS, MAX_DOUBLE_VOTE_BONUS_POINTS)
     }
@@ -1815,6 +2530,43 @@ impl Room {
         true
     }
 
+    fn pass_storyteller_turn(&self, state: &mut RwLockWriteGuard<'_, RoomState>) -> bool {
+        if !matches!(state.game_mode, GameMode::DixitPlus) {
+            return false;
+        }
+        let Some(storyteller) = state.player_order.get(state.active_player).cloned() else {
+            return false;
+        };
+        if !self.force_switch_storyteller(state) {
+            return false;
+        }
+
+        match state.storyteller_pass_penalty {
+            StorytellerPassPenalty::None => {}
+            StorytellerPassPenalty::LosePoints => {
+                let (min_points, max_points) = self.storyteller_pass_penalty_points_bounds();
+                let penalty_points = state
+                    .storyteller_pass_penalty_points
+                    .clamp(min_points, max_points);
+                if let Some(player) = state.players.get_mut(&storyteller) {
+                    // only what was actually lost goes into the round's score change
+                    let lost = penalty_points.min(player.points);
+                    player.points -= lost;
+                    let change = state
+                        .storyteller_pass_penalty_change
+                        .entry(storyteller)
+                        .or_insert(0);
+                    *change = change.saturating_add(lost);
+                }
+            }
+            StorytellerPassPenalty::CountsAsTurn => {
+                let storyteller_count = state.storyteller_counts.entry(storyteller).or_insert(0);
+                *storyteller_count = storyteller_count.saturating_add(1);
+            }
+        }
+        true
+    }
+
     fn rounded_median_u16(values: &[u16]) -> Option<u16> {
         if values.is_empty() {
             return None;
@@ -2230,6 +2982,29 @@ impl Room {
             .clamp(min_points, max_points);
     }
 
+    fn clamp_storyteller_pass_penalty_points(&self, state: &mut RwLockWriteGuard<'_, RoomState>) {
+        let (min_points, max_points) = self.storyteller_pass_penalty_points_bounds();
+        state.storyteller_pass_penalty_points = state
+            .storyteller_pass_penalty_points
+            .clamp(min_points, max_points);
+    }
+
+    fn clamp_catch_up_settings(&self, state: &mut RwLockWriteGuard<'_, RoomState>) {
+        let (min_points, max_points) = self.catch_up_bonus_points_bounds();
+        state.catch_up_bonus_points = state.catch_up_bonus_points.clamp(min_points, max_points);
+        let (min_deficit, max_deficit) = self.catch_up_deficit_points_bounds();
+        state.catch_up_deficit_points = state
+            .catch_up_deficit_points
+            .clamp(min_deficit, max_deficit);
+    }
+
+    fn clamp_secret_objective_bonus_points(&self, state: &mut RwLockWriteGuard<'_, RoomState>) {
+        let (min_points, max_points) = self.secret_objective_bonus_points_bounds();
+        state.secret_objective_bonus_points = state
+            .secret_objective_bonus_points
+            .clamp(min_points, max_points);
+    }
+
     fn clamp_double_vote_bonus_points(&self, state: &mut RwLockWriteGuard<'_, RoomState>) {
         let (min_points, max_points) = self.double_vote_bonus_points_bounds();
         state.double_vote_bonus_normal_points = state
@@ -3256,13 +4031,15 @@ impl Room {
                 beauty_results_display_mode,
                 ..
             } => {
+                // the saved story change already carries the round's pass penalties
                 *beauty_point_change = latest_beauty_deltas.clone();
                 *point_change = match *beauty_results_display_mode {
-                    BeautyResultsDisplayMode::Separate => {
-                        Self::signed_point_change(storyteller_point_change)
-                    }
+                    BeautyResultsDisplayMode::Separate => storyteller_point_change.clone(),
                     BeautyResultsDisplayMode::Summary | BeautyResultsDisplayMode::Combined => {
-                        self.merge_point_changes(storyteller_point_change, &latest_beauty_deltas)
+                        Self::add_signed_point_changes(
+                            storyteller_point_change.clone(),
+                            &latest_beauty_deltas,
+                        )
                     }
                 };
             }
@@ -3361,6 +4138,9 @@ impl Room {
             let existing_beauty_deltas = state.dixit_end_round_history[history_index]
                 .beauty_deltas
                 .clone();
+            let pass_penalty_deltas = state.dixit_end_round_history[history_index]
+                .pass_penalty_deltas
+                .clone();
             let existing_total_after_round = state.dixit_end_round_history[history_index]
                 .total_after_round
                 .clone();
@@ -3392,6 +4172,7 @@ impl Room {
                 .chain(previous_beauty_total_after_round.keys())
                 .chain(existing_story_deltas.keys())
                 .chain(existing_beauty_deltas.keys())
+                .chain(pass_penalty_deltas.keys())
                 .chain(existing_total_after_round.keys())
                 .chain(existing_beauty_total_after_round.keys())
                 .chain(rescored_beauty_deltas.keys())
@@ -3406,6 +4187,7 @@ impl Room {
                     .get(&member_name)
                     .copied()
                     .unwrap_or(0);
+                let pass_penalty = pass_penalty_deltas.get(&member_name).copied().unwrap_or(0);
                 let previous_beauty_delta = existing_beauty_deltas
                     .get(&member_name)
                     .copied()
@@ -3423,7 +4205,8 @@ impl Room {
                             .get(&member_name)
                             .copied()
                             .unwrap_or(0)
-                            .saturating_sub(story_delta);
+                            .saturating_sub(story_delta)
+                            .saturating_add(pass_penalty);
                         Self::apply_signed_delta(prior_total, -previous_beauty_delta)
                     });
                 let beauty_before_round = previous_beauty_total_after_round
@@ -3438,7 +4221,10 @@ impl Room {
                     });
 
                 let total_after = Self::apply_signed_delta(
-                    Self::apply_signed_delta(total_before_round, i32::from(story_delta)),
+                    Self::apply_signed_delta(
+                        total_before_round,
+                        i32::from(story_delta) - i32::from(pass_penalty),
+                    ),
                     rescored_beauty_delta,
                 );
                 let beauty_after =
@@ -3583,6 +4369,9 @@ impl Room {
             hasher.update(token.as_bytes());
             return format!("{:x}", hasher.finalize());
         }
+        if let Some(token_hash) = state.restored_member_token_hashes.get(member_name) {
+            return token_hash.clone();
+        }
 
         let mut hasher = Sha256::new();
         hasher.update(state.room_id.as_bytes());
@@ -3640,10 +4429,10 @@ impl Room {
             return Ok(());
         }
 
-        let Some(game_id) = state.current_dixit_game_id.clone() else {
+        let Some(game_id) = state.current_game_id.clone() else {
             return Ok(());
         };
-        let Some(game_started_at_s) = state.current_dixit_game_started_at_s else {
+        let Some(game_started_at_s) = state.current_game_started_at_s else {
             return Ok(());
         };
         let Some(storyteller_name) = state.player_order.get(state.active_player).cloned() else {
@@ -3710,11 +4499,19 @@ impl Room {
                         .and_then(|observer| observer.points)
                 })
                 .unwrap_or(0);
-            let story_delta = state
-                .storyteller_point_change
-                .get(&member_name)
-                .copied()
-                .unwrap_or(0);
+            let story_delta = i32::from(
+                state
+                    .storyteller_point_change
+                    .get(&member_name)
+                    .copied()
+                    .unwrap_or(0),
+            ) - i32::from(
+                state
+                    .storyteller_pass_penalty_change
+                    .get(&member_name)
+                    .copied()
+                    .unwrap_or(0),
+            );
             let beauty_delta = state
                 .beauty_point_change
                 .get(&member_name)
@@ -3764,6 +4561,18 @@ impl Room {
                 story_votes,
                 beauty_votes,
                 score_log,
+                clue_rating: (!state.player_to_clue_rating.is_empty()).then(|| {
+                    MostBeautifulGameAuditClueRatingRecord {
+                        rating_count: state.player_to_clue_rating.len().min(usize::from(u16::MAX))
+                            as u16,
+                        rating_sum: state
+                            .player_to_clue_rating
+                            .values()
+                            .map(|stars| u32::from(*stars))
+                            .sum(),
+                        max_stars: self.effective_clue_rating_max_stars(state),
+                    }
+                }),
             })?;
 
         let (_, clue_rating_count, clue_rating_bonus) = self.clue_rating_summary(state);
@@ -3781,6 +4590,8 @@ impl Room {
             player_clue_ratings: state.player_to_clue_rating.clone(),
             total_after_round,
             beauty_total_after_round,
+            handicap_after_round: state.member_handicap_points.clone(),
+            pass_penalty_deltas: state.storyteller_pass_penalty_change.clone(),
             results_display_mode: state.beauty_results_display_mode,
         };
         state.dixit_end_round_history.push(round_history_entry);
@@ -3788,17 +4599,23 @@ impl Room {
         Ok(())
     }
 
-    fn start_new_dixit_game_tracking(&self, state: &mut RwLockWriteGuard<'_, RoomState>) {
+    fn start_new_game_tracking(&self, state: &mut RwLockWriteGuard<'_, RoomState>) {
         let mut hasher = Sha256::new();
         hasher.update(state.room_id.as_bytes());
         hasher.update(get_time_ms().to_le_bytes());
         hasher.update(rand::thread_rng().gen::<u64>().to_le_bytes());
-        state.current_dixit_game_id = Some(format!("{:x}", hasher.finalize()));
-        state.current_dixit_game_started_at_s = Some(get_time_s());
+        state.current_game_id = Some(format!("{:x}", hasher.finalize()));
+        state.current_game_started_at_s = Some(get_time_s());
+    }
+
+    fn start_new_dixit_game_tracking(&self, state: &mut RwLockWriteGuard<'_, RoomState>) {
+        self.start_new_game_tracking(state);
         state.dixit_end_round_history.clear();
         state.previous_dixit_results = None;
         state.member_to_clue_rating_average_sum.clear();
         state.member_to_clue_rating_rounds.clear();
+        state.member_to_storyteller_successes.clear();
+        state.member_secret_objectives.clear();
         self.reset_vote_divisor_segment(state);
     }
 
@@ -3823,7 +4640,7 @@ impl Room {
             .get(state.active_player)
             .cloned()
             .unwrap_or_else(|| "none".to_string());
-        let game_id = state.current_dixit_game_id.clone();
+        let game_id = state.current_game_id.clone();
         let game_id_label = game_id
             .as_ref()
             .map(|id| format!("`{id}`"))
@@ -3838,7 +4655,7 @@ impl Room {
              - Stage: `{:?}`\n\
              - Active player: `{}`\n\
              - SQLite DB: `{}`\n\n\
-             Current-round audit rows are written when a Dixit round is recorded, so an in-progress round may not appear yet.\n",
+             Current-round audit rows are written when a round is scored, so an in-progress round may not appear yet.\n",
             state.room_id,
             game_id_label,
             current_round,
@@ -3850,7 +4667,7 @@ impl Room {
 
         let Some(game_id) = game_id else {
             markdown.push_str(
-                "\nNo current Dixit game ID is available yet. Start a Dixit game before querying audit rows.\n",
+                "\nNo current game ID is available yet. Start a game before querying audit rows.\n",
             );
             return markdown;
         };
@@ -3861,6 +4678,40 @@ impl Room {
             None => format!("round_num = {current_round}"),
         };
 
+        if matches!(state.game_mode, GameMode::Stella) {
+            markdown.push_str(&format!(
+                "\n## Current and previous round lookup\n\n\
+                 ```sh\n\
+                 sqlite3 -header -column {db_path_arg} <<'SQL'\n\
+                 SELECT *\n\
+                 FROM mb_games\n\
+                 WHERE game_id = {game_id_sql};\n\n\
+                 SELECT *\n\
+                 FROM mb_stella_rounds\n\
+                 WHERE game_id = {game_id_sql}\n\
+                   AND {round_predicate}\n\
+                 ORDER BY round_num;\n\n\
+                 SELECT *\n\
+                 FROM mb_stella_round_board_cards\n\
+                 WHERE game_id = {game_id_sql}\n\
+                   AND {round_predicate}\n\
+                 ORDER BY round_num, board_order;\n\n\
+                 SELECT *\n\
+                 FROM mb_stella_round_selections\n\
+                 WHERE game_id = {game_id_sql}\n\
+                   AND {round_predicate}\n\
+                 ORDER BY round_num, player_display_name, card_hash;\n\n\
+                 SELECT *\n\
+                 FROM mb_stella_round_players\n\
+                 WHERE game_id = {game_id_sql}\n\
+                   AND {round_predicate}\n\
+                 ORDER BY round_num, player_display_name;\n\
+                 SQL\n\
+                 ```\n"
+            ));
+            return markdown;
+        }
+
         markdown.push_str(&format!(
             "\n## Current and previous round lookup\n\n\
              ```sh\n\
@@ -3900,16 +4751,24 @@ impl Room {
         markdown
     }
 
-    fn finalize_current_dixit_game(
-        &self,
-        state: &mut RwLockWriteGuard<'_, RoomState>,
-    ) -> Result<()> {
-        if !matches!(state.game_mode, GameMode::DixitPlus) {
-            return Ok(());
-        }
-        if let Some(game_id) = state.current_dixit_game_id.clone() {
-            self.most_beautiful_stats
-                .mark_game_complete(&game_id, get_time_s(), state.round)?;
+    fn finalize_current_game(&self, state: &mut RwLockWriteGuard<'_, RoomState>) -> Result<()> {
+        if let Some(game_id) = state.current_game_id.clone() {
+            // rate from the End screen order, which already applies tiebreaks, objectives and handicaps
+            let standings = self
+                .final_standings(state)
+                .into_iter()
+                .map(|standing| GameStandingRecord {
+                    player_hash: self.member_hash_for_name(state, &standing.name),
+                    rank: standing.rank,
+                    points: standing.points,
+                })
+                .collect::<Vec<_>>();
+            self.most_beautiful_stats.mark_game_complete(
+                &game_id,
+                get_time_s(),
+                state.round,
+                &standings,
+            )?;
         }
         Ok(())
     }
@@ -3917,7 +4776,7 @@ impl Room {
     fn transition_to_end(&self, state: &mut RwLockWriteGuard<'_, RoomState>) -> Result<()> {
         self.set_stage(state, RoomStage::End);
         state.paused_reason = None;
-        self.finalize_current_dixit_game(state)
+        self.finalize_current_game(state)
     }
 
     fn transition_to_end_if_game_complete(
@@ -3929,7 +4788,7 @@ impl Room {
         }
 
         self.transition_to_end(state)?;
-        self.broadcast_msg(ServerMsg::EndGame {})?;
+        self.broadcast_msg(self.end_game_msg(state))?;
         self.broadcast_msg(self.room_state(state))?;
         Ok(true)
     }
@@ -3962,6 +4821,7 @@ impl Room {
         state.forced_random_vote_cards.clear();
         state.storyteller_point_change.clear();
         state.beauty_point_change.clear();
+        state.storyteller_pass_penalty_change.clear();
         state.beauty_voting_completed_this_round = false;
         self.clear_ready(state);
         self.set_stage(state, RoomStage::ActiveChooses);
@@ -3981,6 +4841,51 @@ impl Room {
         merged
     }
 
+    // Story-stage change for each member, net of any storyteller-pass penalty from earlier in
+    // the round. Every `storyteller_point_change` sent to clients uses this meaning.
+    fn story_point_change(state: &RwLockWriteGuard<'_, RoomState>) -> HashMap<String, i32> {
+        Self::with_pass_penalties(
+            Self::signed_point_change(&state.storyteller_point_change),
+            &state.storyteller_pass_penalty_change,
+        )
+    }
+
+    // Results-stage change for each member; beauty points are left out while they have a stage
+    // of their own.
+    fn results_point_change(
+        &self,
+        state: &RwLockWriteGuard<'_, RoomState>,
+    ) -> HashMap<String, i32> {
+        let story_point_change = Self::story_point_change(state);
+        if self.uses_separate_beauty_results_stage(state) {
+            story_point_change
+        } else {
+            Self::add_signed_point_changes(story_point_change, &state.beauty_point_change)
+        }
+    }
+
+    fn add_signed_point_changes(
+        mut point_change: HashMap<String, i32>,
+        other: &HashMap<String, i32>,
+    ) -> HashMap<String, i32> {
+        for (member_name, points) in other {
+            let delta = point_change.entry(member_name.clone()).or_insert(0);
+            *delta = delta.saturating_add(*points);
+        }
+        point_change
+    }
+
+    fn with_pass_penalties(
+        mut point_change: HashMap<String, i32>,
+        pass_penalties: &HashMap<String, u16>,
+    ) -> HashMap<String, i32> {
+        for (member_name, penalty) in pass_penalties {
+            let delta = point_change.entry(member_name.clone()).or_insert(0);
+            *delta = delta.saturating_sub(i32::from(*penalty));
+        }
+        point_change
+    }
+
     fn signed_point_change(point_change: &HashMap<String, u16>) -> HashMap<String, i32> {
         point_change
             .iter()
@@ -4002,17 +4907,8 @@ impl Room {
         match state.stage {
             RoomStage::Results => Some(RoundDeltaScores {
                 stage: RoomStage::Results,
-                point_change: if self.uses_separate_beauty_results_stage(state) {
-                    Self::signed_point_change(&state.storyteller_point_change)
-                } else {
-                    self.merge_point_changes(
-                        &state.storyteller_point_change,
-                        &state.beauty_point_change,
-                    )
-                },
-                storyteller_point_change: Self::signed_point_change(
-                    &state.storyteller_point_change,
-                ),
+                point_change: self.results_point_change(state),
+                storyteller_point_change: Self::story_point_change(state),
                 beauty_point_change: state.beauty_point_change.clone(),
             }),
             RoomStage::BeautyResults => Some(RoundDeltaScores {
@@ -4075,32 +4971,49 @@ impl Room {
             .map(|(history_index, entry)| {
                 let include_beauty = !(latest_has_pending_separate_beauty
                     && Some(history_index) == latest_history_index);
-                let total_deltas = if include_beauty {
-                    self.merge_point_changes(&entry.story_deltas, &entry.beauty_deltas)
-                } else {
-                    Self::signed_point_change(&entry.story_deltas)
-                };
+                let total_deltas = Self::with_pass_penalties(
+                    if include_beauty {
+                        self.merge_point_changes(&entry.story_deltas, &entry.beauty_deltas)
+                    } else {
+                        Self::signed_point_change(&entry.story_deltas)
+                    },
+                    &entry.pass_penalty_deltas,
+                );
                 let beauty_deltas = if include_beauty {
                     entry.beauty_deltas.clone()
                 } else {
                     HashMap::new()
                 };
 
+                let total_after_round = if include_beauty {
+                    entry.total_after_round.clone()
+                } else {
+                    Self::live_member_total_points(state)
+                };
+                let raw_total_after_round = total_after_round
+                    .iter()
+                    .map(|(member_name, total)| {
+                        let handicap = entry
+                            .handicap_after_round
+                            .get(member_name)
+                            .copied()
+                            .unwrap_or(0);
+                        (member_name.clone(), total.saturating_sub(handicap))
+                    })
+                    .collect();
+
                 LeaderboardRoundHistoryEntry {
                     round_num: entry.round_num,
                     active_players: entry.active_players.clone(),
                     total_deltas,
                     beauty_deltas,
-                    total_after_round: if include_beauty {
-                        entry.total_after_round.clone()
-                    } else {
-                        Self::live_member_total_points(state)
-                    },
+                    total_after_round,
                     beauty_total_after_round: if include_beauty {
                         entry.beauty_total_after_round.clone()
                     } else {
                         Self::live_member_beauty_points(state)
                     },
+                    raw_total_after_round,
                 }
             })
             .collect()
@@ -4416,15 +5329,8 @@ impl Room {
             player_to_current_cards: state.player_to_current_cards.clone(),
             active_card,
             beauty_results_display_mode: state.beauty_results_display_mode,
-            point_change: if self.uses_separate_beauty_results_stage(state) {
-                Self::signed_point_change(&state.storyteller_point_change)
-            } else {
-                self.merge_point_changes(
-                    &state.storyteller_point_change,
-                    &state.beauty_point_change,
-                )
-            },
-            storyteller_point_change: state.storyteller_point_change.clone(),
+            point_change: self.results_point_change(state),
+            storyteller_point_change: Self::story_point_change(state),
             beauty_point_change: state.beauty_point_change.clone(),
             beauty_vote_totals: self.compute_beauty_vote_totals(state),
             beauty_winning_cards: self.compute_beauty_winning_cards(state),
@@ -4795,7 +5701,10 @@ impl Room {
     ) -> bool {
         match state.name_tokens.get(name) {
             Some(existing_token) => existing_token == token,
-            None => true,
+            None => state
+                .restored_member_token_hashes
+                .get(name)
+                .is_none_or(|token_hash| *token_hash == player_hash_for_token(token)),
         }
     }
 
@@ -4804,9 +5713,22 @@ impl Room {
         state: &RwLockWriteGuard<RoomState>,
         token: &str,
     ) -> Option<String> {
-        state.name_tokens.iter().find_map(|(name, existing_token)| {
-            (existing_token == token && self.member_exists(state, name)).then(|| name.clone())
-        })
+        state
+            .name_tokens
+            .iter()
+            .find_map(|(name, existing_token)| {
+                (existing_token == token && self.member_exists(state, name)).then(|| name.clone())
+            })
+            .or_else(|| {
+                let token_hash = player_hash_for_token(token);
+                state
+                    .restored_member_token_hashes
+                    .iter()
+                    .find_map(|(name, restored_hash)| {
+                        (*restored_hash == token_hash && self.member_exists(state, name))
+                            .then(|| name.clone())
+                    })
+            })
     }
 
     fn member_name_for_room_auth_id(
@@ -4840,6 +5762,10 @@ impl Room {
         }
     }
 
+    fn generate_access_token() -> String {
+        format!("{:032x}", rand::thread_rng().gen::<u128>())
+    }
+
     fn room_auth_id_for_member(
         &self,
         state: &mut RwLockWriteGuard<RoomState>,
@@ -4980,6 +5906,7 @@ impl Room {
         state.forced_random_vote_cards.clear();
         state.storyteller_point_change.clear();
         state.beauty_point_change.clear();
+        state.storyteller_pass_penalty_change.clear();
         state.player_to_disabled_voting_cards.clear();
         state.stage_started_at_s = None;
         state.current_stage_deadline_s = None;
@@ -5356,9 +6283,251 @@ impl Room {
     }
 
     fn midgame_join_score_floor(&self, state: &RwLockWriteGuard<'_, RoomState>) -> u16 {
+        // the same handicapped totals the standings show, so a joiner never ranks below last place
         state.players.values().map(|p| p.points).min().unwrap_or(0)
     }
 
+    fn apply_handicap_offsets_on_game_start(&self, state: &mut RwLockWriteGuard<'_, RoomState>) {
+        state.member_handicap_points.clear();
+        let offsets = state
+            .member_handicap_offsets
+            .iter()
+            .map(|(name, points)| (name.clone(), (*points).min(MAX_HANDICAP_OFFSET_POINTS)))
+            .filter(|(_, points)| *points > 0)
+            .collect::<Vec<_>>();
+        for (name, offset) in offsets {
+            let Some(player) = state.players.get_mut(&name) else {
+                continue;
+            };
+            player.points = player.points.saturating_add(offset);
+            state.member_handicap_points.insert(name, offset);
+        }
+    }
+
+    fn compute_catch_up_bonuses(
+        &self,
+        state: &RwLockWriteGuard<'_, RoomState>,
+    ) -> HashMap<String, u16> {
+        let mut bonuses = HashMap::new();
+        if !state.catch_up_bonus_enabled {
+            return bonuses;
+        }
+        let Some(active_player) = state.player_order.get(state.active_player) else {
+            return bonuses;
+        };
+        let Some(active_card) = state
+            .player_to_current_cards
+            .get(active_player)
+            .and_then(|cards| cards.first())
+        else {
+            return bonuses;
+        };
+        let Some(leader_points) = state.players.values().map(|p| p.points).max() else {
+            return bonuses;
+        };
+
+        let (min_points, max_points) = self.catch_up_bonus_points_bounds();
+        let bonus_points = state.catch_up_bonus_points.clamp(min_points, max_points);
+        let (min_deficit, max_deficit) = self.catch_up_deficit_points_bounds();
+        let deficit = state
+            .catch_up_deficit_points
+            .clamp(min_deficit, max_deficit);
+        for (player, info) in state.players.iter() {
+            if player == active_player
+                || leader_points.saturating_sub(info.points) < deficit
+                || !self.has_non_forced_correct_vote(state, player, active_card)
+            {
+                continue;
+            }
+            bonuses.insert(player.clone(), bonus_points);
+        }
+        bonuses
+    }
+
+    fn chat_block_reason(
+        &self,
+        state: &RwLockWriteGuard<'_, RoomState>,
+        name: &str,
+    ) -> Option<&'static str> {
+        if state.chat_muted_members.contains(name) {
+            return Some("You are muted in chat");
+        }
+        if !state.chat_stage_restrictions_enabled
+            || !matches!(state.game_mode, GameMode::DixitPlus)
+            || !state.players.contains_key(name)
+        {
+            return None;
+        }
+
+        let is_storyteller = state
+            .player_order
+            .get(state.active_player)
+            .map(String::as_str)
+            == Some(name);
+        match state.stage {
+            RoomStage::PlayersChoose if is_storyteller => {
+                Some("Storytellers cannot chat while players choose cards")
+            }
+            RoomStage::Voting => Some("Chat is paused for players during voting"),
+            _ => None,
+        }
+    }
+
+    // returns an error for the sender, or records the action against their rate limit
+    fn check_chat_action(
+        &self,
+        state: &mut RwLockWriteGuard<'_, RoomState>,
+        name: &str,
+        now_ms: u64,
+    ) -> Option<&'static str> {
+        if let Some(reason) = self.chat_block_reason(state, name) {
+            return Some(reason);
+        }
+
+        let recent = state
+            .chat_recent_actions_ms
+            .entry(name.to_string())
+            .or_default();
+        while recent
+            .front()
+            .is_some_and(|sent_ms| now_ms.saturating_sub(*sent_ms) >= CHAT_RATE_LIMIT_WINDOW_MS)
+        {
+            recent.pop_front();
+        }
+        if recent.len() >= CHAT_RATE_LIMIT_MAX_ACTIONS {
+            return Some("You are sending chat messages too quickly");
+        }
+        recent.push_back(now_ms);
+        None
+    }
+
+    fn deal_missing_secret_objectives(&self, state: &mut RwLockWriteGuard<'_, RoomState>) {
+        if !state.secret_objectives_enabled || !matches!(state.game_mode, GameMode::DixitPlus) {
+            return;
+        }
+        let mut kinds = vec![
+            SecretObjectiveKind::DecoyDoubleFool,
+            SecretObjectiveKind::PreciseStoryteller,
+        ];
+        if self.beauty_enabled_for_round(state) {
+            kinds.push(SecretObjectiveKind::BeautyWinnerTwice);
+        }
+
+        // midgame joiners are dealt an objective on their first round
+        let unassigned = state
+            .players
+            .keys()
+            .filter(|name| !state.member_secret_objectives.contains_key(*name))
+            .cloned()
+            .collect::<Vec<_>>();
+        let mut rng = rand::thread_rng();
+        for name in unassigned {
+            let Some(kind) = kinds.choose(&mut rng).copied() else {
+                continue;
+            };
+            state.member_secret_objectives.insert(
+                name,
+                SecretObjective {
+                    kind,
+                    progress: 0,
+                    target: kind.target(),
+                    completed: false,
+                },
+            );
+        }
+    }
+
+    fn advance_secret_objectives(
+        &self,
+        state: &mut RwLockWriteGuard<'_, RoomState>,
+        completed_beauty_voting: bool,
+    ) {
+        if state.member_secret_objectives.is_empty() {
+            return;
+        }
+        let Some(active_player) = state.player_order.get(state.active_player).cloned() else {
+            return;
+        };
+
+        let precise_storyteller =
+            self.storyteller_round_succeeded(state) && self.correct_guesser_count(state) == Some(1);
+        let mut voters_for_card = HashMap::<&str, HashSet<&str>>::new();
+        for (voter, votes) in state.player_to_votes.iter() {
+            let mut forced_counts = HashMap::<&str, usize>::new();
+            for card in state
+                .forced_random_vote_cards
+                .get(voter)
+                .into_iter()
+                .flatten()
+            {
+                *forced_counts.entry(card.as_str()).or_insert(0) += 1;
+            }
+            for card in votes {
+                match forced_counts.get_mut(card.as_str()) {
+                    Some(count) if *count > 0 => *count -= 1,
+                    _ => {
+                        voters_for_card
+                            .entry(card.as_str())
+                            .or_default()
+                            .insert(voter.as_str());
+                    }
+                }
+            }
+        }
+        let beauty_winning_cards = if completed_beauty_voting {
+            self.compute_beauty_winning_cards(state)
+        } else {
+            Vec::new()
+        };
+
+        let mut achieved = Vec::new();
+        for (player, cards) in state.player_to_current_cards.iter() {
+            let Some(objective) = state.member_secret_objectives.get(player) else {
+                continue;
+            };
+            let met = match objective.kind {
+                SecretObjectiveKind::DecoyDoubleFool => {
+                    player != &active_player
+                        && cards.iter().any(|card| {
+                            voters_for_card
+                                .get(card.as_str())
+                                .is_some_and(|voters| voters.len() >= 2)
+                        })
+                }
+                SecretObjectiveKind::BeautyWinnerTwice => {
+                    cards.iter().any(|card| beauty_winning_cards.contains(card))
+                }
+                SecretObjectiveKind::PreciseStoryteller => {
+                    player == &active_player && precise_storyteller
+                }
+            };
+            if met {
+                achieved.push(player.clone());
+            }
+        }
+
+        for player in achieved {
+            if let Some(objective) = state.member_secret_objectives.get_mut(&player) {
+                objective.progress = objective.progress.saturating_add(1).min(objective.target);
+                objective.completed = objective.progress >= objective.target;
+            }
+        }
+    }
+
+    fn secret_objective_bonus(&self, state: &RwLockWriteGuard<'_, RoomState>, name: &str) -> u16 {
+        if !state
+            .member_secret_objectives
+            .get(name)
+            .is_some_and(|objective| objective.completed)
+        {
+            return 0;
+        }
+        let (min_points, max_points) = self.secret_objective_bonus_points_bounds();
+        state
+            .secret_objective_bonus_points
+            .clamp(min_points, max_points)
+    }
+
     fn active_player_score_floor_for_target(
         &self,
         state: &RwLockWriteGuard<'_, RoomState>,
@@ -5889,11 +7058,16 @@ impl Room {
         state.player_pinned_cards.remove(name);
         state.observer_since_round.remove(name);
         state.storyteller_counts.remove(name);
+        state.member_handicap_offsets.remove(name);
+        state.member_handicap_points.remove(name);
+        state.chat_muted_members.remove(name);
+        state.chat_recent_actions_ms.remove(name);
         state.moderators.remove(name);
         if state.creator.as_deref() == Some(name) {
             state.creator = None;
         }
         state.name_tokens.remove(name);
+        state.restored_member_token_hashes.remove(name);
         self.remove_member_from_storyteller_pool(state, name);
         self.remove_room_auth_id_for_member(state, name);
         state.connection_generation.remove(name);
@@ -5985,14 +7159,21 @@ impl Room {
         state.player_pinned_cards.remove(player_name);
         state.observer_since_round.remove(player_name);
         state.storyteller_counts.remove(player_name);
+        state.member_handicap_offsets.remove(player_name);
+        state.member_handicap_points.remove(player_name);
         state.member_to_beauty_points.remove(player_name);
         state.member_to_clue_rating_average_sum.remove(player_name);
         state.member_to_clue_rating_rounds.remove(player_name);
+        state.member_to_storyteller_successes.remove(player_name);
+        state.member_secret_objectives.remove(player_name);
+        state.chat_muted_members.remove(player_name);
+        state.chat_recent_actions_ms.remove(player_name);
         state.moderators.remove(player_name);
         if state.creator.as_deref() == Some(player_name) {
             state.creator = None;
         }
         state.name_tokens.remove(player_name);
+        state.restored_member_token_hashes.remove(player_name);
         self.remove_member_from_storyteller_pool(state, player_name);
         self.remove_room_auth_id_for_member(state, player_name);
         state.connection_generation.remove(player_name);
@@ -6192,6 +7373,7 @@ impl Room {
                                 .send_msg(state, player, self.get_msg(Some(player), state)?)
                                 .await;
                         }
+                        self.broadcast_spectator_stage_msg(state);
                         self.broadcast_msg(self.room_state(state))?;
                     }
                 } else {
@@ -6296,7 +7478,7 @@ impl Room {
                         self.init_beauty_results(state)?;
                     } else if self.should_end_game(state) {
                         self.transition_to_end(state)?;
-                        self.broadcast_msg(ServerMsg::EndGame {})?;
+                        self.broadcast_msg(self.end_game_msg(state))?;
                         self.broadcast_msg(self.room_state(state))?;
                     } else {
                         self.init_round(state).await?;
@@ -6310,7 +7492,7 @@ impl Room {
                 if state.players.values().all(|player| player.ready) {
                     if self.should_end_game(state) {
                         self.transition_to_end(state)?;
-                        self.broadcast_msg(ServerMsg::EndGame {})?;
+                        self.broadcast_msg(self.end_game_msg(state))?;
                         self.broadcast_msg(self.room_state(state))?;
                     } else {
                         self.init_round(state).await?;
@@ -6324,7 +7506,7 @@ impl Room {
                 if state.players.values().all(|player| player.ready) {
                     if self.should_end_game(state) {
                         self.transition_to_end(state)?;
-                        self.broadcast_msg(ServerMsg::EndGame {})?;
+                        self.broadcast_msg(self.end_game_msg(state))?;
                         self.broadcast_msg(self.room_state(state))?;
                     } else {
                         self.init_stella_round(state).await?;
@@ -6519,15 +7701,8 @@ impl Room {
                     .and_then(|cards| cards.first())
                     .cloned()
                     .ok_or_else(|| anyhow!("Missing active card"))?,
-                point_change: if self.uses_separate_beauty_results_stage(state) {
-                    Self::signed_point_change(&state.storyteller_point_change)
-                } else {
-                    self.merge_point_changes(
-                        &state.storyteller_point_change,
-                        &state.beauty_point_change,
-                    )
-                },
-                storyteller_point_change: state.storyteller_point_change.clone(),
+                point_change: self.results_point_change(state),
+                storyteller_point_change: Self::story_point_change(state),
                 beauty_point_change: state.beauty_point_change.clone(),
                 beauty_vote_totals: self.compute_beauty_vote_totals(state),
                 beauty_winning_cards: self.compute_beauty_winning_cards(state),
@@ -6566,7 +7741,7 @@ impl Room {
                 current_stage_deadline_s,
             }),
             RoomStage::Paused => Err(anyhow!("No stage-specific paused message")),
-            RoomStage::End => Ok(ServerMsg::EndGame {}),
+            RoomStage::End => Ok(self.end_game_msg(state)),
             _ => Err(anyhow!("No msg to send")),
         }
     }
@@ -7257,6 +8432,7 @@ impl Room {
                 .send_msg(state, player_name, self.get_msg(Some(player_name), state)?)
                 .await;
         }
+        self.broadcast_spectator_stage_msg(state);
         self.broadcast_msg(self.room_state(&state))?;
 
         Ok(())
@@ -7367,6 +8543,7 @@ impl Room {
                 .send_msg(state, player_name, self.get_msg(Some(player_name), state)?)
                 .await;
         }
+        self.broadcast_spectator_stage_msg(state);
         self.broadcast_msg(self.room_state(state))?;
 
         Ok(())
@@ -7407,6 +8584,24 @@ impl Room {
         let (clue_rating_average, _, clue_rating_bonus) = self.clue_rating_summary(state);
         self.set_stage(state, RoomStage::Results);
         state.storyteller_point_change = self.compute_results(state);
+        if self.storyteller_round_succeeded(state) {
+            let active_player = self.get_active_player(state)?;
+            let successes = state
+                .member_to_storyteller_successes
+                .entry(active_player)
+                .or_insert(0);
+            *successes = successes.saturating_add(1);
+        }
+        self.advance_secret_objectives(state, completed_beauty_voting);
+        for (player, bonus) in self.compute_catch_up_bonuses(state) {
+            let delta = state
+                .storyteller_point_change
+                .entry(player.clone())
+                .or_insert(0);
+            *delta = delta.saturating_add(bonus);
+            let handicap = state.member_handicap_points.entry(player).or_insert(0);
+            *handicap = handicap.saturating_add(bonus);
+        }
         if clue_rating_bonus > 0 {
             let active_player = self.get_active_player(state)?;
             let storyteller_delta = state
@@ -7486,7 +8681,7 @@ impl Room {
         }
 
         while state.deck.len() < needed {
-            if matches!(state.win_condition, WinCondition::CardsFinish) {
+            if self.deck_depletion_ends_game(state) {
                 return false;
             }
 
@@ -7748,6 +8943,7 @@ impl Room {
                 .send_msg(state, player, self.get_msg(Some(player), state)?)
                 .await;
         }
+        self.broadcast_spectator_stage_msg(state);
         self.broadcast_msg(self.room_state(state))?;
         Ok(())
     }
@@ -7985,6 +9181,7 @@ impl Room {
         self.set_stage(state, RoomStage::StellaResults);
         let point_change = self.effective_stella_point_change(state);
         self.record_stella_leaderboard_round_history(state, &point_change);
+        self.record_current_stella_round_audit(state, &point_change)?;
 
         for (player, info) in state.players.iter_mut() {
             if let Some(delta) = point_change.get(player) {
@@ -8041,39 +9238,138 @@ impl Room {
                 .map(|(member_name, delta)| (member_name.clone(), i32::from(*delta)))
                 .collect(),
             beauty_deltas: HashMap::new(),
+            raw_total_after_round: total_after_round.clone(),
             total_after_round,
             beauty_total_after_round: HashMap::new(),
         };
         state.stella_leaderboard_round_history.push(history_entry);
     }
 
-    fn effective_stella_point_change(
+    fn record_current_stella_round_audit(
         &self,
         state: &RwLockWriteGuard<'_, RoomState>,
-    ) -> HashMap<String, u16> {
-        let mut point_change = state.stella_point_change.clone();
+        point_change: &HashMap<String, u16>,
+    ) -> Result<()> {
+        let Some(game_id) = state.current_game_id.clone() else {
+            return Ok(());
+        };
+        let Some(game_started_at_s) = state.current_game_started_at_s else {
+            return Ok(());
+        };
 
-        if let Some(dark_player) = state.stella_dark_player.as_ref() {
-            let selections = state
-                .stella_player_selections
-                .get(dark_player)
-                .cloned()
-                .unwrap_or_default();
-            let has_mistake = selections
-                .iter()
-                .any(|card| state.stella_card_points.get(card).copied().unwrap_or(0) == 0);
-            if has_mistake {
-                let penalty = state
-                    .stella_scored_boxes
-                    .get(dark_player)
-                    .copied()
-                    .unwrap_or(0);
-                if penalty > 0 {
-                    let entry = point_change.entry(dark_player.clone()).or_insert(0);
-                    *entry = entry.saturating_sub(penalty);
+        let board_cards = state
+            .stella_board_cards
+            .iter()
+            .enumerate()
+            .map(|(index, card_hash)| {
+                let reveal_order = state
+                    .stella_revealed_cards
+                    .iter()
+                    .position(|revealed| revealed == card_hash);
+                StellaGameAuditBoardCardRecord {
+                    card_hash: card_hash.clone(),
+                    board_order: u16::try_from(index).unwrap_or(u16::MAX),
+                    reveal_order: reveal_order
+                        .map(|order| u16::try_from(order).unwrap_or(u16::MAX)),
+                    points: state.stella_card_points.get(card_hash).copied(),
                 }
-            }
-        }
+            })
+            .collect::<Vec<_>>();
+
+        let mut selections = state
+            .stella_player_selections
+            .iter()
+            .flat_map(|(player_name, cards)| {
+                let player_hash = self.member_hash_for_name(state, player_name);
+                cards
+                    .iter()
+                    .map(move |card_hash| StellaGameAuditSelectionRecord {
+                        player_hash: player_hash.clone(),
+                        player_display_name: player_name.clone(),
+                        card_hash: card_hash.clone(),
+                    })
+            })
+            .collect::<Vec<_>>();
+        selections.sort_by(|a, b| {
+            a.player_display_name
+                .cmp(&b.player_display_name)
+                .then(a.card_hash.cmp(&b.card_hash))
+        });
+
+        let mut member_names = state
+            .players
+            .keys()
+            .chain(state.observers.keys())
+            .cloned()
+            .collect::<Vec<_>>();
+        member_names.sort();
+        let players = member_names
+            .into_iter()
+            .map(|member_name| {
+                let current_total = state
+                    .players
+                    .get(&member_name)
+                    .map(|player| player.points)
+                    .or_else(|| {
+                        state
+                            .observers
+                            .get(&member_name)
+                            .and_then(|observer| observer.points)
+                    })
+                    .unwrap_or(0);
+                let score_delta = point_change.get(&member_name).copied().unwrap_or(0);
+                StellaGameAuditPlayerRecord {
+                    player_hash: self.member_hash_for_name(state, &member_name),
+                    in_the_dark: state.stella_dark_player.as_deref() == Some(member_name.as_str()),
+                    fell: state.stella_fallen_players.contains(&member_name),
+                    score_delta,
+                    total_after_round: current_total.saturating_add(score_delta),
+                    player_display_name: member_name,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        self.most_beautiful_stats
+            .record_stella_audit_round(&StellaGameAuditRoundRecord {
+                game_id,
+                room_id: state.room_id.clone(),
+                game_started_at_s,
+                recorded_at_s: get_time_s(),
+                round_num: state.round,
+                clue_word: state.stella_clue_word.clone(),
+                board_cards,
+                selections,
+                players,
+            })
+    }
+
+    fn effective_stella_point_change(
+        &self,
+        state: &RwLockWriteGuard<'_, RoomState>,
+    ) -> HashMap<String, u16> {
+        let mut point_change = state.stella_point_change.clone();
+
+        if let Some(dark_player) = state.stella_dark_player.as_ref() {
+            let selections = state
+                .stella_player_selections
+                .get(dark_player)
+                .cloned()
+                .unwrap_or_default();
+            let has_mistake = selections
+                .iter()
+                .any(|card| state.stella_card_points.get(card).copied().unwrap_or(0) == 0);
+            if has_mistake {
+                let penalty = state
+                    .stella_scored_boxes
+                    .get(dark_player)
+                    .copied()
+                    .unwrap_or(0);
+                if penalty > 0 {
+                    let entry = point_change.entry(dark_player.clone()).or_insert(0);
+                    *entry = entry.saturating_sub(penalty);
+                }
+            }
+        }
 
         point_change
     }
@@ -8094,6 +9390,11 @@ impl Room {
 
         if state.round == 0 {
             state.stella_leaderboard_round_history.clear();
+            self.start_new_game_tracking(state);
+            state.game_started_at_s = Some(get_time_s());
+            state.sudden_death_rounds_played = 0;
+        } else if self.win_condition_met(state, &state.win_condition) {
+            state.sudden_death_rounds_played = state.sudden_death_rounds_played.saturating_add(1);
         }
 
         self.clamp_stella_settings(state);
@@ -8124,7 +9425,7 @@ impl Room {
         self.ensure_deck_size(state, board_size);
         if state.deck.len() < board_size {
             self.transition_to_end(state)?;
-            self.broadcast_msg(ServerMsg::EndGame {})?;
+            self.broadcast_msg(self.end_game_msg(state))?;
             self.broadcast_msg(self.room_state(state))?;
             return Ok(());
         }
@@ -8151,6 +9452,7 @@ impl Room {
                 .send_msg(state, player, self.get_msg(Some(player), state)?)
                 .await;
         }
+        self.broadcast_spectator_stage_msg(state);
         self.broadcast_msg(self.room_state(state))?;
         Ok(())
     }
@@ -8190,6 +9492,12 @@ impl Room {
             state.beauty_votes_per_player = self.auto_beauty_votes_per_player(state);
             state.member_to_beauty_points.clear();
             self.start_new_dixit_game_tracking(state);
+            self.apply_handicap_offsets_on_game_start(state);
+            state.game_started_at_s = Some(get_time_s());
+            state.sudden_death_rounds_played = 0;
+        } else if self.win_condition_met(state, &state.win_condition) {
+            // the game only continues past a met win condition for a sudden-death tiebreak
+            state.sudden_death_rounds_played = state.sudden_death_rounds_played.saturating_add(1);
         }
 
         self.clamp_storyteller_loss_complement(state);
@@ -8220,6 +9528,7 @@ impl Room {
         state.forced_random_vote_cards.clear();
         state.storyteller_point_change.clear();
         state.beauty_point_change.clear();
+        state.storyteller_pass_penalty_change.clear();
         state.current_description.clear();
         state.paused_reason = None;
         state.paused_needs_storyteller_selection = false;
@@ -8285,9 +9594,9 @@ impl Room {
                 let next_card = match deck.pop() {
                     Some(card) => card,
                     None => {
-                        if matches!(state.win_condition, WinCondition::CardsFinish) {
+                        if self.deck_depletion_ends_game(state) {
                             self.transition_to_end(state)?;
-                            self.broadcast_msg(ServerMsg::EndGame {})?;
+                            self.broadcast_msg(self.end_game_msg(state))?;
                             self.broadcast_msg(self.room_state(state))?;
                             return Ok(());
                         }
@@ -8306,11 +9615,23 @@ impl Room {
             self.clean_pins_for_player_hand(state, &player);
         }
         self.set_stage(state, RoomStage::ActiveChooses);
+        self.deal_missing_secret_objectives(state);
 
         for player in state.player_order.iter() {
             let _ = self
                 .send_msg(&state, &player, self.get_msg(Some(&player), &state)?)
                 .await;
+            if let Some(objective) = state.member_secret_objectives.get(player) {
+                let _ = self
+                    .send_msg(
+                        state,
+                        player,
+                        ServerMsg::SecretObjective {
+                            objective: objective.clone(),
+                        },
+                    )
+                    .await;
+            }
         }
 
         self.clear_ready(state);
@@ -8438,7 +9759,7 @@ impl Room {
                             self.init_beauty_results(&mut state)?;
                         } else if self.should_end_game(&state) {
                             self.transition_to_end(&mut state)?;
-                            self.broadcast_msg(ServerMsg::EndGame {})?;
+                            self.broadcast_msg(self.end_game_msg(&state))?;
                             self.broadcast_msg(self.room_state(&state))?;
                         } else if state.players.len() >= 3 {
                             self.init_round(&mut state).await?;
@@ -8612,6 +9933,156 @@ impl Room {
                     .await?;
                 }
             }
+            ClientMsg::RequestStatsExportToken {} => {
+                if !self.is_moderator(&state, name) {
+                    if let Some(tx) = state.player_to_socket.get(name) {
+                        tx.send(
+                            ServerMsg::ErrorMsg("Only moderators can export stats".to_string())
+                                .into(),
+                        )
+                        .await?;
+                    }
+                    return Ok(());
+                }
+
+                // moderators share one token, reissued once it expires
+                let now_s = get_time_s();
+                if state.stats_export_token.is_none()
+                    || state.stats_export_token_expires_at_s <= now_s
+                {
+                    state.stats_export_token = Some(Self::generate_access_token());
+                    state.stats_export_token_expires_at_s = now_s + STATS_EXPORT_TOKEN_TTL_S;
+                }
+                if let (Some(tx), Some(token)) = (
+                    state.player_to_socket.get(name),
+                    state.stats_export_token.clone(),
+                ) {
+                    tx.send(
+                        ServerMsg::StatsExportToken {
+                            token,
+                            expires_at_s: state.stats_export_token_expires_at_s,
+                        }
+                        .into(),
+                    )
+                    .await?;
+                }
+            }
+            ClientMsg::SetStatsOptOut { opt_out } => {
+                let player_hash = self.member_hash_for_name(&state, name);
+                if let Err(err) = self.most_beautiful_stats.set_player_stats_opt_out(
+                    &player_hash,
+                    opt_out,
+                    get_time_s(),
+                ) {
+                    println!("Failed to update stats opt-out for {}: {}", name, err);
+                }
+                if let Some(tx) = state.player_to_socket.get(name) {
+                    tx.send(ServerMsg::StatsOptOut { opt_out }.into()).await?;
+                }
+            }
+            ClientMsg::SetRoomPassword { password } => {
+                let error = if !self.is_moderator(&state, name) {
+                    Some("Only moderators can change the room password")
+                } else if password
+                    .as_deref()
+                    .is_some_and(|password| password.trim().len() > MAX_ROOM_PASSWORD_LEN)
+                {
+                    Some("Room password too long")
+                } else {
+                    None
+                };
+                if let Some(error) = error {
+                    if let Some(tx) = state.player_to_socket.get(name) {
+                        tx.send(ServerMsg::ErrorMsg(error.to_string()).into())
+                            .await?;
+                    }
+                    return Ok(());
+                }
+
+                let password = password
+                    .map(|password| password.trim().to_string())
+                    .filter(|password| !password.is_empty());
+                let password_hash = match password {
+                    Some(password) => {
+                        // hash without holding the room lock
+                        drop(state);
+                        let hash =
+                            tokio::task::spawn_blocking(move || hash_room_password(&password))
+                                .await?;
+                        state = self.state.write().await;
+                        // the member may have been demoted or kicked while hashing
+                        if !self.is_moderator(&state, name) {
+                            if let Some(tx) = state.player_to_socket.get(name) {
+                                tx.send(
+                                    ServerMsg::ErrorMsg(
+                                        "Only moderators can change the room password".to_string(),
+                                    )
+                                    .into(),
+                                )
+                                .await?;
+                            }
+                            return Ok(());
+                        }
+                        Some(hash)
+                    }
+                    None => None,
+                };
+                let has_password = password_hash.is_some();
+                state.room_password_hash = password_hash;
+                self.password_failures.lock().unwrap().clear();
+                println!(
+                    "{} {} the room password",
+                    name,
+                    if has_password { "set" } else { "removed" }
+                );
+
+                if let Some(tx) = state.player_to_socket.get(name) {
+                    tx.send(ServerMsg::RoomPasswordUpdated { has_password }.into())
+                        .await?;
+                }
+                self.broadcast_msg(self.room_state(&state))?;
+            }
+            ClientMsg::SetPublicListing { listing } => {
+                let listing = if !self.is_moderator(&state, name) {
+                    Err("Only moderators can list the room publicly")
+                } else if listing.is_some() && state.invite_key.is_some() {
+                    Err("Unlisted rooms can't be listed publicly")
+                } else {
+                    listing
+                        .as_ref()
+                        .map(PublicRoomListing::normalized)
+                        .transpose()
+                };
+                let listing = match listing {
+                    Ok(listing) => listing,
+                    Err(error) => {
+                        if let Some(tx) = state.player_to_socket.get(name) {
+                            tx.send(ServerMsg::ErrorMsg(error.to_string()).into())
+                                .await?;
+                        }
+                        return Ok(());
+                    }
+                };
+                state.public_listing = listing;
+                self.broadcast_msg(self.room_state(&state))?;
+            }
+            ClientMsg::ClaimRoomOwner { owner_token } => {
+                let owner_token = owner_token.trim();
+                let claimed = !owner_token.is_empty()
+                    && state.owner_token_hash.as_deref()
+                        == Some(hash_room_owner_token(owner_token).as_str());
+                if !claimed {
+                    if let Some(tx) = state.player_to_socket.get(name) {
+                        tx.send(ServerMsg::ErrorMsg("Incorrect owner token".to_string()))
+                            .await?;
+                    }
+                    return Ok(());
+                }
+                state.creator = Some(name.to_string());
+                state.moderators.insert(name.to_string());
+                println!("{} claimed room {} as its owner", name, state.room_id);
+                self.broadcast_msg(self.room_state(&state))?;
+            }
             ClientMsg::RaiseScoreToActiveMin { player } => {
                 if !self.is_moderator(&state, name) {
                     if let Some(tx) = state.player_to_socket.get(name) {
@@ -8923,7 +10394,20 @@ impl Room {
                     }
                     return Ok(());
                 }
-                if !win_condition_supported_by_game_mode(state.game_mode, win_condition) {
+                let win_condition = match validate_win_condition(win_condition) {
+                    Ok(win_condition) => win_condition,
+                    Err(err) => {
+                        if let Some(tx) = state.player_to_socket.get(name) {
+                            tx.send(
+                                ServerMsg::ErrorMsg(format!("Invalid win condition: {}", err))
+                                    .into(),
+                            )
+                            .await?;
+                        }
+                        return Ok(());
+                    }
+                };
+                if !win_condition_supported_by_game_mode(state.game_mode, &win_condition) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
@@ -9448,12 +10932,12 @@ impl Room {
                 self.clamp_storyteller_success_points(&mut state);
                 self.broadcast_msg(self.room_state(&state))?;
             }
-            ClientMsg::SetDoubleVoteBonusNormalPoints { points } => {
+            ClientMsg::SetStorytellerPassPenalty { penalty } => {
                 if !self.is_moderator(&state, name) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Only moderators can change double-vote bonus settings".to_string(),
+                                "Only moderators can change storyteller pass settings".to_string(),
                             )
                             .into(),
                         )
@@ -9462,16 +10946,21 @@ impl Room {
                     return Ok(());
                 }
 
-                let can_change_storyteller_setting = match state.game_mode {
-                    GameMode::DixitPlus => Self::is_joining_or_live_dixit_stage(state.stage),
-                    GameMode::Stella => !matches!(state.stage, RoomStage::End),
-                };
-                if !can_change_storyteller_setting {
+                if !matches!(state.game_mode, GameMode::DixitPlus)
+                    || !Self::is_joining_or_live_dixit_stage(state.stage)
+                {
+                    return Ok(());
+                }
+
+                state.storyteller_pass_penalty = penalty;
+                self.broadcast_msg(self.room_state(&state))?;
+            }
+            ClientMsg::SetStorytellerPassPenaltyPoints { points } => {
+                if !self.is_moderator(&state, name) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Storyteller scoring can only be changed during live Dixit stages"
-                                    .to_string(),
+                                "Only moderators can change storyteller pass settings".to_string(),
                             )
                             .into(),
                         )
@@ -9480,16 +10969,22 @@ impl Room {
                     return Ok(());
                 }
 
-                state.double_vote_bonus_normal_points = points;
-                self.clamp_double_vote_bonus_points(&mut state);
+                if !matches!(state.game_mode, GameMode::DixitPlus)
+                    || !Self::is_joining_or_live_dixit_stage(state.stage)
+                {
+                    return Ok(());
+                }
+
+                state.storyteller_pass_penalty_points = points;
+                self.clamp_storyteller_pass_penalty_points(&mut state);
                 self.broadcast_msg(self.room_state(&state))?;
             }
-            ClientMsg::SetDoubleVoteBonusTooManyWrongPoints { points } => {
+            ClientMsg::SetHandicapOffset { player, points } => {
                 if !self.is_moderator(&state, name) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Only moderators can change double-vote bonus settings".to_string(),
+                                "Only moderators can change handicap settings".to_string(),
                             )
                             .into(),
                         )
@@ -9498,15 +10993,13 @@ impl Room {
                     return Ok(());
                 }
 
-                let can_change_storyteller_setting = match state.game_mode {
-                    GameMode::DixitPlus => Self::is_joining_or_live_dixit_stage(state.stage),
-                    GameMode::Stella => !matches!(state.stage, RoomStage::End),
-                };
-                if !can_change_storyteller_setting {
+                if !matches!(state.game_mode, GameMode::DixitPlus)
+                    || !matches!(state.stage, RoomStage::Joining)
+                {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Storyteller scoring can only be changed during live Dixit stages"
+                                "Starting handicaps can only be changed before a Dixit game starts"
                                     .to_string(),
                             )
                             .into(),
@@ -9516,16 +11009,27 @@ impl Room {
                     return Ok(());
                 }
 
-                state.double_vote_bonus_too_many_wrong_points = points;
-                self.clamp_double_vote_bonus_points(&mut state);
+                let target = canonical_member_name(&player);
+                if !self.member_exists(&state, target) {
+                    return Ok(());
+                }
+
+                let points = points.min(MAX_HANDICAP_OFFSET_POINTS);
+                if points == 0 {
+                    state.member_handicap_offsets.remove(target);
+                } else {
+                    state
+                        .member_handicap_offsets
+                        .insert(target.to_string(), points);
+                }
                 self.broadcast_msg(self.room_state(&state))?;
             }
-            ClientMsg::SetDoubleVoteBonusTooManyWrongFollowsNormal { enabled } => {
+            ClientMsg::SetTiebreakRules { rules } => {
                 if !self.is_moderator(&state, name) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Only moderators can change double-vote bonus settings".to_string(),
+                                "Only moderators can change tiebreak rules".to_string(),
                             )
                             .into(),
                         )
@@ -9534,16 +11038,25 @@ impl Room {
                     return Ok(());
                 }
 
-                let can_change_storyteller_setting = match state.game_mode {
-                    GameMode::DixitPlus => Self::is_joining_or_live_dixit_stage(state.stage),
-                    GameMode::Stella => !matches!(state.stage, RoomStage::End),
-                };
-                if !can_change_storyteller_setting {
+                if matches!(state.stage, RoomStage::End) {
+                    return Ok(());
+                }
+
+                let mut deduped_rules = Vec::new();
+                for rule in rules {
+                    if !deduped_rules.contains(&rule) {
+                        deduped_rules.push(rule);
+                    }
+                }
+                state.tiebreak_rules = deduped_rules;
+                self.broadcast_msg(self.room_state(&state))?;
+            }
+            ClientMsg::SetCatchUpBonusEnabled { enabled } => {
+                if !self.is_moderator(&state, name) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Storyteller scoring can only be changed during live Dixit stages"
-                                    .to_string(),
+                                "Only moderators can change handicap settings".to_string(),
                             )
                             .into(),
                         )
@@ -9552,20 +11065,21 @@ impl Room {
                     return Ok(());
                 }
 
-                if !enabled {
-                    state.double_vote_bonus_too_many_wrong_points =
-                        self.effective_double_vote_bonus_normal_points(&state);
+                if !matches!(state.game_mode, GameMode::DixitPlus)
+                    || !Self::is_joining_or_live_dixit_stage(state.stage)
+                {
+                    return Ok(());
                 }
-                state.double_vote_bonus_too_many_wrong_follows_normal = enabled;
-                self.clamp_double_vote_bonus_points(&mut state);
+
+                state.catch_up_bonus_enabled = enabled;
                 self.broadcast_msg(self.room_state(&state))?;
             }
-            ClientMsg::SetDoubleVoteBonusTooManyCorrectPoints { points } => {
+            ClientMsg::SetCatchUpBonusPoints { points } => {
                 if !self.is_moderator(&state, name) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Only moderators can change double-vote bonus settings".to_string(),
+                                "Only moderators can change handicap settings".to_string(),
                             )
                             .into(),
                         )
@@ -9574,16 +11088,22 @@ impl Room {
                     return Ok(());
                 }
 
-                let can_change_storyteller_setting = match state.game_mode {
-                    GameMode::DixitPlus => Self::is_joining_or_live_dixit_stage(state.stage),
-                    GameMode::Stella => !matches!(state.stage, RoomStage::End),
-                };
-                if !can_change_storyteller_setting {
+                if !matches!(state.game_mode, GameMode::DixitPlus)
+                    || !Self::is_joining_or_live_dixit_stage(state.stage)
+                {
+                    return Ok(());
+                }
+
+                state.catch_up_bonus_points = points;
+                self.clamp_catch_up_settings(&mut state);
+                self.broadcast_msg(self.room_state(&state))?;
+            }
+            ClientMsg::SetCatchUpDeficitPoints { points } => {
+                if !self.is_moderator(&state, name) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Storyteller scoring can only be changed during live Dixit stages"
-                                    .to_string(),
+                                "Only moderators can change handicap settings".to_string(),
                             )
                             .into(),
                         )
@@ -9592,16 +11112,22 @@ impl Room {
                     return Ok(());
                 }
 
-                state.double_vote_bonus_too_many_correct_points = points;
-                self.clamp_double_vote_bonus_points(&mut state);
+                if !matches!(state.game_mode, GameMode::DixitPlus)
+                    || !Self::is_joining_or_live_dixit_stage(state.stage)
+                {
+                    return Ok(());
+                }
+
+                state.catch_up_deficit_points = points;
+                self.clamp_catch_up_settings(&mut state);
                 self.broadcast_msg(self.room_state(&state))?;
             }
-            ClientMsg::SetDoubleVoteBonusTooManyCorrectFollowsNormal { enabled } => {
+            ClientMsg::SetSecretObjectivesEnabled { enabled } => {
                 if !self.is_moderator(&state, name) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Only moderators can change double-vote bonus settings".to_string(),
+                                "Only moderators can change secret objective settings".to_string(),
                             )
                             .into(),
                         )
@@ -9610,38 +11136,21 @@ impl Room {
                     return Ok(());
                 }
 
-                let can_change_storyteller_setting = match state.game_mode {
-                    GameMode::DixitPlus => Self::is_joining_or_live_dixit_stage(state.stage),
-                    GameMode::Stella => !matches!(state.stage, RoomStage::End),
-                };
-                if !can_change_storyteller_setting {
-                    if let Some(tx) = state.player_to_socket.get(name) {
-                        tx.send(
-                            ServerMsg::ErrorMsg(
-                                "Storyteller scoring can only be changed during live Dixit stages"
-                                    .to_string(),
-                            )
-                            .into(),
-                        )
-                        .await?;
-                    }
+                if !matches!(state.game_mode, GameMode::DixitPlus)
+                    || !Self::is_joining_or_live_dixit_stage(state.stage)
+                {
                     return Ok(());
                 }
 
-                if !enabled {
-                    state.double_vote_bonus_too_many_correct_points =
-                        self.effective_double_vote_bonus_normal_points(&state);
-                }
-                state.double_vote_bonus_too_many_correct_follows_normal = enabled;
-                self.clamp_double_vote_bonus_points(&mut state);
+                state.secret_objectives_enabled = enabled;
                 self.broadcast_msg(self.room_state(&state))?;
             }
-            ClientMsg::SetVotesPerGuesser { votes } => {
+            ClientMsg::SetSecretObjectiveBonusPoints { points } => {
                 if !self.is_moderator(&state, name) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Only moderators can change votes per guesser".to_string(),
+                                "Only moderators can change secret objective settings".to_string(),
                             )
                             .into(),
                         )
@@ -9650,95 +11159,154 @@ impl Room {
                     return Ok(());
                 }
 
-                if matches!(state.stage, RoomStage::End) {
+                if !matches!(state.game_mode, GameMode::DixitPlus)
+                    || !Self::is_joining_or_live_dixit_stage(state.stage)
+                {
                     return Ok(());
                 }
 
-                if !Self::is_before_voting_stage(state.stage) {
+                state.secret_objective_bonus_points = points;
+                self.clamp_secret_objective_bonus_points(&mut state);
+                self.broadcast_msg(self.room_state(&state))?;
+            }
+            ClientMsg::SendChat { text } => {
+                let text = text.trim();
+                if text.is_empty() {
+                    return Ok(());
+                }
+
+                let now_ms = get_time_ms();
+                let error = if text.chars().count() > MAX_CHAT_MESSAGE_CHARS {
+                    Some(format!(
+                        "Chat messages are limited to {} characters",
+                        MAX_CHAT_MESSAGE_CHARS
+                    ))
+                } else {
+                    self.check_chat_action(&mut state, name, now_ms)
+                        .map(str::to_string)
+                };
+                if let Some(error) = error {
                     if let Some(tx) = state.player_to_socket.get(name) {
-                        tx.send(
-                            ServerMsg::ErrorMsg(
-                                "Votes per guesser can only be changed before voting starts"
-                                    .to_string(),
-                            )
-                            .into(),
-                        )
-                        .await?;
+                        tx.send(ServerMsg::ErrorMsg(error).into()).await?;
                     }
                     return Ok(());
                 }
 
-                state.votes_per_guesser_auto = false;
-                state.votes_per_guesser = votes;
-                self.clamp_votes_per_guesser(&mut state);
-                self.clamp_vote_submission_lengths(&mut state);
-                self.broadcast_msg(self.room_state(&state))?;
+                state.next_chat_message_id += 1;
+                let message = ChatMessage {
+                    id: state.next_chat_message_id,
+                    sender: name.to_string(),
+                    text: text.to_string(),
+                    sent_at_ms: now_ms,
+                    reactions: BTreeMap::new(),
+                };
+                state.chat_history.push_back(message.clone());
+                while state.chat_history.len() > CHAT_HISTORY_LIMIT {
+                    state.chat_history.pop_front();
+                }
+                self.broadcast_msg(ServerMsg::Chat { message })?;
+            }
+            ClientMsg::React { message_id, emoji } => {
+                if !CHAT_REACTIONS.contains(&emoji.as_str())
+                    || !state
+                        .chat_history
+                        .iter()
+                        .any(|message| message.id == message_id)
+                {
+                    return Ok(());
+                }
+
+                if let Some(error) = self.check_chat_action(&mut state, name, get_time_ms()) {
+                    if let Some(tx) = state.player_to_socket.get(name) {
+                        tx.send(ServerMsg::ErrorMsg(error.to_string()).into())
+                            .await?;
+                    }
+                    return Ok(());
+                }
+
+                let Some(message) = state
+                    .chat_history
+                    .iter_mut()
+                    .find(|message| message.id == message_id)
+                else {
+                    return Ok(());
+                };
+                // reacting again with the same emoji removes the reaction
+                let reactors = message.reactions.entry(emoji.clone()).or_default();
+                if !reactors.remove(name) {
+                    reactors.insert(name.to_string());
+                }
+                if reactors.is_empty() {
+                    message.reactions.remove(&emoji);
+                }
+                let message = message.clone();
+                self.broadcast_msg(ServerMsg::Chat { message })?;
             }
-            ClientMsg::SetVotesPerGuesserAuto { enabled } => {
+            ClientMsg::SetChatMuted { member, muted } => {
                 if !self.is_moderator(&state, name) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
-                            ServerMsg::ErrorMsg(
-                                "Only moderators can change vote count settings".to_string(),
-                            )
-                            .into(),
+                            ServerMsg::ErrorMsg("Only moderators can moderate chat".to_string())
+                                .into(),
                         )
                         .await?;
                     }
                     return Ok(());
                 }
 
-                if matches!(state.stage, RoomStage::End) {
+                let target = canonical_member_name(&member);
+                if !self.member_exists(&state, target) || state.moderators.contains(target) {
                     return Ok(());
                 }
 
-                if !Self::is_before_voting_stage(state.stage) {
+                if muted {
+                    state.chat_muted_members.insert(target.to_string());
+                } else {
+                    state.chat_muted_members.remove(target);
+                }
+                self.broadcast_msg(self.room_state(&state))?;
+            }
+            ClientMsg::DeleteChatMessage { message_id } => {
+                if !self.is_moderator(&state, name) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
-                            ServerMsg::ErrorMsg(
-                                "Votes per guesser can only be changed before voting starts"
-                                    .to_string(),
-                            )
-                            .into(),
+                            ServerMsg::ErrorMsg("Only moderators can moderate chat".to_string())
+                                .into(),
                         )
                         .await?;
                     }
                     return Ok(());
                 }
 
-                state.votes_per_guesser_auto = enabled;
-                self.clamp_votes_per_guesser(&mut state);
-                self.clamp_vote_submission_lengths(&mut state);
-                self.broadcast_msg(self.room_state(&state))?;
+                let before = state.chat_history.len();
+                state
+                    .chat_history
+                    .retain(|message| message.id != message_id);
+                if state.chat_history.len() != before {
+                    self.broadcast_msg(ServerMsg::ChatDeleted { message_id })?;
+                }
             }
-            ClientMsg::SetBeautyEnabled { enabled } => {
+            ClientMsg::SetChatStageRestrictionsEnabled { enabled } => {
                 if !self.is_moderator(&state, name) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
-                            ServerMsg::ErrorMsg(
-                                "Only moderators can change Most Beautiful settings".to_string(),
-                            )
-                            .into(),
+                            ServerMsg::ErrorMsg("Only moderators can moderate chat".to_string())
+                                .into(),
                         )
                         .await?;
                     }
                     return Ok(());
                 }
 
-                if matches!(state.stage, RoomStage::End) {
-                    return Ok(());
-                }
-
-                if !matches!(state.game_mode, GameMode::DixitPlus) {
-                    return Ok(());
-                }
-
-                if !Self::is_before_beauty_voting_stage(state.stage) {
+                state.chat_stage_restrictions_enabled = enabled;
+                self.broadcast_msg(self.room_state(&state))?;
+            }
+            ClientMsg::CreateSpectatorLink {} => {
+                if !self.is_moderator(&state, name) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Some Most Beautiful settings can only be changed before beauty voting starts"
-                                    .to_string(),
+                                "Only moderators can manage spectator links".to_string(),
                             )
                             .into(),
                         )
@@ -9747,15 +11315,16 @@ impl Room {
                     return Ok(());
                 }
 
-                state.beauty_enabled = enabled;
+                // a fresh token also disconnects spectators holding the previous link
+                state.spectator_token = Some(Self::generate_access_token());
                 self.broadcast_msg(self.room_state(&state))?;
             }
-            ClientMsg::SetBeautyVotesPerPlayer { votes } => {
+            ClientMsg::RevokeSpectatorLink {} => {
                 if !self.is_moderator(&state, name) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Only moderators can change Most Beautiful settings".to_string(),
+                                "Only moderators can manage spectator links".to_string(),
                             )
                             .into(),
                         )
@@ -9764,20 +11333,15 @@ impl Room {
                     return Ok(());
                 }
 
-                if matches!(state.stage, RoomStage::End) {
-                    return Ok(());
-                }
-
-                if !matches!(state.game_mode, GameMode::DixitPlus) {
-                    return Ok(());
-                }
-
-                if !Self::is_before_beauty_voting_stage(state.stage) {
+                state.spectator_token = None;
+                self.broadcast_msg(self.room_state(&state))?;
+            }
+            ClientMsg::SetSpectatorDelay { seconds } => {
+                if !self.is_moderator(&state, name) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Some Most Beautiful settings can only be changed before beauty voting starts"
-                                    .to_string(),
+                                "Only moderators can manage spectator links".to_string(),
                             )
                             .into(),
                         )
@@ -9786,18 +11350,16 @@ impl Room {
                     return Ok(());
                 }
 
-                state.beauty_votes_per_player_auto = false;
-                state.beauty_votes_per_player = votes;
-                self.clamp_beauty_votes_per_player(&mut state);
-                self.clamp_beauty_vote_submission_lengths(&mut state);
+                state.spectator_delay_s =
+                    seconds.clamp(MIN_SPECTATOR_DELAY_S, MAX_SPECTATOR_DELAY_S);
                 self.broadcast_msg(self.room_state(&state))?;
             }
-            ClientMsg::SetBeautyVotesPerPlayerAuto { enabled } => {
+            ClientMsg::SetDoubleVoteBonusNormalPoints { points } => {
                 if !self.is_moderator(&state, name) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Only moderators can change Most Beautiful settings".to_string(),
+                                "Only moderators can change double-vote bonus settings".to_string(),
                             )
                             .into(),
                         )
@@ -9806,19 +11368,15 @@ impl Room {
                     return Ok(());
                 }
 
-                if matches!(state.stage, RoomStage::End) {
-                    return Ok(());
-                }
-
-                if !matches!(state.game_mode, GameMode::DixitPlus) {
-                    return Ok(());
-                }
-
-                if !Self::is_before_beauty_voting_stage(state.stage) {
+                let can_change_storyteller_setting = match state.game_mode {
+                    GameMode::DixitPlus => Self::is_joining_or_live_dixit_stage(state.stage),
+                    GameMode::Stella => !matches!(state.stage, RoomStage::End),
+                };
+                if !can_change_storyteller_setting {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Some Most Beautiful settings can only be changed before beauty voting starts"
+                                "Storyteller scoring can only be changed during live Dixit stages"
                                     .to_string(),
                             )
                             .into(),
@@ -9828,17 +11386,16 @@ impl Room {
                     return Ok(());
                 }
 
-                state.beauty_votes_per_player_auto = enabled;
-                self.clamp_beauty_votes_per_player(&mut state);
-                self.clamp_beauty_vote_submission_lengths(&mut state);
+                state.double_vote_bonus_normal_points = points;
+                self.clamp_double_vote_bonus_points(&mut state);
                 self.broadcast_msg(self.room_state(&state))?;
             }
-            ClientMsg::SetBeautyAllowDuplicateVotes { enabled } => {
+            ClientMsg::SetDoubleVoteBonusTooManyWrongPoints { points } => {
                 if !self.is_moderator(&state, name) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Only moderators can change Most Beautiful settings".to_string(),
+                                "Only moderators can change double-vote bonus settings".to_string(),
                             )
                             .into(),
                         )
@@ -9847,19 +11404,15 @@ impl Room {
                     return Ok(());
                 }
 
-                if matches!(state.stage, RoomStage::End) {
-                    return Ok(());
-                }
-
-                if !matches!(state.game_mode, GameMode::DixitPlus) {
-                    return Ok(());
-                }
-
-                if !Self::is_before_beauty_voting_stage(state.stage) {
+                let can_change_storyteller_setting = match state.game_mode {
+                    GameMode::DixitPlus => Self::is_joining_or_live_dixit_stage(state.stage),
+                    GameMode::Stella => !matches!(state.stage, RoomStage::End),
+                };
+                if !can_change_storyteller_setting {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Some Most Beautiful settings can only be changed before beauty voting starts"
+                                "Storyteller scoring can only be changed during live Dixit stages"
                                     .to_string(),
                             )
                             .into(),
@@ -9869,15 +11422,16 @@ impl Room {
                     return Ok(());
                 }
 
-                state.beauty_allow_duplicate_votes = enabled;
+                state.double_vote_bonus_too_many_wrong_points = points;
+                self.clamp_double_vote_bonus_points(&mut state);
                 self.broadcast_msg(self.room_state(&state))?;
             }
-            ClientMsg::SetBeautySplitPointsOnTie { enabled } => {
+            ClientMsg::SetDoubleVoteBonusTooManyWrongFollowsNormal { enabled } => {
                 if !self.is_moderator(&state, name) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Only moderators can change Most Beautiful settings".to_string(),
+                                "Only moderators can change double-vote bonus settings".to_string(),
                             )
                             .into(),
                         )
@@ -9886,19 +11440,15 @@ impl Room {
                     return Ok(());
                 }
 
-                if matches!(state.stage, RoomStage::End) {
-                    return Ok(());
-                }
-
-                if !matches!(state.game_mode, GameMode::DixitPlus) {
-                    return Ok(());
-                }
-
-                if !Self::is_joining_or_live_dixit_stage(state.stage) {
+                let can_change_storyteller_setting = match state.game_mode {
+                    GameMode::DixitPlus => Self::is_joining_or_live_dixit_stage(state.stage),
+                    GameMode::Stella => !matches!(state.stage, RoomStage::End),
+                };
+                if !can_change_storyteller_setting {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Most Beautiful scoring can only be changed during live Dixit stages"
+                                "Storyteller scoring can only be changed during live Dixit stages"
                                     .to_string(),
                             )
                             .into(),
@@ -9908,15 +11458,20 @@ impl Room {
                     return Ok(());
                 }
 
-                state.beauty_split_points_on_tie = enabled;
+                if !enabled {
+                    state.double_vote_bonus_too_many_wrong_points =
+                        self.effective_double_vote_bonus_normal_points(&state);
+                }
+                state.double_vote_bonus_too_many_wrong_follows_normal = enabled;
+                self.clamp_double_vote_bonus_points(&mut state);
                 self.broadcast_msg(self.room_state(&state))?;
             }
-            ClientMsg::SetBeautyPointsBonus { points } => {
+            ClientMsg::SetDoubleVoteBonusTooManyCorrectPoints { points } => {
                 if !self.is_moderator(&state, name) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Only moderators can change Most Beautiful settings".to_string(),
+                                "Only moderators can change double-vote bonus settings".to_string(),
                             )
                             .into(),
                         )
@@ -9925,19 +11480,15 @@ impl Room {
                     return Ok(());
                 }
 
-                if matches!(state.stage, RoomStage::End) {
-                    return Ok(());
-                }
-
-                if !matches!(state.game_mode, GameMode::DixitPlus) {
-                    return Ok(());
-                }
-
-                if !Self::is_joining_or_live_dixit_stage(state.stage) {
+                let can_change_storyteller_setting = match state.game_mode {
+                    GameMode::DixitPlus => Self::is_joining_or_live_dixit_stage(state.stage),
+                    GameMode::Stella => !matches!(state.stage, RoomStage::End),
+                };
+                if !can_change_storyteller_setting {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Most Beautiful scoring can only be changed during live Dixit stages"
+                                "Storyteller scoring can only be changed during live Dixit stages"
                                     .to_string(),
                             )
                             .into(),
@@ -9947,16 +11498,16 @@ impl Room {
                     return Ok(());
                 }
 
-                state.beauty_points_bonus = points;
-                self.clamp_beauty_points_bonus(&mut state);
+                state.double_vote_bonus_too_many_correct_points = points;
+                self.clamp_double_vote_bonus_points(&mut state);
                 self.broadcast_msg(self.room_state(&state))?;
             }
-            ClientMsg::SetBeautyScoringMode { mode } => {
+            ClientMsg::SetDoubleVoteBonusTooManyCorrectFollowsNormal { enabled } => {
                 if !self.is_moderator(&state, name) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Only moderators can change Most Beautiful settings".to_string(),
+                                "Only moderators can change double-vote bonus settings".to_string(),
                             )
                             .into(),
                         )
@@ -9965,19 +11516,15 @@ impl Room {
                     return Ok(());
                 }
 
-                if matches!(state.stage, RoomStage::End) {
-                    return Ok(());
-                }
-
-                if !matches!(state.game_mode, GameMode::DixitPlus) {
-                    return Ok(());
-                }
-
-                if !Self::is_joining_or_live_dixit_stage(state.stage) {
+                let can_change_storyteller_setting = match state.game_mode {
+                    GameMode::DixitPlus => Self::is_joining_or_live_dixit_stage(state.stage),
+                    GameMode::Stella => !matches!(state.stage, RoomStage::End),
+                };
+                if !can_change_storyteller_setting {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Most Beautiful scoring can only be changed during live Dixit stages"
+                                "Storyteller scoring can only be changed during live Dixit stages"
                                     .to_string(),
                             )
                             .into(),
@@ -9987,21 +11534,20 @@ impl Room {
                     return Ok(());
                 }
 
-                let previous_mode = state.beauty_scoring_mode;
-                state.beauty_scoring_mode = mode;
-                if matches!(previous_mode, BeautyScoringMode::WinnerBonus)
-                    && matches!(mode, BeautyScoringMode::VoteDivisor)
-                {
-                    self.reset_vote_divisor_segment(&mut state);
+                if !enabled {
+                    state.double_vote_bonus_too_many_correct_points =
+                        self.effective_double_vote_bonus_normal_points(&state);
                 }
+                state.double_vote_bonus_too_many_correct_follows_normal = enabled;
+                self.clamp_double_vote_bonus_points(&mut state);
                 self.broadcast_msg(self.room_state(&state))?;
             }
-            ClientMsg::SetBeautyVotePointsDivisor { divisor } => {
+            ClientMsg::SetVotesPerGuesser { votes } => {
                 if !self.is_moderator(&state, name) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Only moderators can change Most Beautiful settings".to_string(),
+                                "Only moderators can change votes per guesser".to_string(),
                             )
                             .into(),
                         )
@@ -10014,16 +11560,32 @@ impl Room {
                     return Ok(());
                 }
 
-                if !matches!(state.game_mode, GameMode::DixitPlus) {
+                if !Self::is_before_voting_stage(state.stage) {
+                    if let Some(tx) = state.player_to_socket.get(name) {
+                        tx.send(
+                            ServerMsg::ErrorMsg(
+                                "Votes per guesser can only be changed before voting starts"
+                                    .to_string(),
+                            )
+                            .into(),
+                        )
+                        .await?;
+                    }
                     return Ok(());
                 }
 
-                if !Self::is_joining_or_live_dixit_stage(state.stage) {
+                state.votes_per_guesser_auto = false;
+                state.votes_per_guesser = votes;
+                self.clamp_votes_per_guesser(&mut state);
+                self.clamp_vote_submission_lengths(&mut state);
+                self.broadcast_msg(self.room_state(&state))?;
+            }
+            ClientMsg::SetVotesPerGuesserAuto { enabled } => {
+                if !self.is_moderator(&state, name) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Most Beautiful scoring can only be changed during live Dixit stages"
-                                    .to_string(),
+                                "Only moderators can change vote count settings".to_string(),
                             )
                             .into(),
                         )
@@ -10032,28 +11594,30 @@ impl Room {
                     return Ok(());
                 }
 
-                let Some(divisor_tenths) =
-                    Self::beauty_vote_points_divisor_tenths_from_input(divisor)
-                else {
+                if matches!(state.stage, RoomStage::End) {
+                    return Ok(());
+                }
+
+                if !Self::is_before_voting_stage(state.stage) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Most Beautiful vote divisor must be a finite number".to_string(),
+                                "Votes per guesser can only be changed before voting starts"
+                                    .to_string(),
                             )
                             .into(),
                         )
                         .await?;
                     }
                     return Ok(());
-                };
+                }
 
-                state.beauty_vote_points_divisor_tenths = divisor_tenths;
-                self.clamp_beauty_vote_points_divisor_tenths(&mut state);
-                self.clamp_beauty_vote_points_divisor_player_count_base(&mut state);
-                self.rescore_vote_divisor_points(&mut state);
-                self.broadcast_stage_payload_after_vote_divisor_rescore(&state)?;
+                state.votes_per_guesser_auto = enabled;
+                self.clamp_votes_per_guesser(&mut state);
+                self.clamp_vote_submission_lengths(&mut state);
+                self.broadcast_msg(self.room_state(&state))?;
             }
-            ClientMsg::SetBeautyVotePointsDivisorMode { mode } => {
+            ClientMsg::SetBeautyEnabled { enabled } => {
                 if !self.is_moderator(&state, name) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
@@ -10075,11 +11639,11 @@ impl Room {
                     return Ok(());
                 }
 
-                if !Self::is_joining_or_live_dixit_stage(state.stage) {
+                if !Self::is_before_beauty_voting_stage(state.stage) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Most Beautiful scoring can only be changed during live Dixit stages"
+                                "Some Most Beautiful settings can only be changed before beauty voting starts"
                                     .to_string(),
                             )
                             .into(),
@@ -10089,11 +11653,10 @@ impl Room {
                     return Ok(());
                 }
 
-                state.beauty_vote_points_divisor_mode = mode;
-                self.rescore_vote_divisor_points(&mut state);
-                self.broadcast_stage_payload_after_vote_divisor_rescore(&state)?;
+                state.beauty_enabled = enabled;
+                self.broadcast_msg(self.room_state(&state))?;
             }
-            ClientMsg::SetBeautyVotePointsDivisorPlayerCountBase { base } => {
+            ClientMsg::SetBeautyVotesPerPlayer { votes } => {
                 if !self.is_moderator(&state, name) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
@@ -10115,11 +11678,11 @@ impl Room {
                     return Ok(());
                 }
 
-                if !Self::is_joining_or_live_dixit_stage(state.stage) {
+                if !Self::is_before_beauty_voting_stage(state.stage) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Most Beautiful scoring can only be changed during live Dixit stages"
+                                "Some Most Beautiful settings can only be changed before beauty voting starts"
                                     .to_string(),
                             )
                             .into(),
@@ -10129,12 +11692,13 @@ impl Room {
                     return Ok(());
                 }
 
-                state.beauty_vote_points_divisor_player_count_base = base;
-                self.clamp_beauty_vote_points_divisor_player_count_base(&mut state);
-                self.rescore_vote_divisor_points(&mut state);
-                self.broadcast_stage_payload_after_vote_divisor_rescore(&state)?;
+                state.beauty_votes_per_player_auto = false;
+                state.beauty_votes_per_player = votes;
+                self.clamp_beauty_votes_per_player(&mut state);
+                self.clamp_beauty_vote_submission_lengths(&mut state);
+                self.broadcast_msg(self.room_state(&state))?;
             }
-            ClientMsg::SetBeautyResultsDisplayMode { mode } => {
+            ClientMsg::SetBeautyVotesPerPlayerAuto { enabled } => {
                 if !self.is_moderator(&state, name) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
@@ -10156,11 +11720,11 @@ impl Room {
                     return Ok(());
                 }
 
-                if !Self::is_before_results_stage(state.stage) {
+                if !Self::is_before_beauty_voting_stage(state.stage) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Most Beautiful result display can only be changed before results are revealed"
+                                "Some Most Beautiful settings can only be changed before beauty voting starts"
                                     .to_string(),
                             )
                             .into(),
@@ -10170,15 +11734,17 @@ impl Room {
                     return Ok(());
                 }
 
-                state.beauty_results_display_mode = mode;
+                state.beauty_votes_per_player_auto = enabled;
+                self.clamp_beauty_votes_per_player(&mut state);
+                self.clamp_beauty_vote_submission_lengths(&mut state);
                 self.broadcast_msg(self.room_state(&state))?;
             }
-            ClientMsg::SetShowPreviousResultsDuringStorytellerChoosing { enabled } => {
+            ClientMsg::SetBeautyAllowDuplicateVotes { enabled } => {
                 if !self.is_moderator(&state, name) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Only moderators can change previous-results preview".to_string(),
+                                "Only moderators can change Most Beautiful settings".to_string(),
                             )
                             .into(),
                         )
@@ -10195,11 +11761,11 @@ impl Room {
                     return Ok(());
                 }
 
-                if !Self::is_joining_or_live_dixit_stage(state.stage) {
+                if !Self::is_before_beauty_voting_stage(state.stage) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Previous-results preview can only be changed during live Dixit stages"
+                                "Some Most Beautiful settings can only be changed before beauty voting starts"
                                     .to_string(),
                             )
                             .into(),
@@ -10209,15 +11775,15 @@ impl Room {
                     return Ok(());
                 }
 
-                state.show_previous_results_during_storyteller_choosing = enabled;
+                state.beauty_allow_duplicate_votes = enabled;
                 self.broadcast_msg(self.room_state(&state))?;
             }
-            ClientMsg::SetCardsPerHand { cards } => {
+            ClientMsg::SetBeautySplitPointsOnTie { enabled } => {
                 if !self.is_moderator(&state, name) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Only moderators can change cards per hand".to_string(),
+                                "Only moderators can change Most Beautiful settings".to_string(),
                             )
                             .into(),
                         )
@@ -10230,11 +11796,16 @@ impl Room {
                     return Ok(());
                 }
 
-                if !matches!(state.stage, RoomStage::Joining | RoomStage::ActiveChooses) {
+                if !matches!(state.game_mode, GameMode::DixitPlus) {
+                    return Ok(());
+                }
+
+                if !Self::is_joining_or_live_dixit_stage(state.stage) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Cards per hand can only be changed at round start".to_string(),
+                                "Most Beautiful scoring can only be changed during live Dixit stages"
+                                    .to_string(),
                             )
                             .into(),
                         )
@@ -10243,29 +11814,15 @@ impl Room {
                     return Ok(());
                 }
 
-                state.cards_per_hand = cards;
-                self.clamp_cards_per_hand(&mut state);
-                self.clamp_nominations_per_guesser(&mut state);
-                self.apply_cards_per_hand_change(&mut state);
-                self.clamp_player_nominations_lengths(&mut state);
-                for player in state.player_order.clone().iter() {
-                    let player_name = player.as_str();
-                    let _ = self
-                        .send_msg(
-                            &state,
-                            player_name,
-                            self.get_msg(Some(player_name), &state)?,
-                        )
-                        .await;
-                }
+                state.beauty_split_points_on_tie = enabled;
                 self.broadcast_msg(self.room_state(&state))?;
             }
-            ClientMsg::SetNominationsPerGuesser { cards } => {
+            ClientMsg::SetBeautyPointsBonus { points } => {
                 if !self.is_moderator(&state, name) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Only moderators can change nominations per guesser".to_string(),
+                                "Only moderators can change Most Beautiful settings".to_string(),
                             )
                             .into(),
                         )
@@ -10278,11 +11835,15 @@ impl Room {
                     return Ok(());
                 }
 
-                if !Self::is_before_voting_stage(state.stage) {
+                if !matches!(state.game_mode, GameMode::DixitPlus) {
+                    return Ok(());
+                }
+
+                if !Self::is_joining_or_live_dixit_stage(state.stage) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Nominations per guesser can only be changed before voting starts"
+                                "Most Beautiful scoring can only be changed during live Dixit stages"
                                     .to_string(),
                             )
                             .into(),
@@ -10292,18 +11853,16 @@ impl Room {
                     return Ok(());
                 }
 
-                state.nominations_per_guesser = cards;
-                self.clamp_nominations_per_guesser(&mut state);
-                self.clamp_player_nominations_lengths(&mut state);
+                state.beauty_points_bonus = points;
+                self.clamp_beauty_points_bonus(&mut state);
                 self.broadcast_msg(self.room_state(&state))?;
             }
-            ClientMsg::SetBonusCorrectGuessOnThresholdCorrectLoss { enabled } => {
+            ClientMsg::SetBeautyScoringMode { mode } => {
                 if !self.is_moderator(&state, name) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Only moderators can change threshold-correct scoring bonuses"
-                                    .to_string(),
+                                "Only moderators can change Most Beautiful settings".to_string(),
                             )
                             .into(),
                         )
@@ -10316,11 +11875,15 @@ impl Room {
                     return Ok(());
                 }
 
+                if !matches!(state.game_mode, GameMode::DixitPlus) {
+                    return Ok(());
+                }
+
                 if !Self::is_joining_or_live_dixit_stage(state.stage) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Threshold-correct scoring bonuses can only be changed during live Dixit stages"
+                                "Most Beautiful scoring can only be changed during live Dixit stages"
                                     .to_string(),
                             )
                             .into(),
@@ -10330,16 +11893,21 @@ impl Room {
                     return Ok(());
                 }
 
-                state.bonus_correct_guess_on_threshold_correct_loss = enabled;
+                let previous_mode = state.beauty_scoring_mode;
+                state.beauty_scoring_mode = mode;
+                if matches!(previous_mode, BeautyScoringMode::WinnerBonus)
+                    && matches!(mode, BeautyScoringMode::VoteDivisor)
+                {
+                    self.reset_vote_divisor_segment(&mut state);
+                }
                 self.broadcast_msg(self.room_state(&state))?;
             }
-            ClientMsg::SetBonusThresholdLossTogglesApplyToAllStorytellerLossRounds { enabled } => {
+            ClientMsg::SetBeautyVotePointsDivisor { divisor } => {
                 if !self.is_moderator(&state, name) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Only moderators can change storyteller-loss scoring bonus scope"
-                                    .to_string(),
+                                "Only moderators can change Most Beautiful settings".to_string(),
                             )
                             .into(),
                         )
@@ -10352,11 +11920,15 @@ impl Room {
                     return Ok(());
                 }
 
+                if !matches!(state.game_mode, GameMode::DixitPlus) {
+                    return Ok(());
+                }
+
                 if !Self::is_joining_or_live_dixit_stage(state.stage) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Storyteller-loss scoring bonus scope can only be changed during live Dixit stages"
+                                "Most Beautiful scoring can only be changed during live Dixit stages"
                                     .to_string(),
                             )
                             .into(),
@@ -10366,15 +11938,33 @@ impl Room {
                     return Ok(());
                 }
 
-                state.bonus_threshold_loss_toggles_apply_to_all_storyteller_loss_rounds = enabled;
-                self.broadcast_msg(self.room_state(&state))?;
+                let Some(divisor_tenths) =
+                    Self::beauty_vote_points_divisor_tenths_from_input(divisor)
+                else {
+                    if let Some(tx) = state.player_to_socket.get(name) {
+                        tx.send(
+                            ServerMsg::ErrorMsg(
+                                "Most Beautiful vote divisor must be a finite number".to_string(),
+                            )
+                            .into(),
+                        )
+                        .await?;
+                    }
+                    return Ok(());
+                };
+
+                state.beauty_vote_points_divisor_tenths = divisor_tenths;
+                self.clamp_beauty_vote_points_divisor_tenths(&mut state);
+                self.clamp_beauty_vote_points_divisor_player_count_base(&mut state);
+                self.rescore_vote_divisor_points(&mut state);
+                self.broadcast_stage_payload_after_vote_divisor_rescore(&state)?;
             }
-            ClientMsg::SetShowVotingCardNumbers { enabled } => {
+            ClientMsg::SetBeautyVotePointsDivisorMode { mode } => {
                 if !self.is_moderator(&state, name) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Only moderators can change card number overlays".to_string(),
+                                "Only moderators can change Most Beautiful settings".to_string(),
                             )
                             .into(),
                         )
@@ -10387,17 +11977,15 @@ impl Room {
                     return Ok(());
                 }
 
-                let can_change_number_overlays = match state.game_mode {
-                    GameMode::DixitPlus => Self::is_live_dixit_stage(state.stage),
-                    GameMode::Stella => {
-                        matches!(state.stage, RoomStage::Joining | RoomStage::StellaAssociate)
-                    }
-                };
-                if !can_change_number_overlays {
+                if !matches!(state.game_mode, GameMode::DixitPlus) {
+                    return Ok(());
+                }
+
+                if !Self::is_joining_or_live_dixit_stage(state.stage) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Card number overlays can only be changed during live Dixit stages or the Resonance associate stage"
+                                "Most Beautiful scoring can only be changed during live Dixit stages"
                                     .to_string(),
                             )
                             .into(),
@@ -10407,16 +11995,16 @@ impl Room {
                     return Ok(());
                 }
 
-                state.show_voting_card_numbers = enabled;
-                self.broadcast_msg(self.room_state(&state))?;
+                state.beauty_vote_points_divisor_mode = mode;
+                self.rescore_vote_divisor_points(&mut state);
+                self.broadcast_stage_payload_after_vote_divisor_rescore(&state)?;
             }
-            ClientMsg::SetRandomizeVotingCardOrderPerViewer { enabled } => {
+            ClientMsg::SetBeautyVotePointsDivisorPlayerCountBase { base } => {
                 if !self.is_moderator(&state, name) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Only moderators can change voting card order randomization"
-                                    .to_string(),
+                                "Only moderators can change Most Beautiful settings".to_string(),
                             )
                             .into(),
                         )
@@ -10433,11 +12021,11 @@ impl Room {
                     return Ok(());
                 }
 
-                if !Self::is_live_dixit_stage(state.stage) {
+                if !Self::is_joining_or_live_dixit_stage(state.stage) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Voting card order randomization can only be changed during live Dixit stages"
+                                "Most Beautiful scoring can only be changed during live Dixit stages"
                                     .to_string(),
                             )
                             .into(),
@@ -10447,15 +12035,17 @@ impl Room {
                     return Ok(());
                 }
 
-                state.randomize_voting_card_order_per_viewer = enabled;
-                self.broadcast_msg(self.room_state(&state))?;
+                state.beauty_vote_points_divisor_player_count_base = base;
+                self.clamp_beauty_vote_points_divisor_player_count_base(&mut state);
+                self.rescore_vote_divisor_points(&mut state);
+                self.broadcast_stage_payload_after_vote_divisor_rescore(&state)?;
             }
-            ClientMsg::SetRoundStartDiscardCount { count } => {
+            ClientMsg::SetBeautyResultsDisplayMode { mode } => {
                 if !self.is_moderator(&state, name) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Only moderators can change round-start discard count".to_string(),
+                                "Only moderators can change Most Beautiful settings".to_string(),
                             )
                             .into(),
                         )
@@ -10468,11 +12058,15 @@ impl Room {
                     return Ok(());
                 }
 
-                if !Self::is_joining_or_live_dixit_stage(state.stage) {
+                if !matches!(state.game_mode, GameMode::DixitPlus) {
+                    return Ok(());
+                }
+
+                if !Self::is_before_results_stage(state.stage) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Round-start discard count can only be changed during live Dixit stages"
+                                "Most Beautiful result display can only be changed before results are revealed"
                                     .to_string(),
                             )
                             .into(),
@@ -10482,16 +12076,15 @@ impl Room {
                     return Ok(());
                 }
 
-                state.round_start_discard_count = count;
-                self.clamp_round_start_discard_count(&mut state);
+                state.beauty_results_display_mode = mode;
                 self.broadcast_msg(self.room_state(&state))?;
             }
-            ClientMsg::SetRoundStartDiscardAllUnpinned { enabled } => {
+            ClientMsg::SetShowPreviousResultsDuringStorytellerChoosing { enabled } => {
                 if !self.is_moderator(&state, name) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Only moderators can change round-start discard mode".to_string(),
+                                "Only moderators can change previous-results preview".to_string(),
                             )
                             .into(),
                         )
@@ -10504,11 +12097,15 @@ impl Room {
                     return Ok(());
                 }
 
+                if !matches!(state.game_mode, GameMode::DixitPlus) {
+                    return Ok(());
+                }
+
                 if !Self::is_joining_or_live_dixit_stage(state.stage) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Round-start discard mode can only be changed during live Dixit stages"
+                                "Previous-results preview can only be changed during live Dixit stages"
                                     .to_string(),
                             )
                             .into(),
@@ -10518,49 +12115,15 @@ impl Room {
                     return Ok(());
                 }
 
-                state.round_start_discard_all_unpinned = enabled;
+                state.show_previous_results_during_storyteller_choosing = enabled;
                 self.broadcast_msg(self.room_state(&state))?;
             }
-            ClientMsg::SetHandCardPinned { card, pinned } => {
-                if !state.players.contains_key(name) {
-                    return Ok(());
-                }
-
-                if !matches!(state.game_mode, GameMode::DixitPlus) {
-                    return Ok(());
-                }
-
-                if !state
-                    .player_hand
-                    .get(name)
-                    .map(|hand| hand.contains(&card))
-                    .unwrap_or(false)
-                {
-                    return Ok(());
-                }
-
-                if pinned {
-                    state
-                        .player_pinned_cards
-                        .entry(name.to_string())
-                        .or_default()
-                        .insert(card);
-                } else {
-                    self.remove_card_pin(&mut state, name, &card);
-                }
-
-                if let Some(tx) = state.player_to_socket.get(name) {
-                    if let Ok(msg) = self.get_msg(Some(name), &state) {
-                        let _ = tx.send(msg.into()).await;
-                    }
-                }
-            }
-            ClientMsg::SetHintChoosingTimerEnabled { enabled } => {
+            ClientMsg::SetCardsPerHand { cards } => {
                 if !self.is_moderator(&state, name) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Only moderators can change hint timer settings".to_string(),
+                                "Only moderators can change cards per hand".to_string(),
                             )
                             .into(),
                         )
@@ -10573,18 +12136,11 @@ impl Room {
                     return Ok(());
                 }
 
-                let can_change_hint_timer = match state.game_mode {
-                    GameMode::DixitPlus => Self::is_joining_or_live_dixit_stage(state.stage),
-                    GameMode::Stella => {
-                        matches!(state.stage, RoomStage::Joining | RoomStage::StellaAssociate)
-                    }
-                };
-                if !can_change_hint_timer {
+                if !matches!(state.stage, RoomStage::Joining | RoomStage::ActiveChooses) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Hint-stage timer settings can only be changed during setup, live Dixit stages, or the active Resonance associate stage"
-                                    .to_string(),
+                                "Cards per hand can only be changed at round start".to_string(),
                             )
                             .into(),
                         )
@@ -10593,16 +12149,29 @@ impl Room {
                     return Ok(());
                 }
 
-                state.hint_choosing_timer_enabled = enabled;
-                self.clamp_stage_timer_settings(&mut state);
+                state.cards_per_hand = cards;
+                self.clamp_cards_per_hand(&mut state);
+                self.clamp_nominations_per_guesser(&mut state);
+                self.apply_cards_per_hand_change(&mut state);
+                self.clamp_player_nominations_lengths(&mut state);
+                for player in state.player_order.clone().iter() {
+                    let player_name = player.as_str();
+                    let _ = self
+                        .send_msg(
+                            &state,
+                            player_name,
+                            self.get_msg(Some(player_name), &state)?,
+                        )
+                        .await;
+                }
                 self.broadcast_msg(self.room_state(&state))?;
             }
-            ClientMsg::SetHintChoosingTimerDuration { seconds } => {
+            ClientMsg::SetNominationsPerGuesser { cards } => {
                 if !self.is_moderator(&state, name) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Only moderators can change hint timer settings".to_string(),
+                                "Only moderators can change nominations per guesser".to_string(),
                             )
                             .into(),
                         )
@@ -10615,17 +12184,11 @@ impl Room {
                     return Ok(());
                 }
 
-                let can_change_hint_timer = match state.game_mode {
-                    GameMode::DixitPlus => Self::is_joining_or_live_dixit_stage(state.stage),
-                    GameMode::Stella => {
-                        matches!(state.stage, RoomStage::Joining | RoomStage::StellaAssociate)
-                    }
-                };
-                if !can_change_hint_timer {
+                if !Self::is_before_voting_stage(state.stage) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Hint-stage timer settings can only be changed during setup, live Dixit stages, or the active Resonance associate stage"
+                                "Nominations per guesser can only be changed before voting starts"
                                     .to_string(),
                             )
                             .into(),
@@ -10635,16 +12198,18 @@ impl Room {
                     return Ok(());
                 }
 
-                state.hint_choosing_timer_duration_s = seconds;
-                self.clamp_stage_timer_settings(&mut state);
+                state.nominations_per_guesser = cards;
+                self.clamp_nominations_per_guesser(&mut state);
+                self.clamp_player_nominations_lengths(&mut state);
                 self.broadcast_msg(self.room_state(&state))?;
             }
-            ClientMsg::SetForceHintChoosingTimer { enabled } => {
+            ClientMsg::SetBonusCorrectGuessOnThresholdCorrectLoss { enabled } => {
                 if !self.is_moderator(&state, name) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Only moderators can change hint timer settings".to_string(),
+                                "Only moderators can change threshold-correct scoring bonuses"
+                                    .to_string(),
                             )
                             .into(),
                         )
@@ -10657,17 +12222,11 @@ impl Room {
                     return Ok(());
                 }
 
-                let can_change_hint_timer = match state.game_mode {
-                    GameMode::DixitPlus => Self::is_joining_or_live_dixit_stage(state.stage),
-                    GameMode::Stella => {
-                        matches!(state.stage, RoomStage::Joining | RoomStage::StellaAssociate)
-                    }
-                };
-                if !can_change_hint_timer {
+                if !Self::is_joining_or_live_dixit_stage(state.stage) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Hint-stage timer settings can only be changed during setup, live Dixit stages, or the active Resonance associate stage"
+                                "Threshold-correct scoring bonuses can only be changed during live Dixit stages"
                                     .to_string(),
                             )
                             .into(),
@@ -10677,15 +12236,15 @@ impl Room {
                     return Ok(());
                 }
 
-                state.force_hint_choosing_timer = enabled;
+                state.bonus_correct_guess_on_threshold_correct_loss = enabled;
                 self.broadcast_msg(self.room_state(&state))?;
             }
-            ClientMsg::SetCardChoosingTimerEnabled { enabled } => {
+            ClientMsg::SetBonusThresholdLossTogglesApplyToAllStorytellerLossRounds { enabled } => {
                 if !self.is_moderator(&state, name) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Only moderators can change card choosing timer settings"
+                                "Only moderators can change storyteller-loss scoring bonus scope"
                                     .to_string(),
                             )
                             .into(),
@@ -10703,7 +12262,7 @@ impl Room {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Stage timers can only be changed during live Dixit stages"
+                                "Storyteller-loss scoring bonus scope can only be changed during live Dixit stages"
                                     .to_string(),
                             )
                             .into(),
@@ -10713,17 +12272,15 @@ impl Room {
                     return Ok(());
                 }
 
-                state.card_choosing_timer_enabled = enabled;
-                self.clamp_stage_timer_settings(&mut state);
+                state.bonus_threshold_loss_toggles_apply_to_all_storyteller_loss_rounds = enabled;
                 self.broadcast_msg(self.room_state(&state))?;
             }
-            ClientMsg::SetCardChoosingTimerDuration { seconds } => {
+            ClientMsg::SetShowVotingCardNumbers { enabled } => {
                 if !self.is_moderator(&state, name) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Only moderators can change card choosing timer settings"
-                                    .to_string(),
+                                "Only moderators can change card number overlays".to_string(),
                             )
                             .into(),
                         )
@@ -10736,11 +12293,17 @@ impl Room {
                     return Ok(());
                 }
 
-                if !Self::is_joining_or_live_dixit_stage(state.stage) {
+                let can_change_number_overlays = match state.game_mode {
+                    GameMode::DixitPlus => Self::is_live_dixit_stage(state.stage),
+                    GameMode::Stella => {
+                        matches!(state.stage, RoomStage::Joining | RoomStage::StellaAssociate)
+                    }
+                };
+                if !can_change_number_overlays {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Stage timers can only be changed during live Dixit stages"
+                                "Card number overlays can only be changed during live Dixit stages or the Resonance associate stage"
                                     .to_string(),
                             )
                             .into(),
@@ -10750,16 +12313,16 @@ impl Room {
                     return Ok(());
                 }
 
-                state.card_choosing_timer_duration_s = seconds;
-                self.clamp_stage_timer_settings(&mut state);
+                state.show_voting_card_numbers = enabled;
                 self.broadcast_msg(self.room_state(&state))?;
             }
-            ClientMsg::SetVotingTimerEnabled { enabled } => {
+            ClientMsg::SetRandomizeVotingCardOrderPerViewer { enabled } => {
                 if !self.is_moderator(&state, name) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Only moderators can change voting timer settings".to_string(),
+                                "Only moderators can change voting card order randomization"
+                                    .to_string(),
                             )
                             .into(),
                         )
@@ -10772,11 +12335,15 @@ impl Room {
                     return Ok(());
                 }
 
-                if !Self::is_joining_or_live_dixit_stage(state.stage) {
+                if !matches!(state.game_mode, GameMode::DixitPlus) {
+                    return Ok(());
+                }
+
+                if !Self::is_live_dixit_stage(state.stage) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Stage timers can only be changed during live Dixit stages"
+                                "Voting card order randomization can only be changed during live Dixit stages"
                                     .to_string(),
                             )
                             .into(),
@@ -10786,16 +12353,15 @@ impl Room {
                     return Ok(());
                 }
 
-                state.voting_timer_enabled = enabled;
-                self.clamp_stage_timer_settings(&mut state);
+                state.randomize_voting_card_order_per_viewer = enabled;
                 self.broadcast_msg(self.room_state(&state))?;
             }
-            ClientMsg::SetVotingTimerDuration { seconds } => {
+            ClientMsg::SetRoundStartDiscardCount { count } => {
                 if !self.is_moderator(&state, name) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Only moderators can change voting timer settings".to_string(),
+                                "Only moderators can change round-start discard count".to_string(),
                             )
                             .into(),
                         )
@@ -10812,7 +12378,7 @@ impl Room {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Stage timers can only be changed during live Dixit stages"
+                                "Round-start discard count can only be changed during live Dixit stages"
                                     .to_string(),
                             )
                             .into(),
@@ -10822,16 +12388,16 @@ impl Room {
                     return Ok(());
                 }
 
-                state.voting_timer_duration_s = seconds;
-                self.clamp_stage_timer_settings(&mut state);
+                state.round_start_discard_count = count;
+                self.clamp_round_start_discard_count(&mut state);
                 self.broadcast_msg(self.room_state(&state))?;
             }
-            ClientMsg::SetBeautyTimerEnabled { enabled } => {
+            ClientMsg::SetRoundStartDiscardAllUnpinned { enabled } => {
                 if !self.is_moderator(&state, name) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Only moderators can change beauty timer settings".to_string(),
+                                "Only moderators can change round-start discard mode".to_string(),
                             )
                             .into(),
                         )
@@ -10844,15 +12410,11 @@ impl Room {
                     return Ok(());
                 }
 
-                if !matches!(state.game_mode, GameMode::DixitPlus) {
-                    return Ok(());
-                }
-
                 if !Self::is_joining_or_live_dixit_stage(state.stage) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Stage timers can only be changed during live Dixit stages"
+                                "Round-start discard mode can only be changed during live Dixit stages"
                                     .to_string(),
                             )
                             .into(),
@@ -10862,16 +12424,49 @@ impl Room {
                     return Ok(());
                 }
 
-                state.beauty_timer_enabled = enabled;
-                self.clamp_stage_timer_settings(&mut state);
+                state.round_start_discard_all_unpinned = enabled;
                 self.broadcast_msg(self.room_state(&state))?;
             }
-            ClientMsg::SetBeautyTimerDuration { seconds } => {
+            ClientMsg::SetHandCardPinned { card, pinned } => {
+                if !state.players.contains_key(name) {
+                    return Ok(());
+                }
+
+                if !matches!(state.game_mode, GameMode::DixitPlus) {
+                    return Ok(());
+                }
+
+                if !state
+                    .player_hand
+                    .get(name)
+                    .map(|hand| hand.contains(&card))
+                    .unwrap_or(false)
+                {
+                    return Ok(());
+                }
+
+                if pinned {
+                    state
+                        .player_pinned_cards
+                        .entry(name.to_string())
+                        .or_default()
+                        .insert(card);
+                } else {
+                    self.remove_card_pin(&mut state, name, &card);
+                }
+
+                if let Some(tx) = state.player_to_socket.get(name) {
+                    if let Ok(msg) = self.get_msg(Some(name), &state) {
+                        let _ = tx.send(msg.into()).await;
+                    }
+                }
+            }
+            ClientMsg::SetHintChoosingTimerEnabled { enabled } => {
                 if !self.is_moderator(&state, name) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Only moderators can change beauty timer settings".to_string(),
+                                "Only moderators can change hint timer settings".to_string(),
                             )
                             .into(),
                         )
@@ -10884,15 +12479,17 @@ impl Room {
                     return Ok(());
                 }
 
-                if !matches!(state.game_mode, GameMode::DixitPlus) {
-                    return Ok(());
-                }
-
-                if !Self::is_joining_or_live_dixit_stage(state.stage) {
+                let can_change_hint_timer = match state.game_mode {
+                    GameMode::DixitPlus => Self::is_joining_or_live_dixit_stage(state.stage),
+                    GameMode::Stella => {
+                        matches!(state.stage, RoomStage::Joining | RoomStage::StellaAssociate)
+                    }
+                };
+                if !can_change_hint_timer {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Stage timers can only be changed during live Dixit stages"
+                                "Hint-stage timer settings can only be changed during setup, live Dixit stages, or the active Resonance associate stage"
                                     .to_string(),
                             )
                             .into(),
@@ -10902,17 +12499,16 @@ impl Room {
                     return Ok(());
                 }
 
-                state.beauty_timer_duration_s = seconds;
+                state.hint_choosing_timer_enabled = enabled;
                 self.clamp_stage_timer_settings(&mut state);
                 self.broadcast_msg(self.room_state(&state))?;
             }
-            ClientMsg::SetForceCardChoosingTimer { enabled } => {
+            ClientMsg::SetHintChoosingTimerDuration { seconds } => {
                 if !self.is_moderator(&state, name) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Only moderators can change card choosing timer settings"
-                                    .to_string(),
+                                "Only moderators can change hint timer settings".to_string(),
                             )
                             .into(),
                         )
@@ -10925,11 +12521,17 @@ impl Room {
                     return Ok(());
                 }
 
-                if !Self::is_joining_or_live_dixit_stage(state.stage) {
+                let can_change_hint_timer = match state.game_mode {
+                    GameMode::DixitPlus => Self::is_joining_or_live_dixit_stage(state.stage),
+                    GameMode::Stella => {
+                        matches!(state.stage, RoomStage::Joining | RoomStage::StellaAssociate)
+                    }
+                };
+                if !can_change_hint_timer {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Stage timers can only be changed during live Dixit stages"
+                                "Hint-stage timer settings can only be changed during setup, live Dixit stages, or the active Resonance associate stage"
                                     .to_string(),
                             )
                             .into(),
@@ -10939,15 +12541,16 @@ impl Room {
                     return Ok(());
                 }
 
-                state.force_card_choosing_timer = enabled;
+                state.hint_choosing_timer_duration_s = seconds;
+                self.clamp_stage_timer_settings(&mut state);
                 self.broadcast_msg(self.room_state(&state))?;
             }
-            ClientMsg::SetForceVotingTimer { enabled } => {
+            ClientMsg::SetForceHintChoosingTimer { enabled } => {
                 if !self.is_moderator(&state, name) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Only moderators can change voting timer settings".to_string(),
+                                "Only moderators can change hint timer settings".to_string(),
                             )
                             .into(),
                         )
@@ -10960,11 +12563,17 @@ impl Room {
                     return Ok(());
                 }
 
-                if !Self::is_joining_or_live_dixit_stage(state.stage) {
+                let can_change_hint_timer = match state.game_mode {
+                    GameMode::DixitPlus => Self::is_joining_or_live_dixit_stage(state.stage),
+                    GameMode::Stella => {
+                        matches!(state.stage, RoomStage::Joining | RoomStage::StellaAssociate)
+                    }
+                };
+                if !can_change_hint_timer {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Stage timers can only be changed during live Dixit stages"
+                                "Hint-stage timer settings can only be changed during setup, live Dixit stages, or the active Resonance associate stage"
                                     .to_string(),
                             )
                             .into(),
@@ -10974,15 +12583,16 @@ impl Room {
                     return Ok(());
                 }
 
-                state.force_voting_timer = enabled;
+                state.force_hint_choosing_timer = enabled;
                 self.broadcast_msg(self.room_state(&state))?;
             }
-            ClientMsg::SetForceBeautyTimer { enabled } => {
+            ClientMsg::SetCardChoosingTimerEnabled { enabled } => {
                 if !self.is_moderator(&state, name) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Only moderators can change beauty timer settings".to_string(),
+                                "Only moderators can change card choosing timer settings"
+                                    .to_string(),
                             )
                             .into(),
                         )
@@ -10995,10 +12605,6 @@ impl Room {
                     return Ok(());
                 }
 
-                if !matches!(state.game_mode, GameMode::DixitPlus) {
-                    return Ok(());
-                }
-
                 if !Self::is_joining_or_live_dixit_stage(state.stage) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
@@ -11013,35 +12619,17 @@ impl Room {
                     return Ok(());
                 }
 
-                state.force_beauty_timer = enabled;
+                state.card_choosing_timer_enabled = enabled;
+                self.clamp_stage_timer_settings(&mut state);
                 self.broadcast_msg(self.room_state(&state))?;
             }
-            ClientMsg::SetClueRatingEnabled { enabled } => {
-                self.handle_set_clue_rating_enabled(&mut state, name, enabled)
-                    .await?;
-            }
-            ClientMsg::SetClueRatingMaxStars { stars } => {
-                self.handle_set_clue_rating_max_stars(&mut state, name, stars)
-                    .await?;
-            }
-            ClientMsg::SetClueRatingTimerEnabled { enabled } => {
-                self.handle_set_clue_rating_timer_enabled(&mut state, name, enabled)
-                    .await?;
-            }
-            ClientMsg::SetClueRatingTimerDuration { seconds } => {
-                self.handle_set_clue_rating_timer_duration(&mut state, name, seconds)
-                    .await?;
-            }
-            ClientMsg::SetForceClueRatingTimer { enabled } => {
-                self.handle_set_force_clue_rating_timer(&mut state, name, enabled)
-                    .await?;
-            }
-            ClientMsg::SetLeaderboardViewModeDefault { mode } => {
+            ClientMsg::SetCardChoosingTimerDuration { seconds } => {
                 if !self.is_moderator(&state, name) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Only moderators can change leaderboard view defaults".to_string(),
+                                "Only moderators can change card choosing timer settings"
+                                    .to_string(),
                             )
                             .into(),
                         )
@@ -11054,23 +12642,30 @@ impl Room {
                     return Ok(());
                 }
 
-                if !matches!(state.game_mode, GameMode::DixitPlus) {
+                if !Self::is_joining_or_live_dixit_stage(state.stage) {
+                    if let Some(tx) = state.player_to_socket.get(name) {
+                        tx.send(
+                            ServerMsg::ErrorMsg(
+                                "Stage timers can only be changed during live Dixit stages"
+                                    .to_string(),
+                            )
+                            .into(),
+                        )
+                        .await?;
+                    }
                     return Ok(());
                 }
 
-                state.leaderboard_view_mode_default = mode;
-                state.leaderboard_view_mode_default_version = state
-                    .leaderboard_view_mode_default_version
-                    .saturating_add(1);
+                state.card_choosing_timer_duration_s = seconds;
+                self.clamp_stage_timer_settings(&mut state);
                 self.broadcast_msg(self.room_state(&state))?;
             }
-            ClientMsg::SetVotingWrongCardDisableDistribution { distribution } => {
+            ClientMsg::SetVotingTimerEnabled { enabled } => {
                 if !self.is_moderator(&state, name) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Only moderators can change voting wrong-card disabling"
-                                    .to_string(),
+                                "Only moderators can change voting timer settings".to_string(),
                             )
                             .into(),
                         )
@@ -11083,11 +12678,11 @@ impl Room {
                     return Ok(());
                 }
 
-                if !Self::is_before_voting_stage(state.stage) {
+                if !Self::is_joining_or_live_dixit_stage(state.stage) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Voting wrong-card disabling can only be changed before voting starts"
+                                "Stage timers can only be changed during live Dixit stages"
                                     .to_string(),
                             )
                             .into(),
@@ -11097,25 +12692,16 @@ impl Room {
                     return Ok(());
                 }
 
-                let canonical_distribution =
-                    match self.canonicalize_voting_wrong_card_disable_distribution(&distribution) {
-                        Ok(canonical_distribution) => canonical_distribution,
-                        Err(err) => {
-                            if let Some(tx) = state.player_to_socket.get(name) {
-                                tx.send(ServerMsg::ErrorMsg(err.to_string()).into()).await?;
-                            }
-                            return Ok(());
-                        }
-                    };
-                state.voting_wrong_card_disable_distribution = canonical_distribution;
+                state.voting_timer_enabled = enabled;
+                self.clamp_stage_timer_settings(&mut state);
                 self.broadcast_msg(self.room_state(&state))?;
             }
-            ClientMsg::ForceCurrentStage {} => {
+            ClientMsg::SetVotingTimerDuration { seconds } => {
                 if !self.is_moderator(&state, name) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Only moderators can force the current stage".to_string(),
+                                "Only moderators can change voting timer settings".to_string(),
                             )
                             .into(),
                         )
@@ -11124,38 +12710,34 @@ impl Room {
                     return Ok(());
                 }
 
-                let stage_supports_force = matches!(
-                    state.stage,
-                    RoomStage::ActiveChooses
-                        | RoomStage::PlayersChoose
-                        | RoomStage::Voting
-                        | RoomStage::BeautyVoting
-                        | RoomStage::ClueRating
-                        | RoomStage::StellaAssociate
-                        | RoomStage::StellaReveal
-                );
-                if !stage_supports_force {
+                if matches!(state.stage, RoomStage::End) {
                     return Ok(());
                 }
 
-                if !self.force_current_stage(&mut state).await? {
+                if !Self::is_joining_or_live_dixit_stage(state.stage) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Current stage cannot be force-resolved yet".to_string(),
+                                "Stage timers can only be changed during live Dixit stages"
+                                    .to_string(),
                             )
                             .into(),
                         )
                         .await?;
                     }
+                    return Ok(());
                 }
+
+                state.voting_timer_duration_s = seconds;
+                self.clamp_stage_timer_settings(&mut state);
+                self.broadcast_msg(self.room_state(&state))?;
             }
-            ClientMsg::ObserverifyOfflinePendingPlayers {} => {
+            ClientMsg::SetBeautyTimerEnabled { enabled } => {
                 if !self.is_moderator(&state, name) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Only moderators can auto-observerify offline players".to_string(),
+                                "Only moderators can change beauty timer settings".to_string(),
                             )
                             .into(),
                         )
@@ -11164,39 +12746,38 @@ impl Room {
                     return Ok(());
                 }
 
-                let stage_supports_observerify = matches!(
-                    state.stage,
-                    RoomStage::ActiveChooses
-                        | RoomStage::PlayersChoose
-                        | RoomStage::Voting
-                        | RoomStage::BeautyVoting
-                        | RoomStage::ClueRating
-                        | RoomStage::StellaAssociate
-                        | RoomStage::StellaReveal
-                );
-                if !stage_supports_observerify {
+                if matches!(state.stage, RoomStage::End) {
                     return Ok(());
                 }
 
-                if !self.observerify_offline_pending_players(&mut state).await? {
+                if !matches!(state.game_mode, GameMode::DixitPlus) {
+                    return Ok(());
+                }
+
+                if !Self::is_joining_or_live_dixit_stage(state.stage) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "No offline players with pending actions to observerify"
+                                "Stage timers can only be changed during live Dixit stages"
                                     .to_string(),
                             )
                             .into(),
                         )
                         .await?;
                     }
+                    return Ok(());
                 }
+
+                state.beauty_timer_enabled = enabled;
+                self.clamp_stage_timer_settings(&mut state);
+                self.broadcast_msg(self.room_state(&state))?;
             }
-            ClientMsg::ForceStartNextRound {} => {
+            ClientMsg::SetBeautyTimerDuration { seconds } => {
                 if !self.is_moderator(&state, name) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Only moderators can force start next round".to_string(),
+                                "Only moderators can change beauty timer settings".to_string(),
                             )
                             .into(),
                         )
@@ -11205,35 +12786,39 @@ impl Room {
                     return Ok(());
                 }
 
-                if !matches!(
-                    state.stage,
-                    RoomStage::Results | RoomStage::BeautyResults | RoomStage::StellaResults
-                ) {
+                if matches!(state.stage, RoomStage::End) {
                     return Ok(());
                 }
 
-                if matches!(state.stage, RoomStage::Results)
-                    && self.uses_separate_beauty_results_stage(&state)
-                {
-                    self.init_beauty_results(&mut state)?;
+                if !matches!(state.game_mode, GameMode::DixitPlus) {
                     return Ok(());
                 }
 
-                if self.should_end_game(&state) {
-                    self.transition_to_end(&mut state)?;
-                    self.broadcast_msg(ServerMsg::EndGame {})?;
-                    self.broadcast_msg(self.room_state(&state))?;
+                if !Self::is_joining_or_live_dixit_stage(state.stage) {
+                    if let Some(tx) = state.player_to_socket.get(name) {
+                        tx.send(
+                            ServerMsg::ErrorMsg(
+                                "Stage timers can only be changed during live Dixit stages"
+                                    .to_string(),
+                            )
+                            .into(),
+                        )
+                        .await?;
+                    }
                     return Ok(());
                 }
 
-                self.init_round(&mut state).await?;
+                state.beauty_timer_duration_s = seconds;
+                self.clamp_stage_timer_settings(&mut state);
+                self.broadcast_msg(self.room_state(&state))?;
             }
-            ClientMsg::ForceEndGame {} => {
+            ClientMsg::SetForceCardChoosingTimer { enabled } => {
                 if !self.is_moderator(&state, name) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Only moderators can force end the game".to_string(),
+                                "Only moderators can change card choosing timer settings"
+                                    .to_string(),
                             )
                             .into(),
                         )
@@ -11242,57 +12827,51 @@ impl Room {
                     return Ok(());
                 }
 
-                if matches!(state.stage, RoomStage::Joining | RoomStage::End) {
+                if matches!(state.stage, RoomStage::End) {
                     return Ok(());
                 }
 
-                self.transition_to_end(&mut state)?;
-                self.broadcast_msg(ServerMsg::EndGame {})?;
-                self.broadcast_msg(self.room_state(&state))?;
-            }
-            ClientMsg::ResetClue {} => {
-                if !self.is_moderator(&state, name) {
+                if !Self::is_joining_or_live_dixit_stage(state.stage) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
-                            ServerMsg::ErrorMsg("Only moderators can reset the clue".to_string())
-                                .into(),
+                            ServerMsg::ErrorMsg(
+                                "Stage timers can only be changed during live Dixit stages"
+                                    .to_string(),
+                            )
+                            .into(),
                         )
                         .await?;
                     }
                     return Ok(());
                 }
 
-                if self.reset_clue_to_storyteller_choose(&mut state)? {
-                    for player in state.player_order.iter() {
-                        let _ = self
-                            .send_msg(&state, player, self.get_msg(Some(player), &state)?)
-                            .await;
-                    }
-                    self.broadcast_msg(self.room_state(&state))?;
-                }
+                state.force_card_choosing_timer = enabled;
+                self.broadcast_msg(self.room_state(&state))?;
             }
-            ClientMsg::RefreshHands {} => {
+            ClientMsg::SetForceVotingTimer { enabled } => {
                 if !self.is_moderator(&state, name) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
-                            ServerMsg::ErrorMsg("Only moderators can refresh hands".to_string())
-                                .into(),
+                            ServerMsg::ErrorMsg(
+                                "Only moderators can change voting timer settings".to_string(),
+                            )
+                            .into(),
                         )
                         .await?;
                     }
                     return Ok(());
                 }
 
-                if !matches!(state.stage, RoomStage::Joining | RoomStage::ActiveChooses) {
+                if matches!(state.stage, RoomStage::End) {
                     return Ok(());
                 }
 
-                let refreshed = self.refresh_active_player_hands(&mut state).await?;
-                if !refreshed {
+                if !Self::is_joining_or_live_dixit_stage(state.stage) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Not enough cards in the deck to refresh hands".to_string(),
+                                "Stage timers can only be changed during live Dixit stages"
+                                    .to_string(),
                             )
                             .into(),
                         )
@@ -11300,29 +12879,38 @@ impl Room {
                     }
                     return Ok(());
                 }
+
+                state.force_voting_timer = enabled;
                 self.broadcast_msg(self.room_state(&state))?;
             }
-            ClientMsg::ResumeGame {} => {
-                if !matches!(state.stage, RoomStage::Paused) {
-                    return Ok(());
-                }
-
+            ClientMsg::SetForceBeautyTimer { enabled } => {
                 if !self.is_moderator(&state, name) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
-                            ServerMsg::ErrorMsg("Only moderators can resume the game".to_string())
-                                .into(),
+                            ServerMsg::ErrorMsg(
+                                "Only moderators can change beauty timer settings".to_string(),
+                            )
+                            .into(),
                         )
                         .await?;
                     }
                     return Ok(());
                 }
 
-                if self.non_observer_player_count(&state) < 3 {
+                if matches!(state.stage, RoomStage::End) {
+                    return Ok(());
+                }
+
+                if !matches!(state.game_mode, GameMode::DixitPlus) {
+                    return Ok(());
+                }
+
+                if !Self::is_joining_or_live_dixit_stage(state.stage) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Need at least 3 non-observer players to resume".to_string(),
+                                "Stage timers can only be changed during live Dixit stages"
+                                    .to_string(),
                             )
                             .into(),
                         )
@@ -11331,109 +12919,35 @@ impl Room {
                     return Ok(());
                 }
 
-                if state.round == 0 {
-                    self.init_round(&mut state).await?;
-                } else {
-                    self.restart_round_keep_hands(&mut state).await?;
-                }
+                state.force_beauty_timer = enabled;
+                self.broadcast_msg(self.room_state(&state))?;
             }
-            ClientMsg::ActivePlayerChooseCard { card, description } => {
-                if !state.players.contains_key(name) {
-                    return Ok(());
-                }
-
-                if matches!(state.stage, RoomStage::ActiveChooses)
-                    && !state.player_order.is_empty()
-                    && state.player_order[state.active_player] == name
-                {
-                    // verify that player has this card
-                    if !state
-                        .player_hand
-                        .get(name)
-                        .map(|cards| cards.contains(&card))
-                        .unwrap_or(false)
-                    {
-                        return Err(anyhow!("Invalid card chosen by active player"));
-                    }
-
-                    let description = description.trim();
-                    // verify that the description is not empty and is one word
-                    if description.is_empty() {
-                        if let Some(tx) = state.player_to_socket.get(name) {
-                            tx.send(
-                                ServerMsg::ErrorMsg("Description must not be empty".to_string())
-                                    .into(),
-                            )
-                            .await?;
-                        }
-                        return Ok(());
-                    }
-                    state.current_description = description.to_string();
-                    let storyteller_count = state
-                        .storyteller_counts
-                        .entry(name.to_string())
-                        .or_insert(0);
-                    *storyteller_count = storyteller_count.saturating_add(1);
-                    self.set_stage(&mut state, RoomStage::PlayersChoose);
-
-                    // record choice
-                    state
-                        .player_to_current_cards
-                        .insert(name.to_string(), vec![card.to_string()]);
-
-                    // notify players of the active player's choice
-                    for player in state.player_order.iter() {
-                        let _ = self
-                            .send_msg(&state, &player, self.get_msg(Some(&player), &state)?)
-                            .await;
-                    }
-
-                    self.clear_ready(&mut state);
-                    self.broadcast_msg(self.room_state(&state))?;
-                }
-            }
-            ClientMsg::PlayerChooseCard { card } => {
-                self.handle_player_choose_cards(&mut state, name, vec![card])
+            ClientMsg::SetClueRatingEnabled { enabled } => {
+                self.handle_set_clue_rating_enabled(&mut state, name, enabled)
                     .await?;
             }
-            ClientMsg::PlayerChooseCards { cards } => {
-                self.handle_player_choose_cards(&mut state, name, cards)
+            ClientMsg::SetClueRatingMaxStars { stars } => {
+                self.handle_set_clue_rating_max_stars(&mut state, name, stars)
                     .await?;
             }
-            ClientMsg::SetPlayerChooseDraft { cards } => {
-                self.set_player_choose_draft(&mut state, name, cards);
-            }
-            ClientMsg::SetVoteDraft { cards } => {
-                self.set_vote_draft(&mut state, name, cards);
-            }
-            ClientMsg::SetBeautyVoteDraft { cards } => {
-                self.set_beauty_vote_draft(&mut state, name, cards);
-            }
-            ClientMsg::SetStellaSelectionDraft { cards } => {
-                self.set_stella_selection_draft(&mut state, name, cards);
-            }
-            ClientMsg::SubmitVotes { cards } => {
-                self.submit_votes(&mut state, name, cards).await?;
+            ClientMsg::SetClueRatingTimerEnabled { enabled } => {
+                self.handle_set_clue_rating_timer_enabled(&mut state, name, enabled)
+                    .await?;
             }
-            ClientMsg::SubmitBeautyVotes { cards } => {
-                self.submit_beauty_votes(&mut state, name, cards).await?;
+            ClientMsg::SetClueRatingTimerDuration { seconds } => {
+                self.handle_set_clue_rating_timer_duration(&mut state, name, seconds)
+                    .await?;
             }
-            ClientMsg::SubmitClueRating { stars } => {
-                self.submit_clue_rating(&mut state, name, stars).await?;
+            ClientMsg::SetForceClueRatingTimer { enabled } => {
+                self.handle_set_force_clue_rating_timer(&mut state, name, enabled)
+                    .await?;
             }
-            ClientMsg::SubmitStellaSelection { cards } => {
-                if !state.players.contains_key(name)
-                    || !matches!(state.game_mode, GameMode::Stella)
-                    || !matches!(state.stage, RoomStage::StellaAssociate)
-                {
-                    return Ok(());
-                }
-                let unique = cards.iter().collect::<HashSet<_>>();
-                if unique.len() != cards.len() {
+            ClientMsg::SetLeaderboardViewModeDefault { mode } => {
+                if !self.is_moderator(&state, name) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
                             ServerMsg::ErrorMsg(
-                                "Duplicate Stella selections are not allowed".to_string(),
+                                "Only moderators can change leaderboard view defaults".to_string(),
                             )
                             .into(),
                         )
@@ -11441,98 +12955,74 @@ impl Room {
                     }
                     return Ok(());
                 }
-                let count = cards.len().min(usize::from(u16::MAX)) as u16;
-                if count < state.stella_selection_min || count > state.stella_selection_max {
+
+                if matches!(state.stage, RoomStage::End) {
+                    return Ok(());
+                }
+
+                if !matches!(state.game_mode, GameMode::DixitPlus) {
+                    return Ok(());
+                }
+
+                state.leaderboard_view_mode_default = mode;
+                state.leaderboard_view_mode_default_version = state
+                    .leaderboard_view_mode_default_version
+                    .saturating_add(1);
+                self.broadcast_msg(self.room_state(&state))?;
+            }
+            ClientMsg::SetVotingWrongCardDisableDistribution { distribution } => {
+                if !self.is_moderator(&state, name) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
-                            ServerMsg::ErrorMsg(format!(
-                                "Select between {} and {} cards",
-                                state.stella_selection_min, state.stella_selection_max
-                            ))
+                            ServerMsg::ErrorMsg(
+                                "Only moderators can change voting wrong-card disabling"
+                                    .to_string(),
+                            )
                             .into(),
                         )
                         .await?;
                     }
                     return Ok(());
                 }
-                let allowed_cards = state
-                    .stella_board_cards
-                    .iter()
-                    .cloned()
-                    .collect::<HashSet<_>>();
-                if !cards.iter().all(|card| allowed_cards.contains(card)) {
-                    return Err(anyhow!("Invalid Stella card selection"));
-                }
-                state
-                    .stella_player_selections
-                    .insert(name.to_string(), cards);
-                state.stella_player_selection_drafts.remove(name);
-                if let Some(player) = state.players.get_mut(name) {
-                    player.ready = true;
-                }
-                self.broadcast_stella_stage(&state).await?;
-                if state.players.values().all(|player| player.ready) {
-                    self.init_stella_reveal(&mut state).await?;
-                }
-            }
-            ClientMsg::RevealStellaCard { card } => {
-                if !state.players.contains_key(name)
-                    || !matches!(state.game_mode, GameMode::Stella)
-                    || !matches!(state.stage, RoomStage::StellaReveal)
-                {
-                    return Ok(());
-                }
-                if state.stella_queue_during_association {
+
+                if matches!(state.stage, RoomStage::End) {
                     return Ok(());
                 }
-                if self.active_player_name(&state) != Some(name) {
+
+                if !Self::is_before_voting_stage(state.stage) {
+                    if let Some(tx) = state.player_to_socket.get(name) {
+                        tx.send(
+                            ServerMsg::ErrorMsg(
+                                "Voting wrong-card disabling can only be changed before voting starts"
+                                    .to_string(),
+                            )
+                            .into(),
+                        )
+                        .await?;
+                    }
                     return Ok(());
                 }
-                let _ = self
-                    .reveal_stella_card_for_player(&mut state, name, &card)
-                    .await?;
-            }
-            ClientMsg::Vote { card } => {
-                self.submit_votes(&mut state, name, vec![card]).await?;
-            }
-            _ => {
-                // nothing
-            }
-        }
-
-        Ok(())
-    }
-
-    async fn handle_player_choose_cards(
-        &self,
-        state: &mut RwLockWriteGuard<'_, RoomState>,
-        name: &str,
-        cards: Vec<String>,
-    ) -> Result<()> {
-        if !state.players.contains_key(name) {
-            return Ok(());
-        }
 
-        if matches!(state.stage, RoomStage::PlayersChoose) {
-            if state.player_order.is_empty() {
-                return Ok(());
+                let canonical_distribution =
+                    match self.canonicalize_voting_wrong_card_disable_distribution(&distribution) {
+                        Ok(canonical_distribution) => canonical_distribution,
+                        Err(err) => {
+                            if let Some(tx) = state.player_to_socket.get(name) {
+                                tx.send(ServerMsg::ErrorMsg(err.to_string()).into()).await?;
+                            }
+                            return Ok(());
+                        }
+                    };
+                state.voting_wrong_card_disable_distribution = canonical_distribution;
+                self.broadcast_msg(self.room_state(&state))?;
             }
-            if state.player_order[state.active_player] != name {
-                let (_, nomination_max) = self.nominations_per_guesser_bounds(state);
-                let nominations_per_guesser =
-                    usize::from(state.nominations_per_guesser.clamp(1, nomination_max));
-                if cards.len() != nominations_per_guesser {
+            ClientMsg::ForceCurrentStage {} => {
+                if !self.is_moderator(&state, name) {
                     if let Some(tx) = state.player_to_socket.get(name) {
                         tx.send(
-                            ServerMsg::ErrorMsg(format!(
-                                "You must choose exactly {} card{}",
-                                nominations_per_guesser,
-                                if nominations_per_guesser == 1 {
-                                    ""
-                                } else {
-                                    "s"
-                                }
-                            ))
+                            ServerMsg::ErrorMsg(
+                                "Only moderators can force the current stage".to_string(),
+                            )
                             .into(),
                         )
                         .await?;
@@ -11540,170 +13030,613 @@ impl Room {
                     return Ok(());
                 }
 
-                let unique = cards.iter().collect::<HashSet<_>>();
-                if unique.len() != cards.len() {
-                    return Err(anyhow!("Duplicate cards chosen by player"));
+                let stage_supports_force = matches!(
+                    state.stage,
+                    RoomStage::ActiveChooses
+                        | RoomStage::PlayersChoose
+                        | RoomStage::Voting
+                        | RoomStage::BeautyVoting
+                        | RoomStage::ClueRating
+                        | RoomStage::StellaAssociate
+                        | RoomStage::StellaReveal
+                );
+                if !stage_supports_force {
+                    return Ok(());
                 }
 
-                // verify cards are in player's hand
-                for card in cards.iter() {
-                    if !state
-                        .player_hand
-                        .get(name)
-                        .map(|hand| hand.contains(card))
-                        .unwrap_or(false)
-                    {
-                        return Err(anyhow!("Invalid card chosen by player"));
+                if !self.force_current_stage(&mut state).await? {
+                    if let Some(tx) = state.player_to_socket.get(name) {
+                        tx.send(
+                            ServerMsg::ErrorMsg(
+                                "Current stage cannot be force-resolved yet".to_string(),
+                            )
+                            .into(),
+                        )
+                        .await?;
                     }
                 }
+            }
+            ClientMsg::ObserverifyOfflinePendingPlayers {} => {
+                if !self.is_moderator(&state, name) {
+                    if let Some(tx) = state.player_to_socket.get(name) {
+                        tx.send(
+                            ServerMsg::ErrorMsg(
+                                "Only moderators can auto-observerify offline players".to_string(),
+                            )
+                            .into(),
+                        )
+                        .await?;
+                    }
+                    return Ok(());
+                }
 
-                state
-                    .player_to_current_cards
-                    .insert(name.to_string(), cards.clone());
-                state.player_to_current_card_drafts.remove(name);
-
-                // ready
-                state.players.get_mut(name).unwrap().ready = true;
-                self.broadcast_msg(self.room_state(&state))?;
+                let stage_supports_observerify = matches!(
+                    state.stage,
+                    RoomStage::ActiveChooses
+                        | RoomStage::PlayersChoose
+                        | RoomStage::Voting
+                        | RoomStage::BeautyVoting
+                        | RoomStage::ClueRating
+                        | RoomStage::StellaAssociate
+                        | RoomStage::StellaReveal
+                );
+                if !stage_supports_observerify {
+                    return Ok(());
+                }
 
-                // check if everyone except for the active player is ready
-                if state.players.values().filter(|p| p.ready).count()
-                    >= state.players.len().saturating_sub(1)
-                {
-                    self.init_voting(state).await?;
+                if !self.observerify_offline_pending_players(&mut state).await? {
+                    if let Some(tx) = state.player_to_socket.get(name) {
+                        tx.send(
+                            ServerMsg::ErrorMsg(
+                                "No offline players with pending actions to observerify"
+                                    .to_string(),
+                            )
+                            .into(),
+                        )
+                        .await?;
+                    }
                 }
             }
-        }
-        Ok(())
-    }
+            ClientMsg::ForceStartNextRound {} => {
+                if !self.is_moderator(&state, name) {
+                    if let Some(tx) = state.player_to_socket.get(name) {
+                        tx.send(
+                            ServerMsg::ErrorMsg(
+                                "Only moderators can force start next round".to_string(),
+                            )
+                            .into(),
+                        )
+                        .await?;
+                    }
+                    return Ok(());
+                }
 
-    fn compute_results(&self, state: &RwLockWriteGuard<RoomState>) -> HashMap<String, u16> {
-        let mut point_change: HashMap<String, u16> = HashMap::new();
-        let active_player = state.player_order[state.active_player].clone();
-        let active_card = state
-            .player_to_current_cards
-            .get(&active_player)
-            .and_then(|cards| cards.first())
-            .cloned()
-            .unwrap();
+                if !matches!(
+                    state.stage,
+                    RoomStage::Results | RoomStage::BeautyResults | RoomStage::StellaResults
+                ) {
+                    return Ok(());
+                }
 
-        let mut votes_for_card: HashMap<String, u16> = HashMap::new();
-        let mut correct_guessers = 0u16;
-        let mut double_correct_guessers = HashSet::new();
+                if matches!(state.stage, RoomStage::Results)
+                    && self.uses_separate_beauty_results_stage(&state)
+                {
+                    self.init_beauty_results(&mut state)?;
+                    return Ok(());
+                }
 
-        for (player, votes) in state.player_to_votes.iter() {
-            if player == &active_player {
-                continue;
-            }
-            let mut forced_counts = HashMap::<String, usize>::new();
-            for card in state
-                .forced_random_vote_cards
-                .get(player)
-                .cloned()
-                .unwrap_or_default()
-            {
-                *forced_counts.entry(card).or_insert(0) += 1;
+                if self.should_end_game(&state) {
+                    self.transition_to_end(&mut state)?;
+                    self.broadcast_msg(self.end_game_msg(&state))?;
+                    self.broadcast_msg(self.room_state(&state))?;
+                    return Ok(());
+                }
+
+                self.init_round(&mut state).await?;
             }
-            let mut correct_vote_count = 0usize;
-            for card in votes.iter() {
-                let is_forced_random_token = match forced_counts.get_mut(card) {
-                    Some(count) if *count > 0 => {
-                        *count -= 1;
-                        true
-                    }
-                    _ => false,
-                };
-                if !is_forced_random_token {
-                    *votes_for_card.entry(card.to_string()).or_insert(0) += 1;
-                    if card == &active_card {
-                        correct_vote_count += 1;
+            ClientMsg::ForceEndGame {} => {
+                if !self.is_moderator(&state, name) {
+                    if let Some(tx) = state.player_to_socket.get(name) {
+                        tx.send(
+                            ServerMsg::ErrorMsg(
+                                "Only moderators can force end the game".to_string(),
+                            )
+                            .into(),
+                        )
+                        .await?;
                     }
+                    return Ok(());
                 }
-            }
 
-            if correct_vote_count > 0 {
-                correct_guessers = correct_guessers.saturating_add(1);
-            }
-            if correct_vote_count >= 2 {
-                double_correct_guessers.insert(player.to_string());
+                if matches!(state.stage, RoomStage::Joining | RoomStage::End) {
+                    return Ok(());
+                }
+
+                self.transition_to_end(&mut state)?;
+                self.broadcast_msg(self.end_game_msg(&state))?;
+                self.broadcast_msg(self.room_state(&state))?;
             }
-        }
+            ClientMsg::ResetClue {} => {
+                if !self.is_moderator(&state, name) {
+                    if let Some(tx) = state.player_to_socket.get(name) {
+                        tx.send(
+                            ServerMsg::ErrorMsg("Only moderators can reset the clue".to_string())
+                                .into(),
+                        )
+                        .await?;
+                    }
+                    return Ok(());
+                }
 
-        let guesser_count = self.scoring_guesser_count(state) as u16;
-        let complement = self.effective_storyteller_loss_complement_for_guesser_count(
-            state,
-            usize::from(guesser_count),
-        );
-        let threshold = guesser_count.saturating_sub(complement);
-        let wrong_guesses = guesser_count.saturating_sub(correct_guessers);
-        let too_many_correct = correct_guessers >= threshold;
-        let too_many_wrong = wrong_guesses >= threshold;
-        let storyteller_loses = too_many_correct || too_many_wrong;
-        let threshold_loss_bonuses_apply_in_this_storyteller_loss_round = too_many_correct
-            || (too_many_wrong
-                && state.bonus_threshold_loss_toggles_apply_to_all_storyteller_loss_rounds);
-        let round_participants = state
-            .player_to_current_cards
-            .keys()
-            .chain(state.player_to_votes.keys())
-            .filter(|player| {
-                player.as_str() != active_player
-                    && (state.players.contains_key(player.as_str())
-                        || state.observers.contains_key(player.as_str()))
-            })
-            .cloned()
-            .collect::<HashSet<_>>();
+                if self.reset_clue_to_storyteller_choose(&mut state)? {
+                    for player in state.player_order.iter() {
+                        let _ = self
+                            .send_msg(&state, player, self.get_msg(Some(player), &state)?)
+                            .await;
+                    }
+                    self.broadcast_msg(self.room_state(&state))?;
+                }
+            }
+            ClientMsg::PassStoryteller {} => {
+                let is_current_storyteller = matches!(state.stage, RoomStage::ActiveChooses)
+                    && state
+                        .player_order
+                        .get(state.active_player)
+                        .map(String::as_str)
+                        == Some(name);
+                if !is_current_storyteller {
+                    return Ok(());
+                }
 
-        if storyteller_loses {
-            for player in round_participants.iter() {
-                let has_correct_vote =
-                    self.has_non_forced_correct_vote(state, player, &active_card);
+                if !self.pass_storyteller_turn(&mut state) {
+                    if let Some(tx) = state.player_to_socket.get(name) {
+                        tx.send(
+                            ServerMsg::ErrorMsg(
+                                "No other storyteller is available to take your turn".to_string(),
+                            )
+                            .into(),
+                        )
+                        .await?;
+                    }
+                    return Ok(());
+                }
 
-                let mut player_points = 2;
-                if threshold_loss_bonuses_apply_in_this_storyteller_loss_round
-                    && state.bonus_correct_guess_on_threshold_correct_loss
-                    && has_correct_vote
+                // a pass that counts as a storyteller turn can complete a cycles game
+                if matches!(
+                    state.storyteller_pass_penalty,
+                    StorytellerPassPenalty::CountsAsTurn
+                ) && self.transition_to_end_if_game_complete(&mut state)?
                 {
-                    player_points = 3;
+                    return Ok(());
                 }
-
-                point_change.insert(player.to_string(), player_points);
+                self.broadcast_msg(self.room_state(&state))?;
             }
-            point_change.insert(active_player.clone(), 0);
-        } else {
-            for player in round_participants.iter() {
-                let has_correct_vote =
-                    self.has_non_forced_correct_vote(state, player, &active_card);
-                if has_correct_vote {
-                    point_change.insert(player.to_string(), 3);
-                } else {
-                    point_change.insert(player.to_string(), 0);
+            ClientMsg::RefreshHands {} => {
+                if !self.is_moderator(&state, name) {
+                    if let Some(tx) = state.player_to_socket.get(name) {
+                        tx.send(
+                            ServerMsg::ErrorMsg("Only moderators can refresh hands".to_string())
+                                .into(),
+                        )
+                        .await?;
+                    }
+                    return Ok(());
                 }
-            }
-            point_change.insert(
-                active_player.clone(),
-                state.storyteller_success_points.clamp(
-                    MIN_STORYTELLER_SUCCESS_POINTS,
-                    MAX_STORYTELLER_SUCCESS_POINTS,
-                ),
-            );
-        }
 
-        let double_vote_bonus_points = if storyteller_loses {
-            if too_many_wrong {
-                self.effective_double_vote_bonus_too_many_wrong_points(state)
-            } else if too_many_correct {
-                self.effective_double_vote_bonus_too_many_correct_points(state)
-            } else {
-                self.effective_double_vote_bonus_normal_points(state)
-            }
-        } else {
-            self.effective_double_vote_bonus_normal_points(state)
-        };
-        if double_vote_bonus_points > 0 {
-            for player in double_correct_guessers.iter() {
-                let entry = point_change.entry(player.to_string()).or_insert(0);
-                *entry += double_vote_bonus_points;
+                if !matches!(state.stage, RoomStage::Joining | RoomStage::ActiveChooses) {
+                    return Ok(());
+                }
+
+                let refreshed = self.refresh_active_player_hands(&mut state).await?;
+                if !refreshed {
+                    if let Some(tx) = state.player_to_socket.get(name) {
+                        tx.send(
+                            ServerMsg::ErrorMsg(
+                                "Not enough cards in the deck to refresh hands".to_string(),
+                            )
+                            .into(),
+                        )
+                        .await?;
+                    }
+                    return Ok(());
+                }
+                self.broadcast_msg(self.room_state(&state))?;
+            }
+            ClientMsg::ResumeGame {} => {
+                if !matches!(state.stage, RoomStage::Paused) {
+                    return Ok(());
+                }
+
+                if !self.is_moderator(&state, name) {
+                    if let Some(tx) = state.player_to_socket.get(name) {
+                        tx.send(
+                            ServerMsg::ErrorMsg("Only moderators can resume the game".to_string())
+                                .into(),
+                        )
+                        .await?;
+                    }
+                    return Ok(());
+                }
+
+                if self.non_observer_player_count(&state) < 3 {
+                    if let Some(tx) = state.player_to_socket.get(name) {
+                        tx.send(
+                            ServerMsg::ErrorMsg(
+                                "Need at least 3 non-observer players to resume".to_string(),
+                            )
+                            .into(),
+                        )
+                        .await?;
+                    }
+                    return Ok(());
+                }
+
+                if state.round == 0 {
+                    self.init_round(&mut state).await?;
+                } else {
+                    self.restart_round_keep_hands(&mut state).await?;
+                }
+            }
+            ClientMsg::ActivePlayerChooseCard { card, description } => {
+                if !state.players.contains_key(name) {
+                    return Ok(());
+                }
+
+                if matches!(state.stage, RoomStage::ActiveChooses)
+                    && !state.player_order.is_empty()
+                    && state.player_order[state.active_player] == name
+                {
+                    // verify that player has this card
+                    if !state
+                        .player_hand
+                        .get(name)
+                        .map(|cards| cards.contains(&card))
+                        .unwrap_or(false)
+                    {
+                        return Err(anyhow!("Invalid card chosen by active player"));
+                    }
+
+                    let description = description.trim();
+                    // verify that the description is not empty and is one word
+                    if description.is_empty() {
+                        if let Some(tx) = state.player_to_socket.get(name) {
+                            tx.send(
+                                ServerMsg::ErrorMsg("Description must not be empty".to_string())
+                                    .into(),
+                            )
+                            .await?;
+                        }
+                        return Ok(());
+                    }
+                    state.current_description = description.to_string();
+                    let storyteller_count = state
+                        .storyteller_counts
+                        .entry(name.to_string())
+                        .or_insert(0);
+                    *storyteller_count = storyteller_count.saturating_add(1);
+                    self.set_stage(&mut state, RoomStage::PlayersChoose);
+
+                    // record choice
+                    state
+                        .player_to_current_cards
+                        .insert(name.to_string(), vec![card.to_string()]);
+
+                    // notify players of the active player's choice
+                    for player in state.player_order.iter() {
+                        let _ = self
+                            .send_msg(&state, &player, self.get_msg(Some(&player), &state)?)
+                            .await;
+                    }
+
+                    self.clear_ready(&mut state);
+                    self.broadcast_msg(self.room_state(&state))?;
+                }
+            }
+            ClientMsg::PlayerChooseCard { card } => {
+                self.handle_player_choose_cards(&mut state, name, vec![card])
+                    .await?;
+            }
+            ClientMsg::PlayerChooseCards { cards } => {
+                self.handle_player_choose_cards(&mut state, name, cards)
+                    .await?;
+            }
+            ClientMsg::SetPlayerChooseDraft { cards } => {
+                self.set_player_choose_draft(&mut state, name, cards);
+            }
+            ClientMsg::SetVoteDraft { cards } => {
+                self.set_vote_draft(&mut state, name, cards);
+            }
+            ClientMsg::SetBeautyVoteDraft { cards } => {
+                self.set_beauty_vote_draft(&mut state, name, cards);
+            }
+            ClientMsg::SetStellaSelectionDraft { cards } => {
+                self.set_stella_selection_draft(&mut state, name, cards);
+            }
+            ClientMsg::SubmitVotes { cards } => {
+                self.submit_votes(&mut state, name, cards).await?;
+            }
+            ClientMsg::SubmitBeautyVotes { cards } => {
+                self.submit_beauty_votes(&mut state, name, cards).await?;
+            }
+            ClientMsg::SubmitClueRating { stars } => {
+                self.submit_clue_rating(&mut state, name, stars).await?;
+            }
+            ClientMsg::SubmitStellaSelection { cards } => {
+                if !state.players.contains_key(name)
+                    || !matches!(state.game_mode, GameMode::Stella)
+                    || !matches!(state.stage, RoomStage::StellaAssociate)
+                {
+                    return Ok(());
+                }
+                let unique = cards.iter().collect::<HashSet<_>>();
+                if unique.len() != cards.len() {
+                    if let Some(tx) = state.player_to_socket.get(name) {
+                        tx.send(
+                            ServerMsg::ErrorMsg(
+                                "Duplicate Stella selections are not allowed".to_string(),
+                            )
+                            .into(),
+                        )
+                        .await?;
+                    }
+                    return Ok(());
+                }
+                let count = cards.len().min(usize::from(u16::MAX)) as u16;
+                if count < state.stella_selection_min || count > state.stella_selection_max {
+                    if let Some(tx) = state.player_to_socket.get(name) {
+                        tx.send(
+                            ServerMsg::ErrorMsg(format!(
+                                "Select between {} and {} cards",
+                                state.stella_selection_min, state.stella_selection_max
+                            ))
+                            .into(),
+                        )
+                        .await?;
+                    }
+                    return Ok(());
+                }
+                let allowed_cards = state
+                    .stella_board_cards
+                    .iter()
+                    .cloned()
+                    .collect::<HashSet<_>>();
+                if !cards.iter().all(|card| allowed_cards.contains(card)) {
+                    return Err(anyhow!("Invalid Stella card selection"));
+                }
+                state
+                    .stella_player_selections
+                    .insert(name.to_string(), cards);
+                state.stella_player_selection_drafts.remove(name);
+                if let Some(player) = state.players.get_mut(name) {
+                    player.ready = true;
+                }
+                self.broadcast_stella_stage(&state).await?;
+                if state.players.values().all(|player| player.ready) {
+                    self.init_stella_reveal(&mut state).await?;
+                }
+            }
+            ClientMsg::RevealStellaCard { card } => {
+                if !state.players.contains_key(name)
+                    || !matches!(state.game_mode, GameMode::Stella)
+                    || !matches!(state.stage, RoomStage::StellaReveal)
+                {
+                    return Ok(());
+                }
+                if state.stella_queue_during_association {
+                    return Ok(());
+                }
+                if self.active_player_name(&state) != Some(name) {
+                    return Ok(());
+                }
+                let _ = self
+                    .reveal_stella_card_for_player(&mut state, name, &card)
+                    .await?;
+            }
+            ClientMsg::Vote { card } => {
+                self.submit_votes(&mut state, name, vec![card]).await?;
+            }
+            _ => {
+                // nothing
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn handle_player_choose_cards(
+        &self,
+        state: &mut RwLockWriteGuard<'_, RoomState>,
+        name: &str,
+        cards: Vec<String>,
+    ) -> Result<()> {
+        if !state.players.contains_key(name) {
+            return Ok(());
+        }
+
+        if matches!(state.stage, RoomStage::PlayersChoose) {
+            if state.player_order.is_empty() {
+                return Ok(());
+            }
+            if state.player_order[state.active_player] != name {
+                let (_, nomination_max) = self.nominations_per_guesser_bounds(state);
+                let nominations_per_guesser =
+                    usize::from(state.nominations_per_guesser.clamp(1, nomination_max));
+                if cards.len() != nominations_per_guesser {
+                    if let Some(tx) = state.player_to_socket.get(name) {
+                        tx.send(
+                            ServerMsg::ErrorMsg(format!(
+                                "You must choose exactly {} card{}",
+                                nominations_per_guesser,
+                                if nominations_per_guesser == 1 {
+                                    ""
+                                } else {
+                                    "s"
+                                }
+                            ))
+                            .into(),
+                        )
+                        .await?;
+                    }
+                    return Ok(());
+                }
+
+                let unique = cards.iter().collect::<HashSet<_>>();
+                if unique.len() != cards.len() {
+                    return Err(anyhow!("Duplicate cards chosen by player"));
+                }
+
+                // verify cards are in player's hand
+                for card in cards.iter() {
+                    if !state
+                        .player_hand
+                        .get(name)
+                        .map(|hand| hand.contains(card))
+                        .unwrap_or(false)
+                    {
+                        return Err(anyhow!("Invalid card chosen by player"));
+                    }
+                }
+
+                state
+                    .player_to_current_cards
+                    .insert(name.to_string(), cards.clone());
+                state.player_to_current_card_drafts.remove(name);
+
+                // ready
+                state.players.get_mut(name).unwrap().ready = true;
+                self.broadcast_msg(self.room_state(&state))?;
+
+                // check if everyone except for the active player is ready
+                if state.players.values().filter(|p| p.ready).count()
+                    >= state.players.len().saturating_sub(1)
+                {
+                    self.init_voting(state).await?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn compute_results(&self, state: &RwLockWriteGuard<RoomState>) -> HashMap<String, u16> {
+        let mut point_change: HashMap<String, u16> = HashMap::new();
+        let active_player = state.player_order[state.active_player].clone();
+        let active_card = state
+            .player_to_current_cards
+            .get(&active_player)
+            .and_then(|cards| cards.first())
+            .cloned()
+            .unwrap();
+
+        let mut votes_for_card: HashMap<String, u16> = HashMap::new();
+        let mut correct_guessers = 0u16;
+        let mut double_correct_guessers = HashSet::new();
+
+        for (player, votes) in state.player_to_votes.iter() {
+            if player == &active_player {
+                continue;
+            }
+            let mut forced_counts = HashMap::<String, usize>::new();
+            for card in state
+                .forced_random_vote_cards
+                .get(player)
+                .cloned()
+                .unwrap_or_default()
+            {
+                *forced_counts.entry(card).or_insert(0) += 1;
+            }
+            let mut correct_vote_count = 0usize;
+            for card in votes.iter() {
+                let is_forced_random_token = match forced_counts.get_mut(card) {
+                    Some(count) if *count > 0 => {
+                        *count -= 1;
+                        true
+                    }
+                    _ => false,
+                };
+                if !is_forced_random_token {
+                    *votes_for_card.entry(card.to_string()).or_insert(0) += 1;
+                    if card == &active_card {
+                        correct_vote_count += 1;
+                    }
+                }
+            }
+
+            if correct_vote_count > 0 {
+                correct_guessers = correct_guessers.saturating_add(1);
+            }
+            if correct_vote_count >= 2 {
+                double_correct_guessers.insert(player.to_string());
+            }
+        }
+
+        let (too_many_correct, too_many_wrong) =
+            self.storyteller_loss_flags(state, correct_guessers);
+        let storyteller_loses = too_many_correct || too_many_wrong;
+        let threshold_loss_bonuses_apply_in_this_storyteller_loss_round = too_many_correct
+            || (too_many_wrong
+                && state.bonus_threshold_loss_toggles_apply_to_all_storyteller_loss_rounds);
+        let round_participants = state
+            .player_to_current_cards
+            .keys()
+            .chain(state.player_to_votes.keys())
+            .filter(|player| {
+                player.as_str() != active_player
+                    && (state.players.contains_key(player.as_str())
+                        || state.observers.contains_key(player.as_str()))
+            })
+            .cloned()
+            .collect::<HashSet<_>>();
+
+        if storyteller_loses {
+            for player in round_participants.iter() {
+                let has_correct_vote =
+                    self.has_non_forced_correct_vote(state, player, &active_card);
+
+                let mut player_points = 2;
+                if threshold_loss_bonuses_apply_in_this_storyteller_loss_round
+                    && state.bonus_correct_guess_on_threshold_correct_loss
+                    && has_correct_vote
+                {
+                    player_points = 3;
+                }
+
+                point_change.insert(player.to_string(), player_points);
+            }
+            point_change.insert(active_player.clone(), 0);
+        } else {
+            for player in round_participants.iter() {
+                let has_correct_vote =
+                    self.has_non_forced_correct_vote(state, player, &active_card);
+                if has_correct_vote {
+                    point_change.insert(player.to_string(), 3);
+                } else {
+                    point_change.insert(player.to_string(), 0);
+                }
+            }
+            point_change.insert(
+                active_player.clone(),
+                state.storyteller_success_points.clamp(
+                    MIN_STORYTELLER_SUCCESS_POINTS,
+                    MAX_STORYTELLER_SUCCESS_POINTS,
+                ),
+            );
+        }
+
+        let double_vote_bonus_points = if storyteller_loses {
+            if too_many_wrong {
+                self.effective_double_vote_bonus_too_many_wrong_points(state)
+            } else if too_many_correct {
+                self.effective_double_vote_bonus_too_many_correct_points(state)
+            } else {
+                self.effective_double_vote_bonus_normal_points(state)
+            }
+        } else {
+            self.effective_double_vote_bonus_normal_points(state)
+        };
+        if double_vote_bonus_points > 0 {
+            for player in double_correct_guessers.iter() {
+                let entry = point_change.entry(player.to_string()).or_insert(0);
+                *entry += double_vote_bonus_points;
             }
         }
 
@@ -11727,8 +13660,173 @@ impl Room {
         point_change
     }
 
+    // returns (too_many_correct, too_many_wrong) for the current round
+    fn storyteller_loss_flags(
+        &self,
+        state: &RwLockWriteGuard<RoomState>,
+        correct_guessers: u16,
+    ) -> (bool, bool) {
+        let guesser_count = self.scoring_guesser_count(state) as u16;
+        let complement = self.effective_storyteller_loss_complement_for_guesser_count(
+            state,
+            usize::from(guesser_count),
+        );
+        let threshold = guesser_count.saturating_sub(complement);
+        let wrong_guesses = guesser_count.saturating_sub(correct_guessers);
+        (correct_guessers >= threshold, wrong_guesses >= threshold)
+    }
+
+    fn storyteller_round_succeeded(&self, state: &RwLockWriteGuard<RoomState>) -> bool {
+        let Some(correct_guessers) = self.correct_guesser_count(state) else {
+            return false;
+        };
+        let (too_many_correct, too_many_wrong) =
+            self.storyteller_loss_flags(state, correct_guessers);
+        !too_many_correct && !too_many_wrong
+    }
+
+    fn correct_guesser_count(&self, state: &RwLockWriteGuard<RoomState>) -> Option<u16> {
+        let active_player = state.player_order.get(state.active_player)?;
+        let active_card = state
+            .player_to_current_cards
+            .get(active_player)
+            .and_then(|cards| cards.first())?;
+        let correct_guessers = state
+            .player_to_votes
+            .keys()
+            .filter(|player| *player != active_player)
+            .filter(|player| self.has_non_forced_correct_vote(state, player, active_card))
+            .count();
+        Some(u16::try_from(correct_guessers).unwrap_or(u16::MAX))
+    }
+
     fn should_end_game(&self, state: &RwLockWriteGuard<RoomState>) -> bool {
-        match state.win_condition {
+        self.win_condition_met(state, &state.win_condition) && !self.needs_sudden_death_round(state)
+    }
+
+    fn needs_sudden_death_round(&self, state: &RwLockWriteGuard<RoomState>) -> bool {
+        if !state.tiebreak_rules.contains(&TiebreakRule::SuddenDeath)
+            || state.sudden_death_rounds_played >= MAX_SUDDEN_DEATH_ROUNDS
+            || state.players.len() < 2
+        {
+            return false;
+        }
+        let standings = self.final_standings(state);
+        standings
+            .iter()
+            .filter(|standing| !standing.observer && standing.rank == 1)
+            .count()
+            > 1
+    }
+
+    fn final_standings(&self, state: &RwLockWriteGuard<RoomState>) -> Vec<FinalStanding> {
+        let clue_rating_averages = self.member_to_clue_rating_average_for_room_state(state);
+        let mut members = state
+            .players
+            .iter()
+            .map(|(name, player)| (name.clone(), player.points, false))
+            .chain(
+                state
+                    .observers
+                    .iter()
+                    .filter_map(|(name, observer)| Some((name.clone(), observer.points?, true))),
+            )
+            .map(|(name, points, observer)| {
+                let bonus = self.secret_objective_bonus(state, &name);
+                (name, points.saturating_add(bonus), observer)
+            })
+            .collect::<Vec<_>>();
+
+        let compare_by_rule = |rule: TiebreakRule, a: &str, b: &str| -> std::cmp::Ordering {
+            match rule {
+                TiebreakRule::StorytellerSuccesses => {
+                    let successes = |name: &str| {
+                        state
+                            .member_to_storyteller_successes
+                            .get(name)
+                            .copied()
+                            .unwrap_or(0)
+                    };
+                    successes(b).cmp(&successes(a))
+                }
+                TiebreakRule::BeautyPoints => {
+                    let beauty = |name: &str| {
+                        state
+                            .member_to_beauty_points
+                            .get(name)
+                            .copied()
+                            .unwrap_or(0)
+                    };
+                    beauty(b).cmp(&beauty(a))
+                }
+                TiebreakRule::ClueRating => {
+                    let average =
+                        |name: &str| clue_rating_averages.get(name).copied().unwrap_or(0.0);
+                    average(b)
+                        .partial_cmp(&average(a))
+                        .unwrap_or(std::cmp::Ordering::Equal)
+                }
+                TiebreakRule::SuddenDeath => std::cmp::Ordering::Equal,
+            }
+        };
+        let tiebreak = |a: &str, b: &str| -> Option<(TiebreakRule, std::cmp::Ordering)> {
+            state.tiebreak_rules.iter().find_map(|rule| {
+                let ordering = compare_by_rule(*rule, a, b);
+                ordering.is_ne().then_some((*rule, ordering))
+            })
+        };
+
+        members.sort_by(
+            |(name_a, points_a, observer_a), (name_b, points_b, observer_b)| {
+                observer_a
+                    .cmp(observer_b)
+                    .then_with(|| points_b.cmp(points_a))
+                    .then_with(|| {
+                        tiebreak(name_a, name_b)
+                            .map(|(_, ordering)| ordering)
+                            .unwrap_or(std::cmp::Ordering::Equal)
+                    })
+                    .then_with(|| name_a.cmp(name_b))
+            },
+        );
+
+        let mut standings: Vec<FinalStanding> = Vec::with_capacity(members.len());
+        for (index, (name, points, observer)) in members.into_iter().enumerate() {
+            let (rank, decided_by) = match standings.last() {
+                Some(previous) if previous.points == points && previous.observer == observer => {
+                    match tiebreak(&previous.name, &name) {
+                        Some((rule, _)) => (index as u16 + 1, Some(rule)),
+                        None => (previous.rank, None),
+                    }
+                }
+                _ => (index as u16 + 1, None),
+            };
+            standings.push(FinalStanding {
+                rank,
+                secret_objective: state.member_secret_objectives.get(&name).cloned(),
+                secret_objective_bonus: self.secret_objective_bonus(state, &name),
+                name,
+                points,
+                decided_by,
+                observer,
+            });
+        }
+        standings
+    }
+
+    fn end_game_msg(&self, state: &RwLockWriteGuard<RoomState>) -> ServerMsg {
+        ServerMsg::EndGame {
+            standings: self.final_standings(state),
+            game_id: state.current_game_id.clone(),
+        }
+    }
+
+    fn win_condition_met(
+        &self,
+        state: &RwLockWriteGuard<RoomState>,
+        win_condition: &WinCondition,
+    ) -> bool {
+        match *win_condition {
             WinCondition::Points { target_points } => {
                 let max_points = state
                     .players
@@ -11768,8 +13866,69 @@ impl Room {
                     self.storyteller_count_for_member(state, player) >= target_cycles
                 })
             }
-            WinCondition::FixedRounds { target_rounds } => state.round >= target_rounds,
-            WinCondition::CardsFinish => false,
+            WinCondition::FixedRounds { target_rounds } => state.round >= target_rounds,
+            WinCondition::CardsFinish => false,
+            WinCondition::TimeLimit {
+                minutes,
+                finish_cycle,
+            } => {
+                let Some(game_started_at_s) = state.game_started_at_s else {
+                    return false;
+                };
+                let elapsed_s = get_time_s().saturating_sub(game_started_at_s);
+                if elapsed_s < u64::from(minutes) * 60 {
+                    return false;
+                }
+                !finish_cycle || self.storyteller_cycle_is_complete(state)
+            }
+            WinCondition::AnyOf { ref conditions } => conditions
+                .iter()
+                .any(|condition| self.win_condition_met(state, condition)),
+            WinCondition::AllOf { ref conditions } => conditions
+                .iter()
+                .all(|condition| self.win_condition_met(state, condition)),
+        }
+    }
+
+    fn storyteller_cycle_is_complete(&self, state: &RwLockWriteGuard<RoomState>) -> bool {
+        let pool_active = self.storyteller_pool_is_active(state);
+        let counts = state
+            .player_order
+            .iter()
+            .filter(|player| {
+                !pool_active
+                    || state
+                        .member_room_auth_ids
+                        .get(player.as_str())
+                        .map(|room_auth_id| {
+                            state
+                                .storyteller_pool_member_auth_ids
+                                .contains(room_auth_id)
+                        })
+                        .unwrap_or(false)
+            })
+            .map(|player| self.storyteller_count_for_member(state, player))
+            .collect::<Vec<_>>();
+        counts.iter().min() == counts.iter().max()
+    }
+
+    // whether running out of cards should end the game instead of refilling the deck
+    fn deck_depletion_ends_game(&self, state: &RwLockWriteGuard<RoomState>) -> bool {
+        match &state.win_condition {
+            WinCondition::CardsFinish => true,
+            WinCondition::AnyOf { conditions } => conditions
+                .iter()
+                .any(|condition| matches!(condition, WinCondition::CardsFinish)),
+            WinCondition::AllOf { conditions } => {
+                conditions
+                    .iter()
+                    .any(|condition| matches!(condition, WinCondition::CardsFinish))
+                    && conditions
+                        .iter()
+                        .filter(|condition| !matches!(condition, WinCondition::CardsFinish))
+                        .all(|condition| self.win_condition_met(state, condition))
+            }
+            _ => false,
         }
     }
 
@@ -11795,17 +13954,21 @@ impl Room {
         name: &str,
         token: &str,
         room_password: Option<&str>,
+        invite: Option<&str>,
+        client_ip: IpAddr,
     ) {
         let name = canonical_member_name(name);
         // public funciton
-        let (joined_name, connection_generation) =
-            match self.attempt_join(socket, name, token, room_password).await {
-                Ok(generation) => generation,
-                Err(e) => {
-                    println!("Error in attempt_join: {:?}", e);
-                    return;
-                }
-            };
+        let (joined_name, connection_generation) = match self
+            .attempt_join(socket, name, token, room_password, invite, client_ip)
+            .await
+        {
+            Ok(generation) => generation,
+            Err(e) => {
+                println!("Error in attempt_join: {:?}", e);
+                return;
+            }
+        };
 
         let res = self
             .run_ws_loop(socket, &joined_name, connection_generation)
@@ -11847,6 +14010,8 @@ impl Room {
         name: &str,
         token: &str,
         room_password: Option<&str>,
+        invite: Option<&str>,
+        client_ip: IpAddr,
     ) -> Result<(String, u64)> {
         let name = canonical_member_name(name);
         let token = token.trim();
@@ -11867,7 +14032,7 @@ impl Room {
         );
 
         let mut state = self.state.write().await;
-        let (resolved_name, canonical_token) =
+        let (mut resolved_name, mut canonical_token) =
             match self.resolve_join_credentials(&state, name, token) {
                 Ok(value) => value,
                 Err(err) => {
@@ -11880,18 +14045,48 @@ impl Room {
 
         let is_known_member = self.member_exists(&state, &resolved_name);
 
-        if !is_known_member {
-            if let Some(expected_hash) = state.room_password_hash.as_ref() {
-                let submitted = room_password.unwrap_or("").trim();
-                if submitted.is_empty()
-                    || hash_room_password(&state.room_id, submitted) != *expected_hash
-                {
-                    socket
-                        .send(ServerMsg::ErrorMsg("Incorrect room password".to_string()).into())
-                        .await?;
-                    return Err(anyhow!("Incorrect room password"));
-                }
+        // without the invite key an unlisted room is indistinguishable from a missing one
+        if !is_known_member && !Self::invite_matches(&state, invite) {
+            socket.send(ServerMsg::InvalidRoomId {}.into()).await?;
+            return Err(anyhow!("Missing or wrong invite key"));
+        }
+
+        if !is_known_member && state.room_password_hash.is_some() {
+            // the KDF is slow, so the room lock is released while it runs
+            drop(state);
+            let submitted = room_password.unwrap_or("").trim();
+            let check = if submitted.is_empty() {
+                RoomPasswordCheck::Incorrect
+            } else {
+                self.check_room_password(client_ip, submitted).await
+            };
+            let error = match check {
+                RoomPasswordCheck::Correct => None,
+                RoomPasswordCheck::Incorrect => Some("Incorrect room password".to_string()),
+                RoomPasswordCheck::RetryAfter { seconds } => Some(format!(
+                    "Too many wrong passwords; try again in {} seconds",
+                    seconds
+                )),
+            };
+            if let Some(error) = error {
+                socket
+                    .send(ServerMsg::ErrorMsg(error.clone()).into())
+                    .await?;
+                return Err(anyhow!(error));
             }
+
+            state = self.state.write().await;
+            // the name may have been claimed while the lock was released
+            (resolved_name, canonical_token) =
+                match self.resolve_join_credentials(&state, name, token) {
+                    Ok(value) => value,
+                    Err(err) => {
+                        socket
+                            .send(ServerMsg::ErrorMsg(err.to_string()).into())
+                            .await?;
+                        return Err(err);
+                    }
+                };
         }
 
         if let Some(player) = state.players.get_mut(&resolved_name) {
@@ -11929,6 +14124,7 @@ impl Room {
         state
             .name_tokens
             .insert(resolved_name.clone(), canonical_token);
+        state.restored_member_token_hashes.remove(&resolved_name);
         let room_auth_id = self
             .room_auth_id_for_member(&mut state, &resolved_name)
             .ok_or_else(|| anyhow!("Failed to allocate room auth id for {}", resolved_name))?;
@@ -11971,11 +14167,34 @@ impl Room {
                 .into(),
             )
             .await?;
-        socket.send(self.room_state(&state).into()).await?;
+        socket
+            .send(
+                self.member_view(&state, &resolved_name, self.room_state(&state))
+                    .into(),
+            )
+            .await?;
+        socket
+            .send(
+                ServerMsg::ChatHistory {
+                    messages: state.chat_history.iter().cloned().collect(),
+                }
+                .into(),
+            )
+            .await?;
         if state.players.contains_key(&resolved_name) {
             if let Ok(msg) = self.get_msg(Some(&resolved_name), &state) {
                 socket.send(msg.into()).await?;
             }
+            if let Some(objective) = state.member_secret_objectives.get(&resolved_name) {
+                socket
+                    .send(
+                        ServerMsg::SecretObjective {
+                            objective: objective.clone(),
+                        }
+                        .into(),
+                    )
+                    .await?;
+            }
         } else if matches!(
             state.stage,
             RoomStage::Voting
@@ -11992,59 +14211,653 @@ impl Room {
             }
         }
 
-        Ok((resolved_name, generation))
+        Ok((resolved_name, generation))
+    }
+
+    async fn run_ws_loop(
+        &self,
+        socket: &mut WebSocket,
+        name: &str,
+        connection_generation: u64,
+    ) -> Result<()> {
+        let (tx, mut rx) = mpsc::channel(10);
+        {
+            let mut state = self.state.write().await;
+            if state.connection_generation.get(name).copied() != Some(connection_generation) {
+                return Ok(());
+            }
+            state.player_to_socket.insert(name.to_string(), tx);
+        }
+        let mut broadcast_updates = self.broadcast.subscribe();
+        let mut message_bucket = self
+            .message_limits
+            .rate
+            .map(|rate| TokenBucket::new(rate, get_time_ms()));
+
+        loop {
+            tokio::select! {
+                msg = broadcast_updates.recv() => {
+                    let msg = {
+                        let state = self.state.read().await;
+                        if state.connection_generation.get(name).copied() != Some(connection_generation) {
+                            break;
+                        }
+                        self.member_view(&state, name, msg?)
+                    };
+                    socket.send(msg.into()).await?;
+                }
+                msg = socket.recv() => {
+                    if self.state.read().await.connection_generation.get(name).copied() != Some(connection_generation) {
+                        break;
+                    }
+                    match msg {
+                        Some(Ok(WsMessage::Close(_))) => break,
+                        Some(Ok(WsMessage::Ping(payload))) => {
+                            socket.send(WsMessage::Pong(payload)).await?;
+                        }
+                        Some(Ok(WsMessage::Pong(_))) => {}
+                        Some(Ok(msg)) => {
+                            if let Some((reason, message)) =
+                                self.message_limit_violation(&msg, message_bucket.as_mut())
+                            {
+                                println!("Disconnecting {}: {}", name, message);
+                                socket
+                                    .send(ServerMsg::Disconnected { reason, message }.into())
+                                    .await?;
+                                socket.send(WsMessage::Close(None)).await?;
+                                break;
+                            }
+                            self.handle_client_msg(name, connection_generation, msg).await?;
+                        }
+                        _ => break
+                    }
+                },
+                msg = rx.recv() => {
+                    if self.state.read().await.connection_generation.get(name).copied() != Some(connection_generation) {
+                        break;
+                    }
+                    match msg {
+                        Some(msg) => {
+                            socket.send(msg.into()).await?;
+                        }
+                        _ => break,
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn message_limit_violation(
+        &self,
+        msg: &WsMessage,
+        message_bucket: Option<&mut TokenBucket>,
+    ) -> Option<(DisconnectReason, String)> {
+        let len = match msg {
+            WsMessage::Text(text) => text.len(),
+            WsMessage::Binary(bytes) => bytes.len(),
+            _ => 0,
+        };
+        if len > self.message_limits.max_message_bytes {
+            return Some((
+                DisconnectReason::MessageTooLarge,
+                format!(
+                    "Message of {} bytes is over the {} byte limit",
+                    len, self.message_limits.max_message_bytes
+                ),
+            ));
+        }
+        if let Some(bucket) = message_bucket {
+            if !bucket.try_take(get_time_ms()) {
+                return Some((
+                    DisconnectReason::RateLimited,
+                    "Too many messages; reconnecting".to_string(),
+                ));
+            }
+        }
+        None
+    }
+
+    // Room-scoped stats show members' names and votes, so they need a member token, a device
+    // migration room_auth_id, or the room password.
+    pub async fn stats_read_is_allowed(
+        &self,
+        client_ip: IpAddr,
+        member_token: Option<&str>,
+        room_auth_id: Option<&str>,
+        password: Option<&str>,
+    ) -> bool {
+        let state = self.state.read().await;
+        let is_member =
+            |name: &str| state.players.contains_key(name) || state.observers.contains_key(name);
+        fn non_empty(value: Option<&str>) -> Option<&str> {
+            value.map(str::trim).filter(|value| !value.is_empty())
+        }
+
+        if let Some(token) = non_empty(member_token) {
+            if state
+                .name_tokens
+                .iter()
+                .any(|(name, existing)| existing == token && is_member(name))
+            {
+                return true;
+            }
+        }
+        if let Some(room_auth_id) = non_empty(room_auth_id) {
+            if state
+                .room_auth_id_members
+                .get(room_auth_id)
+                .is_some_and(|name| is_member(name))
+            {
+                return true;
+            }
+        }
+        let Some(password) = non_empty(password) else {
+            return false;
+        };
+        if state.room_password_hash.is_none() {
+            return false;
+        }
+        drop(state);
+        self.check_room_password(client_ip, password).await == RoomPasswordCheck::Correct
+    }
+
+    // Verifies a room password on a blocking thread. Each IP gets a few free wrong attempts, then
+    // an exponentially growing wait before the next one is even checked.
+    async fn check_room_password(&self, client_ip: IpAddr, password: &str) -> RoomPasswordCheck {
+        let Some(expected_hash) = self.state.read().await.room_password_hash.clone() else {
+            return RoomPasswordCheck::Incorrect;
+        };
+
+        // Every attempt is counted as a failure before the lock is released for hashing, so
+        // parallel sockets from one IP cannot all slip past the same backoff check.
+        let now_ms = get_time_ms();
+        {
+            let mut failures = self.password_failures.lock().unwrap();
+            failures.retain(|_, entry| {
+                now_ms.saturating_sub(entry.last_failure_ms) < ROOM_PASSWORD_FAILURE_RESET_MS
+            });
+            if let Some(retry_at_ms) = failures
+                .get(&client_ip)
+                .map(RoomPasswordFailures::retry_at_ms)
+                .filter(|retry_at_ms| *retry_at_ms > now_ms)
+            {
+                return RoomPasswordCheck::RetryAfter {
+                    seconds: (retry_at_ms - now_ms).div_ceil(1000),
+                };
+            }
+            let entry = failures.entry(client_ip).or_insert(RoomPasswordFailures {
+                count: 0,
+                last_failure_ms: now_ms,
+            });
+            entry.count += 1;
+            entry.last_failure_ms = now_ms;
+        }
+
+        let password = password.to_string();
+        let correct =
+            tokio::task::spawn_blocking(move || verify_room_password(&expected_hash, &password))
+                .await
+                .unwrap_or(false);
+
+        let mut failures = self.password_failures.lock().unwrap();
+        if correct {
+            failures.remove(&client_ip);
+            return RoomPasswordCheck::Correct;
+        }
+        // the backoff runs from when the guess was rejected, not when it arrived
+        if let Some(entry) = failures.get_mut(&client_ip) {
+            entry.last_failure_ms = get_time_ms();
+        }
+        RoomPasswordCheck::Incorrect
+    }
+
+    pub fn with_unlisted(mut self, unlisted: bool) -> Self {
+        if unlisted {
+            self.state.get_mut().invite_key = Some(Self::generate_access_token());
+        }
+        self
+    }
+
+    pub fn with_owner_token_hash(mut self, owner_token_hash: Option<String>) -> Self {
+        self.state.get_mut().owner_token_hash = owner_token_hash;
+        self
+    }
+
+    pub async fn stage(&self) -> RoomStage {
+        self.state.read().await.stage
+    }
+
+    // The room stops being persistent and is garbage collected like any other room.
+    pub async fn release_persistence(&self) -> Result<()> {
+        let mut state = self.state.write().await;
+        state.owner_token_hash = None;
+        self.broadcast_msg(self.room_state(&state))?;
+        Ok(())
+    }
+
+    fn persistent_settings(state: &RoomState) -> PersistentRoomSettings {
+        PersistentRoomSettings {
+            game_mode: state.game_mode,
+            win_condition: state.win_condition.clone(),
+            moderator_absence_promotion_delay_s: state.moderator_absence_promotion_delay_s,
+            allow_new_players_midgame: state.allow_new_players_midgame,
+            storyteller_loss_complement: state.storyteller_loss_complement,
+            storyteller_loss_complement_auto: state.storyteller_loss_complement_auto,
+            storyteller_pool_enabled: state.storyteller_pool_enabled,
+            storyteller_pool_member_auth_ids: {
+                let mut ids: Vec<String> = state
+                    .storyteller_pool_member_auth_ids
+                    .iter()
+                    .cloned()
+                    .collect();
+                ids.sort();
+                ids
+            },
+            storyteller_success_points: state.storyteller_success_points,
+            storyteller_pass_penalty: state.storyteller_pass_penalty,
+            storyteller_pass_penalty_points: state.storyteller_pass_penalty_points,
+            tiebreak_rules: state.tiebreak_rules.clone(),
+            member_handicap_offsets: state.member_handicap_offsets.clone(),
+            catch_up_bonus_enabled: state.catch_up_bonus_enabled,
+            catch_up_bonus_points: state.catch_up_bonus_points,
+            catch_up_deficit_points: state.catch_up_deficit_points,
+            secret_objectives_enabled: state.secret_objectives_enabled,
+            secret_objective_bonus_points: state.secret_objective_bonus_points,
+            chat_stage_restrictions_enabled: state.chat_stage_restrictions_enabled,
+            spectator_delay_s: state.spectator_delay_s,
+            double_vote_bonus_normal_points: state.double_vote_bonus_normal_points,
+            double_vote_bonus_too_many_wrong_points: state.double_vote_bonus_too_many_wrong_points,
+            double_vote_bonus_too_many_wrong_follows_normal: state
+                .double_vote_bonus_too_many_wrong_follows_normal,
+            double_vote_bonus_too_many_correct_points: state
+                .double_vote_bonus_too_many_correct_points,
+            double_vote_bonus_too_many_correct_follows_normal: state
+                .double_vote_bonus_too_many_correct_follows_normal,
+            votes_per_guesser: state.votes_per_guesser,
+            votes_per_guesser_auto: state.votes_per_guesser_auto,
+            beauty_enabled: state.beauty_enabled,
+            beauty_votes_per_player: state.beauty_votes_per_player,
+            beauty_votes_per_player_auto: state.beauty_votes_per_player_auto,
+            beauty_allow_duplicate_votes: state.beauty_allow_duplicate_votes,
+            beauty_split_points_on_tie: state.beauty_split_points_on_tie,
+            beauty_points_bonus: state.beauty_points_bonus,
+            beauty_scoring_mode: state.beauty_scoring_mode,
+            beauty_vote_points_divisor_mode: state.beauty_vote_points_divisor_mode,
+            beauty_vote_points_divisor_tenths: state.beauty_vote_points_divisor_tenths,
+            beauty_vote_points_divisor_player_count_base: state
+                .beauty_vote_points_divisor_player_count_base,
+            beauty_results_display_mode: state.beauty_results_display_mode,
+            show_previous_results_during_storyteller_choosing: state
+                .show_previous_results_during_storyteller_choosing,
+            randomize_voting_card_order_per_viewer: state.randomize_voting_card_order_per_viewer,
+            cards_per_hand: state.cards_per_hand,
+            nominations_per_guesser: state.nominations_per_guesser,
+            bonus_correct_guess_on_threshold_correct_loss: state
+                .bonus_correct_guess_on_threshold_correct_loss,
+            bonus_threshold_loss_toggles_apply_to_all_storyteller_loss_rounds: state
+                .bonus_threshold_loss_toggles_apply_to_all_storyteller_loss_rounds,
+            show_voting_card_numbers: state.show_voting_card_numbers,
+            round_start_discard_all_unpinned: state.round_start_discard_all_unpinned,
+            round_start_discard_count: state.round_start_discard_count,
+            hint_choosing_timer_enabled: state.hint_choosing_timer_enabled,
+            hint_choosing_timer_duration_s: state.hint_choosing_timer_duration_s,
+            card_choosing_timer_enabled: state.card_choosing_timer_enabled,
+            card_choosing_timer_duration_s: state.card_choosing_timer_duration_s,
+            voting_timer_enabled: state.voting_timer_enabled,
+            voting_timer_duration_s: state.voting_timer_duration_s,
+            beauty_timer_enabled: state.beauty_timer_enabled,
+            beauty_timer_duration_s: state.beauty_timer_duration_s,
+            clue_rating_enabled: state.clue_rating_enabled,
+            clue_rating_max_stars: state.clue_rating_max_stars,
+            clue_rating_timer_enabled: state.clue_rating_timer_enabled,
+            clue_rating_timer_duration_s: state.clue_rating_timer_duration_s,
+            force_hint_choosing_timer: state.force_hint_choosing_timer,
+            force_card_choosing_timer: state.force_card_choosing_timer,
+            force_voting_timer: state.force_voting_timer,
+            force_beauty_timer: state.force_beauty_timer,
+            force_clue_rating_timer: state.force_clue_rating_timer,
+            voting_wrong_card_disable_distribution: state
+                .voting_wrong_card_disable_distribution
+                .clone(),
+            stella_word_pack: state.stella_word_pack.clone(),
+            stella_board_size: state.stella_board_size,
+            stella_selection_min: state.stella_selection_min,
+            stella_selection_max: state.stella_selection_max,
+            stella_queue_during_association: state.stella_queue_during_association,
+            stella_queued_reveal_mode: state.stella_queued_reveal_mode,
+            stella_scout_timer_enabled: state.stella_scout_timer_enabled,
+            stella_scout_timer_duration_s: state.stella_scout_timer_duration_s,
+            force_stella_scout_timer: state.force_stella_scout_timer,
+            copy_card_url_on_hold: state.copy_card_url_on_hold,
+            leaderboard_view_mode_default: state.leaderboard_view_mode_default,
+        }
+    }
+
+    fn apply_persistent_settings(state: &mut RoomState, settings: PersistentRoomSettings) {
+        state.game_mode = settings.game_mode;
+        state.win_condition = settings.win_condition;
+        state.moderator_absence_promotion_delay_s = settings.moderator_absence_promotion_delay_s;
+        state.allow_new_players_midgame = settings.allow_new_players_midgame;
+        state.storyteller_loss_complement = settings.storyteller_loss_complement;
+        state.storyteller_loss_complement_auto = settings.storyteller_loss_complement_auto;
+        state.storyteller_pool_enabled = settings.storyteller_pool_enabled;
+        state.storyteller_pool_member_auth_ids = settings
+            .storyteller_pool_member_auth_ids
+            .into_iter()
+            .collect();
+        state.storyteller_success_points = settings.storyteller_success_points;
+        state.storyteller_pass_penalty = settings.storyteller_pass_penalty;
+        state.storyteller_pass_penalty_points = settings.storyteller_pass_penalty_points;
+        state.tiebreak_rules = settings.tiebreak_rules;
+        state.member_handicap_offsets = settings.member_handicap_offsets;
+        state.catch_up_bonus_enabled = settings.catch_up_bonus_enabled;
+        state.catch_up_bonus_points = settings.catch_up_bonus_points;
+        state.catch_up_deficit_points = settings.catch_up_deficit_points;
+        state.secret_objectives_enabled = settings.secret_objectives_enabled;
+        state.secret_objective_bonus_points = settings.secret_objective_bonus_points;
+        state.chat_stage_restrictions_enabled = settings.chat_stage_restrictions_enabled;
+        state.spectator_delay_s = settings.spectator_delay_s;
+        state.double_vote_bonus_normal_points = settings.double_vote_bonus_normal_points;
+        state.double_vote_bonus_too_many_wrong_points =
+            settings.double_vote_bonus_too_many_wrong_points;
+        state.double_vote_bonus_too_many_wrong_follows_normal =
+            settings.double_vote_bonus_too_many_wrong_follows_normal;
+        state.double_vote_bonus_too_many_correct_points =
+            settings.double_vote_bonus_too_many_correct_points;
+        state.double_vote_bonus_too_many_correct_follows_normal =
+            settings.double_vote_bonus_too_many_correct_follows_normal;
+        state.votes_per_guesser = settings.votes_per_guesser;
+        state.votes_per_guesser_auto = settings.votes_per_guesser_auto;
+        state.beauty_enabled = settings.beauty_enabled;
+        state.beauty_votes_per_player = settings.beauty_votes_per_player;
+        state.beauty_votes_per_player_auto = settings.beauty_votes_per_player_auto;
+        state.beauty_allow_duplicate_votes = settings.beauty_allow_duplicate_votes;
+        state.beauty_split_points_on_tie = settings.beauty_split_points_on_tie;
+        state.beauty_points_bonus = settings.beauty_points_bonus;
+        state.beauty_scoring_mode = settings.beauty_scoring_mode;
+        state.beauty_vote_points_divisor_mode = settings.beauty_vote_points_divisor_mode;
+        state.beauty_vote_points_divisor_tenths = settings.beauty_vote_points_divisor_tenths;
+        state.beauty_vote_points_divisor_player_count_base =
+            settings.beauty_vote_points_divisor_player_count_base;
+        state.beauty_results_display_mode = settings.beauty_results_display_mode;
+        state.show_previous_results_during_storyteller_choosing =
+            settings.show_previous_results_during_storyteller_choosing;
+        state.randomize_voting_card_order_per_viewer =
+            settings.randomize_voting_card_order_per_viewer;
+        state.cards_per_hand = settings.cards_per_hand;
+        state.nominations_per_guesser = settings.nominations_per_guesser;
+        state.bonus_correct_guess_on_threshold_correct_loss =
+            settings.bonus_correct_guess_on_threshold_correct_loss;
+        state.bonus_threshold_loss_toggles_apply_to_all_storyteller_loss_rounds =
+            settings.bonus_threshold_loss_toggles_apply_to_all_storyteller_loss_rounds;
+        state.show_voting_card_numbers = settings.show_voting_card_numbers;
+        state.round_start_discard_all_unpinned = settings.round_start_discard_all_unpinned;
+        state.round_start_discard_count = settings.round_start_discard_count;
+        state.hint_choosing_timer_enabled = settings.hint_choosing_timer_enabled;
+        state.hint_choosing_timer_duration_s = settings.hint_choosing_timer_duration_s;
+        state.card_choosing_timer_enabled = settings.card_choosing_timer_enabled;
+        state.card_choosing_timer_duration_s = settings.card_choosing_timer_duration_s;
+        state.voting_timer_enabled = settings.voting_timer_enabled;
+        state.voting_timer_duration_s = settings.voting_timer_duration_s;
+        state.beauty_timer_enabled = settings.beauty_timer_enabled;
+        state.beauty_timer_duration_s = settings.beauty_timer_duration_s;
+        state.clue_rating_enabled = settings.clue_rating_enabled;
+        state.clue_rating_max_stars = settings.clue_rating_max_stars;
+        state.clue_rating_timer_enabled = settings.clue_rating_timer_enabled;
+        state.clue_rating_timer_duration_s = settings.clue_rating_timer_duration_s;
+        state.force_hint_choosing_timer = settings.force_hint_choosing_timer;
+        state.force_card_choosing_timer = settings.force_card_choosing_timer;
+        state.force_voting_timer = settings.force_voting_timer;
+        state.force_beauty_timer = settings.force_beauty_timer;
+        state.force_clue_rating_timer = settings.force_clue_rating_timer;
+        state.voting_wrong_card_disable_distribution =
+            settings.voting_wrong_card_disable_distribution;
+        state.stella_word_pack = settings.stella_word_pack;
+        state.stella_board_size = settings.stella_board_size;
+        state.stella_selection_min = settings.stella_selection_min;
+        state.stella_selection_max = settings.stella_selection_max;
+        state.stella_queue_during_association = settings.stella_queue_during_association;
+        state.stella_queued_reveal_mode = settings.stella_queued_reveal_mode;
+        state.stella_scout_timer_enabled = settings.stella_scout_timer_enabled;
+        state.stella_scout_timer_duration_s = settings.stella_scout_timer_duration_s;
+        state.force_stella_scout_timer = settings.force_stella_scout_timer;
+        state.copy_card_url_on_hold = settings.copy_card_url_on_hold;
+        state.leaderboard_view_mode_default = settings.leaderboard_view_mode_default;
+    }
+
+    pub async fn persistent_snapshot(&self) -> PersistentRoomSnapshot {
+        let state = self.state.read().await;
+        let mut members: Vec<PersistentRoomMember> = state
+            .players
+            .keys()
+            .chain(state.observers.keys())
+            .filter_map(|name| {
+                let token_hash = state
+                    .name_tokens
+                    .get(name)
+                    .map(|token| player_hash_for_token(token))
+                    .or_else(|| state.restored_member_token_hashes.get(name).cloned())?;
+                Some(PersistentRoomMember {
+                    name: name.clone(),
+                    token_hash,
+                    room_auth_id: state.member_room_auth_ids.get(name).cloned(),
+                })
+            })
+            .collect();
+        members.sort_by(|a, b| a.name.cmp(&b.name));
+        let mut moderators: Vec<String> = state.moderators.iter().cloned().collect();
+        moderators.sort();
+        PersistentRoomSnapshot {
+            creator: state.creator.clone(),
+            members,
+            moderators,
+            room_password_hash: state.room_password_hash.clone(),
+            invite_key: state.invite_key.clone(),
+            public_listing: state.public_listing.clone(),
+            settings: serde_json::to_value(Self::persistent_settings(&state)).unwrap_or_default(),
+        }
+    }
+
+    // Fills a fresh room's lobby from a snapshot. Settings the snapshot lacks, or cannot be
+    // read, keep the defaults.
+    pub async fn restore_persistent_snapshot(&self, snapshot: PersistentRoomSnapshot) {
+        let mut state = self.state.write().await;
+        let mut settings =
+            serde_json::to_value(Self::persistent_settings(&state)).unwrap_or_default();
+        if let (Some(current), serde_json::Value::Object(saved)) =
+            (settings.as_object_mut(), snapshot.settings)
+        {
+            current.extend(saved);
+        }
+        match serde_json::from_value(settings) {
+            Ok(settings) => Self::apply_persistent_settings(&mut state, settings),
+            Err(err) => println!(
+                "Ignoring saved settings for room {}: {}",
+                state.room_id, err
+            ),
+        }
+
+        for member in snapshot.members {
+            state.storyteller_counts.insert(member.name.clone(), 0);
+            state.players.insert(
+                member.name.clone(),
+                PlayerInfo {
+                    connected: false,
+                    points: 0,
+                    // nobody is connected yet, so nobody has readied up for the next game
+                    ready: false,
+                },
+            );
+            if let Some(room_auth_id) = member.room_auth_id {
+                state
+                    .room_auth_id_members
+                    .insert(room_auth_id.clone(), member.name.clone());
+                state
+                    .member_room_auth_ids
+                    .insert(member.name.clone(), room_auth_id);
+            }
+            state
+                .restored_member_token_hashes
+                .insert(member.name, member.token_hash);
+        }
+        for moderator in snapshot.moderators {
+            if state.players.contains_key(&moderator) {
+                state.moderators.insert(moderator);
+            }
+        }
+        state.creator = snapshot.creator;
+        state.room_password_hash = snapshot.room_password_hash;
+        state.invite_key = snapshot.invite_key;
+        state.public_listing = snapshot.public_listing;
+    }
+
+    // None unless the room is listed and a new player could join it right now.
+    pub async fn public_summary(&self) -> Option<PublicRoomSummary> {
+        let state = self.state.read().await;
+        let listing = state.public_listing.as_ref()?;
+        let joinable = match state.stage {
+            RoomStage::Joining => true,
+            RoomStage::End => false,
+            _ => state.allow_new_players_midgame,
+        };
+        let member_count = state.players.len() + state.observers.len();
+        if !joinable || member_count >= self.max_members {
+            return None;
+        }
+        Some(PublicRoomSummary {
+            room_id: state.room_id.clone(),
+            title: listing.title.clone(),
+            language: listing.language.clone(),
+            game_mode: state.game_mode,
+            stage: state.stage,
+            player_count: state.players.len(),
+            observer_count: state.observers.len(),
+            max_members: self.max_members,
+            has_password: state.room_password_hash.is_some(),
+        })
+    }
+
+    pub async fn invite_is_valid(&self, invite: Option<&str>) -> bool {
+        let state = self.state.read().await;
+        Self::invite_matches(&state, invite)
     }
 
-    async fn run_ws_loop(
+    fn invite_matches(state: &RoomState, invite: Option<&str>) -> bool {
+        match state.invite_key.as_deref() {
+            Some(invite_key) => invite.map(str::trim) == Some(invite_key),
+            None => true,
+        }
+    }
+
+    pub async fn stats_export_token_is_valid(&self, token: &str) -> bool {
+        let state = self.state.read().await;
+        !token.is_empty()
+            && state.stats_export_token.as_deref() == Some(token)
+            && state.stats_export_token_expires_at_s > get_time_s()
+    }
+
+    pub async fn on_spectator_connection(&self, socket: &mut WebSocket, spectator_token: &str) {
+        let res = self
+            .run_spectator_loop(socket, spectator_token.trim())
+            .await;
+        if let Err(e) = res {
+            println!("Error in run_spectator_loop: {:?}", e);
+        }
+
+        // the spectator's receiver is gone by now, so this refreshes spectator_count
+        let state = self.state.write().await;
+        if let Err(e) = self.broadcast_msg(self.room_state(&state)) {
+            println!("Error sending broadcast: {}", e);
+        }
+    }
+
+    async fn run_spectator_loop(
         &self,
         socket: &mut WebSocket,
-        name: &str,
-        connection_generation: u64,
+        spectator_token: &str,
     ) -> Result<()> {
-        let (tx, mut rx) = mpsc::channel(10);
-        {
-            let mut state = self.state.write().await;
-            if state.connection_generation.get(name).copied() != Some(connection_generation) {
+        let (mut spectator_updates, snapshot, delay_s) = {
+            let state = self.state.write().await;
+            if spectator_token.is_empty()
+                || state.spectator_token.as_deref() != Some(spectator_token)
+            {
+                drop(state);
+                socket
+                    .send(ServerMsg::ErrorMsg("Invalid spectator link".to_string()).into())
+                    .await?;
                 return Ok(());
             }
-            state.player_to_socket.insert(name.to_string(), tx);
+            let spectator_updates = self.spectator_broadcast.subscribe();
+            // let members see the new spectator_count
+            self.broadcast_msg(self.room_state(&state))?;
+            (
+                spectator_updates,
+                self.spectator_snapshot(&state),
+                state.spectator_delay_s,
+            )
+        };
+
+        socket
+            .send(ServerMsg::JoinedAsSpectator { delay_s }.into())
+            .await?;
+
+        // every update is held back by the room's current delay; due times never decrease
+        // so lowering the delay cannot reorder updates
+        let mut pending = VecDeque::new();
+        let initial_due = tokio::time::Instant::now() + Duration::from_secs(delay_s.into());
+        for msg in snapshot {
+            pending.push_back((initial_due, msg));
         }
-        let mut broadcast_updates = self.broadcast.subscribe();
 
         loop {
+            let next_due = pending.front().map(|(due, _)| *due);
             tokio::select! {
-                msg = broadcast_updates.recv() => {
-                    if self.state.read().await.connection_generation.get(name).copied() != Some(connection_generation) {
-                        break;
+                msg = spectator_updates.recv() => {
+                    let update = match msg {
+                        Ok(msg) => Some(msg),
+                        // a spectator that fell behind skips what it missed and catches up
+                        // from a fresh snapshot instead of being disconnected
+                        Err(broadcast::error::RecvError::Lagged(_)) => None,
+                        Err(err) => return Err(err.into()),
+                    };
+                    let delay_s = {
+                        let state = self.state.read().await;
+                        if state.spectator_token.as_deref() != Some(spectator_token) {
+                            drop(state);
+                            socket
+                                .send(ServerMsg::ErrorMsg("Spectator link was revoked".to_string()).into())
+                                .await?;
+                            break;
+                        }
+                        state.spectator_delay_s
+                    };
+                    let msgs = match update {
+                        Some(msg) => vec![msg],
+                        None => self.spectator_snapshot(&self.state.write().await),
+                    };
+                    let due = (tokio::time::Instant::now() + Duration::from_secs(delay_s.into()))
+                        .max(pending.back().map(|(due, _)| *due).unwrap_or(initial_due));
+                    for msg in msgs {
+                        pending.push_back((due, msg));
                     }
-                    socket.send(msg?.into()).await?;
                 }
-                msg = socket.recv() => {
-                    if self.state.read().await.connection_generation.get(name).copied() != Some(connection_generation) {
-                        break;
+                _ = tokio::time::sleep_until(next_due.unwrap_or_else(tokio::time::Instant::now)), if next_due.is_some() => {
+                    if let Some((_, msg)) = pending.pop_front() {
+                        socket.send(msg.into()).await?;
                     }
+                }
+                msg = socket.recv() => {
                     match msg {
                         Some(Ok(WsMessage::Close(_))) => break,
                         Some(Ok(WsMessage::Ping(payload))) => {
                             socket.send(WsMessage::Pong(payload)).await?;
                         }
-                        Some(Ok(WsMessage::Pong(_))) => {}
-                        Some(Ok(msg)) => {
-                            self.handle_client_msg(name, connection_generation, msg).await?;
-                        }
+                        // spectators are read-only, so anything else is ignored
+                        Some(Ok(_)) => {}
                         _ => break
                     }
-                },
-                msg = rx.recv() => {
-                    if self.state.read().await.connection_generation.get(name).copied() != Some(connection_generation) {
-                        break;
-                    }
-                    match msg {
-                        Some(msg) => {
-                            socket.send(msg.into()).await?;
-                        }
-                        _ => break,
-                    }
                 }
             }
         }
@@ -12053,12 +14866,60 @@ impl Room {
     }
 
     fn broadcast_msg(&self, msg: ServerMsg) -> Result<()> {
+        if self.spectator_broadcast.receiver_count() != 0 {
+            let _ = self
+                .spectator_broadcast
+                .send(msg.clone().without_invite_key().without_spectator_token());
+        }
         if self.broadcast.receiver_count() != 0 {
             self.broadcast.send(msg)?;
         }
         Ok(())
     }
 
+    // Members receive these stages through per-player messages that carry their hand,
+    // so spectators get a separate hand-free copy.
+    fn broadcast_spectator_stage_msg(&self, state: &RwLockWriteGuard<RoomState>) {
+        if self.spectator_broadcast.receiver_count() == 0
+            || !matches!(
+                state.stage,
+                RoomStage::Voting
+                    | RoomStage::BeautyVoting
+                    | RoomStage::StellaAssociate
+                    | RoomStage::StellaReveal
+            )
+        {
+            return;
+        }
+        if let Ok(msg) = self.get_msg(None, state) {
+            let _ = self.spectator_broadcast.send(msg);
+        }
+    }
+
+    // The spectator link is for moderators to share, so other members never see it.
+    fn member_view(&self, state: &RoomState, name: &str, msg: ServerMsg) -> ServerMsg {
+        if state.moderators.contains(name) {
+            msg
+        } else {
+            msg.without_spectator_token()
+        }
+    }
+
+    fn spectator_snapshot(&self, state: &RwLockWriteGuard<RoomState>) -> Vec<ServerMsg> {
+        let mut msgs = vec![
+            self.room_state(state)
+                .without_invite_key()
+                .without_spectator_token(),
+            ServerMsg::ChatHistory {
+                messages: state.chat_history.iter().cloned().collect(),
+            },
+        ];
+        if let Ok(msg) = self.get_msg(None, state) {
+            msgs.push(msg);
+        }
+        msgs
+    }
+
     async fn send_msg(
         &self,
         state: &RwLockWriteGuard<'_, RoomState>,
@@ -12137,6 +14998,18 @@ impl Room {
             storyteller_success_points_min,
             storyteller_success_points_max,
         );
+        let (storyteller_pass_penalty_points_min, storyteller_pass_penalty_points_max) =
+            self.storyteller_pass_penalty_points_bounds();
+        let storyteller_pass_penalty_points = state.storyteller_pass_penalty_points.clamp(
+            storyteller_pass_penalty_points_min,
+            storyteller_pass_penalty_points_max,
+        );
+        let (catch_up_bonus_points_min, catch_up_bonus_points_max) =
+            self.catch_up_bonus_points_bounds();
+        let (catch_up_deficit_points_min, catch_up_deficit_points_max) =
+            self.catch_up_deficit_points_bounds();
+        let (secret_objective_bonus_points_min, secret_objective_bonus_points_max) =
+            self.secret_objective_bonus_points_bounds();
         let (double_vote_bonus_points_min, double_vote_bonus_points_max) =
             self.double_vote_bonus_points_bounds();
         let double_vote_bonus_normal_points = self.effective_double_vote_bonus_normal_points(state);
@@ -12220,7 +15093,8 @@ impl Room {
             round: state.round,
             cards_remaining: state.deck.len() as u32,
             deck_refill_count: state.deck_refill_count,
-            win_condition: state.win_condition,
+            win_condition: state.win_condition.clone(),
+            game_started_at_s: state.game_started_at_s,
             allow_new_players_midgame: state.allow_new_players_midgame,
             copy_card_url_on_hold: state.copy_card_url_on_hold,
             moderator_absence_promotion_delay_s: state.moderator_absence_promotion_delay_s,
@@ -12235,6 +15109,55 @@ impl Room {
             storyteller_success_points,
             storyteller_success_points_min,
             storyteller_success_points_max,
+            storyteller_pass_penalty: state.storyteller_pass_penalty,
+            storyteller_pass_penalty_points,
+            storyteller_pass_penalty_points_min,
+            storyteller_pass_penalty_points_max,
+            tiebreak_rules: state.tiebreak_rules.clone(),
+            sudden_death_rounds_played: state.sudden_death_rounds_played,
+            handicap_offsets: state.member_handicap_offsets.clone(),
+            handicap_offset_max: MAX_HANDICAP_OFFSET_POINTS,
+            handicap_points: state.member_handicap_points.clone(),
+            catch_up_bonus_enabled: state.catch_up_bonus_enabled,
+            catch_up_bonus_points: state
+                .catch_up_bonus_points
+                .clamp(catch_up_bonus_points_min, catch_up_bonus_points_max),
+            catch_up_bonus_points_min,
+            catch_up_bonus_points_max,
+            catch_up_deficit_points: state
+                .catch_up_deficit_points
+                .clamp(catch_up_deficit_points_min, catch_up_deficit_points_max),
+            catch_up_deficit_points_min,
+            catch_up_deficit_points_max,
+            secret_objectives_enabled: state.secret_objectives_enabled,
+            secret_objective_bonus_points: state.secret_objective_bonus_points.clamp(
+                secret_objective_bonus_points_min,
+                secret_objective_bonus_points_max,
+            ),
+            secret_objective_bonus_points_min,
+            secret_objective_bonus_points_max,
+            revealed_secret_objectives: if matches!(state.stage, RoomStage::End) {
+                state.member_secret_objectives.clone()
+            } else {
+                HashMap::new()
+            },
+            chat_muted_members: {
+                let mut muted = state.chat_muted_members.iter().cloned().collect::<Vec<_>>();
+                muted.sort();
+                muted
+            },
+            chat_stage_restrictions_enabled: state.chat_stage_restrictions_enabled,
+            spectator_token: state.spectator_token.clone(),
+            invite_key: state.invite_key.clone(),
+            has_password: state.room_password_hash.is_some(),
+            public_listing: state.public_listing.clone(),
+            persistent: state.owner_token_hash.is_some(),
+            spectator_delay_s: state
+                .spectator_delay_s
+                .clamp(MIN_SPECTATOR_DELAY_S, MAX_SPECTATOR_DELAY_S),
+            spectator_delay_s_min: MIN_SPECTATOR_DELAY_S,
+            spectator_delay_s_max: MAX_SPECTATOR_DELAY_S,
+            spectator_count: self.spectator_broadcast.receiver_count(),
             double_vote_bonus_normal_points,
             double_vote_bonus_too_many_wrong_points,
             double_vote_bonus_too_many_wrong_follows_normal: state
@@ -12377,6 +15300,7 @@ impl Room {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::rate_limit::TokenBucketConfig;
     use std::path::PathBuf;
 
     fn test_default_stella_word_pack() -> Arc<Vec<String>> {
@@ -12475,6 +15399,10 @@ mod tests {
             .insert(name.to_string(), storyteller_count);
     }
 
+    fn test_ip() -> IpAddr {
+        IpAddr::from([127, 0, 0, 1])
+    }
+
     fn setup_connected_member(
         state: &mut RwLockWriteGuard<'_, RoomState>,
         name: &str,
@@ -14619,6 +17547,8 @@ mod tests {
                     ("c".into(), 0),
                     ("d".into(), 0),
                 ]),
+                handicap_after_round: HashMap::new(),
+                pass_penalty_deltas: HashMap::new(),
                 results_display_mode: BeautyResultsDisplayMode::Combined,
             });
         state.previous_dixit_results = Some(PreviousDixitResultsView::Results {
@@ -14776,6 +17706,8 @@ mod tests {
                 player_clue_ratings: HashMap::new(),
                 total_after_round: HashMap::from([("b".into(), 2)]),
                 beauty_total_after_round: HashMap::from([("b".into(), 2)]),
+                handicap_after_round: HashMap::new(),
+                pass_penalty_deltas: HashMap::new(),
                 results_display_mode: BeautyResultsDisplayMode::Separate,
             });
         state
@@ -14793,6 +17725,8 @@ mod tests {
                 player_clue_ratings: HashMap::new(),
                 total_after_round: HashMap::from([("b".into(), 3)]),
                 beauty_total_after_round: HashMap::from([("b".into(), 3)]),
+                handicap_after_round: HashMap::new(),
+                pass_penalty_deltas: HashMap::new(),
                 results_display_mode: BeautyResultsDisplayMode::Separate,
             });
         state.beauty_point_change = HashMap::from([("b".into(), 1)]);
@@ -17192,6 +20126,8 @@ alpha
                     ("c".to_string(), 2),
                 ]),
                 beauty_total_after_round: HashMap::from([("c".to_string(), 2)]),
+                handicap_after_round: HashMap::new(),
+                pass_penalty_deltas: HashMap::new(),
                 results_display_mode: BeautyResultsDisplayMode::Combined,
             });
 
@@ -17241,6 +20177,7 @@ alpha
                 beauty_deltas: HashMap::new(),
                 total_after_round: HashMap::from([("Alice".into(), 10)]),
                 beauty_total_after_round: HashMap::new(),
+                raw_total_after_round: HashMap::new(),
             },
             LeaderboardRoundHistoryEntry {
                 round_num: 2,
@@ -17249,6 +20186,7 @@ alpha
                 beauty_deltas: HashMap::new(),
                 total_after_round: HashMap::from([("Alice".into(), 15), ("Bob".into(), 0)]),
                 beauty_total_after_round: HashMap::new(),
+                raw_total_after_round: HashMap::new(),
             },
             LeaderboardRoundHistoryEntry {
                 round_num: 3,
@@ -17257,6 +20195,7 @@ alpha
                 beauty_deltas: HashMap::new(),
                 total_after_round: HashMap::from([("Alice".into(), 15), ("Bob".into(), 17)]),
                 beauty_total_after_round: HashMap::new(),
+                raw_total_after_round: HashMap::new(),
             },
         ];
         let entries = vec![
@@ -17301,6 +20240,7 @@ alpha
                 beauty_deltas: HashMap::from([("Alice".into(), 2)]),
                 total_after_round: HashMap::from([("Alice".into(), 8)]),
                 beauty_total_after_round: HashMap::from([("Alice".into(), 2)]),
+                raw_total_after_round: HashMap::new(),
             },
             LeaderboardRoundHistoryEntry {
                 round_num: 2,
@@ -17309,6 +20249,7 @@ alpha
                 beauty_deltas: HashMap::from([("Alice".into(), 1)]),
                 total_after_round: HashMap::from([("Alice".into(), 12), ("Bob".into(), 0)]),
                 beauty_total_after_round: HashMap::from([("Alice".into(), 3)]),
+                raw_total_after_round: HashMap::new(),
             },
             LeaderboardRoundHistoryEntry {
                 round_num: 3,
@@ -17317,6 +20258,7 @@ alpha
                 beauty_deltas: HashMap::from([("Bob".into(), 2)]),
                 total_after_round: HashMap::from([("Alice".into(), 13), ("Bob".into(), 15)]),
                 beauty_total_after_round: HashMap::from([("Alice".into(), 3), ("Bob".into(), 2)]),
+                raw_total_after_round: HashMap::new(),
             },
         ];
         let entries = vec![
@@ -17361,6 +20303,7 @@ alpha
                 beauty_deltas: HashMap::from([("Alice".into(), 4)]),
                 total_after_round: HashMap::from([("Alice".into(), 10)]),
                 beauty_total_after_round: HashMap::from([("Alice".into(), 4)]),
+                raw_total_after_round: HashMap::new(),
             },
             LeaderboardRoundHistoryEntry {
                 round_num: 2,
@@ -17369,6 +20312,7 @@ alpha
                 beauty_deltas: HashMap::from([("Bob".into(), 3)]),
                 total_after_round: HashMap::from([("Alice".into(), 10), ("Bob".into(), 15)]),
                 beauty_total_after_round: HashMap::from([("Alice".into(), 4), ("Bob".into(), 3)]),
+                raw_total_after_round: HashMap::new(),
             },
             LeaderboardRoundHistoryEntry {
                 round_num: 3,
@@ -17377,6 +20321,7 @@ alpha
                 beauty_deltas: HashMap::from([("Bob".into(), 1)]),
                 total_after_round: HashMap::from([("Alice".into(), 10), ("Bob".into(), 17)]),
                 beauty_total_after_round: HashMap::from([("Alice".into(), 4), ("Bob".into(), 4)]),
+                raw_total_after_round: HashMap::new(),
             },
         ];
         let entries = vec![
@@ -17421,6 +20366,7 @@ alpha
                 beauty_deltas: HashMap::new(),
                 total_after_round: HashMap::from([("Alice".into(), 10)]),
                 beauty_total_after_round: HashMap::new(),
+                raw_total_after_round: HashMap::new(),
             },
             LeaderboardRoundHistoryEntry {
                 round_num: 2,
@@ -17429,6 +20375,7 @@ alpha
                 beauty_deltas: HashMap::new(),
                 total_after_round: HashMap::from([("Alice".into(), 10), ("Observer".into(), 4)]),
                 beauty_total_after_round: HashMap::new(),
+                raw_total_after_round: HashMap::new(),
             },
         ];
         let entries = vec![
@@ -17465,6 +20412,7 @@ alpha
                 beauty_deltas: HashMap::new(),
                 total_after_round: HashMap::from([("Existing".into(), 10)]),
                 beauty_total_after_round: HashMap::new(),
+                raw_total_after_round: HashMap::new(),
             },
             LeaderboardRoundHistoryEntry {
                 round_num: 2,
@@ -17473,6 +20421,7 @@ alpha
                 beauty_deltas: HashMap::new(),
                 total_after_round: HashMap::from([("Existing".into(), 20)]),
                 beauty_total_after_round: HashMap::new(),
+                raw_total_after_round: HashMap::new(),
             },
             LeaderboardRoundHistoryEntry {
                 round_num: 3,
@@ -17481,6 +20430,7 @@ alpha
                 beauty_deltas: HashMap::new(),
                 total_after_round: HashMap::from([("Existing".into(), 25), ("Bob".into(), 22)]),
                 beauty_total_after_round: HashMap::new(),
+                raw_total_after_round: HashMap::new(),
             },
             LeaderboardRoundHistoryEntry {
                 round_num: 4,
@@ -17489,6 +20439,7 @@ alpha
                 beauty_deltas: HashMap::new(),
                 total_after_round: HashMap::from([("Existing".into(), 28), ("Bob".into(), 26)]),
                 beauty_total_after_round: HashMap::new(),
+                raw_total_after_round: HashMap::new(),
             },
             LeaderboardRoundHistoryEntry {
                 round_num: 5,
@@ -17505,6 +20456,7 @@ alpha
                     ("Alice".into(), 29),
                 ]),
                 beauty_total_after_round: HashMap::new(),
+                raw_total_after_round: HashMap::new(),
             },
         ];
         let entries = vec![
@@ -17561,6 +20513,7 @@ alpha
                 beauty_deltas: HashMap::new(),
                 total_after_round: HashMap::from([("Existing".into(), 10)]),
                 beauty_total_after_round: HashMap::new(),
+                raw_total_after_round: HashMap::new(),
             },
             LeaderboardRoundHistoryEntry {
                 round_num: 2,
@@ -17569,6 +20522,7 @@ alpha
                 beauty_deltas: HashMap::new(),
                 total_after_round: HashMap::from([("Existing".into(), 20)]),
                 beauty_total_after_round: HashMap::new(),
+                raw_total_after_round: HashMap::new(),
             },
             LeaderboardRoundHistoryEntry {
                 round_num: 3,
@@ -17577,6 +20531,7 @@ alpha
                 beauty_deltas: HashMap::new(),
                 total_after_round: HashMap::from([("Existing".into(), 25), ("Bob".into(), 22)]),
                 beauty_total_after_round: HashMap::new(),
+                raw_total_after_round: HashMap::new(),
             },
             LeaderboardRoundHistoryEntry {
                 round_num: 4,
@@ -17585,6 +20540,7 @@ alpha
                 beauty_deltas: HashMap::new(),
                 total_after_round: HashMap::from([("Existing".into(), 28), ("Bob".into(), 26)]),
                 beauty_total_after_round: HashMap::new(),
+                raw_total_after_round: HashMap::new(),
             },
             LeaderboardRoundHistoryEntry {
                 round_num: 5,
@@ -17597,6 +20553,7 @@ alpha
                     ("Alice".into(), 0),
                 ]),
                 beauty_total_after_round: HashMap::new(),
+                raw_total_after_round: HashMap::new(),
             },
             LeaderboardRoundHistoryEntry {
                 round_num: 6,
@@ -17613,6 +20570,7 @@ alpha
                     ("Alice".into(), 30),
                 ]),
                 beauty_total_after_round: HashMap::new(),
+                raw_total_after_round: HashMap::new(),
             },
         ];
         let entries = vec![
@@ -17669,6 +20627,7 @@ alpha
                 beauty_deltas: HashMap::new(),
                 total_after_round: HashMap::from([("Existing".into(), 10)]),
                 beauty_total_after_round: HashMap::new(),
+                raw_total_after_round: HashMap::new(),
             },
             LeaderboardRoundHistoryEntry {
                 round_num: 2,
@@ -17677,6 +20636,7 @@ alpha
                 beauty_deltas: HashMap::new(),
                 total_after_round: HashMap::from([("Existing".into(), 20)]),
                 beauty_total_after_round: HashMap::new(),
+                raw_total_after_round: HashMap::new(),
             },
             LeaderboardRoundHistoryEntry {
                 round_num: 3,
@@ -17685,6 +20645,7 @@ alpha
                 beauty_deltas: HashMap::new(),
                 total_after_round: HashMap::from([("Existing".into(), 25), ("Bob".into(), 22)]),
                 beauty_total_after_round: HashMap::new(),
+                raw_total_after_round: HashMap::new(),
             },
             LeaderboardRoundHistoryEntry {
                 round_num: 4,
@@ -17693,6 +20654,7 @@ alpha
                 beauty_deltas: HashMap::new(),
                 total_after_round: HashMap::from([("Existing".into(), 28), ("Bob".into(), 26)]),
                 beauty_total_after_round: HashMap::new(),
+                raw_total_after_round: HashMap::new(),
             },
         ];
         let entries = vec![
@@ -17733,6 +20695,7 @@ alpha
             beauty_deltas: HashMap::new(),
             total_after_round: HashMap::from([("Existing".into(), 10)]),
             beauty_total_after_round: HashMap::new(),
+            raw_total_after_round: HashMap::new(),
         }];
         let entries = vec![
             CurrentLeaderboardScoreEntry {
@@ -17778,6 +20741,7 @@ alpha
                 beauty_deltas: HashMap::new(),
                 total_after_round: HashMap::from([("Existing".into(), 10)]),
                 beauty_total_after_round: HashMap::new(),
+                raw_total_after_round: HashMap::new(),
             },
             LeaderboardRoundHistoryEntry {
                 round_num: 2,
@@ -17786,6 +20750,7 @@ alpha
                 beauty_deltas: HashMap::new(),
                 total_after_round: HashMap::from([("Existing".into(), 15), ("Fresh".into(), 0)]),
                 beauty_total_after_round: HashMap::new(),
+                raw_total_after_round: HashMap::new(),
             },
             LeaderboardRoundHistoryEntry {
                 round_num: 3,
@@ -17794,6 +20759,7 @@ alpha
                 beauty_deltas: HashMap::new(),
                 total_after_round: HashMap::from([("Existing".into(), 18), ("Bob".into(), 17)]),
                 beauty_total_after_round: HashMap::new(),
+                raw_total_after_round: HashMap::new(),
             },
         ];
         let entries = vec![
@@ -17836,6 +20802,7 @@ alpha
                 beauty_deltas: HashMap::new(),
                 total_after_round: HashMap::from([("Alice".into(), 10)]),
                 beauty_total_after_round: HashMap::new(),
+                raw_total_after_round: HashMap::new(),
             },
             LeaderboardRoundHistoryEntry {
                 round_num: 2,
@@ -17847,6 +20814,7 @@ alpha
                     ("PendingObserver".into(), 0),
                 ]),
                 beauty_total_after_round: HashMap::new(),
+                raw_total_after_round: HashMap::new(),
             },
         ];
         let entries = vec![
@@ -19494,56 +22462,283 @@ alpha
         )
         .await?;
 
-        let state = room.state.write().await;
-        assert!(state.storyteller_pool_enabled);
-        assert!(
-            room.should_end_game(&state),
-            "cycle win condition should ignore active non-pool players when the pool is active"
-        );
+        let state = room.state.write().await;
+        assert!(state.storyteller_pool_enabled);
+        assert!(
+            room.should_end_game(&state),
+            "cycle win condition should ignore active non-pool players when the pool is active"
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn force_end_game_and_reset_clue_are_moderator_actions() -> Result<()> {
+        let room = test_room();
+        {
+            let mut state = room.state.write().await;
+            add_player(&mut state, "host", 0);
+            add_player(&mut state, "p2", 0);
+            add_player(&mut state, "p3", 0);
+            state.stage = RoomStage::PlayersChoose;
+            state.round = 1;
+            state.active_player = 0;
+            state.player_order = vec!["host".into(), "p2".into(), "p3".into()];
+            state.current_description = "clue".to_string();
+            state
+                .player_to_current_cards
+                .insert("host".into(), vec!["host-1".into()]);
+            state
+                .player_to_current_card_drafts
+                .insert("p2".into(), vec!["p2-1".into()]);
+            set_storyteller_count(&mut state, "host", 1);
+            state.moderators.insert("host".to_string());
+            setup_connected_member(&mut state, "host", "t-host", 88);
+        }
+
+        room.handle_client_msg("host", 88, to_ws(ClientMsg::ResetClue {}))
+            .await?;
+
+        {
+            let state = room.state.write().await;
+            assert!(matches!(state.stage, RoomStage::ActiveChooses));
+            assert_eq!(state.current_description, "");
+            assert!(state.player_to_current_cards.is_empty());
+            assert!(state.player_to_current_card_drafts.is_empty());
+            assert_eq!(state.storyteller_counts.get("host").copied(), Some(0));
+        }
+
+        room.handle_client_msg("host", 88, to_ws(ClientMsg::ForceEndGame {}))
+            .await?;
+        let state = room.state.write().await;
+        assert!(matches!(state.stage, RoomStage::End));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn storyteller_pass_applies_lose_points_penalty_and_switches_storyteller() -> Result<()> {
+        let room = test_room();
+        {
+            let mut state = room.state.write().await;
+            add_player(&mut state, "a", 5);
+            add_player(&mut state, "b", 0);
+            add_player(&mut state, "c", 0);
+            state.stage = RoomStage::ActiveChooses;
+            state.round = 1;
+            state.active_player = 0;
+            state.player_order = vec!["a".into(), "b".into(), "c".into()];
+            state.storyteller_pass_penalty = StorytellerPassPenalty::LosePoints;
+            state.storyteller_pass_penalty_points = 2;
+            setup_connected_member(&mut state, "a", "t-a", 91);
+            setup_connected_member(&mut state, "b", "t-b", 92);
+        }
+
+        room.handle_client_msg("b", 92, to_ws(ClientMsg::PassStoryteller {}))
+            .await?;
+        {
+            let state = room.state.write().await;
+            assert_eq!(
+                state.player_order[state.active_player], "a",
+                "only the current storyteller should be able to pass"
+            );
+        }
+
+        room.handle_client_msg("a", 91, to_ws(ClientMsg::PassStoryteller {}))
+            .await?;
+        let mut state = room.state.write().await;
+        assert!(matches!(state.stage, RoomStage::ActiveChooses));
+        assert_ne!(state.player_order[state.active_player], "a");
+        assert_eq!(state.players.get("a").map(|p| p.points), Some(3));
+        assert_eq!(
+            state.storyteller_counts.get("a").copied(),
+            Some(0),
+            "a lose-points pass should not count as a storyteller turn"
+        );
+
+        // the penalty shows up in the round's scores once the round is recorded
+        state.current_game_id = Some("pass-game".to_string());
+        state.current_game_started_at_s = Some(100);
+        room.record_current_dixit_round_audit_and_history(&mut state)?;
+        let history = room.leaderboard_round_history(&state);
+        assert_eq!(history[0].total_deltas.get("a").copied(), Some(-2));
+        assert_eq!(history[0].total_after_round.get("a").copied(), Some(3));
+        let replay = room
+            .most_beautiful_stats
+            .export_game_replay("pass-game")?
+            .ok_or_else(|| anyhow!("expected round to be recorded"))?;
+        let a_score = replay.rounds[0]
+            .scores
+            .iter()
+            .find(|score| score.player_display_name == "a")
+            .ok_or_else(|| anyhow!("expected a score for a"))?;
+        assert_eq!((a_score.story_delta, a_score.total_after_round), (-2, 3));
+
+        // the story change sent with results carries the same penalty as the total change
+        state.stage = RoomStage::Results;
+        let delta_scores = room
+            .current_delta_scores(&state)
+            .ok_or_else(|| anyhow!("expected results delta scores"))?;
+        assert_eq!(delta_scores.point_change.get("a").copied(), Some(-2));
+        assert_eq!(
+            delta_scores.storyteller_point_change.get("a").copied(),
+            Some(-2)
+        );
+        assert_eq!(
+            Room::story_point_change(&state),
+            room.results_point_change(&state)
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn storyteller_pass_counting_as_turn_can_complete_cycles_game() -> Result<()> {
+        let room = test_room_with_condition(WinCondition::Cycles { target_cycles: 1 });
+        {
+            let mut state = room.state.write().await;
+            add_player(&mut state, "a", 0);
+            add_player(&mut state, "b", 0);
+            add_player(&mut state, "c", 0);
+            state.stage = RoomStage::ActiveChooses;
+            state.round = 3;
+            state.active_player = 2;
+            state.player_order = vec!["a".into(), "b".into(), "c".into()];
+            set_storyteller_count(&mut state, "a", 1);
+            set_storyteller_count(&mut state, "b", 1);
+            set_storyteller_count(&mut state, "c", 0);
+            state.storyteller_pass_penalty = StorytellerPassPenalty::CountsAsTurn;
+            setup_connected_member(&mut state, "c", "t-c", 93);
+        }
+
+        room.handle_client_msg("c", 93, to_ws(ClientMsg::PassStoryteller {}))
+            .await?;
+        let state = room.state.write().await;
+        assert_eq!(state.storyteller_counts.get("c").copied(), Some(1));
+        assert!(
+            matches!(state.stage, RoomStage::End),
+            "a pass counted as the last missing storyteller turn should end a cycles game"
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn handicap_offsets_apply_on_game_start_and_raise_midgame_join_floor() -> Result<()> {
+        let room = test_room();
+        let mut state = room.state.write().await;
+        add_player(&mut state, "a", 0);
+        add_player(&mut state, "b", 0);
+        add_player(&mut state, "c", 0);
+        state.member_handicap_offsets.insert("a".into(), 4);
+        state.member_handicap_offsets.insert("gone".into(), 3);
+
+        room.apply_handicap_offsets_on_game_start(&mut state);
+        assert_eq!(state.players.get("a").map(|p| p.points), Some(4));
+        assert_eq!(state.member_handicap_points.get("a").copied(), Some(4));
+        assert!(!state.member_handicap_points.contains_key("gone"));
+
+        state.players.get_mut("b").unwrap().points = 6;
+        state.players.get_mut("c").unwrap().points = 5;
+        assert_eq!(
+            room.midgame_join_score_floor(&state),
+            4,
+            "midgame joiners should start from the lowest score shown in the standings"
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn catch_up_bonus_only_rewards_trailing_correct_guessers() -> Result<()> {
+        let room = test_room();
+        let mut state = room.state.write().await;
+        add_player(&mut state, "a", 9);
+        add_player(&mut state, "b", 2);
+        add_player(&mut state, "c", 8);
+        add_player(&mut state, "d", 1);
+        state.player_order = vec!["a".into(), "b".into(), "c".into(), "d".into()];
+        state.active_player = 0;
+        for player in ["a", "b", "c", "d"] {
+            state
+                .player_to_current_cards
+                .insert(player.into(), vec![format!("c{player}")]);
+        }
+        state.player_to_votes.insert("b".into(), vec!["ca".into()]);
+        state.player_to_votes.insert("c".into(), vec!["ca".into()]);
+        state.player_to_votes.insert("d".into(), vec!["cb".into()]);
+
+        assert!(room.compute_catch_up_bonuses(&state).is_empty());
+
+        state.catch_up_bonus_enabled = true;
+        state.catch_up_bonus_points = 2;
+        state.catch_up_deficit_points = 5;
+        let bonuses = room.compute_catch_up_bonuses(&state);
+        assert_eq!(
+            bonuses,
+            HashMap::from([("b".to_string(), 2)]),
+            "only trailing players with a correct guess should get the catch-up bonus"
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn dixit_leaderboard_history_reports_raw_totals_without_handicaps() -> Result<()> {
+        let room = test_room();
+        let mut state = room.state.write().await;
+        add_player(&mut state, "a", 7);
+        add_player(&mut state, "b", 3);
+        state
+            .dixit_end_round_history
+            .push(DixitEndRoundHistoryEntry {
+                round_num: 1,
+                storyteller: "a".into(),
+                clue: "clue".into(),
+                active_players: vec!["a".into(), "b".into()],
+                story_deltas: HashMap::from([("a".into(), 3), ("b".into(), 3)]),
+                beauty_deltas: HashMap::new(),
+                clue_rating_sum: 0,
+                clue_rating_count: 0,
+                clue_rating_bonus: 0,
+                player_clue_ratings: HashMap::new(),
+                total_after_round: HashMap::from([("a".into(), 7), ("b".into(), 3)]),
+                beauty_total_after_round: HashMap::new(),
+                handicap_after_round: HashMap::from([("a".into(), 4)]),
+                pass_penalty_deltas: HashMap::new(),
+                results_display_mode: BeautyResultsDisplayMode::Combined,
+            });
+
+        let history = room.dixit_leaderboard_round_history(&state);
+        assert_eq!(history[0].total_after_round.get("a").copied(), Some(7));
+        assert_eq!(history[0].raw_total_after_round.get("a").copied(), Some(3));
+        assert_eq!(history[0].raw_total_after_round.get("b").copied(), Some(3));
 
         Ok(())
     }
 
     #[tokio::test]
-    async fn force_end_game_and_reset_clue_are_moderator_actions() -> Result<()> {
+    async fn storyteller_pass_penalty_points_update_is_clamped() -> Result<()> {
         let room = test_room();
         {
             let mut state = room.state.write().await;
             add_player(&mut state, "host", 0);
-            add_player(&mut state, "p2", 0);
-            add_player(&mut state, "p3", 0);
-            state.stage = RoomStage::PlayersChoose;
-            state.round = 1;
-            state.active_player = 0;
-            state.player_order = vec!["host".into(), "p2".into(), "p3".into()];
-            state.current_description = "clue".to_string();
-            state
-                .player_to_current_cards
-                .insert("host".into(), vec!["host-1".into()]);
-            state
-                .player_to_current_card_drafts
-                .insert("p2".into(), vec!["p2-1".into()]);
-            set_storyteller_count(&mut state, "host", 1);
             state.moderators.insert("host".to_string());
-            setup_connected_member(&mut state, "host", "t-host", 88);
+            setup_connected_member(&mut state, "host", "t-host", 94);
         }
 
-        room.handle_client_msg("host", 88, to_ws(ClientMsg::ResetClue {}))
-            .await?;
-
-        {
-            let state = room.state.write().await;
-            assert!(matches!(state.stage, RoomStage::ActiveChooses));
-            assert_eq!(state.current_description, "");
-            assert!(state.player_to_current_cards.is_empty());
-            assert!(state.player_to_current_card_drafts.is_empty());
-            assert_eq!(state.storyteller_counts.get("host").copied(), Some(0));
-        }
+        room.handle_client_msg(
+            "host",
+            94,
+            to_ws(ClientMsg::SetStorytellerPassPenaltyPoints { points: 99 }),
+        )
+        .await?;
 
-        room.handle_client_msg("host", 88, to_ws(ClientMsg::ForceEndGame {}))
-            .await?;
         let state = room.state.write().await;
-        assert!(matches!(state.stage, RoomStage::End));
+        assert_eq!(
+            state.storyteller_pass_penalty_points,
+            MAX_STORYTELLER_PASS_PENALTY_POINTS
+        );
 
         Ok(())
     }
@@ -20791,80 +23986,357 @@ alpha
                 state.show_previous_results_during_storyteller_choosing = true;
             }
 
-            room.handle_client_msg(
-                "host",
-                116,
-                to_ws(ClientMsg::SetShowPreviousResultsDuringStorytellerChoosing {
-                    enabled: false,
-                }),
-            )
+            room.handle_client_msg(
+                "host",
+                116,
+                to_ws(ClientMsg::SetShowPreviousResultsDuringStorytellerChoosing {
+                    enabled: false,
+                }),
+            )
+            .await?;
+
+            let state = room.state.read().await;
+            assert!(
+                !state.show_previous_results_during_storyteller_choosing,
+                "previous-results preview should update during {:?}",
+                stage
+            );
+            drop(state);
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn room_state_exposes_current_delta_scores_for_result_stages() -> Result<()> {
+        let room = test_room();
+        {
+            let mut state = room.state.write().await;
+            state.stage = RoomStage::Results;
+            state.storyteller_point_change = HashMap::from([("storyteller".into(), 3)]);
+            state.beauty_point_change = HashMap::from([("guesser".into(), 2)]);
+            state.beauty_enabled = true;
+        }
+
+        let state = room.state.write().await;
+        match room.room_state(&state) {
+            ServerMsg::RoomState {
+                current_delta_scores,
+                ..
+            } => {
+                let current_delta_scores = current_delta_scores.ok_or_else(|| {
+                    anyhow!("expected current delta scores in Results room state")
+                })?;
+                assert!(
+                    matches!(current_delta_scores.stage, RoomStage::Results),
+                    "current delta scores should retain the result stage"
+                );
+                assert_eq!(
+                    current_delta_scores
+                        .point_change
+                        .get("storyteller")
+                        .copied(),
+                    Some(3),
+                    "current total deltas should include storyteller deltas"
+                );
+                assert_eq!(
+                    current_delta_scores.point_change.get("guesser").copied(),
+                    Some(2),
+                    "current total deltas should include beauty deltas"
+                );
+                assert_eq!(
+                    current_delta_scores
+                        .storyteller_point_change
+                        .get("storyteller")
+                        .copied(),
+                    Some(3),
+                    "current storyteller deltas should be included separately"
+                );
+                assert_eq!(
+                    current_delta_scores
+                        .beauty_point_change
+                        .get("guesser")
+                        .copied(),
+                    Some(2),
+                    "current beauty deltas should be included separately"
+                );
+            }
+            other => return Err(anyhow!("Expected RoomState, got {:?}", other)),
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn active_chooses_stage_message_exposes_previous_results_payload() -> Result<()> {
+        let room = test_room();
+        {
+            let mut state = room.state.write().await;
+            add_player(&mut state, "storyteller", 0);
+            state.stage = RoomStage::ActiveChooses;
+            state.player_order = vec!["storyteller".into()];
+            state.active_player = 0;
+            state
+                .player_hand
+                .insert("storyteller".into(), vec!["hand-card".into()]);
+            state.previous_dixit_results = Some(PreviousDixitResultsView::Results {
+                center_cards: vec!["a".into()],
+                player_to_votes: HashMap::new(),
+                player_to_beauty_votes: HashMap::new(),
+                player_to_clue_rating: HashMap::new(),
+                clue_rating_average: None,
+                clue_rating_count: 0,
+                clue_rating_bonus: 0,
+                player_to_current_cards: HashMap::from([("storyteller".into(), vec!["a".into()])]),
+                active_card: "a".into(),
+                beauty_results_display_mode: BeautyResultsDisplayMode::Combined,
+                point_change: HashMap::from([("storyteller".into(), 3)]),
+                storyteller_point_change: HashMap::from([("storyteller".into(), 3)]),
+                beauty_point_change: HashMap::new(),
+                beauty_vote_totals: HashMap::new(),
+                beauty_winning_cards: Vec::new(),
+            });
+        }
+
+        let state = room.state.write().await;
+        match room.get_msg(Some("storyteller"), &state)? {
+            ServerMsg::StartRound {
+                previous_dixit_results,
+                ..
+            } => match previous_dixit_results {
+                Some(PreviousDixitResultsView::Results { point_change, .. }) => {
+                    assert_eq!(
+                        point_change.get("storyteller").copied(),
+                        Some(3),
+                        "ActiveChooses stage payload should include previous result deltas"
+                    );
+                }
+                other => {
+                    return Err(anyhow!(
+                        "Expected previous results payload, got {:?}",
+                        other
+                    ))
+                }
+            },
+            other => return Err(anyhow!("Expected StartRound, got {:?}", other)),
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn players_choose_stage_message_exposes_previous_results_payload() -> Result<()> {
+        let room = test_room();
+        {
+            let mut state = room.state.write().await;
+            add_player(&mut state, "storyteller", 0);
+            add_player(&mut state, "guesser", 0);
+            state.stage = RoomStage::PlayersChoose;
+            state.player_order = vec!["storyteller".into(), "guesser".into()];
+            state.active_player = 0;
+            state.current_description = "clue".into();
+            state
+                .player_hand
+                .insert("guesser".into(), vec!["hand-card".into()]);
+            state.previous_dixit_results = Some(PreviousDixitResultsView::BeautyResults {
+                center_cards: vec!["a".into()],
+                player_to_beauty_votes: HashMap::new(),
+                player_to_clue_rating: HashMap::new(),
+                clue_rating_average: None,
+                clue_rating_count: 0,
+                clue_rating_bonus: 0,
+                player_to_current_cards: HashMap::from([("storyteller".into(), vec!["a".into()])]),
+                point_change: HashMap::from([("guesser".into(), 2)]),
+                beauty_vote_totals: HashMap::new(),
+                beauty_winning_cards: Vec::new(),
+            });
+        }
+
+        let state = room.state.write().await;
+        match room.get_msg(Some("guesser"), &state)? {
+            ServerMsg::PlayersChoose {
+                previous_dixit_results,
+                ..
+            } => match previous_dixit_results {
+                Some(PreviousDixitResultsView::BeautyResults { point_change, .. }) => {
+                    assert_eq!(
+                        point_change.get("guesser").copied(),
+                        Some(2),
+                        "PlayersChoose stage payload should include previous beauty-result deltas"
+                    );
+                }
+                other => return Err(anyhow!("Expected previous beauty payload, got {:?}", other)),
+            },
+            other => return Err(anyhow!("Expected PlayersChoose, got {:?}", other)),
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn moderator_force_start_next_round_skips_results_ready_wait() -> Result<()> {
+        let room = test_room();
+        {
+            let mut state = room.state.write().await;
+            add_player(&mut state, "host", 0);
+            add_player(&mut state, "p2", 0);
+            add_player(&mut state, "p3", 0);
+            add_player(&mut state, "p4", 0);
+            set_storyteller_count(&mut state, "host", 3);
+            set_storyteller_count(&mut state, "p2", 4);
+            set_storyteller_count(&mut state, "p3", 1);
+            set_storyteller_count(&mut state, "p4", 2);
+            state.stage = RoomStage::Results;
+            state.round = 2;
+            state.player_order = vec!["host".into(), "p2".into(), "p3".into(), "p4".into()];
+            state.active_player = 1;
+            state.moderators.insert("host".to_string());
+            setup_connected_member(&mut state, "host", "t-host", 115);
+        }
+
+        room.handle_client_msg("host", 115, to_ws(ClientMsg::ForceStartNextRound {}))
             .await?;
 
-            let state = room.state.read().await;
-            assert!(
-                !state.show_previous_results_during_storyteller_choosing,
-                "previous-results preview should update during {:?}",
-                stage
-            );
-            drop(state);
-        }
+        let state = room.state.read().await;
+        assert!(
+            matches!(state.stage, RoomStage::ActiveChooses),
+            "force-start should move directly to next round"
+        );
+        assert_eq!(
+            state.round, 3,
+            "force-start should advance round even if not everyone is ready"
+        );
+        assert_eq!(
+            state.active_player, 2,
+            "force-start should choose the lowest-count storyteller for the next round"
+        );
 
         Ok(())
     }
 
     #[tokio::test]
-    async fn room_state_exposes_current_delta_scores_for_result_stages() -> Result<()> {
+    async fn init_round_room_state_exposes_previous_results_preview() -> Result<()> {
         let room = test_room();
         {
             let mut state = room.state.write().await;
-            state.stage = RoomStage::Results;
-            state.storyteller_point_change = HashMap::from([("storyteller".into(), 3)]);
-            state.beauty_point_change = HashMap::from([("guesser".into(), 2)]);
+            add_player(&mut state, "a", 0);
+            add_player(&mut state, "b", 0);
+            add_player(&mut state, "c", 0);
+            add_player(&mut state, "d", 0);
+            state.stage = RoomStage::Voting;
+            state.round = 2;
             state.beauty_enabled = true;
+            state.player_order = vec!["a".into(), "b".into(), "c".into(), "d".into()];
+            state.active_player = 0;
+            state.player_to_clue_rating = HashMap::from([("b".into(), 2), ("c".into(), 3)]);
+            set_storyteller_count(&mut state, "a", 2);
+            set_storyteller_count(&mut state, "b", 1);
+            set_storyteller_count(&mut state, "c", 1);
+            set_storyteller_count(&mut state, "d", 3);
+            state.current_description = "clue".to_string();
+            state
+                .player_to_current_cards
+                .insert("a".into(), vec!["a-card".into()]);
+            state
+                .player_to_current_cards
+                .insert("b".into(), vec!["b-card".into()]);
+            state
+                .player_to_current_cards
+                .insert("c".into(), vec!["c-card".into()]);
+            state
+                .player_to_current_cards
+                .insert("d".into(), vec!["d-card".into()]);
+            state
+                .player_to_votes
+                .insert("b".into(), vec!["a-card".into()]);
+            state
+                .player_to_votes
+                .insert("c".into(), vec!["b-card".into()]);
+            state
+                .player_to_votes
+                .insert("d".into(), vec!["a-card".into()]);
+            state
+                .player_to_beauty_votes
+                .insert("a".into(), vec!["b-card".into()]);
+            state
+                .player_to_beauty_votes
+                .insert("b".into(), vec!["b-card".into()]);
+            state
+                .player_to_beauty_votes
+                .insert("c".into(), vec!["b-card".into()]);
+            state
+                .player_to_beauty_votes
+                .insert("d".into(), vec!["b-card".into()]);
+            state.beauty_voting_completed_this_round = true;
+            state.deck = (0..200).map(|i| format!("draw-{}", i)).collect();
+            room.init_results(&mut state)?;
+            room.init_round(&mut state).await?;
         }
 
         let state = room.state.write().await;
         match room.room_state(&state) {
             ServerMsg::RoomState {
-                current_delta_scores,
+                stage,
+                previous_dixit_results,
                 ..
             } => {
-                let current_delta_scores = current_delta_scores.ok_or_else(|| {
-                    anyhow!("expected current delta scores in Results room state")
-                })?;
                 assert!(
-                    matches!(current_delta_scores.stage, RoomStage::Results),
-                    "current delta scores should retain the result stage"
-                );
-                assert_eq!(
-                    current_delta_scores
-                        .point_change
-                        .get("storyteller")
-                        .copied(),
-                    Some(3),
-                    "current total deltas should include storyteller deltas"
-                );
-                assert_eq!(
-                    current_delta_scores.point_change.get("guesser").copied(),
-                    Some(2),
-                    "current total deltas should include beauty deltas"
-                );
-                assert_eq!(
-                    current_delta_scores
-                        .storyteller_point_change
-                        .get("storyteller")
-                        .copied(),
-                    Some(3),
-                    "current storyteller deltas should be included separately"
-                );
-                assert_eq!(
-                    current_delta_scores
-                        .beauty_point_change
-                        .get("guesser")
-                        .copied(),
-                    Some(2),
-                    "current beauty deltas should be included separately"
+                    matches!(stage, RoomStage::ActiveChooses),
+                    "round init should return to storyteller choosing"
                 );
+                match previous_dixit_results {
+                    Some(PreviousDixitResultsView::Results {
+                        active_card,
+                        center_cards,
+                        player_to_clue_rating,
+                        clue_rating_average,
+                        clue_rating_count,
+                        clue_rating_bonus,
+                        point_change,
+                        storyteller_point_change,
+                        beauty_point_change,
+                        ..
+                    }) => {
+                        assert_eq!(active_card, "a-card");
+                        assert_eq!(center_cards.len(), 4);
+                        assert_eq!(
+                            player_to_clue_rating.get("b").copied(),
+                            Some(2),
+                            "previous storyteller results preview should carry cached clue stars"
+                        );
+                        assert_eq!(
+                            clue_rating_average,
+                            Some(2.5),
+                            "previous storyteller results preview should carry the round clue-star average"
+                        );
+                        assert_eq!(
+                            clue_rating_count, 2,
+                            "previous storyteller results preview should carry the clue-star rating count"
+                        );
+                        assert_eq!(
+                            clue_rating_bonus, 2,
+                            "previous storyteller results preview should carry the clue-star bonus"
+                        );
+                        assert!(
+                            point_change.values().any(|delta| *delta != 0),
+                            "previous storyteller results preview should retain nonzero score deltas"
+                        );
+                        assert!(
+                            storyteller_point_change.values().any(|delta| *delta != 0),
+                            "previous storyteller results preview should retain nonzero storyteller deltas"
+                        );
+                        assert!(
+                            beauty_point_change.values().any(|delta| *delta != 0),
+                            "previous storyteller results preview should retain nonzero beauty deltas"
+                        );
+                    }
+                    other => {
+                        return Err(anyhow!(
+                        "expected previous Dixit results payload during ActiveChooses, got {:?}",
+                        other
+                    ))
+                    }
+                }
             }
             other => return Err(anyhow!("Expected RoomState, got {:?}", other)),
         }
@@ -20873,113 +24345,213 @@ alpha
     }
 
     #[tokio::test]
-    async fn active_chooses_stage_message_exposes_previous_results_payload() -> Result<()> {
+    async fn players_choose_room_state_exposes_previous_results_preview() -> Result<()> {
         let room = test_room();
         {
             let mut state = room.state.write().await;
             add_player(&mut state, "storyteller", 0);
-            state.stage = RoomStage::ActiveChooses;
-            state.player_order = vec!["storyteller".into()];
-            state.active_player = 0;
-            state
-                .player_hand
-                .insert("storyteller".into(), vec!["hand-card".into()]);
+            add_player(&mut state, "guesser", 0);
+            add_player(&mut state, "guesser2", 0);
+            state.stage = RoomStage::PlayersChoose;
             state.previous_dixit_results = Some(PreviousDixitResultsView::Results {
-                center_cards: vec!["a".into()],
+                center_cards: vec!["a".into(), "b".into(), "c".into()],
                 player_to_votes: HashMap::new(),
                 player_to_beauty_votes: HashMap::new(),
-                player_to_clue_rating: HashMap::new(),
-                clue_rating_average: None,
-                clue_rating_count: 0,
-                clue_rating_bonus: 0,
-                player_to_current_cards: HashMap::from([("storyteller".into(), vec!["a".into()])]),
+                player_to_clue_rating: HashMap::from([("guesser".into(), 2)]),
+                clue_rating_average: Some(2.0),
+                clue_rating_count: 1,
+                clue_rating_bonus: 1,
+                player_to_current_cards: HashMap::from([
+                    ("storyteller".into(), vec!["a".into()]),
+                    ("guesser".into(), vec!["b".into()]),
+                    ("guesser2".into(), vec!["c".into()]),
+                ]),
                 active_card: "a".into(),
                 beauty_results_display_mode: BeautyResultsDisplayMode::Combined,
-                point_change: HashMap::from([("storyteller".into(), 3)]),
-                storyteller_point_change: HashMap::from([("storyteller".into(), 3)]),
-                beauty_point_change: HashMap::new(),
+                point_change: HashMap::from([
+                    ("storyteller".into(), 3),
+                    ("guesser".into(), 4),
+                    ("guesser2".into(), 1),
+                ]),
+                storyteller_point_change: HashMap::from([
+                    ("storyteller".into(), 3),
+                    ("guesser".into(), 3),
+                ]),
+                beauty_point_change: HashMap::from([("guesser".into(), 1), ("guesser2".into(), 1)]),
                 beauty_vote_totals: HashMap::new(),
                 beauty_winning_cards: Vec::new(),
             });
         }
 
         let state = room.state.write().await;
-        match room.get_msg(Some("storyteller"), &state)? {
-            ServerMsg::StartRound {
+        match room.room_state(&state) {
+            ServerMsg::RoomState {
+                stage,
                 previous_dixit_results,
                 ..
-            } => match previous_dixit_results {
-                Some(PreviousDixitResultsView::Results { point_change, .. }) => {
-                    assert_eq!(
-                        point_change.get("storyteller").copied(),
-                        Some(3),
-                        "ActiveChooses stage payload should include previous result deltas"
-                    );
-                }
-                other => {
-                    return Err(anyhow!(
-                        "Expected previous results payload, got {:?}",
-                        other
-                    ))
+            } => {
+                assert!(
+                    matches!(stage, RoomStage::PlayersChoose),
+                    "room state should stay in PlayersChoose"
+                );
+                match previous_dixit_results {
+                    Some(PreviousDixitResultsView::Results {
+                        player_to_clue_rating,
+                        point_change,
+                        storyteller_point_change,
+                        beauty_point_change,
+                        ..
+                    }) => {
+                        assert_eq!(
+                            player_to_clue_rating.get("guesser").copied(),
+                            Some(2),
+                            "players choose preview should retain cached clue-star chips"
+                        );
+                        assert_eq!(
+                            point_change.get("guesser").copied(),
+                            Some(4),
+                            "players choose preview should retain prior-round total delta"
+                        );
+                        assert_eq!(
+                            storyteller_point_change.get("storyteller").copied(),
+                            Some(3),
+                            "players choose preview should retain prior-round storyteller delta"
+                        );
+                        assert_eq!(
+                            beauty_point_change.get("guesser2").copied(),
+                            Some(1),
+                            "players choose preview should retain prior-round beauty delta"
+                        );
+                    }
+                    other => {
+                        return Err(anyhow!(
+                            "expected previous storyteller results preview, got {:?}",
+                            other
+                        ))
+                    }
                 }
-            },
-            other => return Err(anyhow!("Expected StartRound, got {:?}", other)),
+            }
+            other => return Err(anyhow!("Expected RoomState, got {:?}", other)),
         }
 
         Ok(())
     }
 
     #[tokio::test]
-    async fn players_choose_stage_message_exposes_previous_results_payload() -> Result<()> {
+    async fn active_chooses_room_state_exposes_previous_beauty_results_clue_ratings() -> Result<()>
+    {
         let room = test_room();
         {
             let mut state = room.state.write().await;
             add_player(&mut state, "storyteller", 0);
             add_player(&mut state, "guesser", 0);
-            state.stage = RoomStage::PlayersChoose;
-            state.player_order = vec!["storyteller".into(), "guesser".into()];
-            state.active_player = 0;
-            state.current_description = "clue".into();
-            state
-                .player_hand
-                .insert("guesser".into(), vec!["hand-card".into()]);
+            add_player(&mut state, "guesser2", 0);
+            state.stage = RoomStage::ActiveChooses;
+            state.game_mode = GameMode::DixitPlus;
             state.previous_dixit_results = Some(PreviousDixitResultsView::BeautyResults {
-                center_cards: vec!["a".into()],
+                center_cards: vec!["a".into(), "b".into(), "c".into()],
                 player_to_beauty_votes: HashMap::new(),
-                player_to_clue_rating: HashMap::new(),
-                clue_rating_average: None,
-                clue_rating_count: 0,
-                clue_rating_bonus: 0,
-                player_to_current_cards: HashMap::from([("storyteller".into(), vec!["a".into()])]),
-                point_change: HashMap::from([("guesser".into(), 2)]),
+                player_to_clue_rating: HashMap::from([("guesser".into(), 3)]),
+                clue_rating_average: Some(3.0),
+                clue_rating_count: 1,
+                clue_rating_bonus: 2,
+                player_to_current_cards: HashMap::from([
+                    ("storyteller".into(), vec!["a".into()]),
+                    ("guesser".into(), vec!["b".into()]),
+                    ("guesser2".into(), vec!["c".into()]),
+                ]),
+                point_change: HashMap::new(),
                 beauty_vote_totals: HashMap::new(),
                 beauty_winning_cards: Vec::new(),
             });
         }
 
         let state = room.state.write().await;
-        match room.get_msg(Some("guesser"), &state)? {
-            ServerMsg::PlayersChoose {
+        match room.room_state(&state) {
+            ServerMsg::RoomState {
+                stage,
                 previous_dixit_results,
                 ..
-            } => match previous_dixit_results {
-                Some(PreviousDixitResultsView::BeautyResults { point_change, .. }) => {
-                    assert_eq!(
-                        point_change.get("guesser").copied(),
-                        Some(2),
-                        "PlayersChoose stage payload should include previous beauty-result deltas"
-                    );
+            } => {
+                assert!(
+                    matches!(stage, RoomStage::ActiveChooses),
+                    "room state should stay in ActiveChooses"
+                );
+                match previous_dixit_results {
+                    Some(PreviousDixitResultsView::BeautyResults {
+                        player_to_clue_rating,
+                        ..
+                    }) => {
+                        assert_eq!(
+                            player_to_clue_rating.get("guesser").copied(),
+                            Some(3),
+                            "beauty-results preview should still carry cached clue-star chips"
+                        );
+                    }
+                    other => {
+                        return Err(anyhow!(
+                            "expected previous beauty results preview, got {:?}",
+                            other
+                        ))
+                    }
                 }
-                other => return Err(anyhow!("Expected previous beauty payload, got {:?}", other)),
-            },
-            other => return Err(anyhow!("Expected PlayersChoose, got {:?}", other)),
+            }
+            other => return Err(anyhow!("Expected RoomState, got {:?}", other)),
         }
 
         Ok(())
     }
 
     #[tokio::test]
-    async fn moderator_force_start_next_round_skips_results_ready_wait() -> Result<()> {
+    async fn previous_beauty_results_preview_carries_clue_rating_summary() -> Result<()> {
+        let room = test_room();
+        let mut state = room.state.write().await;
+        add_player(&mut state, "storyteller", 0);
+        add_player(&mut state, "guesser", 0);
+        add_player(&mut state, "guesser2", 0);
+        state.player_order = vec!["storyteller".into(), "guesser".into(), "guesser2".into()];
+        state.active_player = 0;
+        state.player_to_clue_rating =
+            HashMap::from([("guesser".into(), 4), ("guesser2".into(), 5)]);
+        state
+            .player_to_current_cards
+            .insert("storyteller".into(), vec!["a".into()]);
+        state
+            .player_to_current_cards
+            .insert("guesser".into(), vec!["b".into()]);
+        state
+            .player_to_current_cards
+            .insert("guesser2".into(), vec!["c".into()]);
+
+        match room.previous_dixit_results_from_beauty_results(&state) {
+            PreviousDixitResultsView::BeautyResults {
+                clue_rating_average,
+                clue_rating_count,
+                clue_rating_bonus,
+                ..
+            } => {
+                assert_eq!(
+                    clue_rating_average,
+                    Some(4.5),
+                    "previous beauty results preview should carry the round clue-star average"
+                );
+                assert_eq!(
+                    clue_rating_count, 2,
+                    "previous beauty results preview should carry the clue-star rating count"
+                );
+                assert_eq!(
+                    clue_rating_bonus, 4,
+                    "previous beauty results preview should carry the clue-star bonus"
+                );
+            }
+            other => return Err(anyhow!("expected previous beauty results, got {:?}", other)),
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn eligible_final_results_ready_auto_starts_with_clicking_storyteller() -> Result<()> {
         let room = test_room();
         {
             let mut state = room.state.write().await;
@@ -20987,536 +24559,935 @@ alpha
             add_player(&mut state, "p2", 0);
             add_player(&mut state, "p3", 0);
             add_player(&mut state, "p4", 0);
-            set_storyteller_count(&mut state, "host", 3);
-            set_storyteller_count(&mut state, "p2", 4);
-            set_storyteller_count(&mut state, "p3", 1);
-            set_storyteller_count(&mut state, "p4", 2);
+            set_storyteller_count(&mut state, "host", 1);
+            set_storyteller_count(&mut state, "p2", 1);
+            set_storyteller_count(&mut state, "p3", 2);
+            set_storyteller_count(&mut state, "p4", 3);
             state.stage = RoomStage::Results;
             state.round = 2;
             state.player_order = vec!["host".into(), "p2".into(), "p3".into(), "p4".into()];
-            state.active_player = 1;
-            state.moderators.insert("host".to_string());
-            setup_connected_member(&mut state, "host", "t-host", 115);
+            state.active_player = 3;
+            setup_connected_member(&mut state, "p2", "t-p2", 117);
         }
 
-        room.handle_client_msg("host", 115, to_ws(ClientMsg::ForceStartNextRound {}))
+        room.handle_client_msg("p2", 117, to_ws(ClientMsg::Ready {}))
             .await?;
 
         let state = room.state.read().await;
         assert!(
             matches!(state.stage, RoomStage::ActiveChooses),
-            "force-start should move directly to next round"
+            "eligible final-results ready should start the next round immediately"
         );
+        assert_eq!(state.round, 3, "eligible click should advance the round");
         assert_eq!(
-            state.round, 3,
-            "force-start should advance round even if not everyone is ready"
+            state.player_order[state.active_player], "p2",
+            "first eligible click should win the storyteller tie-break"
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn non_eligible_final_results_ready_does_not_auto_start_round() -> Result<()> {
+        let room = test_room();
+        {
+            let mut state = room.state.write().await;
+            add_player(&mut state, "host", 0);
+            add_player(&mut state, "p2", 0);
+            add_player(&mut state, "p3", 0);
+            add_player(&mut state, "p4", 0);
+            set_storyteller_count(&mut state, "host", 1);
+            set_storyteller_count(&mut state, "p2", 1);
+            set_storyteller_count(&mut state, "p3", 2);
+            set_storyteller_count(&mut state, "p4", 3);
+            state.stage = RoomStage::Results;
+            state.round = 2;
+            state.player_order = vec!["host".into(), "p2".into(), "p3".into(), "p4".into()];
+            state.active_player = 0;
+            setup_connected_member(&mut state, "p4", "t-p4", 118);
+        }
+
+        room.handle_client_msg("p4", 118, to_ws(ClientMsg::Ready {}))
+            .await?;
+
+        let state = room.state.read().await;
+        assert!(
+            matches!(state.stage, RoomStage::Results),
+            "non-eligible ready should leave the room on results"
         );
         assert_eq!(
-            state.active_player, 2,
-            "force-start should choose the lowest-count storyteller for the next round"
+            state.round, 2,
+            "non-eligible ready should not advance the round"
+        );
+        assert!(
+            state
+                .players
+                .get("p4")
+                .map(|player| player.ready)
+                .unwrap_or(false),
+            "non-eligible ready should still mark the player ready"
         );
 
         Ok(())
     }
 
     #[tokio::test]
-    async fn init_round_room_state_exposes_previous_results_preview() -> Result<()> {
-        let room = test_room();
+    async fn switching_to_stella_resets_win_condition_to_fixed_rounds_default() -> Result<()> {
+        let room = test_room_with_condition(WinCondition::Points { target_points: 12 });
+        {
+            let mut state = room.state.write().await;
+            add_player(&mut state, "host", 0);
+            state.moderators.insert("host".to_string());
+            setup_connected_member(&mut state, "host", "t-host", 200);
+        }
+
+        room.handle_client_msg(
+            "host",
+            200,
+            to_ws(ClientMsg::SetGameMode {
+                game_mode: GameMode::Stella,
+            }),
+        )
+        .await?;
+
+        let state = room.state.read().await;
+        assert_eq!(state.game_mode, GameMode::Stella);
+        assert_eq!(state.win_condition, DEFAULT_STELLA_WIN_CONDITION);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn switching_to_dixit_resets_win_condition_to_single_cycle_default() -> Result<()> {
+        let room = test_room_with_condition(DEFAULT_STELLA_WIN_CONDITION);
+        {
+            let mut state = room.state.write().await;
+            add_player(&mut state, "host", 0);
+            state.game_mode = GameMode::Stella;
+            state.moderators.insert("host".to_string());
+            setup_connected_member(&mut state, "host", "t-host", 201);
+        }
+
+        room.handle_client_msg(
+            "host",
+            201,
+            to_ws(ClientMsg::SetGameMode {
+                game_mode: GameMode::DixitPlus,
+            }),
+        )
+        .await?;
+
+        let state = room.state.read().await;
+        assert_eq!(state.game_mode, GameMode::DixitPlus);
+        assert_eq!(state.win_condition, DEFAULT_DIXIT_WIN_CONDITION);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn stella_rejects_cycle_win_condition() -> Result<()> {
+        let room = test_room_with_condition(DEFAULT_STELLA_WIN_CONDITION);
         {
             let mut state = room.state.write().await;
-            add_player(&mut state, "a", 0);
-            add_player(&mut state, "b", 0);
-            add_player(&mut state, "c", 0);
-            add_player(&mut state, "d", 0);
-            state.stage = RoomStage::Voting;
-            state.round = 2;
-            state.beauty_enabled = true;
-            state.player_order = vec!["a".into(), "b".into(), "c".into(), "d".into()];
-            state.active_player = 0;
-            state.player_to_clue_rating = HashMap::from([("b".into(), 2), ("c".into(), 3)]);
-            set_storyteller_count(&mut state, "a", 2);
-            set_storyteller_count(&mut state, "b", 1);
-            set_storyteller_count(&mut state, "c", 1);
-            set_storyteller_count(&mut state, "d", 3);
-            state.current_description = "clue".to_string();
-            state
-                .player_to_current_cards
-                .insert("a".into(), vec!["a-card".into()]);
-            state
-                .player_to_current_cards
-                .insert("b".into(), vec!["b-card".into()]);
-            state
-                .player_to_current_cards
-                .insert("c".into(), vec!["c-card".into()]);
-            state
-                .player_to_current_cards
-                .insert("d".into(), vec!["d-card".into()]);
-            state
-                .player_to_votes
-                .insert("b".into(), vec!["a-card".into()]);
-            state
-                .player_to_votes
-                .insert("c".into(), vec!["b-card".into()]);
-            state
-                .player_to_votes
-                .insert("d".into(), vec!["a-card".into()]);
-            state
-                .player_to_beauty_votes
-                .insert("a".into(), vec!["b-card".into()]);
-            state
-                .player_to_beauty_votes
-                .insert("b".into(), vec!["b-card".into()]);
-            state
-                .player_to_beauty_votes
-                .insert("c".into(), vec!["b-card".into()]);
-            state
-                .player_to_beauty_votes
-                .insert("d".into(), vec!["b-card".into()]);
-            state.beauty_voting_completed_this_round = true;
-            state.deck = (0..200).map(|i| format!("draw-{}", i)).collect();
-            room.init_results(&mut state)?;
-            room.init_round(&mut state).await?;
+            add_player(&mut state, "host", 0);
+            state.game_mode = GameMode::Stella;
+            state.moderators.insert("host".to_string());
+            setup_connected_member(&mut state, "host", "t-host", 202);
         }
 
-        let state = room.state.write().await;
-        match room.room_state(&state) {
-            ServerMsg::RoomState {
-                stage,
-                previous_dixit_results,
-                ..
-            } => {
-                assert!(
-                    matches!(stage, RoomStage::ActiveChooses),
-                    "round init should return to storyteller choosing"
-                );
-                match previous_dixit_results {
-                    Some(PreviousDixitResultsView::Results {
-                        active_card,
-                        center_cards,
-                        player_to_clue_rating,
-                        clue_rating_average,
-                        clue_rating_count,
-                        clue_rating_bonus,
-                        point_change,
-                        storyteller_point_change,
-                        beauty_point_change,
-                        ..
-                    }) => {
-                        assert_eq!(active_card, "a-card");
-                        assert_eq!(center_cards.len(), 4);
-                        assert_eq!(
-                            player_to_clue_rating.get("b").copied(),
-                            Some(2),
-                            "previous storyteller results preview should carry cached clue stars"
-                        );
-                        assert_eq!(
-                            clue_rating_average,
-                            Some(2.5),
-                            "previous storyteller results preview should carry the round clue-star average"
-                        );
-                        assert_eq!(
-                            clue_rating_count, 2,
-                            "previous storyteller results preview should carry the clue-star rating count"
-                        );
-                        assert_eq!(
-                            clue_rating_bonus, 2,
-                            "previous storyteller results preview should carry the clue-star bonus"
-                        );
-                        assert!(
-                            point_change.values().any(|delta| *delta != 0),
-                            "previous storyteller results preview should retain nonzero score deltas"
-                        );
-                        assert!(
-                            storyteller_point_change.values().any(|delta| *delta != 0),
-                            "previous storyteller results preview should retain nonzero storyteller deltas"
-                        );
-                        assert!(
-                            beauty_point_change.values().any(|delta| *delta != 0),
-                            "previous storyteller results preview should retain nonzero beauty deltas"
-                        );
-                    }
-                    other => {
-                        return Err(anyhow!(
-                        "expected previous Dixit results payload during ActiveChooses, got {:?}",
-                        other
-                    ))
-                    }
-                }
-            }
-            other => return Err(anyhow!("Expected RoomState, got {:?}", other)),
-        }
+        room.handle_client_msg(
+            "host",
+            202,
+            to_ws(ClientMsg::SetWinCondition {
+                win_condition: WinCondition::Cycles { target_cycles: 2 },
+            }),
+        )
+        .await?;
+
+        let state = room.state.read().await;
+        assert_eq!(state.win_condition, DEFAULT_STELLA_WIN_CONDITION);
 
         Ok(())
     }
 
     #[tokio::test]
-    async fn players_choose_room_state_exposes_previous_results_preview() -> Result<()> {
+    async fn stella_submission_respects_configured_selection_minimum() -> Result<()> {
         let room = test_room();
         {
             let mut state = room.state.write().await;
-            add_player(&mut state, "storyteller", 0);
-            add_player(&mut state, "guesser", 0);
-            add_player(&mut state, "guesser2", 0);
-            state.stage = RoomStage::PlayersChoose;
-            state.previous_dixit_results = Some(PreviousDixitResultsView::Results {
-                center_cards: vec!["a".into(), "b".into(), "c".into()],
-                player_to_votes: HashMap::new(),
-                player_to_beauty_votes: HashMap::new(),
-                player_to_clue_rating: HashMap::from([("guesser".into(), 2)]),
-                clue_rating_average: Some(2.0),
-                clue_rating_count: 1,
-                clue_rating_bonus: 1,
-                player_to_current_cards: HashMap::from([
-                    ("storyteller".into(), vec!["a".into()]),
-                    ("guesser".into(), vec!["b".into()]),
-                    ("guesser2".into(), vec!["c".into()]),
-                ]),
-                active_card: "a".into(),
-                beauty_results_display_mode: BeautyResultsDisplayMode::Combined,
-                point_change: HashMap::from([
-                    ("storyteller".into(), 3),
-                    ("guesser".into(), 4),
-                    ("guesser2".into(), 1),
-                ]),
-                storyteller_point_change: HashMap::from([
-                    ("storyteller".into(), 3),
-                    ("guesser".into(), 3),
-                ]),
-                beauty_point_change: HashMap::from([("guesser".into(), 1), ("guesser2".into(), 1)]),
-                beauty_vote_totals: HashMap::new(),
-                beauty_winning_cards: Vec::new(),
-            });
+            add_player(&mut state, "a", 0);
+            add_player(&mut state, "b", 0);
+            add_player(&mut state, "c", 0);
+            state.game_mode = GameMode::Stella;
+            state.stage = RoomStage::StellaAssociate;
+            state.player_order = vec!["a".into(), "b".into(), "c".into()];
+            state.stella_board_cards = vec![
+                "card-1".into(),
+                "card-2".into(),
+                "card-3".into(),
+                "card-4".into(),
+            ];
+            state.stella_selection_min = 3;
+            state.stella_selection_max = 4;
+            setup_connected_member(&mut state, "a", "t-a", 601);
         }
 
-        let state = room.state.write().await;
-        match room.room_state(&state) {
-            ServerMsg::RoomState {
-                stage,
-                previous_dixit_results,
-                ..
-            } => {
-                assert!(
-                    matches!(stage, RoomStage::PlayersChoose),
-                    "room state should stay in PlayersChoose"
-                );
-                match previous_dixit_results {
-                    Some(PreviousDixitResultsView::Results {
-                        player_to_clue_rating,
-                        point_change,
-                        storyteller_point_change,
-                        beauty_point_change,
-                        ..
-                    }) => {
-                        assert_eq!(
-                            player_to_clue_rating.get("guesser").copied(),
-                            Some(2),
-                            "players choose preview should retain cached clue-star chips"
-                        );
-                        assert_eq!(
-                            point_change.get("guesser").copied(),
-                            Some(4),
-                            "players choose preview should retain prior-round total delta"
-                        );
-                        assert_eq!(
-                            storyteller_point_change.get("storyteller").copied(),
-                            Some(3),
-                            "players choose preview should retain prior-round storyteller delta"
-                        );
-                        assert_eq!(
-                            beauty_point_change.get("guesser2").copied(),
-                            Some(1),
-                            "players choose preview should retain prior-round beauty delta"
-                        );
-                    }
-                    other => {
-                        return Err(anyhow!(
-                            "expected previous storyteller results preview, got {:?}",
-                            other
-                        ))
-                    }
-                }
-            }
-            other => return Err(anyhow!("Expected RoomState, got {:?}", other)),
+        room.handle_client_msg(
+            "a",
+            601,
+            to_ws(ClientMsg::SubmitStellaSelection {
+                cards: vec!["card-1".to_string()],
+            }),
+        )
+        .await?;
+
+        let state = room.state.read().await;
+        assert!(
+            !state.stella_player_selections.contains_key("a"),
+            "submission below the configured Stella minimum should be rejected"
+        );
+        assert!(
+            !state.players.get("a").unwrap().ready,
+            "rejected Stella submission should not ready the player"
+        );
+        assert!(
+            matches!(state.stage, RoomStage::StellaAssociate),
+            "invalid Stella submission should not advance the stage"
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn stella_submission_can_overwrite_a_players_locked_selection() -> Result<()> {
+        let room = test_room();
+        {
+            let mut state = room.state.write().await;
+            add_player(&mut state, "a", 0);
+            add_player(&mut state, "b", 0);
+            add_player(&mut state, "c", 0);
+            state.game_mode = GameMode::Stella;
+            state.stage = RoomStage::StellaAssociate;
+            state.player_order = vec!["a".into(), "b".into(), "c".into()];
+            state.stella_board_cards = vec![
+                "card-1".into(),
+                "card-2".into(),
+                "card-3".into(),
+                "card-4".into(),
+            ];
+            state.stella_selection_min = 1;
+            state.stella_selection_max = 4;
+            setup_connected_member(&mut state, "a", "t-a", 611);
         }
 
+        room.handle_client_msg(
+            "a",
+            611,
+            to_ws(ClientMsg::SubmitStellaSelection {
+                cards: vec!["card-1".to_string()],
+            }),
+        )
+        .await?;
+        room.handle_client_msg(
+            "a",
+            611,
+            to_ws(ClientMsg::SubmitStellaSelection {
+                cards: vec!["card-2".to_string(), "card-3".to_string()],
+            }),
+        )
+        .await?;
+
+        let state = room.state.read().await;
+        assert_eq!(
+            state.stella_player_selections.get("a"),
+            Some(&vec!["card-2".to_string(), "card-3".to_string()]),
+            "re-locking Stella selections should overwrite the player's prior saved picks"
+        );
+        assert!(
+            state.players.get("a").unwrap().ready,
+            "the player should remain ready after updating a locked Stella selection"
+        );
+
         Ok(())
     }
 
     #[tokio::test]
-    async fn active_chooses_room_state_exposes_previous_beauty_results_clue_ratings() -> Result<()>
+    async fn stella_associate_allows_card_number_overlay_changes_but_reveal_does_not() -> Result<()>
     {
         let room = test_room();
         {
             let mut state = room.state.write().await;
-            add_player(&mut state, "storyteller", 0);
-            add_player(&mut state, "guesser", 0);
-            add_player(&mut state, "guesser2", 0);
-            state.stage = RoomStage::ActiveChooses;
-            state.game_mode = GameMode::DixitPlus;
-            state.previous_dixit_results = Some(PreviousDixitResultsView::BeautyResults {
-                center_cards: vec!["a".into(), "b".into(), "c".into()],
-                player_to_beauty_votes: HashMap::new(),
-                player_to_clue_rating: HashMap::from([("guesser".into(), 3)]),
-                clue_rating_average: Some(3.0),
-                clue_rating_count: 1,
-                clue_rating_bonus: 2,
-                player_to_current_cards: HashMap::from([
-                    ("storyteller".into(), vec!["a".into()]),
-                    ("guesser".into(), vec!["b".into()]),
-                    ("guesser2".into(), vec!["c".into()]),
-                ]),
-                point_change: HashMap::new(),
-                beauty_vote_totals: HashMap::new(),
-                beauty_winning_cards: Vec::new(),
-            });
+            add_player(&mut state, "host", 0);
+            add_player(&mut state, "p2", 0);
+            add_player(&mut state, "p3", 0);
+            state.game_mode = GameMode::Stella;
+            state.stage = RoomStage::StellaAssociate;
+            state.show_voting_card_numbers = false;
+            state.moderators.insert("host".to_string());
+            setup_connected_member(&mut state, "host", "t-host", 612);
         }
 
-        let state = room.state.write().await;
-        match room.room_state(&state) {
-            ServerMsg::RoomState {
-                stage,
-                previous_dixit_results,
-                ..
-            } => {
-                assert!(
-                    matches!(stage, RoomStage::ActiveChooses),
-                    "room state should stay in ActiveChooses"
-                );
-                match previous_dixit_results {
-                    Some(PreviousDixitResultsView::BeautyResults {
-                        player_to_clue_rating,
-                        ..
-                    }) => {
-                        assert_eq!(
-                            player_to_clue_rating.get("guesser").copied(),
-                            Some(3),
-                            "beauty-results preview should still carry cached clue-star chips"
-                        );
-                    }
-                    other => {
-                        return Err(anyhow!(
-                            "expected previous beauty results preview, got {:?}",
-                            other
-                        ))
-                    }
-                }
-            }
-            other => return Err(anyhow!("Expected RoomState, got {:?}", other)),
+        room.handle_client_msg(
+            "host",
+            612,
+            to_ws(ClientMsg::SetShowVotingCardNumbers { enabled: true }),
+        )
+        .await?;
+
+        {
+            let state = room.state.read().await;
+            assert!(
+                state.show_voting_card_numbers,
+                "Stella associate should allow enabling card number overlays"
+            );
+        }
+
+        {
+            let mut state = room.state.write().await;
+            state.stage = RoomStage::StellaReveal;
         }
 
+        room.handle_client_msg(
+            "host",
+            612,
+            to_ws(ClientMsg::SetShowVotingCardNumbers { enabled: false }),
+        )
+        .await?;
+
+        let state = room.state.read().await;
+        assert!(
+            state.show_voting_card_numbers,
+            "Stella reveal should keep card number overlays unchanged once associate is over"
+        );
+
         Ok(())
     }
 
     #[tokio::test]
-    async fn previous_beauty_results_preview_carries_clue_rating_summary() -> Result<()> {
+    async fn redraw_stella_board_preserves_existing_board_when_redraw_fails() -> Result<()> {
         let room = test_room();
         let mut state = room.state.write().await;
-        add_player(&mut state, "storyteller", 0);
-        add_player(&mut state, "guesser", 0);
-        add_player(&mut state, "guesser2", 0);
-        state.player_order = vec!["storyteller".into(), "guesser".into(), "guesser2".into()];
-        state.active_player = 0;
-        state.player_to_clue_rating =
-            HashMap::from([("guesser".into(), 4), ("guesser2".into(), 5)]);
-        state
-            .player_to_current_cards
-            .insert("storyteller".into(), vec!["a".into()]);
-        state
-            .player_to_current_cards
-            .insert("guesser".into(), vec!["b".into()]);
-        state
-            .player_to_current_cards
-            .insert("guesser2".into(), vec!["c".into()]);
 
-        match room.previous_dixit_results_from_beauty_results(&state) {
-            PreviousDixitResultsView::BeautyResults {
-                clue_rating_average,
-                clue_rating_count,
-                clue_rating_bonus,
+        state.game_mode = GameMode::Stella;
+        state.stage = RoomStage::StellaAssociate;
+        state.win_condition = WinCondition::CardsFinish;
+        state.stella_board_size = 4;
+        state.stella_board_cards = vec![
+            "board-1".into(),
+            "board-2".into(),
+            "board-3".into(),
+            "board-4".into(),
+        ];
+        state.deck = vec!["deck-1".into()];
+        state.discard_pile = vec!["discard-1".into()];
+
+        let err = room.redraw_stella_board(&mut state).unwrap_err();
+
+        assert!(
+            err.to_string().contains("Not enough cards"),
+            "redraw failure should surface the deck exhaustion error"
+        );
+        assert_eq!(
+            state.stella_board_cards,
+            vec![
+                "board-1".to_string(),
+                "board-2".to_string(),
+                "board-3".to_string(),
+                "board-4".to_string(),
+            ],
+            "failed redraws must leave the existing board intact"
+        );
+        assert_eq!(
+            state.deck,
+            vec!["deck-1".to_string()],
+            "failed redraws must restore the prior deck state"
+        );
+        assert_eq!(
+            state.discard_pile,
+            vec!["discard-1".to_string()],
+            "failed redraws must not leak the current board into the discard pile"
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn stella_custom_word_pack_edits_are_accepted_midgame_and_saved_for_reselection(
+    ) -> Result<()> {
+        let room = test_room();
+        {
+            let mut state = room.state.write().await;
+            add_player(&mut state, "host", 0);
+            add_player(&mut state, "p2", 0);
+            add_player(&mut state, "p3", 0);
+            state.game_mode = GameMode::Stella;
+            state.stage = RoomStage::StellaAssociate;
+            state.player_order = vec!["host".into(), "p2".into(), "p3".into()];
+            state.stella_clue_word = "before".into();
+            state.stella_word_pack = vec!["before".into(), "after".into()];
+            state.stella_board_cards = vec![
+                "card-1".into(),
+                "card-2".into(),
+                "card-3".into(),
+                "card-4".into(),
+            ];
+            state
+                .stella_player_selections
+                .insert("host".into(), vec!["card-1".into(), "card-3".into()]);
+            state.players.get_mut("host").unwrap().ready = true;
+            state.moderators.insert("host".to_string());
+            setup_connected_member(&mut state, "host", "t-host", 612_001);
+        }
+
+        room.handle_client_msg(
+            "host",
+            612_001,
+            to_ws(ClientMsg::SetStellaWordPack {
+                words: "custom alpha\ncustom beta".to_string(),
+            }),
+        )
+        .await?;
+
+        let state = room.state.write().await;
+        assert_eq!(
+            state.stella_word_pack,
+            vec!["custom alpha".to_string(), "custom beta".to_string()],
+            "midgame custom edits should update the active pack instead of being dropped"
+        );
+        assert_eq!(
+            state.stella_player_selections.get("host"),
+            Some(&vec!["card-1".to_string(), "card-3".to_string()]),
+            "changing the pack mid-associate should not wipe current submissions"
+        );
+        assert!(
+            state.players.get("host").unwrap().ready,
+            "changing the pack mid-associate should preserve ready flags"
+        );
+
+        match room.room_state(&state) {
+            ServerMsg::RoomState {
+                stella_word_pack_preset_names,
+                stella_selected_word_pack_name,
+                stella_word_pack_is_unsaved,
                 ..
             } => {
-                assert_eq!(
-                    clue_rating_average,
-                    Some(4.5),
-                    "previous beauty results preview should carry the round clue-star average"
+                assert!(
+                    stella_word_pack_preset_names
+                        .contains(&CURRENT_GAME_STELLA_WORD_PACK_NAME.to_string()),
+                    "the current game's custom pack should remain selectable for the rest of the game"
                 );
                 assert_eq!(
-                    clue_rating_count, 2,
-                    "previous beauty results preview should carry the clue-star rating count"
+                    stella_selected_word_pack_name,
+                    Some(CURRENT_GAME_STELLA_WORD_PACK_NAME.to_string()),
+                    "room state should treat the saved game pack as the active preset"
                 );
-                assert_eq!(
-                    clue_rating_bonus, 4,
-                    "previous beauty results preview should carry the clue-star bonus"
+                assert!(
+                    !stella_word_pack_is_unsaved,
+                    "game-saved custom packs should be reselectable presets, not one-off unsaved state"
                 );
             }
-            other => return Err(anyhow!("expected previous beauty results, got {:?}", other)),
+            _ => return Err(anyhow!("Expected RoomState")),
         }
 
         Ok(())
     }
 
     #[tokio::test]
-    async fn eligible_final_results_ready_auto_starts_with_clicking_storyteller() -> Result<()> {
+    async fn stella_custom_word_pack_from_lobby_can_be_reselected_midgame() -> Result<()> {
         let room = test_room();
         {
             let mut state = room.state.write().await;
             add_player(&mut state, "host", 0);
             add_player(&mut state, "p2", 0);
             add_player(&mut state, "p3", 0);
-            add_player(&mut state, "p4", 0);
-            set_storyteller_count(&mut state, "host", 1);
-            set_storyteller_count(&mut state, "p2", 1);
-            set_storyteller_count(&mut state, "p3", 2);
-            set_storyteller_count(&mut state, "p4", 3);
-            state.stage = RoomStage::Results;
-            state.round = 2;
-            state.player_order = vec!["host".into(), "p2".into(), "p3".into(), "p4".into()];
-            state.active_player = 3;
-            setup_connected_member(&mut state, "p2", "t-p2", 117);
+            state.game_mode = GameMode::Stella;
+            state.stage = RoomStage::Joining;
+            state.stella_word_pack = vec!["custom alpha".into(), "custom beta".into()];
+            state.moderators.insert("host".to_string());
+            setup_connected_member(&mut state, "host", "t-host", 612_002);
+
+            room.init_stella_round(&mut state).await?;
         }
 
-        room.handle_client_msg("p2", 117, to_ws(ClientMsg::Ready {}))
-            .await?;
+        {
+            let state = room.state.write().await;
+            match room.room_state(&state) {
+                ServerMsg::RoomState {
+                    stella_word_pack_preset_names,
+                    stella_selected_word_pack_name,
+                    stella_word_pack_is_unsaved,
+                    stella_word_pack_words,
+                    ..
+                } => {
+                    assert!(
+                        stella_word_pack_preset_names
+                            .contains(&CURRENT_GAME_STELLA_WORD_PACK_NAME.to_string()),
+                        "starting a game with a custom pack should save it for that game's preset picker"
+                    );
+                    assert_eq!(
+                        stella_selected_word_pack_name,
+                        Some(CURRENT_GAME_STELLA_WORD_PACK_NAME.to_string()),
+                    );
+                    assert!(!stella_word_pack_is_unsaved);
+                    assert!(
+                        stella_word_pack_words.is_empty(),
+                        "midgame room state should still avoid echoing the raw custom word list"
+                    );
+                }
+                _ => return Err(anyhow!("Expected RoomState")),
+            }
+        }
+
+        room.handle_client_msg(
+            "host",
+            612_002,
+            to_ws(ClientMsg::SetStellaWordPackPreset {
+                name: "Delta".to_string(),
+            }),
+        )
+        .await?;
+        room.handle_client_msg(
+            "host",
+            612_002,
+            to_ws(ClientMsg::SetStellaWordPackPreset {
+                name: CURRENT_GAME_STELLA_WORD_PACK_NAME.to_string(),
+            }),
+        )
+        .await?;
 
         let state = room.state.read().await;
-        assert!(
-            matches!(state.stage, RoomStage::ActiveChooses),
-            "eligible final-results ready should start the next round immediately"
+        assert_eq!(
+            state.stella_word_pack,
+            vec!["custom alpha".to_string(), "custom beta".to_string()],
+            "the saved game-local pack should remain available after switching away and back"
         );
-        assert_eq!(state.round, 3, "eligible click should advance the round");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn stella_custom_word_pack_size_is_limited() -> Result<()> {
+        let room = test_room();
+        let mut socket_rx = {
+            let mut state = room.state.write().await;
+            add_player(&mut state, "host", 0);
+            state.game_mode = GameMode::Stella;
+            state.stage = RoomStage::Joining;
+            state.moderators.insert("host".to_string());
+            setup_connected_member(&mut state, "host", "t-host", 612_003);
+            attach_test_socket(&mut state, "host")
+        };
+
+        let oversized = "a".repeat(MAX_CUSTOM_STELLA_WORD_PACK_BYTES + 1);
+        room.handle_client_msg(
+            "host",
+            612_003,
+            to_ws(ClientMsg::SetStellaWordPack { words: oversized }),
+        )
+        .await?;
+
+        let error = socket_rx
+            .recv()
+            .await
+            .ok_or_else(|| anyhow!("Expected size-limit error"))?;
+        match error {
+            ServerMsg::ErrorMsg(message) => assert_eq!(
+                message, "Word pack must be at most 3 MB",
+                "oversized custom packs should be rejected before the server stores them"
+            ),
+            other => {
+                return Err(anyhow!(
+                    "Expected size-limit error message, got {:?}",
+                    other
+                ))
+            }
+        }
+
+        let state = room.state.read().await;
         assert_eq!(
-            state.player_order[state.active_player], "p2",
-            "first eligible click should win the storyteller tie-break"
+            state.stella_word_pack,
+            (*test_default_stella_word_pack()).clone(),
+            "rejecting an oversized pack should leave the active pack unchanged"
         );
 
         Ok(())
     }
 
     #[tokio::test]
-    async fn non_eligible_final_results_ready_does_not_auto_start_round() -> Result<()> {
+    async fn stella_saved_game_word_pack_is_cleared_when_the_game_ends() -> Result<()> {
+        let room = test_room();
+        let mut state = room.state.write().await;
+
+        state.game_mode = GameMode::Stella;
+        state.stella_saved_game_word_pack = Some(vec!["custom alpha".into()]);
+
+        room.set_stage(&mut state, RoomStage::End);
+
+        assert!(
+            state.stella_saved_game_word_pack.is_none(),
+            "game-scoped custom packs should be garbage-collected after the game ends"
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn reset_stella_clue_clears_ready_flags_before_new_submissions() -> Result<()> {
         let room = test_room();
         {
             let mut state = room.state.write().await;
             add_player(&mut state, "host", 0);
             add_player(&mut state, "p2", 0);
             add_player(&mut state, "p3", 0);
-            add_player(&mut state, "p4", 0);
-            set_storyteller_count(&mut state, "host", 1);
-            set_storyteller_count(&mut state, "p2", 1);
-            set_storyteller_count(&mut state, "p3", 2);
-            set_storyteller_count(&mut state, "p4", 3);
-            state.stage = RoomStage::Results;
-            state.round = 2;
-            state.player_order = vec!["host".into(), "p2".into(), "p3".into(), "p4".into()];
+            state.game_mode = GameMode::Stella;
+            state.stage = RoomStage::StellaAssociate;
+            state.player_order = vec!["host".into(), "p2".into(), "p3".into()];
             state.active_player = 0;
-            setup_connected_member(&mut state, "p4", "t-p4", 118);
+            state.stella_clue_word = "before".into();
+            state.stella_word_pack = vec!["before".into(), "after".into()];
+            state.stella_board_cards = vec![
+                "card-1".into(),
+                "card-2".into(),
+                "card-3".into(),
+                "card-4".into(),
+                "card-5".into(),
+                "card-6".into(),
+            ];
+            state.stella_selection_min = 1;
+            state.stella_selection_max = 6;
+            state.moderators.insert("host".to_string());
+            setup_connected_member(&mut state, "host", "t-host", 602);
+            setup_connected_member(&mut state, "p2", "t-p2", 603);
+            setup_connected_member(&mut state, "p3", "t-p3", 604);
         }
 
-        room.handle_client_msg("p4", 118, to_ws(ClientMsg::Ready {}))
+        room.handle_client_msg(
+            "host",
+            602,
+            to_ws(ClientMsg::SubmitStellaSelection {
+                cards: vec!["card-1".to_string()],
+            }),
+        )
+        .await?;
+        room.handle_client_msg(
+            "p2",
+            603,
+            to_ws(ClientMsg::SubmitStellaSelection {
+                cards: vec!["card-2".to_string()],
+            }),
+        )
+        .await?;
+        room.handle_client_msg("host", 602, to_ws(ClientMsg::ResetStellaClue {}))
             .await?;
+        room.handle_client_msg(
+            "p3",
+            604,
+            to_ws(ClientMsg::SubmitStellaSelection {
+                cards: vec!["card-3".to_string()],
+            }),
+        )
+        .await?;
 
         let state = room.state.read().await;
         assert!(
-            matches!(state.stage, RoomStage::Results),
-            "non-eligible ready should leave the room on results"
+            matches!(state.stage, RoomStage::StellaAssociate),
+            "a single post-reset Stella submission should not advance to reveal"
         );
         assert_eq!(
-            state.round, 2,
-            "non-eligible ready should not advance the round"
+            state.stella_player_selections.get("p3"),
+            Some(&vec!["card-3".to_string()]),
+            "post-reset round state should only contain fresh submissions"
         );
         assert!(
-            state
-                .players
-                .get("p4")
-                .map(|player| player.ready)
-                .unwrap_or(false),
-            "non-eligible ready should still mark the player ready"
+            !state.players.get("host").unwrap().ready && !state.players.get("p2").unwrap().ready,
+            "resetting the Stella clue must clear earlier ready flags"
+        );
+        assert!(
+            state.players.get("p3").unwrap().ready,
+            "the new submission should still ready the submitting player"
         );
 
         Ok(())
     }
 
     #[tokio::test]
-    async fn switching_to_stella_resets_win_condition_to_fixed_rounds_default() -> Result<()> {
-        let room = test_room_with_condition(WinCondition::Points { target_points: 12 });
+    async fn stella_word_pack_change_mid_associate_preserves_current_round_state() -> Result<()> {
+        let room = test_room();
         {
             let mut state = room.state.write().await;
             add_player(&mut state, "host", 0);
+            add_player(&mut state, "p2", 0);
+            add_player(&mut state, "p3", 0);
+            state.game_mode = GameMode::Stella;
+            state.stage = RoomStage::StellaAssociate;
+            state.player_order = vec!["host".into(), "p2".into(), "p3".into()];
+            state.active_player = 0;
+            state.stella_clue_word = "before".into();
+            state.stella_word_pack = vec!["before".into(), "after".into()];
+            state.stella_board_cards = vec![
+                "card-1".into(),
+                "card-2".into(),
+                "card-3".into(),
+                "card-4".into(),
+            ];
+            state
+                .stella_player_selections
+                .insert("host".into(), vec!["card-1".into(), "card-3".into()]);
+            state
+                .stella_player_selection_counts
+                .insert("host".into(), 2);
+            state.players.get_mut("host").unwrap().ready = true;
+            state.players.get_mut("p2").unwrap().ready = true;
             state.moderators.insert("host".to_string());
-            setup_connected_member(&mut state, "host", "t-host", 200);
+            setup_connected_member(&mut state, "host", "t-host", 605);
         }
 
         room.handle_client_msg(
             "host",
-            200,
-            to_ws(ClientMsg::SetGameMode {
-                game_mode: GameMode::Stella,
+            605,
+            to_ws(ClientMsg::SetStellaWordPackPreset {
+                name: "Delta".to_string(),
             }),
         )
         .await?;
 
         let state = room.state.read().await;
-        assert_eq!(state.game_mode, GameMode::Stella);
-        assert_eq!(state.win_condition, DEFAULT_STELLA_WIN_CONDITION);
+        assert!(matches!(state.stage, RoomStage::StellaAssociate));
+        assert_eq!(state.stella_clue_word, "before");
+        assert_eq!(
+            state.stella_board_cards,
+            vec![
+                "card-1".to_string(),
+                "card-2".to_string(),
+                "card-3".to_string(),
+                "card-4".to_string(),
+            ]
+        );
+        assert_eq!(
+            state.stella_player_selections.get("host"),
+            Some(&vec!["card-1".to_string(), "card-3".to_string()])
+        );
+        assert_eq!(
+            state.stella_player_selection_counts.get("host"),
+            Some(&2),
+            "changing the pack should not clear locked selection counts"
+        );
+        assert!(
+            state.players.get("host").unwrap().ready && state.players.get("p2").unwrap().ready,
+            "changing the pack should preserve ready flags"
+        );
+        assert_eq!(
+            state.stella_word_pack,
+            vec!["delta".to_string(), "epsilon".to_string()]
+        );
 
         Ok(())
     }
 
     #[tokio::test]
-    async fn switching_to_dixit_resets_win_condition_to_single_cycle_default() -> Result<()> {
-        let room = test_room_with_condition(DEFAULT_STELLA_WIN_CONDITION);
+    async fn reset_stella_clue_uses_newly_switched_word_pack() -> Result<()> {
+        let room = test_room();
         {
             let mut state = room.state.write().await;
             add_player(&mut state, "host", 0);
+            add_player(&mut state, "p2", 0);
+            add_player(&mut state, "p3", 0);
             state.game_mode = GameMode::Stella;
+            state.stage = RoomStage::StellaAssociate;
+            state.player_order = vec!["host".into(), "p2".into(), "p3".into()];
+            state.active_player = 0;
+            state.stella_clue_word = "before".into();
+            state.stella_word_pack = vec!["before".into()];
+            state.stella_board_cards = vec![
+                "card-1".into(),
+                "card-2".into(),
+                "card-3".into(),
+                "card-4".into(),
+            ];
             state.moderators.insert("host".to_string());
-            setup_connected_member(&mut state, "host", "t-host", 201);
+            setup_connected_member(&mut state, "host", "t-host", 606);
+            setup_connected_member(&mut state, "p2", "t-p2", 607);
+            setup_connected_member(&mut state, "p3", "t-p3", 608);
         }
 
         room.handle_client_msg(
             "host",
-            201,
-            to_ws(ClientMsg::SetGameMode {
-                game_mode: GameMode::DixitPlus,
+            606,
+            to_ws(ClientMsg::SetStellaWordPackPreset {
+                name: "After".to_string(),
             }),
         )
         .await?;
+        room.handle_client_msg("host", 606, to_ws(ClientMsg::ResetStellaClue {}))
+            .await?;
 
         let state = room.state.read().await;
-        assert_eq!(state.game_mode, GameMode::DixitPlus);
-        assert_eq!(state.win_condition, DEFAULT_DIXIT_WIN_CONDITION);
+        assert_eq!(state.stella_word_pack, vec!["after".to_string()]);
+        assert_eq!(
+            state.stella_clue_word, "after",
+            "reset clue should draw from the newly selected pack"
+        );
 
         Ok(())
     }
 
     #[tokio::test]
-    async fn stella_rejects_cycle_win_condition() -> Result<()> {
-        let room = test_room_with_condition(DEFAULT_STELLA_WIN_CONDITION);
-        {
-            let mut state = room.state.write().await;
-            add_player(&mut state, "host", 0);
-            state.game_mode = GameMode::Stella;
-            state.moderators.insert("host".to_string());
-            setup_connected_member(&mut state, "host", "t-host", 202);
+    async fn stella_dark_penalty_only_counts_boxes_the_player_scored() -> Result<()> {
+        let room = test_room();
+        let mut state = room.state.write().await;
+
+        add_player(&mut state, "dark", 0);
+        add_player(&mut state, "b", 0);
+        add_player(&mut state, "c", 0);
+        state.stage = RoomStage::StellaReveal;
+        state.stella_dark_player = Some("dark".to_string());
+        state.stella_player_selections.insert(
+            "dark".to_string(),
+            vec![
+                "shared-early".into(),
+                "fall-card".into(),
+                "shared-late-1".into(),
+                "shared-late-2".into(),
+            ],
+        );
+        state.stella_revealed_cards = vec!["shared-early".into()];
+        state.stella_card_points.insert("shared-early".into(), 3);
+        state.stella_card_points.insert("fall-card".into(), 0);
+        state.stella_card_points.insert("shared-late-1".into(), 3);
+        state.stella_card_points.insert("shared-late-2".into(), 2);
+        state.stella_point_change.insert("dark".to_string(), 3);
+        state.stella_scored_boxes.insert("dark".to_string(), 1);
+
+        room.init_stella_results(&mut state)?;
+
+        assert_eq!(
+            state.stella_point_change.get("dark").copied(),
+            Some(2),
+            "dark penalty should subtract one star only for the box that actually scored"
+        );
+        assert_eq!(
+            state.players.get("dark").map(|player| player.points),
+            Some(2),
+            "dark player should keep bonus points from boxes scored before falling"
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn stella_reveal_message_includes_live_point_change_with_dark_penalty() -> Result<()> {
+        let room = test_room();
+        let mut state = room.state.write().await;
+
+        add_player(&mut state, "dark", 0);
+        add_player(&mut state, "b", 0);
+        add_player(&mut state, "c", 0);
+        state.stage = RoomStage::StellaReveal;
+        state.player_order = vec!["dark".into(), "b".into(), "c".into()];
+        state.active_player = 1;
+        state.stella_dark_player = Some("dark".to_string());
+        state.stella_player_selections.insert(
+            "dark".to_string(),
+            vec![
+                "shared-early".into(),
+                "fall-card".into(),
+                "shared-late".into(),
+            ],
+        );
+        state
+            .stella_player_selections
+            .insert("b".to_string(), vec!["shared-early".into()]);
+        state
+            .stella_player_selections
+            .insert("c".to_string(), vec!["shared-late".into()]);
+        state.stella_revealed_cards = vec!["shared-early".into()];
+        state.stella_card_points.insert("shared-early".into(), 3);
+        state.stella_card_points.insert("fall-card".into(), 0);
+        state.stella_point_change.insert("dark".to_string(), 3);
+        state.stella_scored_boxes.insert("dark".to_string(), 1);
+
+        match room.get_msg(Some("b"), &state)? {
+            ServerMsg::StellaReveal {
+                point_change,
+                revealed_card_choosers,
+                scout,
+                ..
+            } => {
+                assert_eq!(scout.as_deref(), Some("b"));
+                assert_eq!(
+                    point_change.get("dark").copied(),
+                    Some(2),
+                    "live Stella reveal point changes should include dark-player penalty"
+                );
+                assert_eq!(
+                    revealed_card_choosers.get("shared-early"),
+                    Some(&vec!["b".to_string(), "dark".to_string()]),
+                    "revealed Stella cards should list chooser names in alphabetical order"
+                );
+                assert!(
+                    !revealed_card_choosers.contains_key("shared-late"),
+                    "unrevealed Stella cards should keep chooser names hidden"
+                );
+            }
+            _ => return Err(anyhow!("Expected StellaReveal")),
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn stella_results_message_includes_revealed_card_chooser_names() -> Result<()> {
+        let room = test_room();
+        let mut state = room.state.write().await;
+
+        add_player(&mut state, "charlie", 0);
+        add_player(&mut state, "alice", 0);
+        add_player(&mut state, "bravo", 0);
+        state.stage = RoomStage::StellaResults;
+        state.stella_revealed_cards = vec!["shared".into(), "solo".into()];
+        state.stella_player_selections.insert(
+            "charlie".to_string(),
+            vec!["shared".into(), "hidden".into()],
+        );
+        state
+            .stella_player_selections
+            .insert("alice".to_string(), vec!["shared".into(), "solo".into()]);
+        state
+            .stella_player_selections
+            .insert("bravo".to_string(), vec!["shared".into()]);
+
+        match room.get_msg(None, &state)? {
+            ServerMsg::StellaResults {
+                revealed_card_choosers,
+                ..
+            } => {
+                assert_eq!(
+                    revealed_card_choosers.get("shared"),
+                    Some(&vec![
+                        "alice".to_string(),
+                        "bravo".to_string(),
+                        "charlie".to_string()
+                    ]),
+                    "results should expose chooser names for revealed cards in stable order"
+                );
+                assert_eq!(
+                    revealed_card_choosers.get("solo"),
+                    Some(&vec!["alice".to_string()]),
+                    "results should include single chooser entries"
+                );
+                assert!(
+                    !revealed_card_choosers.contains_key("hidden"),
+                    "unrevealed cards should not leak chooser names in results payloads"
+                );
+            }
+            _ => return Err(anyhow!("Expected StellaResults")),
         }
 
-        room.handle_client_msg(
-            "host",
-            202,
-            to_ws(ClientMsg::SetWinCondition {
-                win_condition: WinCondition::Cycles { target_cycles: 2 },
-            }),
-        )
-        .await?;
-
-        let state = room.state.read().await;
-        assert_eq!(state.win_condition, DEFAULT_STELLA_WIN_CONDITION);
-
         Ok(())
     }
 
     #[tokio::test]
-    async fn stella_submission_respects_configured_selection_minimum() -> Result<()> {
+    async fn stella_reveal_handoff_updates_next_scout_after_current_finishes() -> Result<()> {
         let room = test_room();
         {
             let mut state = room.state.write().await;
@@ -21524,904 +25495,745 @@ alpha
             add_player(&mut state, "b", 0);
             add_player(&mut state, "c", 0);
             state.game_mode = GameMode::Stella;
-            state.stage = RoomStage::StellaAssociate;
+            state.stage = RoomStage::StellaReveal;
+            state.stella_queue_during_association = false;
             state.player_order = vec!["a".into(), "b".into(), "c".into()];
-            state.stella_board_cards = vec![
-                "card-1".into(),
-                "card-2".into(),
-                "card-3".into(),
-                "card-4".into(),
-            ];
-            state.stella_selection_min = 3;
-            state.stella_selection_max = 4;
-            setup_connected_member(&mut state, "a", "t-a", 601);
+            state.active_player = 0;
+            state.stella_board_cards = vec!["a-last".into(), "b-next".into()];
+            state
+                .stella_player_selections
+                .insert("a".to_string(), vec!["a-last".into()]);
+            state
+                .stella_player_selections
+                .insert("b".to_string(), vec!["b-next".into()]);
+            state
+                .stella_player_selections
+                .insert("c".to_string(), vec!["b-next".into()]);
+            setup_connected_member(&mut state, "a", "t-a", 701);
         }
 
         room.handle_client_msg(
             "a",
-            601,
-            to_ws(ClientMsg::SubmitStellaSelection {
-                cards: vec!["card-1".to_string()],
+            701,
+            to_ws(ClientMsg::RevealStellaCard {
+                card: "a-last".to_string(),
             }),
         )
         .await?;
 
-        let state = room.state.read().await;
-        assert!(
-            !state.stella_player_selections.contains_key("a"),
-            "submission below the configured Stella minimum should be rejected"
-        );
+        let state = room.state.write().await;
         assert!(
-            !state.players.get("a").unwrap().ready,
-            "rejected Stella submission should not ready the player"
+            matches!(state.stage, RoomStage::StellaReveal),
+            "Stella reveal should continue when later scouts still have unrevealed cards"
         );
-        assert!(
-            matches!(state.stage, RoomStage::StellaAssociate),
-            "invalid Stella submission should not advance the stage"
+        assert_eq!(
+            state
+                .player_order
+                .get(state.active_player)
+                .map(String::as_str),
+            Some("b"),
+            "after the current scout finishes, the next eligible scout should become active"
         );
+        match room.get_msg(Some("b"), &state)? {
+            ServerMsg::StellaReveal { scout, .. } => {
+                assert_eq!(scout.as_deref(), Some("b"));
+            }
+            _ => return Err(anyhow!("Expected StellaReveal")),
+        }
 
         Ok(())
     }
 
     #[tokio::test]
-    async fn stella_submission_can_overwrite_a_players_locked_selection() -> Result<()> {
+    async fn refresh_hands_discards_and_redraws_active_players_only() -> Result<()> {
         let room = test_room();
-        {
-            let mut state = room.state.write().await;
-            add_player(&mut state, "a", 0);
-            add_player(&mut state, "b", 0);
-            add_player(&mut state, "c", 0);
-            state.game_mode = GameMode::Stella;
-            state.stage = RoomStage::StellaAssociate;
-            state.player_order = vec!["a".into(), "b".into(), "c".into()];
-            state.stella_board_cards = vec![
-                "card-1".into(),
-                "card-2".into(),
-                "card-3".into(),
-                "card-4".into(),
-            ];
-            state.stella_selection_min = 1;
-            state.stella_selection_max = 4;
-            setup_connected_member(&mut state, "a", "t-a", 611);
-        }
 
-        room.handle_client_msg(
-            "a",
-            611,
-            to_ws(ClientMsg::SubmitStellaSelection {
-                cards: vec!["card-1".to_string()],
-            }),
-        )
-        .await?;
-        room.handle_client_msg(
-            "a",
-            611,
-            to_ws(ClientMsg::SubmitStellaSelection {
-                cards: vec!["card-2".to_string(), "card-3".to_string()],
-            }),
-        )
-        .await?;
-
-        let state = room.state.read().await;
-        assert_eq!(
-            state.stella_player_selections.get("a"),
-            Some(&vec!["card-2".to_string(), "card-3".to_string()]),
-            "re-locking Stella selections should overwrite the player's prior saved picks"
-        );
-        assert!(
-            state.players.get("a").unwrap().ready,
-            "the player should remain ready after updating a locked Stella selection"
-        );
-
-        Ok(())
-    }
+        let host_before = vec!["h1".to_string(), "h2".to_string(), "h3".to_string()];
+        let p2_before = vec!["p21".to_string(), "p22".to_string(), "p23".to_string()];
+        let p3_before = vec!["p31".to_string(), "p32".to_string(), "p33".to_string()];
+        let observer_hand = vec!["o1".to_string(), "o2".to_string(), "o3".to_string()];
 
-    #[tokio::test]
-    async fn stella_associate_allows_card_number_overlay_changes_but_reveal_does_not() -> Result<()>
-    {
-        let room = test_room();
         {
             let mut state = room.state.write().await;
             add_player(&mut state, "host", 0);
             add_player(&mut state, "p2", 0);
             add_player(&mut state, "p3", 0);
-            state.game_mode = GameMode::Stella;
-            state.stage = RoomStage::StellaAssociate;
-            state.show_voting_card_numbers = false;
-            state.moderators.insert("host".to_string());
-            setup_connected_member(&mut state, "host", "t-host", 612);
-        }
-
-        room.handle_client_msg(
-            "host",
-            612,
-            to_ws(ClientMsg::SetShowVotingCardNumbers { enabled: true }),
-        )
-        .await?;
-
-        {
-            let state = room.state.read().await;
-            assert!(
-                state.show_voting_card_numbers,
-                "Stella associate should allow enabling card number overlays"
+            state.player_order = vec!["host".into(), "p2".into(), "p3".into()];
+            state.stage = RoomStage::ActiveChooses;
+            state.cards_per_hand = 3;
+            state
+                .player_hand
+                .insert("host".to_string(), host_before.clone());
+            state
+                .player_hand
+                .insert("p2".to_string(), p2_before.clone());
+            state
+                .player_hand
+                .insert("p3".to_string(), p3_before.clone());
+            state.observers.insert(
+                "obs".to_string(),
+                ObserverInfo {
+                    connected: true,
+                    points: Some(0),
+                    join_requested: false,
+                    auto_join_on_next_round: false,
+                },
             );
+            state
+                .observer_hand
+                .insert("obs".to_string(), observer_hand.clone());
+            state.moderators.insert("host".to_string());
+            setup_connected_member(&mut state, "host", "t-host", 116);
         }
 
-        {
-            let mut state = room.state.write().await;
-            state.stage = RoomStage::StellaReveal;
-        }
-
-        room.handle_client_msg(
-            "host",
-            612,
-            to_ws(ClientMsg::SetShowVotingCardNumbers { enabled: false }),
-        )
-        .await?;
+        room.handle_client_msg("host", 116, to_ws(ClientMsg::RefreshHands {}))
+            .await?;
 
         let state = room.state.read().await;
         assert!(
-            state.show_voting_card_numbers,
-            "Stella reveal should keep card number overlays unchanged once associate is over"
+            matches!(state.stage, RoomStage::ActiveChooses),
+            "refresh should not change stage"
         );
 
-        Ok(())
-    }
-
-    #[tokio::test]
-    async fn redraw_stella_board_preserves_existing_board_when_redraw_fails() -> Result<()> {
-        let room = test_room();
-        let mut state = room.state.write().await;
-
-        state.game_mode = GameMode::Stella;
-        state.stage = RoomStage::StellaAssociate;
-        state.win_condition = WinCondition::CardsFinish;
-        state.stella_board_size = 4;
-        state.stella_board_cards = vec![
-            "board-1".into(),
-            "board-2".into(),
-            "board-3".into(),
-            "board-4".into(),
-        ];
-        state.deck = vec!["deck-1".into()];
-        state.discard_pile = vec!["discard-1".into()];
-
-        let err = room.redraw_stella_board(&mut state).unwrap_err();
+        for card in host_before
+            .iter()
+            .chain(p2_before.iter())
+            .chain(p3_before.iter())
+        {
+            assert!(
+                state.discard_pile.contains(card),
+                "all previous active-player cards should move to discard"
+            );
+        }
 
-        assert!(
-            err.to_string().contains("Not enough cards"),
-            "redraw failure should surface the deck exhaustion error"
-        );
-        assert_eq!(
-            state.stella_board_cards,
-            vec![
-                "board-1".to_string(),
-                "board-2".to_string(),
-                "board-3".to_string(),
-                "board-4".to_string(),
-            ],
-            "failed redraws must leave the existing board intact"
-        );
-        assert_eq!(
-            state.deck,
-            vec!["deck-1".to_string()],
-            "failed redraws must restore the prior deck state"
-        );
         assert_eq!(
-            state.discard_pile,
-            vec!["discard-1".to_string()],
-            "failed redraws must not leak the current board into the discard pile"
+            state.observer_hand.get("obs"),
+            Some(&observer_hand),
+            "observer preserved hand should not be refreshed"
         );
 
+        for player in ["host", "p2", "p3"] {
+            assert_eq!(
+                state.player_hand.get(player).map(|hand| hand.len()),
+                Some(3),
+                "refreshed hand should be topped up to cards-per-hand"
+            );
+        }
+
         Ok(())
     }
 
     #[tokio::test]
-    async fn stella_custom_word_pack_edits_are_accepted_midgame_and_saved_for_reselection(
-    ) -> Result<()> {
-        let room = test_room();
+    async fn refresh_hands_is_rejected_in_cards_finish_when_deck_is_short() -> Result<()> {
+        let room = test_room_with_condition(WinCondition::CardsFinish);
+
+        let host_before = vec!["h1".to_string(), "h2".to_string(), "h3".to_string()];
+        let p2_before = vec!["p21".to_string(), "p22".to_string(), "p23".to_string()];
+        let p3_before = vec!["p31".to_string(), "p32".to_string(), "p33".to_string()];
+        let discard_before = vec!["dx".to_string(), "dy".to_string(), "dz".to_string()];
+
         {
             let mut state = room.state.write().await;
             add_player(&mut state, "host", 0);
             add_player(&mut state, "p2", 0);
             add_player(&mut state, "p3", 0);
-            state.game_mode = GameMode::Stella;
-            state.stage = RoomStage::StellaAssociate;
             state.player_order = vec!["host".into(), "p2".into(), "p3".into()];
-            state.stella_clue_word = "before".into();
-            state.stella_word_pack = vec!["before".into(), "after".into()];
-            state.stella_board_cards = vec![
-                "card-1".into(),
-                "card-2".into(),
-                "card-3".into(),
-                "card-4".into(),
-            ];
+            state.stage = RoomStage::ActiveChooses;
+            state.cards_per_hand = 3;
             state
-                .stella_player_selections
-                .insert("host".into(), vec!["card-1".into(), "card-3".into()]);
-            state.players.get_mut("host").unwrap().ready = true;
+                .player_hand
+                .insert("host".to_string(), host_before.clone());
+            state
+                .player_hand
+                .insert("p2".to_string(), p2_before.clone());
+            state
+                .player_hand
+                .insert("p3".to_string(), p3_before.clone());
+            state.deck = vec!["draw1".to_string(), "draw2".to_string()];
+            state.discard_pile = discard_before.clone();
             state.moderators.insert("host".to_string());
-            setup_connected_member(&mut state, "host", "t-host", 612_001);
+            setup_connected_member(&mut state, "host", "t-host", 117);
         }
 
-        room.handle_client_msg(
-            "host",
-            612_001,
-            to_ws(ClientMsg::SetStellaWordPack {
-                words: "custom alpha\ncustom beta".to_string(),
-            }),
-        )
-        .await?;
+        room.handle_client_msg("host", 117, to_ws(ClientMsg::RefreshHands {}))
+            .await?;
 
-        let state = room.state.write().await;
+        let state = room.state.read().await;
         assert_eq!(
-            state.stella_word_pack,
-            vec!["custom alpha".to_string(), "custom beta".to_string()],
-            "midgame custom edits should update the active pack instead of being dropped"
+            state.player_hand.get("host"),
+            Some(&host_before),
+            "refresh should not discard host hand when cards-finish deck is insufficient"
         );
         assert_eq!(
-            state.stella_player_selections.get("host"),
-            Some(&vec!["card-1".to_string(), "card-3".to_string()]),
-            "changing the pack mid-associate should not wipe current submissions"
+            state.player_hand.get("p2"),
+            Some(&p2_before),
+            "refresh should not discard p2 hand when cards-finish deck is insufficient"
         );
-        assert!(
-            state.players.get("host").unwrap().ready,
-            "changing the pack mid-associate should preserve ready flags"
+        assert_eq!(
+            state.player_hand.get("p3"),
+            Some(&p3_before),
+            "refresh should not discard p3 hand when cards-finish deck is insufficient"
+        );
+        assert_eq!(
+            state.discard_pile, discard_before,
+            "cards-finish refresh rejection should not mutate discard pile"
         );
-
-        match room.room_state(&state) {
-            ServerMsg::RoomState {
-                stella_word_pack_preset_names,
-                stella_selected_word_pack_name,
-                stella_word_pack_is_unsaved,
-                ..
-            } => {
-                assert!(
-                    stella_word_pack_preset_names
-                        .contains(&CURRENT_GAME_STELLA_WORD_PACK_NAME.to_string()),
-                    "the current game's custom pack should remain selectable for the rest of the game"
-                );
-                assert_eq!(
-                    stella_selected_word_pack_name,
-                    Some(CURRENT_GAME_STELLA_WORD_PACK_NAME.to_string()),
-                    "room state should treat the saved game pack as the active preset"
-                );
-                assert!(
-                    !stella_word_pack_is_unsaved,
-                    "game-saved custom packs should be reselectable presets, not one-off unsaved state"
-                );
-            }
-            _ => return Err(anyhow!("Expected RoomState")),
-        }
 
         Ok(())
     }
 
     #[tokio::test]
-    async fn stella_custom_word_pack_from_lobby_can_be_reselected_midgame() -> Result<()> {
+    async fn round_start_discard_count_discards_from_each_hand_on_round_init() -> Result<()> {
         let room = test_room();
+
         {
             let mut state = room.state.write().await;
-            add_player(&mut state, "host", 0);
-            add_player(&mut state, "p2", 0);
-            add_player(&mut state, "p3", 0);
-            state.game_mode = GameMode::Stella;
-            state.stage = RoomStage::Joining;
-            state.stella_word_pack = vec!["custom alpha".into(), "custom beta".into()];
-            state.moderators.insert("host".to_string());
-            setup_connected_member(&mut state, "host", "t-host", 612_002);
-
-            room.init_stella_round(&mut state).await?;
+            add_player(&mut state, "a", 0);
+            add_player(&mut state, "b", 0);
+            add_player(&mut state, "c", 0);
+            state.player_order = vec!["a".into(), "b".into(), "c".into()];
+            state.active_player = 0;
+            state.round = 1;
+            state.stage = RoomStage::Results;
+            state.cards_per_hand = 4;
+            state.round_start_discard_all_unpinned = false;
+            state.round_start_discard_count = 2;
+            state.player_hand.insert(
+                "a".to_string(),
+                vec!["a1".into(), "a2".into(), "a3".into(), "a4".into()],
+            );
+            state.player_hand.insert(
+                "b".to_string(),
+                vec!["b1".into(), "b2".into(), "b3".into(), "b4".into()],
+            );
+            state.player_hand.insert(
+                "c".to_string(),
+                vec!["c1".into(), "c2".into(), "c3".into(), "c4".into()],
+            );
+            state.deck = (0..200).map(|i| format!("draw-{}", i)).collect();
+            state.discard_pile.clear();
         }
 
         {
-            let state = room.state.write().await;
-            match room.room_state(&state) {
-                ServerMsg::RoomState {
-                    stella_word_pack_preset_names,
-                    stella_selected_word_pack_name,
-                    stella_word_pack_is_unsaved,
-                    stella_word_pack_words,
-                    ..
-                } => {
-                    assert!(
-                        stella_word_pack_preset_names
-                            .contains(&CURRENT_GAME_STELLA_WORD_PACK_NAME.to_string()),
-                        "starting a game with a custom pack should save it for that game's preset picker"
-                    );
-                    assert_eq!(
-                        stella_selected_word_pack_name,
-                        Some(CURRENT_GAME_STELLA_WORD_PACK_NAME.to_string()),
-                    );
-                    assert!(!stella_word_pack_is_unsaved);
-                    assert!(
-                        stella_word_pack_words.is_empty(),
-                        "midgame room state should still avoid echoing the raw custom word list"
-                    );
-                }
-                _ => return Err(anyhow!("Expected RoomState")),
-            }
-        }
-
-        room.handle_client_msg(
-            "host",
-            612_002,
-            to_ws(ClientMsg::SetStellaWordPackPreset {
-                name: "Delta".to_string(),
-            }),
-        )
-        .await?;
-        room.handle_client_msg(
-            "host",
-            612_002,
-            to_ws(ClientMsg::SetStellaWordPackPreset {
-                name: CURRENT_GAME_STELLA_WORD_PACK_NAME.to_string(),
-            }),
-        )
-        .await?;
-
-        let state = room.state.read().await;
-        assert_eq!(
-            state.stella_word_pack,
-            vec!["custom alpha".to_string(), "custom beta".to_string()],
-            "the saved game-local pack should remain available after switching away and back"
-        );
-
-        Ok(())
-    }
-
-    #[tokio::test]
-    async fn stella_custom_word_pack_size_is_limited() -> Result<()> {
-        let room = test_room();
-        let mut socket_rx = {
             let mut state = room.state.write().await;
-            add_player(&mut state, "host", 0);
-            state.game_mode = GameMode::Stella;
-            state.stage = RoomStage::Joining;
-            state.moderators.insert("host".to_string());
-            setup_connected_member(&mut state, "host", "t-host", 612_003);
-            attach_test_socket(&mut state, "host")
-        };
-
-        let oversized = "a".repeat(MAX_CUSTOM_STELLA_WORD_PACK_BYTES + 1);
-        room.handle_client_msg(
-            "host",
-            612_003,
-            to_ws(ClientMsg::SetStellaWordPack { words: oversized }),
-        )
-        .await?;
-
-        let error = socket_rx
-            .recv()
-            .await
-            .ok_or_else(|| anyhow!("Expected size-limit error"))?;
-        match error {
-            ServerMsg::ErrorMsg(message) => assert_eq!(
-                message, "Word pack must be at most 3 MB",
-                "oversized custom packs should be rejected before the server stores them"
-            ),
-            other => {
-                return Err(anyhow!(
-                    "Expected size-limit error message, got {:?}",
-                    other
-                ))
-            }
+            room.init_round(&mut state).await?;
         }
 
         let state = room.state.read().await;
+        assert!(
+            matches!(state.stage, RoomStage::ActiveChooses),
+            "round init should move to storyteller choosing"
+        );
         assert_eq!(
-            state.stella_word_pack,
-            (*test_default_stella_word_pack()).clone(),
-            "rejecting an oversized pack should leave the active pack unchanged"
+            state.discard_pile.len(),
+            6,
+            "round-start discard should remove N cards per active player"
         );
+        for player in ["a", "b", "c"] {
+            assert_eq!(
+                state.player_hand.get(player).map(|hand| hand.len()),
+                Some(4),
+                "round-start discard must be followed by top-up"
+            );
+        }
 
         Ok(())
     }
 
     #[tokio::test]
-    async fn stella_saved_game_word_pack_is_cleared_when_the_game_ends() -> Result<()> {
-        let room = test_room();
-        let mut state = room.state.write().await;
-
-        state.game_mode = GameMode::Stella;
-        state.stella_saved_game_word_pack = Some(vec!["custom alpha".into()]);
-
-        room.set_stage(&mut state, RoomStage::End);
+    async fn room_state_defaults_to_discard_all_unpinned_at_round_start() -> Result<()> {
+        let room = test_room();
+        let state = room.state.write().await;
 
-        assert!(
-            state.stella_saved_game_word_pack.is_none(),
-            "game-scoped custom packs should be garbage-collected after the game ends"
-        );
+        match room.room_state(&state) {
+            ServerMsg::RoomState {
+                round_start_discard_all_unpinned,
+                ..
+            } => assert!(
+                round_start_discard_all_unpinned,
+                "new rooms should default to discarding every unpinned card at round start"
+            ),
+            other => panic!("expected RoomState, got {other:?}"),
+        }
 
         Ok(())
     }
 
     #[tokio::test]
-    async fn reset_stella_clue_clears_ready_flags_before_new_submissions() -> Result<()> {
+    async fn round_start_discards_all_unpinned_cards_and_preserves_pins() -> Result<()> {
         let room = test_room();
+
         {
             let mut state = room.state.write().await;
-            add_player(&mut state, "host", 0);
-            add_player(&mut state, "p2", 0);
-            add_player(&mut state, "p3", 0);
-            state.game_mode = GameMode::Stella;
-            state.stage = RoomStage::StellaAssociate;
-            state.player_order = vec!["host".into(), "p2".into(), "p3".into()];
+            add_player(&mut state, "a", 0);
+            add_player(&mut state, "b", 0);
+            add_player(&mut state, "c", 0);
+            state.player_order = vec!["a".into(), "b".into(), "c".into()];
             state.active_player = 0;
-            state.stella_clue_word = "before".into();
-            state.stella_word_pack = vec!["before".into(), "after".into()];
-            state.stella_board_cards = vec![
-                "card-1".into(),
-                "card-2".into(),
-                "card-3".into(),
-                "card-4".into(),
-                "card-5".into(),
-                "card-6".into(),
-            ];
-            state.stella_selection_min = 1;
-            state.stella_selection_max = 6;
-            state.moderators.insert("host".to_string());
-            setup_connected_member(&mut state, "host", "t-host", 602);
-            setup_connected_member(&mut state, "p2", "t-p2", 603);
-            setup_connected_member(&mut state, "p3", "t-p3", 604);
+            state.round = 1;
+            state.stage = RoomStage::Results;
+            state.cards_per_hand = 4;
+            state.round_start_discard_all_unpinned = true;
+            state.round_start_discard_count = 0;
+            state
+                .player_hand
+                .insert("a".into(), vec!["a-pin".into(), "a1".into(), "a2".into()]);
+            state
+                .player_hand
+                .insert("b".into(), vec!["b-pin".into(), "b1".into(), "b2".into()]);
+            state
+                .player_hand
+                .insert("c".into(), vec!["c-pin".into(), "c1".into(), "c2".into()]);
+            state
+                .player_pinned_cards
+                .insert("a".into(), HashSet::from(["a-pin".to_string()]));
+            state
+                .player_pinned_cards
+                .insert("b".into(), HashSet::from(["b-pin".to_string()]));
+            state
+                .player_pinned_cards
+                .insert("c".into(), HashSet::from(["c-pin".to_string()]));
+            state.deck = (0..200).map(|i| format!("draw-{}", i)).collect();
+            state.discard_pile.clear();
         }
 
-        room.handle_client_msg(
-            "host",
-            602,
-            to_ws(ClientMsg::SubmitStellaSelection {
-                cards: vec!["card-1".to_string()],
-            }),
-        )
-        .await?;
-        room.handle_client_msg(
-            "p2",
-            603,
-            to_ws(ClientMsg::SubmitStellaSelection {
-                cards: vec!["card-2".to_string()],
-            }),
-        )
-        .await?;
-        room.handle_client_msg("host", 602, to_ws(ClientMsg::ResetStellaClue {}))
-            .await?;
-        room.handle_client_msg(
-            "p3",
-            604,
-            to_ws(ClientMsg::SubmitStellaSelection {
-                cards: vec!["card-3".to_string()],
-            }),
-        )
-        .await?;
+        {
+            let mut state = room.state.write().await;
+            room.init_round(&mut state).await?;
+        }
 
         let state = room.state.read().await;
-        assert!(
-            matches!(state.stage, RoomStage::StellaAssociate),
-            "a single post-reset Stella submission should not advance to reveal"
-        );
-        assert_eq!(
-            state.stella_player_selections.get("p3"),
-            Some(&vec!["card-3".to_string()]),
-            "post-reset round state should only contain fresh submissions"
-        );
-        assert!(
-            !state.players.get("host").unwrap().ready && !state.players.get("p2").unwrap().ready,
-            "resetting the Stella clue must clear earlier ready flags"
-        );
-        assert!(
-            state.players.get("p3").unwrap().ready,
-            "the new submission should still ready the submitting player"
-        );
+        for (player, pinned, unpinned) in [
+            ("a", "a-pin", vec!["a1", "a2"]),
+            ("b", "b-pin", vec!["b1", "b2"]),
+            ("c", "c-pin", vec!["c1", "c2"]),
+        ] {
+            let hand = state
+                .player_hand
+                .get(player)
+                .expect("player should have hand");
+            assert!(
+                hand.contains(&pinned.to_string()),
+                "pinned card should stay in {player}'s hand"
+            );
+            assert_eq!(hand.len(), 4, "{player}'s hand should be topped up");
+            for card in unpinned {
+                assert!(
+                    state.discard_pile.contains(&card.to_string()),
+                    "unpinned card {card} should be discarded at round start"
+                );
+                assert!(
+                    !hand.contains(&card.to_string()),
+                    "unpinned card {card} should leave {player}'s hand"
+                );
+            }
+        }
 
         Ok(())
     }
 
     #[tokio::test]
-    async fn stella_word_pack_change_mid_associate_preserves_current_round_state() -> Result<()> {
+    async fn numeric_round_start_discard_never_discards_pinned_cards_and_clamps() -> Result<()> {
         let room = test_room();
+
         {
             let mut state = room.state.write().await;
-            add_player(&mut state, "host", 0);
-            add_player(&mut state, "p2", 0);
-            add_player(&mut state, "p3", 0);
-            state.game_mode = GameMode::Stella;
-            state.stage = RoomStage::StellaAssociate;
-            state.player_order = vec!["host".into(), "p2".into(), "p3".into()];
+            add_player(&mut state, "a", 0);
+            add_player(&mut state, "b", 0);
+            add_player(&mut state, "c", 0);
+            state.player_order = vec!["a".into(), "b".into(), "c".into()];
             state.active_player = 0;
-            state.stella_clue_word = "before".into();
-            state.stella_word_pack = vec!["before".into(), "after".into()];
-            state.stella_board_cards = vec![
-                "card-1".into(),
-                "card-2".into(),
-                "card-3".into(),
-                "card-4".into(),
-            ];
-            state
-                .stella_player_selections
-                .insert("host".into(), vec!["card-1".into(), "card-3".into()]);
-            state
-                .stella_player_selection_counts
-                .insert("host".into(), 2);
-            state.players.get_mut("host").unwrap().ready = true;
-            state.players.get_mut("p2").unwrap().ready = true;
-            state.moderators.insert("host".to_string());
-            setup_connected_member(&mut state, "host", "t-host", 605);
+            state.round = 1;
+            state.stage = RoomStage::Results;
+            state.cards_per_hand = 4;
+            state.round_start_discard_all_unpinned = false;
+            state.round_start_discard_count = 3;
+            state.player_hand.insert(
+                "a".into(),
+                vec!["a-pin-1".into(), "a-pin-2".into(), "a-free".into()],
+            );
+            state.player_hand.insert(
+                "b".into(),
+                vec!["b-pin-1".into(), "b-pin-2".into(), "b-free".into()],
+            );
+            state.player_hand.insert(
+                "c".into(),
+                vec!["c-pin-1".into(), "c-pin-2".into(), "c-free".into()],
+            );
+            state.player_pinned_cards.insert(
+                "a".into(),
+                HashSet::from(["a-pin-1".to_string(), "a-pin-2".to_string()]),
+            );
+            state.player_pinned_cards.insert(
+                "b".into(),
+                HashSet::from(["b-pin-1".to_string(), "b-pin-2".to_string()]),
+            );
+            state.player_pinned_cards.insert(
+                "c".into(),
+                HashSet::from(["c-pin-1".to_string(), "c-pin-2".to_string()]),
+            );
+            state.deck = (0..200).map(|i| format!("draw-{}", i)).collect();
+            state.discard_pile.clear();
         }
 
-        room.handle_client_msg(
-            "host",
-            605,
-            to_ws(ClientMsg::SetStellaWordPackPreset {
-                name: "Delta".to_string(),
-            }),
-        )
-        .await?;
+        {
+            let mut state = room.state.write().await;
+            room.init_round(&mut state).await?;
+        }
 
         let state = room.state.read().await;
-        assert!(matches!(state.stage, RoomStage::StellaAssociate));
-        assert_eq!(state.stella_clue_word, "before");
-        assert_eq!(
-            state.stella_board_cards,
-            vec![
-                "card-1".to_string(),
-                "card-2".to_string(),
-                "card-3".to_string(),
-                "card-4".to_string(),
-            ]
-        );
-        assert_eq!(
-            state.stella_player_selections.get("host"),
-            Some(&vec!["card-1".to_string(), "card-3".to_string()])
-        );
-        assert_eq!(
-            state.stella_player_selection_counts.get("host"),
-            Some(&2),
-            "changing the pack should not clear locked selection counts"
-        );
-        assert!(
-            state.players.get("host").unwrap().ready && state.players.get("p2").unwrap().ready,
-            "changing the pack should preserve ready flags"
-        );
-        assert_eq!(
-            state.stella_word_pack,
-            vec!["delta".to_string(), "epsilon".to_string()]
-        );
+        for (player, pinned, free) in [
+            ("a", vec!["a-pin-1", "a-pin-2"], "a-free"),
+            ("b", vec!["b-pin-1", "b-pin-2"], "b-free"),
+            ("c", vec!["c-pin-1", "c-pin-2"], "c-free"),
+        ] {
+            let hand = state
+                .player_hand
+                .get(player)
+                .expect("player should have hand");
+            for card in pinned {
+                assert!(
+                    hand.contains(&card.to_string()),
+                    "numeric discard must preserve pinned card {card}"
+                );
+            }
+            assert!(
+                state.discard_pile.contains(&free.to_string()),
+                "numeric discard should discard the only unpinned card for {player}"
+            );
+            assert_eq!(hand.len(), 4, "{player}'s hand should be topped up");
+        }
 
         Ok(())
     }
 
     #[tokio::test]
-    async fn reset_stella_clue_uses_newly_switched_word_pack() -> Result<()> {
+    async fn refresh_hands_preserves_pinned_cards_and_redraws_unpinned_only() -> Result<()> {
         let room = test_room();
+
         {
             let mut state = room.state.write().await;
             add_player(&mut state, "host", 0);
             add_player(&mut state, "p2", 0);
             add_player(&mut state, "p3", 0);
-            state.game_mode = GameMode::Stella;
-            state.stage = RoomStage::StellaAssociate;
             state.player_order = vec!["host".into(), "p2".into(), "p3".into()];
-            state.active_player = 0;
-            state.stella_clue_word = "before".into();
-            state.stella_word_pack = vec!["before".into()];
-            state.stella_board_cards = vec![
-                "card-1".into(),
-                "card-2".into(),
-                "card-3".into(),
-                "card-4".into(),
-            ];
+            state.stage = RoomStage::ActiveChooses;
+            state.cards_per_hand = 3;
+            state.player_hand.insert(
+                "host".into(),
+                vec!["h-pin".into(), "h1".into(), "h2".into()],
+            );
+            state.player_hand.insert(
+                "p2".into(),
+                vec!["p2-pin".into(), "p21".into(), "p22".into()],
+            );
+            state.player_hand.insert(
+                "p3".into(),
+                vec!["p3-pin".into(), "p31".into(), "p32".into()],
+            );
+            state
+                .player_pinned_cards
+                .insert("host".into(), HashSet::from(["h-pin".to_string()]));
+            state
+                .player_pinned_cards
+                .insert("p2".into(), HashSet::from(["p2-pin".to_string()]));
+            state
+                .player_pinned_cards
+                .insert("p3".into(), HashSet::from(["p3-pin".to_string()]));
             state.moderators.insert("host".to_string());
-            setup_connected_member(&mut state, "host", "t-host", 606);
-            setup_connected_member(&mut state, "p2", "t-p2", 607);
-            setup_connected_member(&mut state, "p3", "t-p3", 608);
+            setup_connected_member(&mut state, "host", "t-host", 118);
         }
 
-        room.handle_client_msg(
-            "host",
-            606,
-            to_ws(ClientMsg::SetStellaWordPackPreset {
-                name: "After".to_string(),
-            }),
-        )
-        .await?;
-        room.handle_client_msg("host", 606, to_ws(ClientMsg::ResetStellaClue {}))
+        room.handle_client_msg("host", 118, to_ws(ClientMsg::RefreshHands {}))
             .await?;
 
         let state = room.state.read().await;
-        assert_eq!(state.stella_word_pack, vec!["after".to_string()]);
-        assert_eq!(
-            state.stella_clue_word, "after",
-            "reset clue should draw from the newly selected pack"
-        );
-
-        Ok(())
-    }
-
-    #[tokio::test]
-    async fn stella_dark_penalty_only_counts_boxes_the_player_scored() -> Result<()> {
-        let room = test_room();
-        let mut state = room.state.write().await;
-
-        add_player(&mut state, "dark", 0);
-        add_player(&mut state, "b", 0);
-        add_player(&mut state, "c", 0);
-        state.stage = RoomStage::StellaReveal;
-        state.stella_dark_player = Some("dark".to_string());
-        state.stella_player_selections.insert(
-            "dark".to_string(),
-            vec![
-                "shared-early".into(),
-                "fall-card".into(),
-                "shared-late-1".into(),
-                "shared-late-2".into(),
-            ],
-        );
-        state.stella_revealed_cards = vec!["shared-early".into()];
-        state.stella_card_points.insert("shared-early".into(), 3);
-        state.stella_card_points.insert("fall-card".into(), 0);
-        state.stella_card_points.insert("shared-late-1".into(), 3);
-        state.stella_card_points.insert("shared-late-2".into(), 2);
-        state.stella_point_change.insert("dark".to_string(), 3);
-        state.stella_scored_boxes.insert("dark".to_string(), 1);
-
-        room.init_stella_results(&mut state)?;
-
-        assert_eq!(
-            state.stella_point_change.get("dark").copied(),
-            Some(2),
-            "dark penalty should subtract one star only for the box that actually scored"
-        );
-        assert_eq!(
-            state.players.get("dark").map(|player| player.points),
-            Some(2),
-            "dark player should keep bonus points from boxes scored before falling"
-        );
-
-        Ok(())
-    }
-
-    #[tokio::test]
-    async fn stella_reveal_message_includes_live_point_change_with_dark_penalty() -> Result<()> {
-        let room = test_room();
-        let mut state = room.state.write().await;
-
-        add_player(&mut state, "dark", 0);
-        add_player(&mut state, "b", 0);
-        add_player(&mut state, "c", 0);
-        state.stage = RoomStage::StellaReveal;
-        state.player_order = vec!["dark".into(), "b".into(), "c".into()];
-        state.active_player = 1;
-        state.stella_dark_player = Some("dark".to_string());
-        state.stella_player_selections.insert(
-            "dark".to_string(),
-            vec![
-                "shared-early".into(),
-                "fall-card".into(),
-                "shared-late".into(),
-            ],
-        );
-        state
-            .stella_player_selections
-            .insert("b".to_string(), vec!["shared-early".into()]);
-        state
-            .stella_player_selections
-            .insert("c".to_string(), vec!["shared-late".into()]);
-        state.stella_revealed_cards = vec!["shared-early".into()];
-        state.stella_card_points.insert("shared-early".into(), 3);
-        state.stella_card_points.insert("fall-card".into(), 0);
-        state.stella_point_change.insert("dark".to_string(), 3);
-        state.stella_scored_boxes.insert("dark".to_string(), 1);
-
-        match room.get_msg(Some("b"), &state)? {
-            ServerMsg::StellaReveal {
-                point_change,
-                revealed_card_choosers,
-                scout,
-                ..
-            } => {
-                assert_eq!(scout.as_deref(), Some("b"));
-                assert_eq!(
-                    point_change.get("dark").copied(),
-                    Some(2),
-                    "live Stella reveal point changes should include dark-player penalty"
-                );
-                assert_eq!(
-                    revealed_card_choosers.get("shared-early"),
-                    Some(&vec!["b".to_string(), "dark".to_string()]),
-                    "revealed Stella cards should list chooser names in alphabetical order"
+        for (player, pinned, discarded) in [
+            ("host", "h-pin", vec!["h1", "h2"]),
+            ("p2", "p2-pin", vec!["p21", "p22"]),
+            ("p3", "p3-pin", vec!["p31", "p32"]),
+        ] {
+            let hand = state
+                .player_hand
+                .get(player)
+                .expect("player should have hand");
+            assert!(hand.contains(&pinned.to_string()));
+            assert_eq!(hand.len(), 3);
+            for card in discarded {
+                assert!(
+                    state.discard_pile.contains(&card.to_string()),
+                    "refresh should discard unpinned card {card}"
                 );
                 assert!(
-                    !revealed_card_choosers.contains_key("shared-late"),
-                    "unrevealed Stella cards should keep chooser names hidden"
+                    !hand.contains(&card.to_string()),
+                    "refresh should remove unpinned card {card} from {player}'s hand"
                 );
             }
-            _ => return Err(anyhow!("Expected StellaReveal")),
         }
 
         Ok(())
     }
 
     #[tokio::test]
-    async fn stella_results_message_includes_revealed_card_chooser_names() -> Result<()> {
+    async fn pin_changes_are_allowed_while_storyteller_is_choosing() -> Result<()> {
         let room = test_room();
-        let mut state = room.state.write().await;
+        {
+            let mut state = room.state.write().await;
+            add_player(&mut state, "a", 0);
+            add_player(&mut state, "b", 0);
+            add_player(&mut state, "c", 0);
+            state.stage = RoomStage::ActiveChooses;
+            state.player_order = vec!["a".into(), "b".into(), "c".into()];
+            state.active_player = 0;
+            state
+                .player_hand
+                .insert("a".into(), vec!["a-1".into(), "a-2".into()]);
+            setup_connected_member(&mut state, "a", "t-a", 119);
+        }
 
-        add_player(&mut state, "charlie", 0);
-        add_player(&mut state, "alice", 0);
-        add_player(&mut state, "bravo", 0);
-        state.stage = RoomStage::StellaResults;
-        state.stella_revealed_cards = vec!["shared".into(), "solo".into()];
-        state.stella_player_selections.insert(
-            "charlie".to_string(),
-            vec!["shared".into(), "hidden".into()],
-        );
-        state
-            .stella_player_selections
-            .insert("alice".to_string(), vec!["shared".into(), "solo".into()]);
-        state
-            .stella_player_selections
-            .insert("bravo".to_string(), vec!["shared".into()]);
+        room.handle_client_msg(
+            "a",
+            119,
+            to_ws(ClientMsg::SetHandCardPinned {
+                card: "a-1".to_string(),
+                pinned: true,
+            }),
+        )
+        .await?;
 
-        match room.get_msg(None, &state)? {
-            ServerMsg::StellaResults {
-                revealed_card_choosers,
-                ..
-            } => {
-                assert_eq!(
-                    revealed_card_choosers.get("shared"),
-                    Some(&vec![
-                        "alice".to_string(),
-                        "bravo".to_string(),
-                        "charlie".to_string()
-                    ]),
-                    "results should expose chooser names for revealed cards in stable order"
-                );
-                assert_eq!(
-                    revealed_card_choosers.get("solo"),
-                    Some(&vec!["alice".to_string()]),
-                    "results should include single chooser entries"
-                );
-                assert!(
-                    !revealed_card_choosers.contains_key("hidden"),
-                    "unrevealed cards should not leak chooser names in results payloads"
-                );
-            }
-            _ => return Err(anyhow!("Expected StellaResults")),
-        }
+        let state = room.state.read().await;
+        assert!(
+            state
+                .player_pinned_cards
+                .get("a")
+                .map(|cards| cards.contains("a-1"))
+                .unwrap_or(false),
+            "pin changes should be applied during ActiveChooses"
+        );
 
         Ok(())
     }
 
     #[tokio::test]
-    async fn stella_reveal_handoff_updates_next_scout_after_current_finishes() -> Result<()> {
+    async fn played_pinned_cards_leave_hand_and_are_unpinned_when_voting_begins() -> Result<()> {
         let room = test_room();
         {
             let mut state = room.state.write().await;
             add_player(&mut state, "a", 0);
             add_player(&mut state, "b", 0);
             add_player(&mut state, "c", 0);
-            state.game_mode = GameMode::Stella;
-            state.stage = RoomStage::StellaReveal;
-            state.stella_queue_during_association = false;
+            state.stage = RoomStage::PlayersChoose;
             state.player_order = vec!["a".into(), "b".into(), "c".into()];
             state.active_player = 0;
-            state.stella_board_cards = vec!["a-last".into(), "b-next".into()];
+            state.cards_per_hand = 3;
+            state.player_hand.insert(
+                "a".into(),
+                vec!["a-played".into(), "a-stays".into(), "a-free".into()],
+            );
             state
-                .stella_player_selections
-                .insert("a".to_string(), vec!["a-last".into()]);
+                .player_pinned_cards
+                .insert("a".into(), HashSet::from(["a-played".to_string()]));
             state
-                .stella_player_selections
-                .insert("b".to_string(), vec!["b-next".into()]);
+                .player_to_current_cards
+                .insert("a".into(), vec!["a-played".into()]);
             state
-                .stella_player_selections
-                .insert("c".to_string(), vec!["b-next".into()]);
-            setup_connected_member(&mut state, "a", "t-a", 701);
+                .player_to_current_cards
+                .insert("b".into(), vec!["b-1".into()]);
+            state
+                .player_to_current_cards
+                .insert("c".into(), vec!["c-1".into()]);
         }
 
-        room.handle_client_msg(
-            "a",
-            701,
-            to_ws(ClientMsg::RevealStellaCard {
-                card: "a-last".to_string(),
-            }),
-        )
-        .await?;
+        {
+            let mut state = room.state.write().await;
+            room.init_voting(&mut state).await?;
+        }
 
-        let state = room.state.write().await;
+        let state = room.state.read().await;
         assert!(
-            matches!(state.stage, RoomStage::StellaReveal),
-            "Stella reveal should continue when later scouts still have unrevealed cards"
+            !state
+                .player_hand
+                .get("a")
+                .map(|hand| hand.contains(&"a-played".to_string()))
+                .unwrap_or(false),
+            "played pinned card should leave the player's hand"
         );
-        assert_eq!(
+        assert!(
             state
-                .player_order
-                .get(state.active_player)
-                .map(String::as_str),
-            Some("b"),
-            "after the current scout finishes, the next eligible scout should become active"
+                .player_pinned_cards
+                .get("a")
+                .map(|cards| !cards.contains("a-played"))
+                .unwrap_or(true),
+            "played card should be unpinned when it leaves the hand"
         );
-        match room.get_msg(Some("b"), &state)? {
-            ServerMsg::StellaReveal { scout, .. } => {
-                assert_eq!(scout.as_deref(), Some("b"));
-            }
-            _ => return Err(anyhow!("Expected StellaReveal")),
-        }
 
         Ok(())
     }
 
     #[tokio::test]
-    async fn refresh_hands_discards_and_redraws_active_players_only() -> Result<()> {
-        let room = test_room();
+    async fn any_of_win_condition_ends_when_either_condition_is_met() -> Result<()> {
+        let room = test_room_with_condition(WinCondition::AnyOf {
+            conditions: vec![
+                WinCondition::Points { target_points: 30 },
+                WinCondition::Cycles { target_cycles: 2 },
+            ],
+        });
+        let mut state = room.state.write().await;
+        add_player(&mut state, "a", 12);
+        add_player(&mut state, "b", 9);
+        add_player(&mut state, "c", 4);
+        state.player_order = vec!["a".into(), "b".into(), "c".into()];
+        set_storyteller_count(&mut state, "a", 2);
+        set_storyteller_count(&mut state, "b", 2);
+        set_storyteller_count(&mut state, "c", 1);
+        assert!(!room.should_end_game(&state));
 
-        let host_before = vec!["h1".to_string(), "h2".to_string(), "h3".to_string()];
-        let p2_before = vec!["p21".to_string(), "p22".to_string(), "p23".to_string()];
-        let p3_before = vec!["p31".to_string(), "p32".to_string(), "p33".to_string()];
-        let observer_hand = vec!["o1".to_string(), "o2".to_string(), "o3".to_string()];
+        set_storyteller_count(&mut state, "c", 2);
+        assert!(
+            room.should_end_game(&state),
+            "any-of should end once the cycles condition is met"
+        );
 
-        {
-            let mut state = room.state.write().await;
-            add_player(&mut state, "host", 0);
-            add_player(&mut state, "p2", 0);
-            add_player(&mut state, "p3", 0);
-            state.player_order = vec!["host".into(), "p2".into(), "p3".into()];
-            state.stage = RoomStage::ActiveChooses;
-            state.cards_per_hand = 3;
-            state
-                .player_hand
-                .insert("host".to_string(), host_before.clone());
-            state
-                .player_hand
-                .insert("p2".to_string(), p2_before.clone());
-            state
-                .player_hand
-                .insert("p3".to_string(), p3_before.clone());
-            state.observers.insert(
-                "obs".to_string(),
-                ObserverInfo {
-                    connected: true,
-                    points: Some(0),
-                    join_requested: false,
-                    auto_join_on_next_round: false,
-                },
-            );
-            state
-                .observer_hand
-                .insert("obs".to_string(), observer_hand.clone());
-            state.moderators.insert("host".to_string());
-            setup_connected_member(&mut state, "host", "t-host", 116);
-        }
+        state.win_condition = WinCondition::AllOf {
+            conditions: vec![
+                WinCondition::Points { target_points: 30 },
+                WinCondition::Cycles { target_cycles: 2 },
+            ],
+        };
+        assert!(
+            !room.should_end_game(&state),
+            "all-of should wait for the points condition too"
+        );
+        state.players.get_mut("a").unwrap().points = 30;
+        assert!(room.should_end_game(&state));
 
-        room.handle_client_msg("host", 116, to_ws(ClientMsg::RefreshHands {}))
-            .await?;
+        Ok(())
+    }
 
-        let state = room.state.read().await;
+    #[tokio::test]
+    async fn time_limit_win_condition_can_wait_for_the_current_cycle() -> Result<()> {
+        let room = test_room_with_condition(WinCondition::TimeLimit {
+            minutes: 60,
+            finish_cycle: true,
+        });
+        let mut state = room.state.write().await;
+        add_player(&mut state, "a", 0);
+        add_player(&mut state, "b", 0);
+        add_player(&mut state, "c", 0);
+        state.player_order = vec!["a".into(), "b".into(), "c".into()];
+        set_storyteller_count(&mut state, "a", 2);
+        set_storyteller_count(&mut state, "b", 2);
+        set_storyteller_count(&mut state, "c", 1);
+
+        state.game_started_at_s = Some(get_time_s().saturating_sub(30 * 60));
+        assert!(!room.should_end_game(&state));
+
+        state.game_started_at_s = Some(get_time_s().saturating_sub(61 * 60));
         assert!(
-            matches!(state.stage, RoomStage::ActiveChooses),
-            "refresh should not change stage"
+            !room.should_end_game(&state),
+            "an expired time limit should still wait for the current storyteller cycle"
         );
 
-        for card in host_before
-            .iter()
-            .chain(p2_before.iter())
-            .chain(p3_before.iter())
-        {
-            assert!(
-                state.discard_pile.contains(card),
-                "all previous active-player cards should move to discard"
-            );
-        }
+        set_storyteller_count(&mut state, "c", 2);
+        assert!(room.should_end_game(&state));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn all_of_cards_finish_keeps_refilling_until_other_conditions_are_met() -> Result<()> {
+        let room = test_room_with_condition(WinCondition::AllOf {
+            conditions: vec![
+                WinCondition::CardsFinish,
+                WinCondition::FixedRounds { target_rounds: 3 },
+            ],
+        });
+        let mut state = room.state.write().await;
+        state.round = 1;
+        assert!(!room.deck_depletion_ends_game(&state));
+
+        state.round = 3;
+        assert!(room.deck_depletion_ends_game(&state));
+
+        Ok(())
+    }
 
+    #[tokio::test]
+    async fn final_standings_apply_tiebreak_rules_in_order() -> Result<()> {
+        let room = test_room();
+        let mut state = room.state.write().await;
+        add_player(&mut state, "a", 10);
+        add_player(&mut state, "b", 10);
+        add_player(&mut state, "c", 7);
+        state.member_to_storyteller_successes.insert("a".into(), 2);
+        state.member_to_storyteller_successes.insert("b".into(), 2);
+        state.member_to_beauty_points.insert("b".into(), 4);
+
+        let standings = room.final_standings(&state);
         assert_eq!(
-            state.observer_hand.get("obs"),
-            Some(&observer_hand),
-            "observer preserved hand should not be refreshed"
+            standings
+                .iter()
+                .map(|standing| (standing.name.as_str(), standing.rank))
+                .collect::<Vec<_>>(),
+            vec![("a", 1), ("b", 1), ("c", 3)],
+            "without tiebreak rules, equal scores share a rank"
         );
 
-        for player in ["host", "p2", "p3"] {
+        state.tiebreak_rules = vec![
+            TiebreakRule::StorytellerSuccesses,
+            TiebreakRule::BeautyPoints,
+        ];
+        let standings = room.final_standings(&state);
+        assert_eq!(standings[0].name, "b");
+        assert_eq!(standings[0].rank, 1);
+        assert_eq!(standings[1].name, "a");
+        assert_eq!(standings[1].rank, 2);
+        assert_eq!(standings[1].decided_by, Some(TiebreakRule::BeautyPoints));
+        assert_eq!(standings[2].decided_by, None);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn completed_game_is_rated_from_tiebroken_standings() -> Result<()> {
+        let room = test_room();
+        let mut state = room.state.write().await;
+        add_player(&mut state, "a", 4);
+        add_player(&mut state, "b", 6);
+        state.game_mode = GameMode::Stella;
+        state.round = 1;
+        state.current_game_id = Some("tiebreak-game".to_string());
+        state.current_game_started_at_s = Some(100);
+        state.stage = RoomStage::StellaReveal;
+        state.stella_clue_word = "ember".to_string();
+        state.stella_board_cards = vec!["s1".into(), "s2".into()];
+        state.stella_player_selections = HashMap::from([
+            ("a".to_string(), vec!["s1".to_string()]),
+            ("b".to_string(), vec!["s2".to_string()]),
+        ]);
+        state.stella_revealed_cards = vec!["s1".into()];
+        state.stella_point_change.insert("a".to_string(), 2);
+        room.init_stella_results(&mut state)?;
+
+        // both finish on 6, so only the tiebreak separates them
+        state.players.get_mut("a").unwrap().points = 6;
+        state.players.get_mut("b").unwrap().points = 6;
+        state.tiebreak_rules = vec![TiebreakRule::BeautyPoints];
+        state.member_to_beauty_points.insert("b".into(), 4);
+        room.finalize_current_game(&mut state)?;
+
+        for (name, placement) in [("b", 1), ("a", 2)] {
+            let history = room
+                .most_beautiful_stats
+                .player_rating_history(&room.member_hash_for_name(&state, name), None)?;
             assert_eq!(
-                state.player_hand.get(player).map(|hand| hand.len()),
-                Some(3),
-                "refreshed hand should be topped up to cards-per-hand"
+                history
+                    .history
+                    .iter()
+                    .map(|point| point.placement)
+                    .collect::<Vec<_>>(),
+                vec![placement],
+                "{name} should be rated by the End screen rank"
             );
         }
 
@@ -22429,135 +26241,180 @@ alpha
     }
 
     #[tokio::test]
-    async fn refresh_hands_is_rejected_in_cards_finish_when_deck_is_short() -> Result<()> {
-        let room = test_room_with_condition(WinCondition::CardsFinish);
+    async fn sudden_death_tiebreak_delays_game_end_while_leaders_are_tied() -> Result<()> {
+        let room = test_room_with_condition(WinCondition::Points { target_points: 10 });
+        let mut state = room.state.write().await;
+        add_player(&mut state, "a", 10);
+        add_player(&mut state, "b", 10);
+        add_player(&mut state, "c", 4);
+        state.tiebreak_rules = vec![TiebreakRule::ClueRating, TiebreakRule::SuddenDeath];
+        assert!(
+            !room.should_end_game(&state),
+            "tied leaders should trigger a sudden-death round"
+        );
 
-        let host_before = vec!["h1".to_string(), "h2".to_string(), "h3".to_string()];
-        let p2_before = vec!["p21".to_string(), "p22".to_string(), "p23".to_string()];
-        let p3_before = vec!["p31".to_string(), "p32".to_string(), "p33".to_string()];
-        let discard_before = vec!["dx".to_string(), "dy".to_string(), "dz".to_string()];
+        state.sudden_death_rounds_played = MAX_SUDDEN_DEATH_ROUNDS;
+        assert!(
+            room.should_end_game(&state),
+            "sudden death should stop after the configured number of extra rounds"
+        );
 
-        {
-            let mut state = room.state.write().await;
-            add_player(&mut state, "host", 0);
-            add_player(&mut state, "p2", 0);
-            add_player(&mut state, "p3", 0);
-            state.player_order = vec!["host".into(), "p2".into(), "p3".into()];
-            state.stage = RoomStage::ActiveChooses;
-            state.cards_per_hand = 3;
-            state
-                .player_hand
-                .insert("host".to_string(), host_before.clone());
-            state
-                .player_hand
-                .insert("p2".to_string(), p2_before.clone());
-            state
-                .player_hand
-                .insert("p3".to_string(), p3_before.clone());
-            state.deck = vec!["draw1".to_string(), "draw2".to_string()];
-            state.discard_pile = discard_before.clone();
-            state.moderators.insert("host".to_string());
-            setup_connected_member(&mut state, "host", "t-host", 117);
+        state.sudden_death_rounds_played = 0;
+        state.players.get_mut("a").unwrap().points = 13;
+        assert!(room.should_end_game(&state));
+        match room.end_game_msg(&state) {
+            ServerMsg::EndGame { standings, .. } => {
+                assert_eq!(standings[0].name, "a");
+                assert_eq!(standings[0].rank, 1);
+            }
+            other => panic!("expected EndGame, got {other:?}"),
         }
 
-        room.handle_client_msg("host", 117, to_ws(ClientMsg::RefreshHands {}))
-            .await?;
+        Ok(())
+    }
 
-        let state = room.state.read().await;
-        assert_eq!(
-            state.player_hand.get("host"),
-            Some(&host_before),
-            "refresh should not discard host hand when cards-finish deck is insufficient"
-        );
-        assert_eq!(
-            state.player_hand.get("p2"),
-            Some(&p2_before),
-            "refresh should not discard p2 hand when cards-finish deck is insufficient"
+    #[tokio::test]
+    async fn observers_with_leftover_points_cannot_force_sudden_death_or_take_first() -> Result<()>
+    {
+        let room = test_room_with_condition(WinCondition::Points { target_points: 10 });
+        let mut state = room.state.write().await;
+        add_player(&mut state, "a", 12);
+        add_player(&mut state, "b", 8);
+        add_player(&mut state, "c", 4);
+        state.observers.insert(
+            "obs".to_string(),
+            ObserverInfo {
+                connected: true,
+                points: Some(12),
+                join_requested: false,
+                auto_join_on_next_round: false,
+            },
         );
-        assert_eq!(
-            state.player_hand.get("p3"),
-            Some(&p3_before),
-            "refresh should not discard p3 hand when cards-finish deck is insufficient"
+        state.tiebreak_rules = vec![TiebreakRule::SuddenDeath];
+        assert!(
+            room.should_end_game(&state),
+            "an observer tied with the leader should not force a sudden-death round"
         );
+
+        state.observers.get_mut("obs").unwrap().points = Some(15);
+        let standings = room.final_standings(&state);
         assert_eq!(
-            state.discard_pile, discard_before,
-            "cards-finish refresh rejection should not mutate discard pile"
+            standings
+                .iter()
+                .map(|standing| (standing.name.as_str(), standing.rank, standing.observer))
+                .collect::<Vec<_>>(),
+            vec![
+                ("a", 1, false),
+                ("b", 2, false),
+                ("c", 3, false),
+                ("obs", 4, true)
+            ],
+            "observers rank below every active player"
         );
 
         Ok(())
     }
 
     #[tokio::test]
-    async fn round_start_discard_count_discards_from_each_hand_on_round_init() -> Result<()> {
+    async fn secret_objectives_track_round_results_and_add_bonus_to_standings() -> Result<()> {
         let room = test_room();
-
-        {
-            let mut state = room.state.write().await;
-            add_player(&mut state, "a", 0);
-            add_player(&mut state, "b", 0);
-            add_player(&mut state, "c", 0);
-            state.player_order = vec!["a".into(), "b".into(), "c".into()];
-            state.active_player = 0;
-            state.round = 1;
-            state.stage = RoomStage::Results;
-            state.cards_per_hand = 4;
-            state.round_start_discard_all_unpinned = false;
-            state.round_start_discard_count = 2;
-            state.player_hand.insert(
-                "a".to_string(),
-                vec!["a1".into(), "a2".into(), "a3".into(), "a4".into()],
-            );
-            state.player_hand.insert(
-                "b".to_string(),
-                vec!["b1".into(), "b2".into(), "b3".into(), "b4".into()],
-            );
-            state.player_hand.insert(
-                "c".to_string(),
-                vec!["c1".into(), "c2".into(), "c3".into(), "c4".into()],
-            );
-            state.deck = (0..200).map(|i| format!("draw-{}", i)).collect();
-            state.discard_pile.clear();
+        let mut state = room.state.write().await;
+        for player in ["a", "b", "c", "d", "e"] {
+            add_player(&mut state, player, 5);
+            state
+                .player_to_current_cards
+                .insert(player.into(), vec![format!("c{player}")]);
         }
-
-        {
-            let mut state = room.state.write().await;
-            room.init_round(&mut state).await?;
+        state.active_player = 0;
+        state.storyteller_loss_complement_auto = false;
+        state.storyteller_loss_complement = 0;
+        state.secret_objective_bonus_points = 4;
+        for (player, kind) in [
+            ("a", SecretObjectiveKind::PreciseStoryteller),
+            ("b", SecretObjectiveKind::DecoyDoubleFool),
+            ("c", SecretObjectiveKind::DecoyDoubleFool),
+        ] {
+            state.member_secret_objectives.insert(
+                player.into(),
+                SecretObjective {
+                    kind,
+                    progress: 0,
+                    target: kind.target(),
+                    completed: false,
+                },
+            );
         }
+        state.player_to_votes.insert("b".into(), vec!["ca".into()]);
+        state.player_to_votes.insert("c".into(), vec!["cb".into()]);
+        state.player_to_votes.insert("d".into(), vec!["cb".into()]);
+        state.player_to_votes.insert("e".into(), vec!["cc".into()]);
+        assert!(room.storyteller_round_succeeded(&state));
 
-        let state = room.state.read().await;
+        room.advance_secret_objectives(&mut state, false);
+        assert!(state.member_secret_objectives["a"].completed);
+        assert!(state.member_secret_objectives["b"].completed);
         assert!(
-            matches!(state.stage, RoomStage::ActiveChooses),
-            "round init should move to storyteller choosing"
+            !state.member_secret_objectives["c"].completed,
+            "a single fooled voter should not complete the decoy objective"
         );
+
+        let standings = room.final_standings(&state);
+        let a = standings
+            .iter()
+            .find(|standing| standing.name == "a")
+            .unwrap();
+        assert_eq!(a.points, 9);
+        assert_eq!(a.secret_objective_bonus, 4);
+        let c = standings
+            .iter()
+            .find(|standing| standing.name == "c")
+            .unwrap();
+        assert_eq!(c.points, 5);
         assert_eq!(
-            state.discard_pile.len(),
-            6,
-            "round-start discard should remove N cards per active player"
+            c.secret_objective.as_ref().map(|objective| objective.kind),
+            Some(SecretObjectiveKind::DecoyDoubleFool)
         );
-        for player in ["a", "b", "c"] {
-            assert_eq!(
-                state.player_hand.get(player).map(|hand| hand.len()),
-                Some(4),
-                "round-start discard must be followed by top-up"
-            );
-        }
 
         Ok(())
     }
 
     #[tokio::test]
-    async fn room_state_defaults_to_discard_all_unpinned_at_round_start() -> Result<()> {
+    async fn secret_objectives_are_dealt_privately_and_revealed_at_end() -> Result<()> {
         let room = test_room();
-        let state = room.state.write().await;
+        let mut state = room.state.write().await;
+        let mut receivers = Vec::new();
+        for player in ["a", "b", "c"] {
+            add_player(&mut state, player, 0);
+            receivers.push(attach_test_socket(&mut state, player));
+        }
+        state.secret_objectives_enabled = true;
+
+        room.init_round(&mut state).await?;
 
+        for rx in receivers.iter_mut() {
+            let mut dealt = None;
+            while let Ok(msg) = rx.try_recv() {
+                if let ServerMsg::SecretObjective { objective } = msg {
+                    dealt = Some(objective);
+                }
+            }
+            let objective = dealt.expect("every player should receive their own objective");
+            assert!(!objective.completed);
+        }
         match room.room_state(&state) {
             ServerMsg::RoomState {
-                round_start_discard_all_unpinned,
+                revealed_secret_objectives,
                 ..
-            } => assert!(
-                round_start_discard_all_unpinned,
-                "new rooms should default to discarding every unpinned card at round start"
-            ),
+            } => assert!(revealed_secret_objectives.is_empty()),
+            other => panic!("expected RoomState, got {other:?}"),
+        }
+
+        room.transition_to_end(&mut state)?;
+        match room.room_state(&state) {
+            ServerMsg::RoomState {
+                revealed_secret_objectives,
+                ..
+            } => assert_eq!(revealed_secret_objectives.len(), 3),
             other => panic!("expected RoomState, got {other:?}"),
         }
 
@@ -22565,316 +26422,666 @@ alpha
     }
 
     #[tokio::test]
-    async fn round_start_discards_all_unpinned_cards_and_preserves_pins() -> Result<()> {
+    async fn chat_history_is_bounded_and_sends_are_rate_limited() -> Result<()> {
         let room = test_room();
+        let mut rx = {
+            let mut state = room.state.write().await;
+            add_player(&mut state, "a", 0);
+            setup_connected_member(&mut state, "a", "t-a", 7);
+            attach_test_socket(&mut state, "a")
+        };
+
+        for index in 0..=CHAT_HISTORY_LIMIT {
+            room.state.write().await.chat_recent_actions_ms.clear();
+            room.handle_client_msg(
+                "a",
+                7,
+                to_ws(ClientMsg::SendChat {
+                    text: format!("  hello {index}  "),
+                }),
+            )
+            .await?;
+        }
+        {
+            let state = room.state.write().await;
+            assert_eq!(state.chat_history.len(), CHAT_HISTORY_LIMIT);
+            assert_eq!(
+                state.chat_history.front().map(|message| message.id),
+                Some(2)
+            );
+            assert_eq!(
+                state
+                    .chat_history
+                    .back()
+                    .map(|message| message.text.as_str()),
+                Some("hello 100")
+            );
+        }
+
+        room.state.write().await.chat_recent_actions_ms.clear();
+        for _ in 0..=CHAT_RATE_LIMIT_MAX_ACTIONS {
+            room.handle_client_msg(
+                "a",
+                7,
+                to_ws(ClientMsg::SendChat {
+                    text: "spam".to_string(),
+                }),
+            )
+            .await?;
+        }
+        let mut rate_limited = false;
+        while let Ok(msg) = rx.try_recv() {
+            if let ServerMsg::ErrorMsg(error) = msg {
+                rate_limited |= error.contains("too quickly");
+            }
+        }
+        assert!(rate_limited, "sending past the limit should be refused");
+        let state = room.state.write().await;
+        assert_eq!(
+            state.chat_history.back().map(|message| message.id),
+            Some((CHAT_HISTORY_LIMIT + 1 + CHAT_RATE_LIMIT_MAX_ACTIONS) as u64)
+        );
+
+        Ok(())
+    }
 
+    #[tokio::test]
+    async fn chat_is_restricted_for_storyteller_and_voting_players() -> Result<()> {
+        let room = test_room();
         {
             let mut state = room.state.write().await;
-            add_player(&mut state, "a", 0);
-            add_player(&mut state, "b", 0);
-            add_player(&mut state, "c", 0);
-            state.player_order = vec!["a".into(), "b".into(), "c".into()];
+            add_player(&mut state, "st", 0);
+            add_player(&mut state, "p2", 0);
+            add_player(&mut state, "p3", 0);
+            state.observers.insert(
+                "obs".to_string(),
+                ObserverInfo {
+                    connected: true,
+                    points: None,
+                    join_requested: false,
+                    auto_join_on_next_round: false,
+                },
+            );
+            for (index, member) in ["st", "p2", "obs"].into_iter().enumerate() {
+                setup_connected_member(&mut state, member, &format!("t-{member}"), index as u64);
+            }
             state.active_player = 0;
-            state.round = 1;
-            state.stage = RoomStage::Results;
-            state.cards_per_hand = 4;
-            state.round_start_discard_all_unpinned = true;
-            state.round_start_discard_count = 0;
-            state
-                .player_hand
-                .insert("a".into(), vec!["a-pin".into(), "a1".into(), "a2".into()]);
-            state
-                .player_hand
-                .insert("b".into(), vec!["b-pin".into(), "b1".into(), "b2".into()]);
-            state
-                .player_hand
-                .insert("c".into(), vec!["c-pin".into(), "c1".into(), "c2".into()]);
-            state
-                .player_pinned_cards
-                .insert("a".into(), HashSet::from(["a-pin".to_string()]));
-            state
-                .player_pinned_cards
-                .insert("b".into(), HashSet::from(["b-pin".to_string()]));
-            state
-                .player_pinned_cards
-                .insert("c".into(), HashSet::from(["c-pin".to_string()]));
-            state.deck = (0..200).map(|i| format!("draw-{}", i)).collect();
-            state.discard_pile.clear();
+            state.stage = RoomStage::PlayersChoose;
+        }
+        let send = |member: &'static str, generation: u64| {
+            let room = &room;
+            async move {
+                room.handle_client_msg(
+                    member,
+                    generation,
+                    to_ws(ClientMsg::SendChat {
+                        text: format!("from {member}"),
+                    }),
+                )
+                .await
+            }
+        };
+
+        send("st", 0).await?;
+        send("p2", 1).await?;
+        room.state.write().await.stage = RoomStage::Voting;
+        send("p2", 1).await?;
+        send("obs", 2).await?;
+        {
+            let state = room.state.write().await;
+            assert_eq!(
+                state
+                    .chat_history
+                    .iter()
+                    .map(|message| message.sender.as_str())
+                    .collect::<Vec<_>>(),
+                vec!["p2", "obs"],
+                "storyteller is blocked while players choose and players are blocked while voting"
+            );
         }
 
+        room.state.write().await.chat_stage_restrictions_enabled = false;
+        send("p2", 1).await?;
+        assert_eq!(room.state.write().await.chat_history.len(), 3);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn moderators_can_mute_members_and_delete_chat_messages() -> Result<()> {
+        let room = test_room();
         {
             let mut state = room.state.write().await;
-            room.init_round(&mut state).await?;
+            add_player(&mut state, "host", 0);
+            add_player(&mut state, "p2", 0);
+            state.moderators.insert("host".to_string());
+            setup_connected_member(&mut state, "host", "t-host", 1);
+            setup_connected_member(&mut state, "p2", "t-p2", 2);
         }
 
-        let state = room.state.read().await;
-        for (player, pinned, unpinned) in [
-            ("a", "a-pin", vec!["a1", "a2"]),
-            ("b", "b-pin", vec!["b1", "b2"]),
-            ("c", "c-pin", vec!["c1", "c2"]),
-        ] {
-            let hand = state
-                .player_hand
-                .get(player)
-                .expect("player should have hand");
+        room.handle_client_msg(
+            "p2",
+            2,
+            to_ws(ClientMsg::SendChat {
+                text: "hi".to_string(),
+            }),
+        )
+        .await?;
+        for _ in 0..2 {
+            room.handle_client_msg(
+                "host",
+                1,
+                to_ws(ClientMsg::React {
+                    message_id: 1,
+                    emoji: "👍".to_string(),
+                }),
+            )
+            .await?;
+        }
+        room.handle_client_msg(
+            "p2",
+            2,
+            to_ws(ClientMsg::React {
+                message_id: 1,
+                emoji: "😂".to_string(),
+            }),
+        )
+        .await?;
+        {
+            let state = room.state.write().await;
+            let reactions = &state.chat_history[0].reactions;
             assert!(
-                hand.contains(&pinned.to_string()),
-                "pinned card should stay in {player}'s hand"
+                !reactions.contains_key("👍"),
+                "reacting twice should toggle off"
+            );
+            assert_eq!(
+                reactions.get("😂"),
+                Some(&BTreeSet::from(["p2".to_string()]))
             );
-            assert_eq!(hand.len(), 4, "{player}'s hand should be topped up");
-            for card in unpinned {
-                assert!(
-                    state.discard_pile.contains(&card.to_string()),
-                    "unpinned card {card} should be discarded at round start"
-                );
-                assert!(
-                    !hand.contains(&card.to_string()),
-                    "unpinned card {card} should leave {player}'s hand"
-                );
-            }
         }
 
+        room.handle_client_msg(
+            "p2",
+            2,
+            to_ws(ClientMsg::SetChatMuted {
+                member: "host".to_string(),
+                muted: true,
+            }),
+        )
+        .await?;
+        room.handle_client_msg(
+            "host",
+            1,
+            to_ws(ClientMsg::SetChatMuted {
+                member: "p2".to_string(),
+                muted: true,
+            }),
+        )
+        .await?;
+        room.handle_client_msg(
+            "p2",
+            2,
+            to_ws(ClientMsg::SendChat {
+                text: "muted".to_string(),
+            }),
+        )
+        .await?;
+        room.handle_client_msg(
+            "host",
+            1,
+            to_ws(ClientMsg::DeleteChatMessage { message_id: 1 }),
+        )
+        .await?;
+
+        let state = room.state.write().await;
+        assert!(state.chat_muted_members.contains("p2"));
+        assert!(!state.chat_muted_members.contains("host"));
+        assert!(state.chat_history.is_empty());
+
         Ok(())
     }
 
     #[tokio::test]
-    async fn numeric_round_start_discard_never_discards_pinned_cards_and_clamps() -> Result<()> {
+    async fn spectator_links_are_moderator_managed_and_not_members() -> Result<()> {
         let room = test_room();
-
         {
             let mut state = room.state.write().await;
-            add_player(&mut state, "a", 0);
-            add_player(&mut state, "b", 0);
-            add_player(&mut state, "c", 0);
-            state.player_order = vec!["a".into(), "b".into(), "c".into()];
-            state.active_player = 0;
-            state.round = 1;
-            state.stage = RoomStage::Results;
-            state.cards_per_hand = 4;
-            state.round_start_discard_all_unpinned = false;
-            state.round_start_discard_count = 3;
-            state.player_hand.insert(
-                "a".into(),
-                vec!["a-pin-1".into(), "a-pin-2".into(), "a-free".into()],
-            );
-            state.player_hand.insert(
-                "b".into(),
-                vec!["b-pin-1".into(), "b-pin-2".into(), "b-free".into()],
-            );
-            state.player_hand.insert(
-                "c".into(),
-                vec!["c-pin-1".into(), "c-pin-2".into(), "c-free".into()],
-            );
-            state.player_pinned_cards.insert(
-                "a".into(),
-                HashSet::from(["a-pin-1".to_string(), "a-pin-2".to_string()]),
-            );
-            state.player_pinned_cards.insert(
-                "b".into(),
-                HashSet::from(["b-pin-1".to_string(), "b-pin-2".to_string()]),
+            add_player(&mut state, "host", 0);
+            add_player(&mut state, "p2", 0);
+            state.moderators.insert("host".to_string());
+            setup_connected_member(&mut state, "host", "t-host", 1);
+            setup_connected_member(&mut state, "p2", "t-p2", 2);
+        }
+
+        room.handle_client_msg("p2", 2, to_ws(ClientMsg::CreateSpectatorLink {}))
+            .await?;
+        assert!(room.state.write().await.spectator_token.is_none());
+
+        room.handle_client_msg("host", 1, to_ws(ClientMsg::CreateSpectatorLink {}))
+            .await?;
+        let first_token = room
+            .state
+            .write()
+            .await
+            .spectator_token
+            .clone()
+            .expect("moderator should create a spectator link");
+        assert_eq!(first_token.len(), 32);
+        {
+            let state = room.state.write().await;
+            let token_for = |msg: ServerMsg| match msg {
+                ServerMsg::RoomState {
+                    spectator_token, ..
+                } => spectator_token,
+                _ => None,
+            };
+            assert_eq!(
+                token_for(room.member_view(&state, "host", room.room_state(&state))),
+                Some(first_token.clone())
             );
-            state.player_pinned_cards.insert(
-                "c".into(),
-                HashSet::from(["c-pin-1".to_string(), "c-pin-2".to_string()]),
+            assert_eq!(
+                token_for(room.member_view(&state, "p2", room.room_state(&state))),
+                None,
+                "only moderators should receive the spectator link"
             );
-            state.deck = (0..200).map(|i| format!("draw-{}", i)).collect();
-            state.discard_pile.clear();
+            assert_eq!(token_for(room.spectator_snapshot(&state).remove(0)), None);
         }
 
+        room.handle_client_msg("host", 1, to_ws(ClientMsg::CreateSpectatorLink {}))
+            .await?;
+        room.handle_client_msg(
+            "host",
+            1,
+            to_ws(ClientMsg::SetSpectatorDelay {
+                seconds: MAX_SPECTATOR_DELAY_S + 1,
+            }),
+        )
+        .await?;
         {
-            let mut state = room.state.write().await;
-            room.init_round(&mut state).await?;
+            let state = room.state.write().await;
+            assert_ne!(
+                state.spectator_token.as_deref(),
+                Some(first_token.as_str()),
+                "recreating the link should invalidate the old token"
+            );
+            assert_eq!(state.spectator_delay_s, MAX_SPECTATOR_DELAY_S);
         }
 
-        let state = room.state.read().await;
-        for (player, pinned, free) in [
-            ("a", vec!["a-pin-1", "a-pin-2"], "a-free"),
-            ("b", vec!["b-pin-1", "b-pin-2"], "b-free"),
-            ("c", vec!["c-pin-1", "c-pin-2"], "c-free"),
-        ] {
-            let hand = state
-                .player_hand
-                .get(player)
-                .expect("player should have hand");
-            for card in pinned {
-                assert!(
-                    hand.contains(&card.to_string()),
-                    "numeric discard must preserve pinned card {card}"
-                );
+        let _spectator = room.spectator_broadcast.subscribe();
+        {
+            let state = room.state.write().await;
+            assert_eq!(room.total_members(&state), 2);
+            match room.room_state(&state) {
+                ServerMsg::RoomState {
+                    players,
+                    observers,
+                    spectator_count,
+                    ..
+                } => {
+                    assert_eq!(players.len(), 2);
+                    assert!(observers.is_empty());
+                    assert_eq!(spectator_count, 1);
+                }
+                _ => return Err(anyhow!("Expected RoomState message")),
             }
-            assert!(
-                state.discard_pile.contains(&free.to_string()),
-                "numeric discard should discard the only unpinned card for {player}"
-            );
-            assert_eq!(hand.len(), 4, "{player}'s hand should be topped up");
         }
 
+        room.handle_client_msg("host", 1, to_ws(ClientMsg::RevokeSpectatorLink {}))
+            .await?;
+        assert!(room.state.write().await.spectator_token.is_none());
+
         Ok(())
     }
 
     #[tokio::test]
-    async fn refresh_hands_preserves_pinned_cards_and_redraws_unpinned_only() -> Result<()> {
+    async fn spectators_receive_public_stage_views_without_hands() -> Result<()> {
         let room = test_room();
+        let mut state = room.state.write().await;
+        add_player(&mut state, "a", 0);
+        add_player(&mut state, "b", 0);
+        add_player(&mut state, "c", 0);
+        state.player_order = vec!["a".into(), "b".into(), "c".into()];
+        state.active_player = 0;
+        state.voting_order_seed = 7;
+        for player in ["a", "b", "c"] {
+            state
+                .player_to_current_cards
+                .insert(player.into(), vec![format!("card-{player}")]);
+        }
+        state.stage = RoomStage::Voting;
+        let mut spectator = room.spectator_broadcast.subscribe();
 
-        {
+        room.broadcast_spectator_stage_msg(&state);
+        match spectator.try_recv()? {
+            ServerMsg::BeginVoting {
+                center_cards,
+                hand,
+                pinned_cards,
+                ..
+            } => {
+                assert_eq!(center_cards.len(), 3);
+                assert!(hand.is_empty());
+                assert!(pinned_cards.is_empty());
+            }
+            _ => return Err(anyhow!("Expected BeginVoting message")),
+        }
+
+        state.stage = RoomStage::ActiveChooses;
+        let snapshot = room.spectator_snapshot(&state);
+        assert_eq!(
+            snapshot.len(),
+            2,
+            "spectators should not receive a hand-bearing StartRound"
+        );
+        assert!(matches!(snapshot[0], ServerMsg::RoomState { .. }));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn stats_export_tokens_are_moderator_only_and_expire() -> Result<()> {
+        let room = test_room();
+        let mut host_rx = {
             let mut state = room.state.write().await;
             add_player(&mut state, "host", 0);
             add_player(&mut state, "p2", 0);
-            add_player(&mut state, "p3", 0);
-            state.player_order = vec!["host".into(), "p2".into(), "p3".into()];
-            state.stage = RoomStage::ActiveChooses;
-            state.cards_per_hand = 3;
-            state.player_hand.insert(
-                "host".into(),
-                vec!["h-pin".into(), "h1".into(), "h2".into()],
-            );
-            state.player_hand.insert(
-                "p2".into(),
-                vec!["p2-pin".into(), "p21".into(), "p22".into()],
-            );
-            state.player_hand.insert(
-                "p3".into(),
-                vec!["p3-pin".into(), "p31".into(), "p32".into()],
-            );
-            state
-                .player_pinned_cards
-                .insert("host".into(), HashSet::from(["h-pin".to_string()]));
-            state
-                .player_pinned_cards
-                .insert("p2".into(), HashSet::from(["p2-pin".to_string()]));
-            state
-                .player_pinned_cards
-                .insert("p3".into(), HashSet::from(["p3-pin".to_string()]));
             state.moderators.insert("host".to_string());
-            setup_connected_member(&mut state, "host", "t-host", 118);
-        }
+            setup_connected_member(&mut state, "host", "t-host", 1);
+            setup_connected_member(&mut state, "p2", "t-p2", 2);
+            attach_test_socket(&mut state, "host")
+        };
 
-        room.handle_client_msg("host", 118, to_ws(ClientMsg::RefreshHands {}))
+        room.handle_client_msg("p2", 2, to_ws(ClientMsg::RequestStatsExportToken {}))
             .await?;
+        assert!(room.state.write().await.stats_export_token.is_none());
 
-        let state = room.state.read().await;
-        for (player, pinned, discarded) in [
-            ("host", "h-pin", vec!["h1", "h2"]),
-            ("p2", "p2-pin", vec!["p21", "p22"]),
-            ("p3", "p3-pin", vec!["p31", "p32"]),
-        ] {
-            let hand = state
-                .player_hand
-                .get(player)
-                .expect("player should have hand");
-            assert!(hand.contains(&pinned.to_string()));
-            assert_eq!(hand.len(), 3);
-            for card in discarded {
-                assert!(
-                    state.discard_pile.contains(&card.to_string()),
-                    "refresh should discard unpinned card {card}"
-                );
-                assert!(
-                    !hand.contains(&card.to_string()),
-                    "refresh should remove unpinned card {card} from {player}'s hand"
-                );
+        async fn request_token(
+            room: &Room,
+            host_rx: &mut mpsc::Receiver<ServerMsg>,
+        ) -> Result<String> {
+            room.handle_client_msg("host", 1, to_ws(ClientMsg::RequestStatsExportToken {}))
+                .await?;
+            match host_rx.try_recv()? {
+                ServerMsg::StatsExportToken {
+                    token,
+                    expires_at_s,
+                } => {
+                    assert!(expires_at_s > get_time_s());
+                    Ok(token)
+                }
+                _ => Err(anyhow!("Expected StatsExportToken message")),
             }
         }
+        let token = request_token(&room, &mut host_rx).await?;
+        assert!(room.stats_export_token_is_valid(&token).await);
+        assert!(!room.stats_export_token_is_valid("wrong").await);
+        assert_eq!(
+            request_token(&room, &mut host_rx).await?,
+            token,
+            "moderators should share the token until it expires"
+        );
+
+        room.state.write().await.stats_export_token_expires_at_s = 0;
+        assert!(!room.stats_export_token_is_valid(&token).await);
+        assert_ne!(request_token(&room, &mut host_rx).await?, token);
 
         Ok(())
     }
 
     #[tokio::test]
-    async fn pin_changes_are_allowed_while_storyteller_is_choosing() -> Result<()> {
+    async fn stella_results_record_round_in_game_audit() -> Result<()> {
         let room = test_room();
-        {
+        let mut state = room.state.write().await;
+
+        add_player(&mut state, "a", 4);
+        add_player(&mut state, "b", 0);
+        add_player(&mut state, "c", 0);
+        state.game_mode = GameMode::Stella;
+        state.round = 1;
+        state.current_game_id = Some("stella-game".to_string());
+        state.current_game_started_at_s = Some(100);
+        state.stage = RoomStage::StellaReveal;
+        state.stella_clue_word = "ember".to_string();
+        state.stella_board_cards = vec!["s1".into(), "s2".into(), "s3".into()];
+        state.stella_player_selections = HashMap::from([
+            ("a".to_string(), vec!["s1".to_string(), "s2".to_string()]),
+            ("b".to_string(), vec!["s1".to_string(), "s3".to_string()]),
+        ]);
+        state.stella_revealed_cards = vec!["s1".into(), "s3".into()];
+        state.stella_card_points = HashMap::from([("s1".to_string(), 2), ("s3".to_string(), 0)]);
+        state.stella_fallen_players.insert("b".to_string());
+        state.stella_dark_player = Some("c".to_string());
+        state.stella_point_change.insert("a".to_string(), 2);
+
+        room.init_stella_results(&mut state)?;
+
+        let replay = room
+            .most_beautiful_stats
+            .export_game_replay("stella-game")?
+            .ok_or_else(|| anyhow!("expected Stella game to be recorded"))?;
+        assert_eq!(replay.game_mode, "stella");
+        assert!(replay.rounds.is_empty());
+        assert_eq!(replay.stella_rounds.len(), 1);
+        let round = &replay.stella_rounds[0];
+        assert_eq!(round.clue_word, "ember");
+        assert_eq!(
+            round
+                .board_cards
+                .iter()
+                .map(|card| (card.card_hash.as_str(), card.reveal_order, card.points))
+                .collect::<Vec<_>>(),
+            vec![
+                ("s1", Some(0), Some(2)),
+                ("s2", None, None),
+                ("s3", Some(1), Some(0))
+            ]
+        );
+        assert_eq!(round.selections.len(), 4);
+        let player = |name: &str| {
+            round
+                .players
+                .iter()
+                .find(|player| player.player_display_name == name)
+                .cloned()
+        };
+        let a = player("a").ok_or_else(|| anyhow!("expected a"))?;
+        assert_eq!((a.score_delta, a.total_after_round), (2, 6));
+        assert!(player("b").is_some_and(|b| b.fell && !b.in_the_dark));
+        assert!(player("c").is_some_and(|c| c.in_the_dark && !c.fell));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn stats_opt_out_hides_member_name_in_recorded_rounds() -> Result<()> {
+        let room = test_room();
+        let mut b_rx = {
             let mut state = room.state.write().await;
             add_player(&mut state, "a", 0);
             add_player(&mut state, "b", 0);
-            add_player(&mut state, "c", 0);
-            state.stage = RoomStage::ActiveChooses;
-            state.player_order = vec!["a".into(), "b".into(), "c".into()];
-            state.active_player = 0;
-            state
-                .player_hand
-                .insert("a".into(), vec!["a-1".into(), "a-2".into()]);
-            setup_connected_member(&mut state, "a", "t-a", 119);
-        }
+            setup_connected_member(&mut state, "a", "t-a", 1);
+            setup_connected_member(&mut state, "b", "t-b", 2);
+            attach_test_socket(&mut state, "b")
+        };
 
-        room.handle_client_msg(
-            "a",
-            119,
-            to_ws(ClientMsg::SetHandCardPinned {
-                card: "a-1".to_string(),
-                pinned: true,
-            }),
-        )
-        .await?;
+        room.handle_client_msg("b", 2, to_ws(ClientMsg::SetStatsOptOut { opt_out: true }))
+            .await?;
+        assert!(matches!(
+            b_rx.try_recv()?,
+            ServerMsg::StatsOptOut { opt_out: true }
+        ));
 
-        let state = room.state.read().await;
-        assert!(
-            state
-                .player_pinned_cards
-                .get("a")
-                .map(|cards| cards.contains("a-1"))
-                .unwrap_or(false),
-            "pin changes should be applied during ActiveChooses"
+        let mut state = room.state.write().await;
+        state.game_mode = GameMode::Stella;
+        state.round = 1;
+        state.current_game_id = Some("opt-out-game".to_string());
+        state.current_game_started_at_s = Some(100);
+        state.stage = RoomStage::StellaReveal;
+        state.stella_clue_word = "ember".to_string();
+        state.stella_board_cards = vec!["s1".into(), "s2".into()];
+        state.stella_player_selections = HashMap::from([
+            ("a".to_string(), vec!["s1".to_string()]),
+            ("b".to_string(), vec!["s2".to_string()]),
+        ]);
+        state.stella_revealed_cards = vec!["s1".into()];
+        room.init_stella_results(&mut state)?;
+
+        let b_hash = room.member_hash_for_name(&state, "b");
+        let replay = room
+            .most_beautiful_stats
+            .export_game_replay("opt-out-game")?
+            .ok_or_else(|| anyhow!("expected game to be recorded"))?;
+        let names = replay.stella_rounds[0]
+            .players
+            .iter()
+            .map(|player| {
+                (
+                    player.player_hash == b_hash,
+                    player.player_display_name.as_str(),
+                )
+            })
+            .collect::<HashSet<_>>();
+        assert_eq!(
+            names,
+            HashSet::from([
+                (false, "a"),
+                (true, crate::most_beautiful_stats::ANONYMOUS_PLAYER_NAME)
+            ])
         );
 
         Ok(())
     }
 
     #[tokio::test]
-    async fn played_pinned_cards_leave_hand_and_are_unpinned_when_voting_begins() -> Result<()> {
+    async fn room_stats_require_membership_or_room_password() -> Result<()> {
         let room = test_room();
         {
             let mut state = room.state.write().await;
             add_player(&mut state, "a", 0);
-            add_player(&mut state, "b", 0);
-            add_player(&mut state, "c", 0);
-            state.stage = RoomStage::PlayersChoose;
-            state.player_order = vec!["a".into(), "b".into(), "c".into()];
-            state.active_player = 0;
-            state.cards_per_hand = 3;
-            state.player_hand.insert(
-                "a".into(),
-                vec!["a-played".into(), "a-stays".into(), "a-free".into()],
-            );
-            state
-                .player_pinned_cards
-                .insert("a".into(), HashSet::from(["a-played".to_string()]));
-            state
-                .player_to_current_cards
-                .insert("a".into(), vec!["a-played".into()]);
-            state
-                .player_to_current_cards
-                .insert("b".into(), vec!["b-1".into()]);
-            state
-                .player_to_current_cards
-                .insert("c".into(), vec!["c-1".into()]);
+            setup_connected_member(&mut state, "a", "t-a", 1);
+            setup_connected_member(&mut state, "gone", "t-gone", 2);
         }
+        assert!(
+            room.stats_read_is_allowed(test_ip(), Some("t-a"), None, None)
+                .await
+        );
+        assert!(
+            !room
+                .stats_read_is_allowed(test_ip(), Some("t-gone"), None, None)
+                .await
+        );
+        assert!(
+            !room
+                .stats_read_is_allowed(test_ip(), None, None, None)
+                .await
+        );
+        assert!(
+            !room
+                .stats_read_is_allowed(test_ip(), Some(""), None, Some(""))
+                .await
+        );
+        assert!(
+            !room
+                .stats_read_is_allowed(test_ip(), None, None, Some("secret"))
+                .await,
+            "rooms without a password need proof of membership"
+        );
 
-        {
+        let room_auth_id = {
             let mut state = room.state.write().await;
-            room.init_voting(&mut state).await?;
-        }
-
-        let state = room.state.read().await;
+            state.room_password_hash = Some(hash_room_password("secret"));
+            room.room_auth_id_for_member(&mut state, "a")
+                .ok_or_else(|| anyhow!("expected room auth id"))?
+        };
         assert!(
-            !state
-                .player_hand
-                .get("a")
-                .map(|hand| hand.contains(&"a-played".to_string()))
-                .unwrap_or(false),
-            "played pinned card should leave the player's hand"
+            room.stats_read_is_allowed(test_ip(), None, Some(&room_auth_id), None)
+                .await
         );
         assert!(
-            state
-                .player_pinned_cards
-                .get("a")
-                .map(|cards| !cards.contains("a-played"))
-                .unwrap_or(true),
-            "played card should be unpinned when it leaves the hand"
+            !room
+                .stats_read_is_allowed(test_ip(), None, Some("bogus"), None)
+                .await
+        );
+        assert!(
+            room.stats_read_is_allowed(test_ip(), None, None, Some("secret"))
+                .await
+        );
+        assert!(
+            !room
+                .stats_read_is_allowed(test_ip(), None, None, Some("wrong"))
+                .await
         );
 
         Ok(())
     }
 
+    #[tokio::test]
+    async fn unlisted_rooms_need_invite_key_and_hide_it_from_spectators() -> Result<()> {
+        assert!(test_room().invite_is_valid(None).await);
+
+        let room = test_room().with_unlisted(true);
+        let state = room.state.write().await;
+        let invite_key = state
+            .invite_key
+            .clone()
+            .ok_or_else(|| anyhow!("expected invite key"))?;
+        drop(state);
+        assert!(!room.invite_is_valid(None).await);
+        assert!(!room.invite_is_valid(Some("wrong")).await);
+        assert!(room.invite_is_valid(Some(&invite_key)).await);
+
+        let state = room.state.write().await;
+        match room.room_state(&state) {
+            ServerMsg::RoomState {
+                invite_key: Some(key),
+                ..
+            } => assert_eq!(key, invite_key),
+            _ => return Err(anyhow!("members should receive the invite key")),
+        }
+        assert!(matches!(
+            room.spectator_snapshot(&state)[0],
+            ServerMsg::RoomState {
+                invite_key: None,
+                ..
+            }
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn oversized_or_rapid_messages_disconnect_with_a_reason() {
+        let room = test_room().with_message_limits(WsMessageLimits {
+            rate: TokenBucketConfig::per_second(1, 2),
+            max_message_bytes: 16,
+        });
+        let mut bucket = TokenBucket::new(room.message_limits.rate.unwrap(), get_time_ms());
+        let small = WsMessage::Text("{}".to_string());
+
+        assert!(matches!(
+            room.message_limit_violation(&WsMessage::Text("x".repeat(17)), Some(&mut bucket)),
+            Some((DisconnectReason::MessageTooLarge, _))
+        ));
+        assert!(room
+            .message_limit_violation(&small, Some(&mut bucket))
+            .is_none());
+        assert!(room
+            .message_limit_violation(&small, Some(&mut bucket))
+            .is_none());
+        assert!(matches!(
+            room.message_limit_violation(&small, Some(&mut bucket)),
+            Some((DisconnectReason::RateLimited, _))
+        ));
+        assert!(room.message_limit_violation(&small, None).is_none());
+
+        let msg = serde_json::to_value(ServerMsg::Disconnected {
+            reason: DisconnectReason::RateLimited,
+            message: "slow down".to_string(),
+        })
+        .unwrap();
+        assert_eq!(msg["Disconnected"]["reason"], "rate_limited");
+    }
+
     #[tokio::test]
     async fn cycles_win_condition_uses_active_storyteller_counts_only() -> Result<()> {
         let room = test_room_with_condition(WinCondition::Cycles { target_cycles: 2 });
@@ -23802,16 +28009,343 @@ alpha
     }
 
     #[test]
-    fn room_password_hash_is_room_scoped() {
-        let a = hash_room_password("room-a", "secret");
-        let b = hash_room_password("room-b", "secret");
-        let a_again = hash_room_password("room-a", "secret");
+    fn room_password_hashes_are_salted_argon2() {
+        let a = hash_room_password("secret");
+        let b = hash_room_password("secret");
+
+        assert!(a.starts_with("$argon2id$"));
+        assert_ne!(a, b, "every hash gets its own salt");
+        assert!(verify_room_password(&a, "secret"));
+        assert!(verify_room_password(&b, "secret"));
+        assert!(!verify_room_password(&a, "Secret"));
+        assert!(!verify_room_password("not a phc string", "secret"));
+    }
+
+    #[tokio::test]
+    async fn wrong_room_passwords_back_off_per_ip() {
+        let room = test_room();
+        room.state.write().await.room_password_hash = Some(hash_room_password("secret"));
+        let attacker: IpAddr = "203.0.113.9".parse().unwrap();
+
+        for _ in 0..ROOM_PASSWORD_FREE_ATTEMPTS {
+            assert_eq!(
+                room.check_room_password(attacker, "guess").await,
+                RoomPasswordCheck::Incorrect
+            );
+        }
+        assert!(matches!(
+            room.check_room_password(attacker, "secret").await,
+            RoomPasswordCheck::RetryAfter { seconds: 1 }
+        ));
+        assert_eq!(
+            room.check_room_password(test_ip(), "secret").await,
+            RoomPasswordCheck::Correct,
+            "backoff is per IP"
+        );
+
+        // let the wait run out, then fail once more: the next wait doubles
+        room.password_failures
+            .lock()
+            .unwrap()
+            .get_mut(&attacker)
+            .unwrap()
+            .last_failure_ms -= 2_000;
+        assert_eq!(
+            room.check_room_password(attacker, "guess").await,
+            RoomPasswordCheck::Incorrect
+        );
+        assert!(matches!(
+            room.check_room_password(attacker, "secret").await,
+            RoomPasswordCheck::RetryAfter { seconds: 2 }
+        ));
+
+        room.password_failures
+            .lock()
+            .unwrap()
+            .get_mut(&attacker)
+            .unwrap()
+            .last_failure_ms -= 3_000;
+        assert_eq!(
+            room.check_room_password(attacker, "secret").await,
+            RoomPasswordCheck::Correct
+        );
+        assert!(room.password_failures.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn parallel_wrong_room_passwords_share_one_backoff() {
+        let room = Arc::new(test_room());
+        room.state.write().await.room_password_hash = Some(hash_room_password("secret"));
+        let attacker: IpAddr = "203.0.113.9".parse().unwrap();
+
+        let mut attempts = tokio::task::JoinSet::new();
+        for _ in 0..10 {
+            let room = room.clone();
+            attempts.spawn(async move { room.check_room_password(attacker, "guess").await });
+        }
+        let mut checked = 0;
+        let mut refused = 0;
+        while let Some(result) = attempts.join_next().await {
+            match result.unwrap() {
+                RoomPasswordCheck::Incorrect => checked += 1,
+                RoomPasswordCheck::RetryAfter { .. } => refused += 1,
+                RoomPasswordCheck::Correct => panic!("wrong password accepted"),
+            }
+        }
+        assert_eq!(checked, ROOM_PASSWORD_FREE_ATTEMPTS);
+        assert_eq!(refused, 10 - ROOM_PASSWORD_FREE_ATTEMPTS);
+        assert_eq!(
+            room.password_failures.lock().unwrap()[&attacker].count,
+            ROOM_PASSWORD_FREE_ATTEMPTS
+        );
+    }
+
+    #[tokio::test]
+    async fn moderators_can_change_and_remove_room_password() -> Result<()> {
+        let room = test_room();
+        let (mut host_rx, mut guest_rx) = {
+            let mut state = room.state.write().await;
+            add_player(&mut state, "host", 0);
+            add_player(&mut state, "guest", 0);
+            setup_connected_member(&mut state, "host", "t-host", 1);
+            setup_connected_member(&mut state, "guest", "t-guest", 2);
+            state.moderators.insert("host".to_string());
+            (
+                attach_test_socket(&mut state, "host"),
+                attach_test_socket(&mut state, "guest"),
+            )
+        };
+
+        room.handle_client_msg(
+            "guest",
+            2,
+            to_ws(ClientMsg::SetRoomPassword {
+                password: Some("mine".to_string()),
+            }),
+        )
+        .await?;
+        assert!(matches!(guest_rx.try_recv()?, ServerMsg::ErrorMsg(_)));
+        assert!(room.state.read().await.room_password_hash.is_none());
+
+        room.handle_client_msg(
+            "host",
+            1,
+            to_ws(ClientMsg::SetRoomPassword {
+                password: Some("  new pass ".to_string()),
+            }),
+        )
+        .await?;
+        assert!(matches!(
+            host_rx.try_recv()?,
+            ServerMsg::RoomPasswordUpdated { has_password: true }
+        ));
+        let hash = room.state.read().await.room_password_hash.clone();
+        assert!(hash.is_some_and(|hash| verify_room_password(&hash, "new pass")));
+
+        room.handle_client_msg(
+            "host",
+            1,
+            to_ws(ClientMsg::SetRoomPassword {
+                password: Some(" ".to_string()),
+            }),
+        )
+        .await?;
+        assert!(matches!(
+            host_rx.try_recv()?,
+            ServerMsg::RoomPasswordUpdated {
+                has_password: false
+            }
+        ));
+        assert!(room.state.read().await.room_password_hash.is_none());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn public_listing_puts_joinable_rooms_in_the_directory() -> Result<()> {
+        let room = test_room();
+        let mut guest_rx = {
+            let mut state = room.state.write().await;
+            add_player(&mut state, "host", 0);
+            add_player(&mut state, "guest", 0);
+            setup_connected_member(&mut state, "host", "t-host", 1);
+            setup_connected_member(&mut state, "guest", "t-guest", 2);
+            state.moderators.insert("host".to_string());
+            attach_test_socket(&mut state, "guest")
+        };
+        let listing = PublicRoomListing {
+            title: " Thursday club ".to_string(),
+            language: "en".to_string(),
+        };
+        assert!(room.public_summary().await.is_none());
+
+        room.handle_client_msg(
+            "guest",
+            2,
+            to_ws(ClientMsg::SetPublicListing {
+                listing: Some(listing.clone()),
+            }),
+        )
+        .await?;
+        assert!(matches!(guest_rx.try_recv()?, ServerMsg::ErrorMsg(_)));
+        assert!(room.public_summary().await.is_none());
+
+        room.handle_client_msg(
+            "host",
+            1,
+            to_ws(ClientMsg::SetPublicListing {
+                listing: Some(listing),
+            }),
+        )
+        .await?;
+        let summary = room.public_summary().await.expect("listed while joining");
+        assert_eq!(summary.title, "Thursday club");
+        assert_eq!(summary.player_count, 2);
+        assert_eq!(summary.stage, RoomStage::Joining);
+
+        {
+            let mut state = room.state.write().await;
+            state.stage = RoomStage::Voting;
+            state.allow_new_players_midgame = false;
+        }
+        assert!(room.public_summary().await.is_none());
+        room.state.write().await.allow_new_players_midgame = true;
+        assert!(room.public_summary().await.is_some());
+
+        room.handle_client_msg(
+            "host",
+            1,
+            to_ws(ClientMsg::SetPublicListing { listing: None }),
+        )
+        .await?;
+        assert!(room.public_summary().await.is_none());
 
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn unlisted_rooms_cannot_be_listed_publicly() -> Result<()> {
+        let room = test_room().with_unlisted(true);
+        let mut host_rx = {
+            let mut state = room.state.write().await;
+            add_player(&mut state, "host", 0);
+            setup_connected_member(&mut state, "host", "t-host", 1);
+            state.moderators.insert("host".to_string());
+            attach_test_socket(&mut state, "host")
+        };
+
+        room.handle_client_msg(
+            "host",
+            1,
+            to_ws(ClientMsg::SetPublicListing {
+                listing: Some(PublicRoomListing {
+                    title: "Secret".to_string(),
+                    language: "en".to_string(),
+                }),
+            }),
+        )
+        .await?;
+        assert!(matches!(host_rx.try_recv()?, ServerMsg::ErrorMsg(_)));
+        assert!(room.state.read().await.public_listing.is_none());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn persistent_snapshot_restores_settings_members_and_reserved_names() -> Result<()> {
+        let room = test_room();
+        let pool = {
+            let mut state = room.state.write().await;
+            for (name, token, generation) in [("host", "t-host", 1), ("guest", "t-guest", 2)] {
+                add_player(&mut state, name, 3);
+                setup_connected_member(&mut state, name, token, generation);
+            }
+            state.moderators.insert("host".to_string());
+            state.creator = Some("host".to_string());
+            state.cards_per_hand = 7;
+            state.beauty_enabled = false;
+            set_storyteller_pool_members(&room, &mut state, &["host", "guest"])
+        };
+        let snapshot: PersistentRoomSnapshot =
+            serde_json::from_str(&serde_json::to_string(&room.persistent_snapshot().await)?)?;
+        assert_eq!(snapshot.moderators, vec!["host".to_string()]);
+        assert_eq!(
+            snapshot.members[0].token_hash,
+            player_hash_for_token("t-guest")
+        );
+
+        let restored = test_room().with_owner_token_hash(Some(hash_room_owner_token("owner")));
+        restored.restore_persistent_snapshot(snapshot.clone()).await;
+        let state = restored.state.write().await;
+        assert_eq!(state.cards_per_hand, 7);
+        assert!(!state.beauty_enabled);
+        assert_eq!(
+            state.storyteller_pool_member_auth_ids,
+            pool.into_iter().collect::<HashSet<_>>()
+        );
+        assert_eq!(state.creator.as_deref(), Some("host"));
+        assert!(state.moderators.contains("host"));
+        assert!(state
+            .players
+            .values()
+            .all(|player| !player.connected && !player.ready && player.points == 0));
+        assert!(!restored.has_valid_token_for_name(&state, "guest", "t-host"));
+        assert!(restored.has_valid_token_for_name(&state, "guest", "t-guest"));
+        assert_eq!(
+            restored.member_name_for_token(&state, "t-guest").as_deref(),
+            Some("guest")
+        );
         assert_eq!(
-            a, a_again,
-            "same room+password should hash deterministically"
+            restored.member_hash_for_name(&state, "guest"),
+            player_hash_for_token("t-guest")
         );
-        assert_ne!(a, b, "room id should scope password hash");
+        assert!(matches!(
+            restored.room_state(&state),
+            ServerMsg::RoomState {
+                persistent: true,
+                ..
+            }
+        ));
+        drop(state);
+        assert_eq!(restored.persistent_snapshot().await, snapshot);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn owner_token_claims_a_persistent_room() -> Result<()> {
+        let room = test_room().with_owner_token_hash(Some(hash_room_owner_token("owner-token")));
+        let mut guest_rx = {
+            let mut state = room.state.write().await;
+            add_player(&mut state, "guest", 0);
+            setup_connected_member(&mut state, "guest", "t-guest", 1);
+            attach_test_socket(&mut state, "guest")
+        };
+
+        room.handle_client_msg(
+            "guest",
+            1,
+            to_ws(ClientMsg::ClaimRoomOwner {
+                owner_token: "wrong".to_string(),
+            }),
+        )
+        .await?;
+        assert!(matches!(guest_rx.try_recv()?, ServerMsg::ErrorMsg(_)));
+        assert!(!room.state.read().await.moderators.contains("guest"));
+
+        room.handle_client_msg(
+            "guest",
+            1,
+            to_ws(ClientMsg::ClaimRoomOwner {
+                owner_token: " owner-token ".to_string(),
+            }),
+        )
+        .await?;
+        let state = room.state.read().await;
+        assert!(state.moderators.contains("guest"));
+        assert_eq!(state.creator.as_deref(), Some("guest"));
+
+        Ok(())
     }
 
     #[tokio::test]
@@ -24151,8 +28685,8 @@ alpha
             add_player(&mut state, "host", 0);
             add_player(&mut state, "alice", 0);
             state.moderators.insert("host".to_string());
-            state.current_dixit_game_id = Some("game-current-123".to_string());
-            state.current_dixit_game_started_at_s = Some(1234);
+            state.current_game_id = Some("game-current-123".to_string());
+            state.current_game_started_at_s = Some(1234);
             state.round = 3;
             state.stage = RoomStage::Voting;
             state.active_player = 1;
@@ -24199,7 +28733,7 @@ alpha
             let mut state = room.state.write().await;
             add_player(&mut state, "host", 0);
             state.moderators.insert("host".to_string());
-            state.current_dixit_game_id = Some("game-round-one".to_string());
+            state.current_game_id = Some("game-round-one".to_string());
             state.round = 1;
             setup_connected_member(&mut state, "host", "t-host", 30_601);
             attach_test_socket(&mut state, "host")
@@ -24248,7 +28782,7 @@ alpha
         match response {
             ServerMsg::CurrentInfoMarkdown { markdown } => {
                 assert!(markdown.contains("- Current game ID: none"));
-                assert!(markdown.contains("No current Dixit game ID is available yet."));
+                assert!(markdown.contains("No current game ID is available yet."));
                 assert!(!markdown.contains("sqlite3 -header -column"));
             }
             other => return Err(anyhow!("expected current-info markdown, got {:?}", other)),
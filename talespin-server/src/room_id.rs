@@ -0,0 +1,198 @@
+use rand::{seq::SliceRandom, Rng};
+
+pub const DEFAULT_ROOM_ID_LENGTH: usize = 4;
+pub const DEFAULT_ROOM_ID_WORD_COUNT: usize = 3;
+pub const DEFAULT_ROOM_ID_ALPHABET: &str = "abcdefghijklmnopqrstuvwxyz";
+const ROOM_ID_LENGTH_RANGE: std::ops::RangeInclusive<usize> = 3..=32;
+const ROOM_ID_WORD_COUNT_RANGE: std::ops::RangeInclusive<usize> = 2..=6;
+const CUSTOM_ROOM_ID_LENGTH_RANGE: std::ops::RangeInclusive<usize> = 3..=40;
+
+// Short, easy-to-say words for word-based room codes, e.g. `amber-otter-quill`.
+const ROOM_ID_WORDS: [&str; 256] = [
+    "acorn", "acre", "alpine", "amber", "anchor", "apple", "arch", "arrow", "aspen", "atlas",
+    "aurora", "autumn", "badge", "badger", "bagel", "bamboo", "banjo", "barley", "basil", "bay",
+    "beach", "beacon", "beetle", "bell", "berry", "birch", "biscuit", "bison", "blossom", "bluff",
+    "bonnet", "boulder", "bramble", "breeze", "brick", "bridge", "brook", "bubble", "button",
+    "cabin", "cactus", "camel", "candle", "candy", "canvas", "canyon", "carrot", "castle", "cedar",
+    "cellar", "chalk", "cherry", "chess", "cider", "cinder", "cliff", "cloak", "cloud", "clover",
+    "cobalt", "cocoa", "comet", "compass", "copper", "coral", "cotton", "crane", "cricket", "crow",
+    "crystal", "cuckoo", "daisy", "delta", "denim", "desert", "dew", "dingo", "dolphin", "dragon",
+    "drift", "drum", "dune", "dusk", "eagle", "echo", "elm", "ember", "fable", "falcon", "fawn",
+    "feather", "fern", "fiddle", "fig", "finch", "fjord", "flame", "flint", "fog", "forest",
+    "fossil", "fox", "frost", "galaxy", "gale", "garden", "garnet", "geyser", "ginger", "glacier",
+    "glade", "glow", "goblet", "grove", "guitar", "harbor", "harp", "hawk", "hazel", "heron",
+    "hickory", "hill", "hollow", "honey", "horizon", "igloo", "indigo", "iris", "island", "ivory",
+    "jade", "jasmine", "jelly", "jungle", "kayak", "kelp", "kettle", "kiwi", "koala", "lagoon",
+    "lantern", "larch", "lemon", "lilac", "lily", "linen", "lotus", "lunar", "magnet", "mango",
+    "maple", "marble", "meadow", "melon", "meteor", "mint", "mirror", "mist", "moose", "mosaic",
+    "moss", "muffin", "nectar", "nest", "nutmeg", "oasis", "ocean", "olive", "onyx", "opal",
+    "orbit", "orchid", "otter", "owl", "paddle", "panda", "paper", "parrot", "peach", "pebble",
+    "pepper", "petal", "piano", "pickle", "pine", "pixel", "planet", "plum", "pocket", "pony",
+    "poppy", "prairie", "prism", "pumpkin", "quartz", "quill", "rabbit", "radar", "rainbow",
+    "raven", "reef", "ribbon", "river", "robin", "rocket", "rose", "ruby", "saddle", "saffron",
+    "salmon", "sapphire", "satin", "scarf", "shell", "sierra", "silver", "sketch", "slate", "sled",
+    "sparrow", "spice", "spruce", "squash", "star", "stone", "storm", "sugar", "summit", "sunset",
+    "swan", "tango", "teapot", "thistle", "thunder", "tiger", "timber", "topaz", "torch", "tulip",
+    "tundra", "turtle", "umbrella", "valley", "velvet", "violet", "walnut", "walrus", "willow",
+    "window", "winter", "wizard", "wren", "yarrow", "yeti", "zebra", "zephyr",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RoomIdGenerator {
+    Letters { length: usize, alphabet: Vec<char> },
+    Words { count: usize },
+}
+
+impl Default for RoomIdGenerator {
+    fn default() -> Self {
+        Self::Letters {
+            length: DEFAULT_ROOM_ID_LENGTH,
+            alphabet: DEFAULT_ROOM_ID_ALPHABET.chars().collect(),
+        }
+    }
+}
+
+impl RoomIdGenerator {
+    // Room ids are lowercased on every lookup, so the alphabet is too.
+    pub fn letters(length: usize, alphabet: &str) -> Result<Self, String> {
+        if !ROOM_ID_LENGTH_RANGE.contains(&length) {
+            return Err(format!(
+                "room id length must be between {} and {}",
+                ROOM_ID_LENGTH_RANGE.start(),
+                ROOM_ID_LENGTH_RANGE.end()
+            ));
+        }
+        let mut chars = Vec::new();
+        for ch in alphabet.trim().chars().map(|ch| ch.to_ascii_lowercase()) {
+            if !ch.is_ascii_alphanumeric() {
+                return Err(format!(
+                    "room id alphabet may only contain letters and digits, got '{ch}'"
+                ));
+            }
+            if !chars.contains(&ch) {
+                chars.push(ch);
+            }
+        }
+        if chars.len() < 2 {
+            return Err("room id alphabet needs at least two distinct characters".to_string());
+        }
+        Ok(Self::Letters {
+            length,
+            alphabet: chars,
+        })
+    }
+
+    pub fn words(count: usize) -> Result<Self, String> {
+        if !ROOM_ID_WORD_COUNT_RANGE.contains(&count) {
+            return Err(format!(
+                "room id word count must be between {} and {}",
+                ROOM_ID_WORD_COUNT_RANGE.start(),
+                ROOM_ID_WORD_COUNT_RANGE.end()
+            ));
+        }
+        Ok(Self::Words { count })
+    }
+
+    pub fn generate(&self) -> String {
+        let mut rng = rand::thread_rng();
+        match self {
+            Self::Letters { length, alphabet } => (0..*length)
+                .map(|_| alphabet[rng.gen_range(0..alphabet.len())])
+                .collect(),
+            Self::Words { count } => (0..*count)
+                .filter_map(|_| ROOM_ID_WORDS.choose(&mut rng).copied())
+                .collect::<Vec<_>>()
+                .join("-"),
+        }
+    }
+
+    // number of distinct ids, saturating, for the startup log
+    pub fn id_space(&self) -> u64 {
+        let (base, exponent) = match self {
+            Self::Letters { length, alphabet } => (alphabet.len() as u64, *length),
+            Self::Words { count } => (ROOM_ID_WORDS.len() as u64, *count),
+        };
+        (0..exponent).fold(1u64, |total, _| total.saturating_mul(base))
+    }
+}
+
+// Ids chosen for persistent rooms, e.g. `thursday-club`. They end up in `/game/<id>` links.
+pub fn normalize_custom_room_id(room_id: &str) -> Result<String, String> {
+    let room_id = room_id.trim().to_lowercase();
+    if !CUSTOM_ROOM_ID_LENGTH_RANGE.contains(&room_id.len()) {
+        return Err(format!(
+            "Room ids must be {}-{} characters",
+            CUSTOM_ROOM_ID_LENGTH_RANGE.start(),
+            CUSTOM_ROOM_ID_LENGTH_RANGE.end()
+        ));
+    }
+    if !room_id
+        .chars()
+        .all(|ch| ch.is_ascii_lowercase() || ch.is_ascii_digit() || ch == '-')
+        || room_id.starts_with('-')
+        || room_id.ends_with('-')
+    {
+        return Err(
+            "Room ids may only use letters, digits, and '-', and cannot start or end with '-'"
+                .to_string(),
+        );
+    }
+    Ok(room_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn letter_ids_use_the_configured_length_and_alphabet() {
+        let generator = RoomIdGenerator::letters(8, "AB12b").unwrap();
+        assert_eq!(
+            generator,
+            RoomIdGenerator::Letters {
+                length: 8,
+                alphabet: vec!['a', 'b', '1', '2'],
+            }
+        );
+        let id = generator.generate();
+        assert_eq!(id.len(), 8);
+        assert!(id.chars().all(|ch| "ab12".contains(ch)));
+        assert_eq!(generator.id_space(), 4u64.pow(8));
+
+        assert!(RoomIdGenerator::letters(2, DEFAULT_ROOM_ID_ALPHABET).is_err());
+        assert!(RoomIdGenerator::letters(6, "a").is_err());
+        assert!(RoomIdGenerator::letters(6, "ab-").is_err());
+        assert_eq!(RoomIdGenerator::default().id_space(), 26u64.pow(4));
+    }
+
+    #[test]
+    fn word_ids_join_distinct_lowercase_words() {
+        assert_eq!(
+            ROOM_ID_WORDS.iter().collect::<HashSet<_>>().len(),
+            ROOM_ID_WORDS.len()
+        );
+        assert!(ROOM_ID_WORDS
+            .iter()
+            .all(|word| word.chars().all(|ch| ch.is_ascii_lowercase())));
+
+        let id = RoomIdGenerator::words(3).unwrap().generate();
+        let parts = id.split('-').collect::<Vec<_>>();
+        assert_eq!(parts.len(), 3);
+        assert!(parts.iter().all(|part| ROOM_ID_WORDS.contains(part)));
+        assert!(RoomIdGenerator::words(1).is_err());
+    }
+
+    #[test]
+    fn custom_room_ids_are_lowercase_link_safe_slugs() {
+        assert_eq!(
+            normalize_custom_room_id(" Thursday-Club ").as_deref(),
+            Ok("thursday-club")
+        );
+        assert!(normalize_custom_room_id("ab").is_err());
+        assert!(normalize_custom_room_id(&"a".repeat(41)).is_err());
+        assert!(normalize_custom_room_id("thursday club").is_err());
+        assert!(normalize_custom_room_id("-club").is_err());
+        assert!(normalize_custom_room_id("club/1").is_err());
+    }
+}